@@ -1,9 +1,57 @@
 use axum::{
+    extract::{Path, State},
     http::StatusCode,
-    routing::get,
+    middleware,
+    routing::{get, post},
     Json, Router,
 };
+use serde::Serialize;
 use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::api::error::ApiError;
+use crate::api::auth::{require_admin, AdminAuth};
+use crate::mexc::{Credentials, LatencySnapshot, MexcClient, QueueDepth, RecordedError, UserClientError};
+use crate::storage::{DynamoDBStore, ExportBundle};
+use crate::trading::{FlattenError, FlattenSummary, Flattener};
+use crate::utils::{Config, EffectiveSetting, RuntimeSettings};
+
+pub struct AdminState {
+    pub mexc_client: Arc<MexcClient>,
+    pub auth: Arc<AdminAuth>,
+    pub runtime: Arc<RuntimeSettings>,
+    pub store: Arc<DynamoDBStore>,
+    /// Beim Start geladene Config (Basis für `/config/effective`)
+    pub config: Arc<Config>,
+    /// Glattstellen aller Positionen eines Users
+    pub flattener: Arc<Flattener>,
+}
+
+/// Zustand der Schutzmechanismen auf einen Blick
+#[derive(Debug, Serialize)]
+pub struct ProtectionStatus {
+    pub rate_limit: RateLimitStatus,
+    pub latency: LatencySnapshot,
+    pub maintenance: MaintenanceStatus,
+    pub queue: QueueDepth,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateLimitStatus {
+    pub used_weight: Option<u32>,
+    /// MEXC_WEIGHT_LIMIT (None = unbekannt)
+    pub weight_limit: Option<u32>,
+    pub remaining_weight: Option<u32>,
+    /// Laufende Sperre laut Retry-After
+    pub retry_after_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceStatus {
+    pub active: bool,
+    pub since: Option<i64>,
+}
 
 /// Health Check Endpoint
 pub async fn health() -> (StatusCode, Json<serde_json::Value>) {
@@ -33,10 +81,267 @@ pub async fn metrics() -> (StatusCode, String) {
     (StatusCode::OK, "# Metrics endpoint\n".to_string())
 }
 
+/// POST /api/admin/mexc/rotate-key - MEXC Keys validieren und atomar austauschen
+pub async fn rotate_key(
+    State(state): State<Arc<AdminState>>,
+    Json(payload): Json<Credentials>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if payload.api_key.is_empty() || payload.secret_key.is_empty() {
+        return Err(ApiError::Validation("api_key and secret_key are required".to_string()));
+    }
+
+    match state.mexc_client.rotate_credentials(payload).await {
+        Ok(()) => Ok(Json(json!({ "rotated": true }))),
+        Err(e) => {
+            tracing::warn!("MEXC key rotation rejected: {}", e);
+            Err(ApiError::Validation(e.to_string()))
+        }
+    }
+}
+
+/// POST /api/admin/config/reload - Nicht-geheime Tunables neu laden und atomar austauschen
+pub async fn reload_config(State(state): State<Arc<AdminState>>) -> Json<serde_json::Value> {
+    let runtime = state.runtime.reload_from_env();
+    tracing::info!("Runtime config reloaded: {:?}", runtime);
+    Json(json!({ "reloaded": true, "config": *runtime }))
+}
+
+/// GET /api/admin/config/effective - Effektive Einstellungen mit Herkunft, Secrets geschwärzt
+pub async fn effective_config(
+    State(state): State<Arc<AdminState>>,
+) -> Json<BTreeMap<String, EffectiveSetting>> {
+    Json(state.config.effective_snapshot(&state.runtime.load()))
+}
+
+/// GET /api/admin/protection - Rate-Limit, Latenz, Wartung und Queue in einem Payload
+pub async fn protection(State(state): State<Arc<AdminState>>) -> Json<ProtectionStatus> {
+    let client = &state.mexc_client;
+    let used_weight = client.rate_limit().used_weight();
+    let weight_limit = (state.config.mexc_weight_limit > 0).then_some(state.config.mexc_weight_limit);
+    let maintenance_since = client.maintenance().since();
+
+    Json(ProtectionStatus {
+        rate_limit: RateLimitStatus {
+            used_weight,
+            weight_limit,
+            remaining_weight: weight_limit.map(|limit| limit.saturating_sub(used_weight.unwrap_or(0))),
+            retry_after_ms: client.rate_limit().retry_remaining().map(|d| d.as_millis() as u64),
+        },
+        latency: client.latency().snapshot(),
+        maintenance: MaintenanceStatus {
+            active: maintenance_since.is_some(),
+            since: maintenance_since,
+        },
+        queue: client.queue_depth(),
+    })
+}
+
+/// Letzte MEXC-Fehler, neueste zuerst
+#[derive(Debug, Serialize)]
+pub struct RecentErrors {
+    /// MEXC_ERROR_LOG_SIZE
+    pub capacity: usize,
+    pub errors: Vec<RecordedError>,
+}
+
+/// GET /api/admin/errors/recent - Die letzten MEXC-Fehlerantworten aus dem Ringpuffer
+pub async fn recent_errors(State(state): State<Arc<AdminState>>) -> Json<RecentErrors> {
+    let log = state.mexc_client.error_log();
+    Json(RecentErrors {
+        capacity: log.capacity(),
+        errors: log.recent(),
+    })
+}
+
+/// POST /api/admin/flatten/:user_id - Alle offenen Orders stornieren, alle Positionen
+/// per Market schließen; wiederholbar (danach ist nichts mehr offen)
+pub async fn flatten_user(
+    State(state): State<Arc<AdminState>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<FlattenSummary>, ApiError> {
+    state.flattener.flatten(&user_id).await.map(Json).map_err(|e| match e {
+        FlattenError::InProgress(_) => ApiError::Conflict(e.to_string()),
+        FlattenError::Client(UserClientError::MissingCredentials(_)) => ApiError::Unauthorized(e.to_string()),
+        FlattenError::Client(_) | FlattenError::Store(_) => {
+            tracing::error!("Flatten failed for user {}: {}", user_id, e);
+            ApiError::Internal(e.to_string())
+        }
+    })
+}
+
+/// GET /api/admin/export/:user_id - Backup aller DynamoDB Items eines Users
+pub async fn export_user(
+    State(state): State<Arc<AdminState>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<ExportBundle>, ApiError> {
+    let bundle = state.store.export_all(&user_id).await.map_err(|e| {
+        tracing::error!("Export failed for user {}: {}", user_id, e);
+        ApiError::Internal(e.to_string())
+    })?;
+    Ok(Json(bundle))
+}
+
+/// POST /api/admin/import - Backup-Bundle zurückschreiben
+pub async fn import_user(
+    State(state): State<Arc<AdminState>>,
+    Json(bundle): Json<ExportBundle>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let imported = state.store.import_bundle(&bundle).await.map_err(|e| {
+        tracing::error!("Import failed for user {}: {}", bundle.user_id, e);
+        ApiError::Internal(e.to_string())
+    })?;
+    Ok(Json(json!({ "user_id": bundle.user_id, "imported": imported })))
+}
+
 /// Router für Admin/Health Endpoints
-pub fn admin_router() -> Router {
+pub fn admin_router(state: Arc<AdminState>) -> Router {
+    let protected = Router::new()
+        .route("/mexc/rotate-key", post(rotate_key))
+        .route("/config/reload", post(reload_config))
+        .route("/config/effective", get(effective_config))
+        .route("/protection", get(protection))
+        .route("/errors/recent", get(recent_errors))
+        .route("/flatten/:user_id", post(flatten_user))
+        .route("/export/:user_id", get(export_user))
+        .route("/import", post(import_user))
+        .route_layer(middleware::from_fn_with_state(state.auth.clone(), require_admin))
+        .with_state(state);
+
     Router::new()
         .route("/health", get(health))
         .route("/ready", get(ready))
         .route("/metrics", get(metrics))
+        .merge(protected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mexc::MexcClientPool;
+    use crate::test_support::{mexc_client, spawn_server, test_config, MockDynamo};
+    use axum::extract::Query;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_rotate_key_rejects_invalid_keys() {
+        let router = Router::new().route(
+            "/api/v3/account",
+            get(|| async { (StatusCode::UNAUTHORIZED, "invalid api key") }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let mexc = Arc::new(mexc_client(&base_url));
+        let clients = Arc::new(MexcClientPool::single_tenant(mexc.clone()));
+        let state = Arc::new(AdminState {
+            mexc_client: mexc,
+            auth: Arc::new(AdminAuth::new(Some("admin".to_string()))),
+            runtime: Arc::new(RuntimeSettings::new(&Default::default())),
+            store: store.clone(),
+            config: Arc::new(Config::default()),
+            flattener: Arc::new(Flattener::new(clients, store, 4)),
+        });
+
+        let result = rotate_key(
+            State(state.clone()),
+            Json(Credentials {
+                api_key: "new-key".to_string(),
+                secret_key: "new-secret".to_string(),
+            }),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(state.mexc_client.api_key(), "test-key");
+    }
+
+    #[tokio::test]
+    async fn test_protection_reports_each_mechanism() {
+        let router = Router::new().route(
+            "/api/v3/account",
+            get(|| async {
+                (
+                    [("x-mbx-used-weight-1m", "150")],
+                    Json(json!({ "balances": [] })),
+                )
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let mexc = Arc::new(mexc_client(&base_url));
+        let clients = Arc::new(MexcClientPool::single_tenant(mexc.clone()));
+        let state = Arc::new(AdminState {
+            mexc_client: mexc,
+            auth: Arc::new(AdminAuth::new(Some("admin".to_string()))),
+            runtime: Arc::new(RuntimeSettings::new(&Default::default())),
+            store: store.clone(),
+            config: Arc::new(Config {
+                mexc_weight_limit: 1200,
+                ..Default::default()
+            }),
+            flattener: Arc::new(Flattener::new(clients, store, 4)),
+        });
+        state.mexc_client.get_account_balance().await.unwrap();
+
+        let Json(status) = protection(State(state)).await;
+        let body = serde_json::to_value(&status).unwrap();
+
+        // Keine Platzhalter für Mechanismen, die es nicht gibt
+        assert!(body.get("circuit_breaker").is_none());
+        assert!(body.get("kill_switch").is_none());
+        assert_eq!(body["rate_limit"]["used_weight"], 150);
+        assert_eq!(body["rate_limit"]["weight_limit"], 1200);
+        assert_eq!(body["rate_limit"]["remaining_weight"], 1050);
+        assert!(body["rate_limit"]["retry_after_ms"].is_null());
+        assert!(body["latency"]["ewma_ms"].as_f64().unwrap() >= 0.0);
+        assert_eq!(body["latency"]["samples"], 1);
+        assert_eq!(body["maintenance"]["active"], false);
+        assert!(body["maintenance"]["since"].is_null());
+        assert!(body["queue"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_recent_errors_newest_first_and_bounded() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                let code = params["orderId"].parse::<i64>().unwrap();
+                (StatusCode::BAD_REQUEST, Json(json!({ "code": code, "msg": "Order does not exist." })))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let mexc = Arc::new(
+            MexcClient::new(&Config {
+                mexc_error_log_size: 3,
+                ..test_config(&base_url)
+            })
+            .unwrap(),
+        );
+        let clients = Arc::new(MexcClientPool::single_tenant(mexc.clone()));
+        let state = Arc::new(AdminState {
+            mexc_client: mexc,
+            auth: Arc::new(AdminAuth::new(Some("admin".to_string()))),
+            runtime: Arc::new(RuntimeSettings::new(&Default::default())),
+            store: store.clone(),
+            config: Arc::new(Config::default()),
+            flattener: Arc::new(Flattener::new(clients, store, 4)),
+        });
+        for order_id in ["1", "2", "3", "4", "5"] {
+            assert!(state.mexc_client.get_order("ETHUSDT", order_id).await.is_err());
+        }
+
+        let Json(recent) = recent_errors(State(state)).await;
+
+        assert_eq!(recent.capacity, 3);
+        let codes: Vec<_> = recent.errors.iter().map(|e| e.code.as_deref().unwrap()).collect();
+        assert_eq!(codes, ["5", "4", "3"]);
+        let newest = &recent.errors[0];
+        assert_eq!(newest.endpoint, "/api/v3/order");
+        assert_eq!(newest.status, 400);
+        assert_eq!(newest.message, "Order does not exist.");
+        assert_eq!(newest.symbol.as_deref(), Some("ETHUSDT"));
+    }
 }
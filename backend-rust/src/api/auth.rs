@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::sync::Arc;
+
+use crate::api::error::ApiError;
+
+/// Admin-Authentifizierung per Bearer Token (ADMIN_API_TOKEN)
+pub struct AdminAuth {
+    token: Option<String>,
+}
+
+impl AdminAuth {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// Prüfe den Authorization Header; ohne konfigurierten Token wird alles abgelehnt
+    pub fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        let (Some(expected), Some(provided)) = (
+            self.token.as_deref(),
+            authorization.and_then(|v| v.strip_prefix("Bearer ")),
+        ) else {
+            return false;
+        };
+        constant_time_eq(expected.as_bytes(), provided.trim().as_bytes())
+    }
+}
+
+/// Middleware für Admin-Routen
+pub async fn require_admin(
+    State(auth): State<Arc<AdminAuth>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    if !auth.is_authorized(authorization) {
+        return ApiError::Unauthorized("Unauthorized".to_string()).into_response();
+    }
+
+    next.run(req).await
+}
+
+/// User-Authentifizierung per Bearer-JWT (HS256 mit JWT_SECRET, `sub` = User-ID)
+pub struct UserAuth {
+    secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    /// Unix Sekunden
+    exp: i64,
+}
+
+impl UserAuth {
+    pub fn new(secret: Option<String>) -> Self {
+        Self {
+            secret: secret.filter(|s| !s.is_empty()),
+        }
+    }
+
+    /// User-ID aus einem gültigen, nicht abgelaufenen Token (None = ungültig
+    /// oder kein JWT_SECRET konfiguriert)
+    pub fn verify(&self, token: &str, now_secs: i64) -> Option<String> {
+        let secret = self.secret.as_deref()?;
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+
+        let header: serde_json::Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).ok()?).ok()?;
+        if header["alg"] != "HS256" {
+            return None;
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+        mac.update(&token.as_bytes()[..token.rfind('.')?]);
+        mac.verify_slice(&URL_SAFE_NO_PAD.decode(signature).ok()?).ok()?;
+
+        let claims: Claims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        (claims.exp > now_secs && !claims.sub.is_empty()).then_some(claims.sub)
+    }
+
+    /// Nur der User selbst darf auf seine Ressource zugreifen
+    pub fn authorize(&self, headers: &HeaderMap, user_id: &str) -> Result<(), ApiError> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or_else(|| ApiError::Unauthorized("Unauthorized".to_string()))?;
+
+        match self.verify(token, chrono::Utc::now().timestamp()) {
+            Some(subject) if constant_time_eq(subject.as_bytes(), user_id.as_bytes()) => Ok(()),
+            _ => Err(ApiError::Unauthorized("Unauthorized".to_string())),
+        }
+    }
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_token_check() {
+        let auth = AdminAuth::new(Some("s3cret".to_string()));
+        assert!(auth.is_authorized(Some("Bearer s3cret")));
+        assert!(!auth.is_authorized(Some("Bearer wrong")));
+        assert!(!auth.is_authorized(Some("s3cret")));
+        assert!(!auth.is_authorized(None));
+
+        let disabled = AdminAuth::new(None);
+        assert!(!disabled.is_authorized(Some("Bearer ")));
+    }
+
+    /// Signiertes HS256-Token für Tests
+    pub(crate) fn sign_jwt(secret: &str, sub: &str, exp: i64) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::json!({ "sub": sub, "exp": exp }).to_string());
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.{}", header, payload).as_bytes());
+        format!("{}.{}.{}", header, payload, URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_user_token_check() {
+        let auth = UserAuth::new(Some("jwt-secret".to_string()));
+        let token = sign_jwt("jwt-secret", "user-1", 2_000);
+        assert_eq!(auth.verify(&token, 1_000).as_deref(), Some("user-1"));
+        // Abgelaufen, falsches Secret, manipulierter Payload
+        assert_eq!(auth.verify(&token, 2_000), None);
+        assert_eq!(auth.verify(&sign_jwt("other", "user-1", 2_000), 1_000), None);
+        let forged = sign_jwt("jwt-secret", "user-2", 2_000);
+        let spliced = format!(
+            "{}.{}",
+            &forged[..forged.rfind('.').unwrap()],
+            &token[token.rfind('.').unwrap() + 1..]
+        );
+        assert_eq!(auth.verify(&spliced, 1_000), None);
+        // Ohne JWT_SECRET wird nichts akzeptiert
+        assert_eq!(UserAuth::new(None).verify(&token, 1_000), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Einheitliches Ergebnis von Batch-Endpoints: erfolgreiche Einträge und
+//! Fehlschläge je Item mit typisiertem Fehlercode
+use serde::Serialize;
+
+use crate::api::error::ApiError;
+use crate::mexc::MexcError;
+
+/// `{ succeeded: [...], failed: [{ id, error_code, message }] }` in Eingabereihenfolge
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult<T> {
+    pub succeeded: Vec<T>,
+    pub failed: Vec<BatchFailure>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    pub fn push(&mut self, result: Result<T, BatchFailure>) {
+        match result {
+            Ok(item) => self.succeeded.push(item),
+            Err(failure) => self.failed.push(failure),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> FromIterator<Result<T, BatchFailure>> for BatchResult<T> {
+    fn from_iter<I: IntoIterator<Item = Result<T, BatchFailure>>>(iter: I) -> Self {
+        let mut batch = Self::default();
+        for result in iter {
+            batch.push(result);
+        }
+        batch
+    }
+}
+
+/// Fehlgeschlagenes Item eines Batches
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchFailure {
+    /// Lokale ID des Items (z.B. order_id)
+    pub id: String,
+    /// MEXC-Fehlercode (z.B. "30004"), sonst die Art des lokalen Fehlers (z.B. "validation")
+    pub error_code: String,
+    pub message: String,
+}
+
+impl BatchFailure {
+    pub fn new(id: impl Into<String>, error_code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            error_code: error_code.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Code aus einem `MexcError` übernehmen; andere Fehler gelten als "upstream"
+    pub fn from_error(id: impl Into<String>, error: &anyhow::Error, message: impl Into<String>) -> Self {
+        let code = error.downcast_ref::<MexcError>().and_then(|e| e.code.clone());
+        Self::new(id, code.unwrap_or_else(|| "upstream".to_string()), message)
+    }
+
+    /// API-Fehler mit dem MEXC-Code der Ablehnung, falls vorhanden
+    pub fn from_api(id: impl Into<String>, error: &ApiError, mexc_code: Option<String>) -> Self {
+        Self::new(id, mexc_code.unwrap_or_else(|| error.kind().to_string()), error.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mixed_batch_serializes_typed_codes() {
+        let rejected = anyhow::Error::new(MexcError::from_response(
+            400,
+            r#"{"code":30004,"msg":"Insufficient position"}"#,
+        ));
+        let batch: BatchResult<_> = vec![
+            Ok(json!({ "order_id": "a" })),
+            Err(BatchFailure::from_error("b", &rejected, "rejected")),
+            Err(BatchFailure::from_api("c", &ApiError::RateLimited("slow down".into()), None)),
+            Err(BatchFailure::from_error("d", &anyhow::anyhow!("connection reset"), "network")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(batch.len(), 4);
+        assert_eq!(
+            serde_json::to_value(&batch).unwrap(),
+            json!({
+                "succeeded": [{ "order_id": "a" }],
+                "failed": [
+                    { "id": "b", "error_code": "30004", "message": "rejected" },
+                    { "id": "c", "error_code": "rate_limited", "message": "slow down" },
+                    { "id": "d", "error_code": "upstream", "message": "network" },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::convert::Infallible;
+
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Antwortformat laut `Accept` Header (Default: JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    Msgpack,
+}
+
+impl ResponseFormat {
+    pub fn from_accept(accept: &str) -> Self {
+        let wants_msgpack = accept.split(',').any(|part| {
+            let media_type = part.split(';').next().unwrap_or_default().trim();
+            media_type.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+                || media_type.eq_ignore_ascii_case("application/x-msgpack")
+        });
+
+        if wants_msgpack {
+            ResponseFormat::Msgpack
+        } else {
+            ResponseFormat::Json
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ResponseFormat {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .map(ResponseFormat::from_accept)
+            .unwrap_or_default())
+    }
+}
+
+/// Antwort im ausgehandelten Format (JSON oder msgpack)
+#[derive(Debug)]
+pub struct Encoded<T>(pub ResponseFormat, pub T);
+
+impl<T: Serialize> IntoResponse for Encoded<T> {
+    fn into_response(self) -> Response {
+        match self.0 {
+            ResponseFormat::Json => Json(self.1).into_response(),
+            ResponseFormat::Msgpack => match rmp_serde::to_vec_named(&self.1) {
+                Ok(bytes) => ([(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)], bytes).into_response(),
+                Err(e) => {
+                    tracing::error!("Failed to encode msgpack response: {}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_accept() {
+        assert_eq!(ResponseFormat::from_accept("application/msgpack"), ResponseFormat::Msgpack);
+        assert_eq!(
+            ResponseFormat::from_accept("text/html, application/x-msgpack;q=0.9"),
+            ResponseFormat::Msgpack
+        );
+        assert_eq!(ResponseFormat::from_accept("application/json"), ResponseFormat::Json);
+        assert_eq!(ResponseFormat::from_accept("*/*"), ResponseFormat::Json);
+    }
+}
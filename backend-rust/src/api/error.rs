@@ -0,0 +1,118 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+/// Einheitlicher API-Fehler, gerendert als `{ "error": { "type", "message" }, "code" }`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    NotFound(String),
+    Validation(String),
+    /// Fehler von MEXC oder anderen externen Diensten
+    Upstream(String),
+    Internal(String),
+    Unauthorized(String),
+    RateLimited(String),
+    Conflict(String),
+    /// Post-only Order hätte sofort gematcht (Taker) und wurde von MEXC abgelehnt
+    WouldTakeLiquidity(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::Upstream(_) => StatusCode::BAD_GATEWAY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Conflict(_) | ApiError::WouldTakeLiquidity(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    /// Maschinenlesbarer Fehlertyp
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Validation(_) => "validation",
+            ApiError::Upstream(_) => "upstream",
+            ApiError::Internal(_) => "internal",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::RateLimited(_) => "rate_limited",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::WouldTakeLiquidity(_) => "would_take_liquidity",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::NotFound(m)
+            | ApiError::Validation(m)
+            | ApiError::Upstream(m)
+            | ApiError::Internal(m)
+            | ApiError::Unauthorized(m)
+            | ApiError::RateLimited(m)
+            | ApiError::Conflict(m)
+            | ApiError::WouldTakeLiquidity(m) => m,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind(), self.message())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = json!({
+            "error": {
+                "type": self.kind(),
+                "message": self.message(),
+            },
+            "code": status.as_u16(),
+        });
+        (status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn render(error: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body failed");
+        (status, serde_json::from_slice(&bytes).expect("body is not JSON"))
+    }
+
+    #[tokio::test]
+    async fn test_variants_render_status_and_json() {
+        let cases = [
+            (ApiError::NotFound("x".into()), StatusCode::NOT_FOUND, "not_found"),
+            (ApiError::Validation("x".into()), StatusCode::BAD_REQUEST, "validation"),
+            (ApiError::Upstream("x".into()), StatusCode::BAD_GATEWAY, "upstream"),
+            (ApiError::Internal("x".into()), StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+            (ApiError::Unauthorized("x".into()), StatusCode::UNAUTHORIZED, "unauthorized"),
+            (ApiError::RateLimited("x".into()), StatusCode::TOO_MANY_REQUESTS, "rate_limited"),
+            (ApiError::Conflict("x".into()), StatusCode::CONFLICT, "conflict"),
+            (ApiError::WouldTakeLiquidity("x".into()), StatusCode::CONFLICT, "would_take_liquidity"),
+        ];
+
+        for (error, expected_status, expected_kind) in cases {
+            let (status, body) = render(error).await;
+            assert_eq!(status, expected_status);
+            assert_eq!(body["code"], expected_status.as_u16());
+            assert_eq!(body["error"]["type"], expected_kind);
+            assert_eq!(body["error"]["message"], "x");
+        }
+    }
+}
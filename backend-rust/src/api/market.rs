@@ -1,32 +1,104 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     routing::get,
-    Json, Router,
+    Router,
 };
+use serde::Deserialize;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::api::error::ApiError;
+use crate::api::encoding::{Encoded, ResponseFormat};
+use crate::mexc::websocket::PriceCache;
 use crate::mexc::MexcClient;
+use crate::utils::Config;
+
+/// Lesepfad bei MEXC-Ausfall: erfolgreiche REST-Preise werden gemerkt und bei
+/// Fehlern (als stale markiert) ausgeliefert, solange sie jünger als `max_age`
+/// sind. Nur für Marktdaten, Order-Endpunkte schlagen weiterhin sofort fehl.
+#[derive(Clone)]
+pub struct StalePriceFallback {
+    cache: Arc<PriceCache>,
+    max_age: Duration,
+}
+
+impl Default for StalePriceFallback {
+    fn default() -> Self {
+        Self::new(Arc::new(PriceCache::new(Duration::ZERO)), Duration::ZERO)
+    }
+}
+
+impl StalePriceFallback {
+    pub fn new(cache: Arc<PriceCache>, max_age: Duration) -> Self {
+        Self { cache, max_age }
+    }
+
+    pub fn from_config(cache: Arc<PriceCache>, config: &Config) -> Self {
+        Self::new(cache, Duration::from_secs(config.stale_price_fallback_secs))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.max_age.is_zero()
+    }
+
+    /// Frischen Preis merken
+    pub fn record(&self, symbol: &str, price: f64) {
+        if self.is_enabled() {
+            self.cache.record(symbol, price);
+        }
+    }
+
+    /// Letzter bekannter Preis samt Alter, sofern aktiviert und nicht zu alt
+    pub fn lookup(&self, symbol: &str) -> Option<(f64, Duration)> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.cache.last_known(symbol).filter(|(_, age)| *age <= self.max_age)
+    }
+}
 
 pub struct MarketState {
     pub mexc_client: Arc<MexcClient>,
+    pub fallback: StalePriceFallback,
 }
 
 /// GET /api/market/ticker/:symbol - Get Current Price
 pub async fn get_ticker(
     State(state): State<Arc<MarketState>>,
+    format: ResponseFormat,
     Path(symbol): Path<String>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+) -> Result<Encoded<serde_json::Value>, ApiError> {
     match state.mexc_client.get_ticker(&symbol).await {
-        Ok(ticker) => Ok(Json(json!({
-            "symbol": ticker.symbol,
-            "price": ticker.price,
-            "timestamp": ticker.timestamp,
-        }))),
+        Ok(ticker) => {
+            state.fallback.record(&ticker.symbol, ticker.price);
+            Ok(Encoded(
+                format,
+                json!({
+                    "symbol": ticker.symbol,
+                    "price": ticker.price,
+                    "timestamp": ticker.timestamp,
+                    "stale": false,
+                }),
+            ))
+        }
         Err(e) => {
-            tracing::error!("Failed to get ticker: {}", e);
-            Err((StatusCode::BAD_GATEWAY, e.to_string()))
+            let Some((price, age)) = state.fallback.lookup(&symbol) else {
+                tracing::error!("Failed to get ticker: {}", e);
+                return Err(ApiError::Upstream(e.to_string()));
+            };
+            tracing::warn!("Failed to get ticker ({}), serving cached price for {}", e, symbol);
+            let age_ms = age.as_millis() as i64;
+            Ok(Encoded(
+                format,
+                json!({
+                    "symbol": symbol,
+                    "price": price,
+                    "timestamp": chrono::Utc::now().timestamp_millis() - age_ms,
+                    "stale": true,
+                    "age_ms": age_ms,
+                }),
+            ))
         }
     }
 }
@@ -34,7 +106,8 @@ pub async fn get_ticker(
 /// GET /api/market/balance - Get Account Balance
 pub async fn get_balance(
     State(state): State<Arc<MarketState>>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    format: ResponseFormat,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
     match state.mexc_client.get_account_balance().await {
         Ok(balance) => {
             let balances: Vec<_> = balance
@@ -49,11 +122,53 @@ pub async fn get_balance(
                 })
                 .collect();
 
-            Ok(Json(json!({ "balances": balances })))
+            Ok(Encoded(format, json!({ "balances": balances })))
         }
         Err(e) => {
             tracing::error!("Failed to get balance: {}", e);
-            Err((StatusCode::BAD_GATEWAY, e.to_string()))
+            Err(ApiError::Upstream(e.to_string()))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DepthQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// GET /api/market/depth/:symbol?limit=20 - Order Book inkl. Mid-Price und Spread
+pub async fn get_depth(
+    State(state): State<Arc<MarketState>>,
+    format: ResponseFormat,
+    Path(symbol): Path<String>,
+    Query(query): Query<DepthQuery>,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
+    let limit = query.limit.unwrap_or(20);
+
+    match state.mexc_client.get_order_book(&symbol, limit).await {
+        Ok(book) => {
+            let spread_pct = match (book.spread(), book.mid_price()) {
+                (Some(spread), Some(mid)) if mid > 0.0 => Some(spread / mid * 100.0),
+                _ => None,
+            };
+
+            Ok(Encoded(
+                format,
+                json!({
+                    "symbol": symbol,
+                    "bids": book.bids,
+                    "asks": book.asks,
+                    "timestamp": book.timestamp,
+                    "mid_price": book.mid_price(),
+                    "spread": book.spread(),
+                    "spread_pct": spread_pct,
+                }),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Failed to get order book: {}", e);
+            Err(ApiError::Upstream(e.to_string()))
         }
     }
 }
@@ -63,5 +178,110 @@ pub fn market_router(state: Arc<MarketState>) -> Router {
     Router::new()
         .route("/ticker/:symbol", get(get_ticker))
         .route("/balance", get(get_balance))
+        .route("/depth/:symbol", get(get_depth))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server};
+    use axum::Json;
+    use crate::mexc::TickerResponse;
+    use crate::utils::MockClock;
+    use axum::extract::RawQuery;
+
+    #[tokio::test]
+    async fn test_get_depth_returns_book_and_spread() {
+        let router = Router::new().route(
+            "/api/v3/depth",
+            get(|RawQuery(query): RawQuery| async move {
+                assert!(query.unwrap_or_default().contains("limit=50"));
+                Json(json!({
+                    "bids": [["99.5", "2"], ["99.0", "1"]],
+                    "asks": [["100.5", "3"]],
+                    "timestamp": 1700000000000i64,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let state = Arc::new(MarketState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            fallback: StalePriceFallback::default(),
+        });
+
+        let Encoded(_, body) = get_depth(
+            State(state),
+            ResponseFormat::Json,
+            Path("ETHUSDT".to_string()),
+            Query(DepthQuery { limit: Some(30) }),
+        )
+        .await
+        .expect("depth failed");
+
+        assert_eq!(body["bids"], json!([[99.5, 2.0], [99.0, 1.0]]));
+        assert_eq!(body["asks"], json!([[100.5, 3.0]]));
+        assert_eq!(body["timestamp"], 1700000000000i64);
+        assert_eq!(body["mid_price"], 100.0);
+        assert_eq!(body["spread"], 1.0);
+        assert_eq!(body["spread_pct"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_ticker_as_msgpack() {
+        let mexc = Router::new().route(
+            "/api/v3/ticker/24hr",
+            get(|| async { Json(json!({ "symbol": "ETHUSDT", "price": 2500.5, "timestamp": 42 })) }),
+        );
+        let mexc_url = spawn_server(mexc).await;
+        let api_url = spawn_server(market_router(Arc::new(MarketState {
+            mexc_client: Arc::new(mexc_client(&mexc_url)),
+            fallback: StalePriceFallback::default(),
+        })))
+        .await;
+
+        let response = reqwest::Client::new()
+            .get(format!("{}/ticker/ETHUSDT", api_url))
+            .header("Accept", "application/msgpack")
+            .send()
+            .await
+            .expect("request failed");
+
+        assert_eq!(response.headers()["content-type"], "application/msgpack");
+        let bytes = response.bytes().await.expect("body failed");
+        let ticker: TickerResponse = rmp_serde::from_slice(&bytes).expect("invalid msgpack");
+        assert_eq!(ticker.symbol, "ETHUSDT");
+        assert_eq!(ticker.price, 2500.5);
+        assert_eq!(ticker.timestamp, 42);
+    }
+
+    #[tokio::test]
+    async fn test_ticker_serves_stale_cached_price_when_mexc_is_down() {
+        let clock = Arc::new(MockClock::from_millis(1_700_000_000_000));
+        let cache = Arc::new(PriceCache::new(Duration::from_secs(10)).with_clock(clock.clone()));
+        let fallback = StalePriceFallback::new(cache, Duration::from_secs(300));
+        fallback.record("ETHUSDT", 2500.5);
+        clock.advance(Duration::from_secs(42));
+
+        let mexc = Router::new().route(
+            "/api/v3/ticker/24hr",
+            get(|| async { (axum::http::StatusCode::BAD_GATEWAY, "upstream down") }),
+        );
+        let base_url = spawn_server(mexc).await;
+        let state = Arc::new(MarketState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            fallback,
+        });
+
+        let Encoded(_, body) = get_ticker(State(state.clone()), ResponseFormat::Json, Path("ETHUSDT".to_string()))
+            .await
+            .expect("fallback not served");
+        assert_eq!(body["price"], 2500.5);
+        assert_eq!(body["stale"], true);
+        assert_eq!(body["age_ms"], 42_000);
+
+        // Ohne gecachten Preis bleibt es beim Fehler
+        let missing = get_ticker(State(state), ResponseFormat::Json, Path("BTCUSDT".to_string())).await;
+        assert!(matches!(missing, Err(ApiError::Upstream(_))));
+    }
+}
@@ -1,9 +1,29 @@
 pub mod admin;
+pub mod auth;
+pub mod batch;
+pub mod encoding;
+pub mod error;
 pub mod market;
+pub mod pnl;
+pub mod positions;
+pub mod rate_limit;
+pub mod schedule;
+pub mod simulate;
 pub mod status;
 pub mod trading;
+pub mod webhook;
 
-pub use admin::admin_router;
-pub use market::{market_router, MarketState};
+pub use admin::{admin_router, AdminState};
+pub use auth::{AdminAuth, UserAuth};
+pub use batch::{BatchFailure, BatchResult};
+pub use encoding::{Encoded, ResponseFormat};
+pub use error::ApiError;
+pub use market::{market_router, MarketState, StalePriceFallback};
+pub use pnl::{pnl_router, PnlState};
+pub use positions::{positions_router, PositionsState};
+pub use rate_limit::ApiRateLimiter;
+pub use schedule::{schedule_router, ScheduleState};
+pub use simulate::{simulate_router, SimulateState};
 pub use status::{status_router, StatusState};
 pub use trading::{trading_router, TradingState};
+pub use webhook::{webhook_router, ReplayGuard, WebhookState};
@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::api::error::ApiError;
+use crate::storage::DynamoDBStore;
+use crate::trading::pnl::{summarize_by_strategy, summarize_realized_pnl, PnlReport, StrategyPnl};
+
+pub struct PnlState {
+    pub store: Arc<DynamoDBStore>,
+    /// Label für Positionen ohne Strategie (PNL_DEFAULT_STRATEGY)
+    pub default_strategy: String,
+}
+
+#[derive(Deserialize)]
+pub struct PnlQuery {
+    /// Unix ms, default 0
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Unix ms, default jetzt
+    #[serde(default)]
+    pub to: Option<i64>,
+}
+
+impl PnlQuery {
+    /// (from, to) mit Defaults, Err wenn from > to
+    fn range(&self) -> Result<(i64, i64), ApiError> {
+        let from = self.from.unwrap_or(0);
+        let to = self
+            .to
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        if from > to {
+            return Err(ApiError::Validation("from must be <= to".to_string()));
+        }
+        Ok((from, to))
+    }
+}
+
+/// GET /api/v1/pnl/:user_id?from=...&to=... - Realisierter PnL im Zeitraum
+pub async fn get_pnl(
+    State(state): State<Arc<PnlState>>,
+    Path(user_id): Path<String>,
+    Query(query): Query<PnlQuery>,
+) -> Result<Json<PnlReport>, ApiError> {
+    let (from, to) = query.range()?;
+
+    match state.store.query_closed_positions(&user_id, from, to).await {
+        Ok(positions) => Ok(Json(summarize_realized_pnl(&positions))),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            Err(ApiError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// GET /api/v1/pnl/:user_id/by-strategy?from=...&to=... - PnL, Win-Rate und
+/// durchschnittliche Rendite pro Strategie
+pub async fn get_pnl_by_strategy(
+    State(state): State<Arc<PnlState>>,
+    Path(user_id): Path<String>,
+    Query(query): Query<PnlQuery>,
+) -> Result<Json<BTreeMap<String, StrategyPnl>>, ApiError> {
+    let (from, to) = query.range()?;
+
+    match state.store.query_closed_positions(&user_id, from, to).await {
+        Ok(positions) => Ok(Json(summarize_by_strategy(&positions, &state.default_strategy))),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            Err(ApiError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Router für PnL Endpunkte (unter /api/v1)
+pub fn pnl_router(state: Arc<PnlState>) -> Router {
+    Router::new()
+        .route("/pnl/:user_id", get(get_pnl))
+        .route("/pnl/:user_id/by-strategy", get(get_pnl_by_strategy))
+        .with_state(state)
+}
@@ -0,0 +1,522 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, patch},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::error::ApiError;
+use crate::api::encoding::{Encoded, ResponseFormat};
+use crate::api::market::StalePriceFallback;
+use crate::api::trading::NoteRequest;
+use crate::mexc::MexcClient;
+use crate::storage::{DynamoDBStore, PositionItem, ReadConsistency};
+use crate::trading::net_positions;
+
+pub struct PositionsState {
+    pub mexc_client: Arc<MexcClient>,
+    pub store: Arc<DynamoDBStore>,
+    /// Zuletzt bekannte Preise für das Portfolio bei MEXC-Ausfall
+    pub fallback: StalePriceFallback,
+    /// Ab diesem Alter von `updated_at` holt `?refresh=true` neue Preise
+    pub refresh_staleness: Duration,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListPositionsQuery {
+    /// Preise nur bei veralteten Positionen neu laden (statt immer)
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// GET /api/v1/positions/:user_id/:position_id - Einzelne Position mit Live-Kennzahlen
+pub async fn get_position(
+    State(state): State<Arc<PositionsState>>,
+    format: ResponseFormat,
+    Path((user_id, position_id)): Path<(String, String)>,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
+    let mut position = state
+        .store
+        .get_position(&user_id, &position_id, ReadConsistency::Strong)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?
+        .ok_or(ApiError::NotFound("Position not found".to_string()))?;
+
+    // Nur offene Positionen mit Live-Preis neu bewerten
+    if position.status == "open" {
+        let ticker = state
+            .mexc_client
+            .get_ticker(&position.symbol)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get ticker: {}", e);
+                ApiError::Upstream(e.to_string())
+            })?;
+        position.calculate_pnl(ticker.price);
+    }
+
+    let until = position
+        .closed_at
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let time_in_position_ms = (until - position.entry_time).max(0);
+    let distance_to_stop_pct = position.distance_to_stop_pct();
+
+    Ok(Encoded(
+        format,
+        json!({
+            "position": position,
+            "time_in_position_ms": time_in_position_ms,
+            "distance_to_stop_pct": distance_to_stop_pct,
+        }),
+    ))
+}
+
+/// PATCH /api/v1/positions/:user_id/:position_id/note - Journal-Notiz setzen oder löschen
+pub async fn set_position_note(
+    State(state): State<Arc<PositionsState>>,
+    Path((user_id, position_id)): Path<(String, String)>,
+    Json(payload): Json<NoteRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let note = payload.sanitized()?;
+    let mut position = state
+        .store
+        .get_position(&user_id, &position_id, ReadConsistency::Strong)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+        .ok_or(ApiError::NotFound("Position not found".to_string()))?;
+
+    position.note = note;
+    position.updated_at = chrono::Utc::now().to_rfc3339();
+    let storage_error = |e: anyhow::Error| ApiError::Internal(format!("Storage error: {}", e));
+    state.store.put_position(&position).await.map_err(storage_error)?;
+    // Geschlossene Positionen auch im CLOSED#-Record (PnL-Reports) aktualisieren
+    if position.closed_at.is_some() {
+        state.store.put_closed_position(&position).await.map_err(storage_error)?;
+    }
+
+    Ok(Json(json!({
+        "position_id": position.position_id,
+        "note": position.note,
+        "updated_at": position.updated_at,
+    })))
+}
+
+/// GET /api/v1/positions/:user_id?refresh=true - Offene Positionen, Preise per Batch-Ticker
+/// aktualisiert; mit `refresh=true` nur, wenn eine Position älter als die Staleness ist
+pub async fn list_positions(
+    State(state): State<Arc<PositionsState>>,
+    format: ResponseFormat,
+    Path(user_id): Path<String>,
+    Query(query): Query<ListPositionsQuery>,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
+    let mut positions = state
+        .store
+        .query_open_positions(&user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?;
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let oldest_update_age_ms = positions.iter().map(|p| update_age_ms(p, now_ms)).max();
+    let stale = oldest_update_age_ms.is_some_and(|age| age > state.refresh_staleness.as_millis() as i64);
+    let refreshed = !query.refresh || stale;
+
+    let missing_prices = if refreshed {
+        refresh_prices(&state.mexc_client, &mut positions)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get tickers: {}", e);
+                ApiError::Upstream(e.to_string())
+            })?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Encoded(
+        format,
+        json!({
+            "user_id": user_id,
+            "positions": positions,
+            "missing_prices": missing_prices,
+            "refresh": {
+                "conditional": query.refresh,
+                "refreshed": refreshed,
+                "oldest_update_age_ms": oldest_update_age_ms,
+                "staleness_ms": state.refresh_staleness.as_millis() as i64,
+            },
+        }),
+    ))
+}
+
+/// GET /api/v1/portfolio/:user_id - Netto-Exposure pro Symbol (long und short verrechnet)
+/// mit den einzelnen Legs
+pub async fn get_portfolio(
+    State(state): State<Arc<PositionsState>>,
+    format: ResponseFormat,
+    Path(user_id): Path<String>,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
+    let mut positions = state
+        .store
+        .query_open_positions(&user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?;
+
+    let mut stale_prices = Vec::new();
+    let missing_prices = match refresh_prices(&state.mexc_client, &mut positions).await {
+        Ok(missing) => {
+            for position in positions.iter().filter(|p| !missing.contains(&p.symbol)) {
+                state.fallback.record(&position.symbol, position.current_price);
+            }
+            missing
+        }
+        Err(e) => {
+            let (stale, missing) = apply_cached_prices(&state.fallback, &mut positions);
+            if stale.is_empty() && !positions.is_empty() {
+                tracing::error!("Failed to get tickers: {}", e);
+                return Err(ApiError::Upstream(e.to_string()));
+            }
+            tracing::warn!("Failed to get tickers ({}), serving cached prices", e);
+            stale_prices = stale;
+            missing
+        }
+    };
+
+    let net = net_positions(&positions);
+    let total_pnl: f64 = net.iter().map(|p| p.net_pnl).sum();
+
+    Ok(Encoded(
+        format,
+        json!({
+            "user_id": user_id,
+            "positions": net,
+            "total_unrealized_pnl": total_pnl,
+            "missing_prices": missing_prices,
+            "stale_prices": stale_prices,
+        }),
+    ))
+}
+
+/// Alter des gespeicherten `updated_at` in ms (unlesbar gilt als beliebig alt)
+fn update_age_ms(position: &PositionItem, now_ms: i64) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(&position.updated_at)
+        .map(|updated| (now_ms - updated.timestamp_millis()).max(0))
+        .unwrap_or(i64::MAX)
+}
+
+/// Fallback bei MEXC-Ausfall: Positionen mit zuletzt bekannten Preisen bewerten.
+/// Gibt (stale Symbole mit Alter, Symbole ohne Cache-Eintrag) zurück.
+fn apply_cached_prices(
+    fallback: &StalePriceFallback,
+    positions: &mut [PositionItem],
+) -> (Vec<serde_json::Value>, Vec<String>) {
+    let symbols: BTreeSet<String> = positions.iter().map(|p| p.symbol.clone()).collect();
+    let mut stale = Vec::new();
+    let mut missing = Vec::new();
+    for symbol in symbols {
+        let Some((price, age)) = fallback.lookup(&symbol) else {
+            missing.push(symbol);
+            continue;
+        };
+        for position in positions.iter_mut().filter(|p| p.symbol == symbol) {
+            position.calculate_pnl(price);
+        }
+        stale.push(json!({ "symbol": symbol, "age_ms": age.as_millis() as i64 }));
+    }
+    (stale, missing)
+}
+
+/// Preise aller Positionen mit einem Batch-Request aktualisieren.
+/// Symbole ohne Preis in der Antwort behalten den gespeicherten Preis und werden zurückgegeben.
+async fn refresh_prices(
+    mexc_client: &MexcClient,
+    positions: &mut [PositionItem],
+) -> anyhow::Result<Vec<String>> {
+    let symbols: Vec<String> = positions
+        .iter()
+        .map(|p| p.symbol.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let prices = mexc_client.get_tickers(&symbols).await?;
+
+    for position in positions.iter_mut() {
+        if let Some(price) = prices.get(&position.symbol) {
+            position.calculate_pnl(*price);
+        }
+    }
+
+    Ok(symbols
+        .into_iter()
+        .filter(|symbol| !prices.contains_key(symbol))
+        .collect())
+}
+
+/// Router für Positions-Endpunkte (unter /api/v1)
+pub fn positions_router(state: Arc<PositionsState>) -> Router {
+    Router::new()
+        .route("/positions/:user_id", get(list_positions))
+        .route("/portfolio/:user_id", get(get_portfolio))
+        .route("/positions/:user_id/:position_id", get(get_position))
+        .route("/positions/:user_id/:position_id/note", patch(set_position_note))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::Json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn position_item(id: &str, symbol: &str, price: f64) -> serde_json::Value {
+        leg_item(id, symbol, price, 1.0, "long")
+    }
+
+    fn leg_item(id: &str, symbol: &str, price: f64, quantity: f64, side: &str) -> serde_json::Value {
+        json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": format!("POSITION#1#{}", id) },
+            "position_id": { "S": id },
+            "symbol": { "S": symbol },
+            "entry_price": { "N": price.to_string() },
+            "current_price": { "N": price.to_string() },
+            "quantity": { "N": quantity.to_string() },
+            "side": { "S": side },
+            "entry_time": { "N": "1" },
+            "status": { "S": "open" },
+            "updated_at": { "S": "2024-01-01T00:00:00Z" },
+            "ttl": { "N": "0" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_positions_uses_one_batched_ticker_fetch() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/ticker/price",
+            get({
+                let calls = calls.clone();
+                move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Json(json!([
+                        { "symbol": "ETHUSDT", "price": "110" },
+                        { "symbol": "OTHERUSDT", "price": "1" },
+                    ]))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 3,
+                "Items": [
+                    position_item("pos-1", "ETHUSDT", 100.0),
+                    position_item("pos-2", "ETHUSDT", 105.0),
+                    position_item("pos-3", "GONEUSDT", 2.0),
+                ]
+            }),
+        );
+
+        let state = Arc::new(PositionsState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            store: Arc::new(dynamo.store().await),
+            fallback: StalePriceFallback::default(),
+            refresh_staleness: Duration::from_secs(30),
+        });
+
+        let Encoded(_, body) = list_positions(
+            State(state),
+            ResponseFormat::Json,
+            Path("user-1".to_string()),
+            Query(ListPositionsQuery::default()),
+        )
+        .await
+        .expect("listing failed");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(body["positions"][0]["current_price"], 110.0);
+        assert_eq!(body["positions"][1]["pnl"], 5.0);
+        assert_eq!(body["positions"][2]["current_price"], 2.0);
+        assert_eq!(body["missing_prices"], json!(["GONEUSDT"]));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_refresh_depends_on_updated_at() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/ticker/price",
+            get({
+                let calls = calls.clone();
+                move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Json(json!([{ "symbol": "ETHUSDT", "price": "110" }]))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let state = Arc::new(PositionsState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            store: Arc::new(dynamo.store().await),
+            fallback: StalePriceFallback::default(),
+            refresh_staleness: Duration::from_secs(30),
+        });
+        let list = || {
+            list_positions(
+                State(state.clone()),
+                ResponseFormat::Json,
+                Path("user-1".to_string()),
+                Query(ListPositionsQuery { refresh: true }),
+            )
+        };
+
+        // Vor 5 Sekunden aktualisiert -> kein MEXC-Call
+        let mut fresh = position_item("pos-1", "ETHUSDT", 100.0);
+        fresh["updated_at"] = json!({ "S": (chrono::Utc::now() - chrono::Duration::seconds(5)).to_rfc3339() });
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [fresh] }));
+        let Encoded(_, body) = list().await.expect("listing failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(body["refresh"]["refreshed"], false);
+        assert_eq!(body["positions"][0]["current_price"], 100.0);
+
+        // updated_at von 2024 -> Preise werden geholt
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [position_item("pos-1", "ETHUSDT", 100.0)] }));
+        let Encoded(_, body) = list().await.expect("listing failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(body["refresh"]["refreshed"], true);
+        assert!(body["refresh"]["oldest_update_age_ms"].as_i64().unwrap() > 30_000);
+        assert_eq!(body["positions"][0]["current_price"], 110.0);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_nets_long_and_short_legs() {
+        let router = Router::new().route(
+            "/api/v3/ticker/price",
+            get(|| async { Json(json!([{ "symbol": "ETHUSDT", "price": "120" }])) }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 2,
+                "Items": [
+                    leg_item("long-1", "ETHUSDT", 100.0, 2.0, "long"),
+                    leg_item("short-1", "ETHUSDT", 110.0, 1.0, "short"),
+                ]
+            }),
+        );
+
+        let state = Arc::new(PositionsState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            store: Arc::new(dynamo.store().await),
+            fallback: StalePriceFallback::default(),
+            refresh_staleness: Duration::from_secs(30),
+        });
+
+        let Encoded(_, body) = get_portfolio(State(state), ResponseFormat::Json, Path("user-1".to_string()))
+            .await
+            .expect("portfolio failed");
+
+        // Long 2 @100 (+40) und Short 1 @110 (-10) -> netto long 1 @90, PnL +30
+        let eth = &body["positions"][0];
+        assert_eq!(eth["symbol"], "ETHUSDT");
+        assert_eq!(eth["side"], "long");
+        assert_eq!(eth["net_quantity"], 1.0);
+        assert_eq!(eth["blended_entry"], 90.0);
+        assert_eq!(eth["net_pnl"], 30.0);
+        assert_eq!(eth["legs"].as_array().unwrap().len(), 2);
+        assert_eq!(eth["legs"][1]["pnl"], -10.0);
+        assert_eq!(body["total_unrealized_pnl"], 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_position_refreshes_price() {
+        let router = Router::new().route(
+            "/api/v3/ticker/24hr",
+            get(|| async { Json(json!({ "symbol": "ETHUSDT", "price": 110.0, "timestamp": 0 })) }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 1,
+                "Items": [{
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": "POSITION#1#pos-1" },
+                    "position_id": { "S": "pos-1" },
+                    "symbol": { "S": "ETHUSDT" },
+                    "entry_price": { "N": "100" },
+                    "current_price": { "N": "100" },
+                    "quantity": { "N": "2" },
+                    "side": { "S": "long" },
+                    "entry_time": { "N": "1" },
+                    "status": { "S": "open" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "stop_price": { "N": "99" },
+                    "ttl": { "N": "0" }
+                }]
+            }),
+        );
+
+        let state = Arc::new(PositionsState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            store: Arc::new(dynamo.store().await),
+            fallback: StalePriceFallback::default(),
+            refresh_staleness: Duration::from_secs(30),
+        });
+
+        let Encoded(_, body) = get_position(
+            State(state),
+            ResponseFormat::Json,
+            Path(("user-1".to_string(), "pos-1".to_string())),
+        )
+        .await
+        .expect("position lookup failed");
+
+        assert_eq!(body["position"]["current_price"], 110.0);
+        assert_eq!(body["position"]["pnl"], 20.0);
+        assert_eq!(body["distance_to_stop_pct"], 10.0);
+        assert!(body["time_in_position_ms"].as_i64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_position_not_found() {
+        let dynamo = MockDynamo::start().await;
+        let state = Arc::new(PositionsState {
+            mexc_client: Arc::new(mexc_client("http://127.0.0.1:9")),
+            store: Arc::new(dynamo.store().await),
+            fallback: StalePriceFallback::default(),
+            refresh_staleness: Duration::from_secs(30),
+        });
+
+        let err = get_position(
+            State(state),
+            ResponseFormat::Json,
+            Path(("user-1".to_string(), "missing".to_string())),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,167 @@
+use axum::{
+    extract::{ConnectInfo, RawPathParams, Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::api::error::ApiError;
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::Config;
+
+/// Ab dieser Anzahl Buckets werden volle (inaktive) Buckets verworfen
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_ms: i64,
+}
+
+/// Token-Bucket pro User (bzw. Client-IP ohne User in der Route):
+/// `rps` Requests pro Sekunde, kurzfristig bis zu `burst`
+pub struct ApiRateLimiter {
+    rps: f64,
+    burst: f64,
+    clock: Arc<dyn Clock>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl ApiRateLimiter {
+    pub fn new(rps: f64, burst: u32) -> Self {
+        Self {
+            rps,
+            burst: f64::from(burst.max(1)),
+            clock: Arc::new(SystemClock),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Nur wenn API_RATE_LIMIT_RPS > 0
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        (config.api_rate_limit_rps > 0.0)
+            .then(|| Arc::new(Self::new(config.api_rate_limit_rps, config.api_rate_limit_burst)))
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Einen Token für `key` verbrauchen; Err(Wartezeit) wenn der Bucket leer ist
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = self.clock.now_millis();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            let (rps, burst) = (self.rps, self.burst);
+            buckets.retain(|_, b| b.tokens + (now - b.updated_ms) as f64 / 1000.0 * rps < burst);
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated_ms: now,
+        });
+        let elapsed = (now - bucket.updated_ms).max(0) as f64 / 1000.0;
+        bucket.tokens = (bucket.tokens + elapsed * self.rps).min(self.burst);
+        bucket.updated_ms = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rps))
+        }
+    }
+}
+
+/// Schlüssel des Buckets: `user_id` aus der Route, sonst die Client-IP
+fn client_key(params: &RawPathParams, req: &Request) -> String {
+    if let Some((_, user_id)) = params.iter().find(|(name, _)| *name == "user_id") {
+        return format!("user:{}", user_id);
+    }
+
+    let forwarded = req
+        .headers()
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+    let peer = || {
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0.ip().to_string())
+    };
+    format!("ip:{}", forwarded.or_else(peer).unwrap_or_else(|| "unknown".to_string()))
+}
+
+/// Middleware: 429 mit Retry-After (ganze Sekunden) bei leerem Bucket
+pub async fn rate_limit(
+    State(limiter): State<Arc<ApiRateLimiter>>,
+    params: RawPathParams,
+    req: Request,
+    next: Next,
+) -> Response {
+    let key = client_key(&params, &req);
+    if let Err(wait) = limiter.check(&key) {
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        tracing::warn!("Rate limit exceeded for {}, retry after {}s", key, retry_after);
+
+        let mut response =
+            ApiError::RateLimited(format!("Too many requests, retry after {}s", retry_after)).into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_server;
+    use crate::utils::clock::MockClock;
+    use axum::{middleware, routing::get, Router};
+
+    #[tokio::test]
+    async fn test_burst_gets_429_and_bucket_refills() {
+        let clock = Arc::new(MockClock::from_millis(0));
+        let limiter = Arc::new(ApiRateLimiter::new(2.0, 3).with_clock(clock.clone()));
+        let router = Router::new()
+            .route("/order/:user_id", get(|| async { "ok" }))
+            .route_layer(middleware::from_fn_with_state(limiter, rate_limit));
+        let base_url = spawn_server(router).await;
+        let http = reqwest::Client::new();
+        let get_order = |user: &str| http.get(format!("{}/order/{}", base_url, user)).send();
+
+        for _ in 0..3 {
+            assert_eq!(get_order("user-1").await.unwrap().status(), 200);
+        }
+        let limited = get_order("user-1").await.unwrap();
+        assert_eq!(limited.status(), 429);
+        assert_eq!(limited.headers()["retry-after"], "1");
+        let body: serde_json::Value = limited.json().await.unwrap();
+        assert_eq!(body["error"]["type"], "rate_limited");
+        assert_eq!(body["code"], 429);
+
+        // Eigener Bucket pro User
+        assert_eq!(get_order("user-2").await.unwrap().status(), 200);
+
+        // 2 RPS: nach 500ms ist genau ein Token nachgefüllt
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(get_order("user-1").await.unwrap().status(), 200);
+        assert_eq!(get_order("user-1").await.unwrap().status(), 429);
+
+        clock.advance(Duration::from_secs(10));
+        for _ in 0..3 {
+            assert_eq!(get_order("user-1").await.unwrap().status(), 200);
+        }
+    }
+}
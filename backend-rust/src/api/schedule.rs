@@ -0,0 +1,453 @@
+use axum::{
+    extract::{Path, State},
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::auth::{require_admin, AdminAuth};
+use crate::api::encoding::{Encoded, ResponseFormat};
+use crate::api::error::ApiError;
+use crate::storage::{CalendarEventItem, DynamoDBStore};
+use crate::trading::{SnipeOrderParams, SnipeOutcome, SnipingManager};
+use crate::utils::Config;
+
+/// Bereits gefeuerte Snipes bleiben so lange in der Übersicht sichtbar
+const RECENT_WINDOW_MS: i64 = 15 * 60_000;
+
+pub struct ScheduleState {
+    pub store: Arc<DynamoDBStore>,
+    /// Für manuelles Feuern (`/fire`)
+    pub sniper: Arc<SnipingManager>,
+    /// Overrides (skip/fire) nur mit Admin-Token
+    pub auth: Arc<AdminAuth>,
+    /// Wie weit der Scheduler vorausplant (SCHEDULER_LOOKAHEAD_HOURS)
+    pub lookahead: Duration,
+    /// Snipe-Parameter, mit denen gefeuert wird
+    pub entry_tiers: Vec<String>,
+    pub max_attempts: u32,
+    pub validate_before_fire: bool,
+}
+
+impl ScheduleState {
+    pub fn from_config(
+        store: Arc<DynamoDBStore>,
+        sniper: Arc<SnipingManager>,
+        auth: Arc<AdminAuth>,
+        config: &Config,
+    ) -> Self {
+        Self {
+            store,
+            sniper,
+            auth,
+            lookahead: Duration::from_secs(config.scheduler_lookahead_hours * 3600),
+            entry_tiers: config.snipe_entry_tiers.clone(),
+            max_attempts: config.snipe_max_attempts.max(1),
+            validate_before_fire: config.snipe_validate_before_fire,
+        }
+    }
+}
+
+/// Queue-Status eines geplanten Snipes aus dem Event-Status
+fn queue_status(event: &CalendarEventItem) -> &'static str {
+    match event.status.as_str() {
+        "detected" => "pending",
+        "sniping" => "firing",
+        _ => "done",
+    }
+}
+
+/// GET /api/v1/schedule/:user_id - Geplante Snipes (persistierte Calendar Events im
+/// Scheduler-Fenster) nach Feuerzeit, mit Countdown und Status
+pub async fn get_schedule(
+    State(state): State<Arc<ScheduleState>>,
+    format: ResponseFormat,
+    Path(user_id): Path<String>,
+) -> Result<Encoded<serde_json::Value>, ApiError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let window_end = now + state.lookahead.as_millis() as i64;
+    let mut events = state
+        .store
+        .query_calendar_events_by_time(&user_id, now - RECENT_WINDOW_MS, window_end)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?;
+    events.sort_by(|a, b| (a.launch_time, &a.event_id).cmp(&(b.launch_time, &b.event_id)));
+
+    let scheduled: Vec<_> = events
+        .iter()
+        .map(|event| {
+            json!({
+                "event_id": event.event_id,
+                "symbol": event.symbol,
+                "token_name": event.token_name,
+                "fire_at": event.launch_time,
+                "countdown_ms": (event.launch_time - now).max(0),
+                "status": queue_status(event),
+                "event_status": event.status,
+                "attempts": event.attempts,
+                "pattern": event.detected_pattern,
+                "confidence": event.confidence,
+                "executed_orders": event.executed_orders,
+            })
+        })
+        .collect();
+
+    Ok(Encoded(
+        format,
+        json!({
+            "user_id": user_id,
+            "now": now,
+            "scheduled": scheduled,
+            "params": {
+                "entry_tiers": state.entry_tiers,
+                "max_attempts": state.max_attempts,
+                "validate_before_fire": state.validate_before_fire,
+            },
+        }),
+    ))
+}
+
+async fn load_event(
+    state: &ScheduleState,
+    user_id: &str,
+    event_id: &str,
+) -> Result<CalendarEventItem, ApiError> {
+    state
+        .store
+        .get_calendar_event(user_id, event_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?
+        .ok_or(ApiError::NotFound("Scheduled event not found".to_string()))
+}
+
+async fn transition(
+    state: &ScheduleState,
+    event: &CalendarEventItem,
+    from: &str,
+    to: &str,
+) -> Result<bool, ApiError> {
+    state
+        .store
+        .transition_calendar_event_status(event, from, to)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))
+}
+
+/// POST /api/v1/schedule/:user_id/:event_id/skip - Geplanten Snipe auslassen (idempotent).
+/// Läuft über den bedingten Übergang detected -> skipped; hat der Scheduler das Event
+/// bereits beansprucht, gibt es 409.
+pub async fn skip_scheduled(
+    State(state): State<Arc<ScheduleState>>,
+    Path((user_id, event_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut event = load_event(&state, &user_id, &event_id).await?;
+    let changed = event.status == "detected" && transition(&state, &event, "detected", "skipped").await?;
+    if !changed {
+        // Race mit dem Scheduler: aktuellen Stand neu lesen
+        event = load_event(&state, &user_id, &event_id).await?;
+        if event.status != "skipped" {
+            return Err(ApiError::Conflict(format!(
+                "Event {} is {} and can no longer be skipped",
+                event_id, event.status
+            )));
+        }
+    }
+    tracing::info!("Scheduled snipe {} for {} skipped manually", event_id, event.symbol);
+
+    Ok(Json(json!({
+        "event_id": event_id,
+        "status": "skipped",
+        "changed": changed,
+    })))
+}
+
+/// POST /api/v1/schedule/:user_id/:event_id/fire - Geplanten Snipe sofort feuern.
+/// Das Event wird per detected -> sniping beansprucht, damit Scheduler und Override
+/// nie beide kaufen. Scheitert der Snipe vor dem Senden der Order, geht es zurück
+/// an den Scheduler; kann die Order MEXC erreicht haben -> needs_reconcile.
+pub async fn fire_scheduled(
+    State(state): State<Arc<ScheduleState>>,
+    Path((user_id, event_id)): Path<(String, String)>,
+    Json(mut params): Json<SnipeOrderParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if params.quantity <= 0.0 {
+        return Err(ApiError::Validation("quantity must be > 0".to_string()));
+    }
+    // Manuelles Feuern gilt als Bestätigung (auch für zurückgehaltene Re-Listings)
+    params.confirmed = true;
+    let mut event = load_event(&state, &user_id, &event_id).await?;
+    event.attempts += 1;
+    if event.status != "detected" || !transition(&state, &event, "detected", "sniping").await? {
+        let current = load_event(&state, &user_id, &event_id).await?;
+        return Err(ApiError::Conflict(format!(
+            "Event {} is {} and cannot be fired",
+            event_id, current.status
+        )));
+    }
+    tracing::info!("Firing scheduled snipe {} for {} manually", event_id, event.symbol);
+
+    let outcome = match state.sniper.execute_snipe(&user_id, &event, params).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            tracing::error!("Manual snipe for {} failed: {}", event.symbol, e);
+            // Order evtl. platziert: nicht an den Scheduler zurückgeben, sonst kauft er erneut
+            let reconcile = state
+                .sniper
+                .reconcile_if_placed(&event, &e)
+                .await
+                .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))?;
+            match reconcile {
+                Some(outcome) => outcome,
+                None => {
+                    transition(&state, &event, "sniping", "detected").await?;
+                    return Err(ApiError::Upstream(e.to_string()));
+                }
+            }
+        }
+    };
+
+    Ok(Json(json!({
+        "event_id": event_id,
+        "outcome": outcome_json(&outcome),
+    })))
+}
+
+fn outcome_json(outcome: &SnipeOutcome) -> serde_json::Value {
+    match outcome {
+        SnipeOutcome::Executed { order_id, entry_tier } => {
+            json!({ "result": "executed", "order_id": order_id, "entry_tier": entry_tier.as_str() })
+        }
+        SnipeOutcome::Skipped { reason } => json!({ "result": "skipped", "reason": reason }),
+        SnipeOutcome::Missed { attempts, reason } => {
+            json!({ "result": "missed", "attempts": attempts, "reason": reason })
+        }
+        SnipeOutcome::Aborted {
+            order_id,
+            slippage_pct,
+            unwind_order_id,
+        } => json!({
+            "result": "aborted",
+            "order_id": order_id,
+            "slippage_pct": slippage_pct,
+            "unwind_order_id": unwind_order_id,
+        }),
+        SnipeOutcome::NeedsReconcile { client_order_id, reason } => json!({
+            "result": "needs_reconcile",
+            "client_order_id": client_order_id,
+            "reason": reason,
+        }),
+    }
+}
+
+/// Router für den Scheduler-Überblick und manuelle Overrides (unter /api/v1)
+pub fn schedule_router(state: Arc<ScheduleState>) -> Router {
+    Router::new()
+        .route("/schedule/:user_id/:event_id/skip", post(skip_scheduled))
+        .route("/schedule/:user_id/:event_id/fire", post(fire_scheduled))
+        .route_layer(middleware::from_fn_with_state(state.auth.clone(), require_admin))
+        .route("/schedule/:user_id", get(get_schedule))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn schedule_state(dynamo: &MockDynamo, mexc_url: &str) -> Arc<ScheduleState> {
+        let store = Arc::new(dynamo.store().await);
+        let sniper = SnipingManager::new(Arc::new(mexc_client(mexc_url)), store.clone(), &Config::default());
+        Arc::new(ScheduleState::from_config(
+            store,
+            Arc::new(sniper),
+            Arc::new(AdminAuth::new(Some("admin".to_string()))),
+            &Config::default(),
+        ))
+    }
+
+    fn order_router(orders: Arc<AtomicUsize>) -> Router {
+        Router::new().route(
+            "/api/v3/order",
+            post(move || async move {
+                orders.fetch_add(1, Ordering::SeqCst);
+                Json(json!({
+                    "order_id": "mexc-1",
+                    "symbol": "NEWUSDT",
+                    "side": "BUY",
+                    "order_type": "MARKET",
+                    "quantity": 1.0,
+                    "price": 1.0,
+                    "status": "FILLED",
+                    "filled_qty": 1.0,
+                    "created_at": 0,
+                }))
+            }),
+        )
+    }
+
+    fn buy() -> SnipeOrderParams {
+        SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: None,
+            confirmed: false,
+        }
+    }
+
+    fn event_item(symbol: &str, launch_time: i64, status: &str) -> serde_json::Value {
+        json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": format!("CALENDAR#{}#{}", launch_time, symbol) },
+            "event_id": { "S": format!("{}-event", symbol) },
+            "token_name": { "S": symbol },
+            "symbol": { "S": symbol },
+            "launch_time": { "N": launch_time.to_string() },
+            "detected_pattern": { "S": "sts:2" },
+            "confidence": { "N": "0.9" },
+            "created_at": { "S": "2024-01-01T00:00:00Z" },
+            "status": { "S": status },
+            "ttl": { "N": "0" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_schedule_lists_upcoming_snipes_by_fire_time() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({ "Count": 2, "Items": [
+                event_item("LATERUSDT", now + 3_600_000, "detected"),
+                event_item("SOONUSDT", now + 60_000, "sniping"),
+            ] }),
+        );
+        let state = schedule_state(&dynamo, "http://127.0.0.1:9").await;
+
+        let Encoded(_, body) = get_schedule(State(state), ResponseFormat::Json, Path("user-1".to_string()))
+            .await
+            .expect("schedule failed");
+
+        let scheduled = body["scheduled"].as_array().unwrap();
+        assert_eq!(scheduled.len(), 2);
+        assert_eq!(scheduled[0]["symbol"], "SOONUSDT");
+        assert_eq!(scheduled[0]["status"], "firing");
+        assert_eq!(scheduled[1]["symbol"], "LATERUSDT");
+        assert_eq!(scheduled[1]["status"], "pending");
+        assert_eq!(scheduled[1]["fire_at"], now + 3_600_000);
+        let countdown = scheduled[0]["countdown_ms"].as_i64().unwrap();
+        assert!(countdown > 0 && countdown <= 60_000);
+        assert_eq!(body["params"]["entry_tiers"], json!(["market"]));
+    }
+
+    #[tokio::test]
+    async fn test_skip_before_fire_blocks_firing() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let mexc_url = spawn_server(order_router(orders.clone())).await;
+        let dynamo = MockDynamo::start().await;
+        let state = schedule_state(&dynamo, &mexc_url).await;
+        let launch = chrono::Utc::now().timestamp_millis() + 60_000;
+
+        // Overrides nur mit Admin-Token
+        let api_url = spawn_server(schedule_router(state.clone())).await;
+        let response = reqwest::Client::new()
+            .post(format!("{}/schedule/user-1/NEWUSDT-event/skip", api_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "detected")] }));
+        let Json(body) = skip_scheduled(State(state.clone()), Path(("user-1".to_string(), "NEWUSDT-event".to_string())))
+            .await
+            .expect("skip failed");
+        assert_eq!(body["changed"], true);
+        let update = &dynamo.requests("UpdateItem")[0];
+        assert_eq!(update["ConditionExpression"], "#status = :from");
+        assert_eq!(update["ExpressionAttributeValues"][":to"]["S"], "skipped");
+
+        // Zweites Skip ist idempotent, Fire danach ein Konflikt ohne Order
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "skipped")] }));
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "skipped")] }));
+        let Json(body) = skip_scheduled(State(state.clone()), Path(("user-1".to_string(), "NEWUSDT-event".to_string())))
+            .await
+            .expect("repeated skip failed");
+        assert_eq!(body["changed"], false);
+
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "skipped")] }));
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "skipped")] }));
+        let fired = fire_scheduled(
+            State(state),
+            Path(("user-1".to_string(), "NEWUSDT-event".to_string())),
+            Json(buy()),
+        )
+        .await;
+        assert!(matches!(fired, Err(ApiError::Conflict(_))));
+        assert_eq!(orders.load(Ordering::SeqCst), 0);
+        assert_eq!(dynamo.requests("UpdateItem").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_force_fire_claims_event_once() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let mexc_url = spawn_server(order_router(orders.clone())).await;
+        let dynamo = MockDynamo::start().await;
+        let state = schedule_state(&dynamo, &mexc_url).await;
+        let launch = chrono::Utc::now().timestamp_millis() + 3_600_000;
+        let path = || Path(("user-1".to_string(), "NEWUSDT-event".to_string()));
+
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "detected")] }));
+        let Json(body) = fire_scheduled(State(state.clone()), path(), Json(buy()))
+            .await
+            .expect("fire failed");
+        assert_eq!(body["outcome"]["result"], "executed");
+        assert_eq!(body["outcome"]["entry_tier"], "market");
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+        let claim = &dynamo.requests("UpdateItem")[0];
+        assert_eq!(claim["ExpressionAttributeValues"][":from"]["S"], "detected");
+        assert_eq!(claim["ExpressionAttributeValues"][":to"]["S"], "sniping");
+
+        // Scheduler hat das Event zwischen Lesen und Claim beansprucht: keine zweite Order
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "detected")] }));
+        dynamo.respond_error("UpdateItem", "ConditionalCheckFailedException");
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "sniping")] }));
+        let raced = fire_scheduled(State(state), path(), Json(buy())).await;
+        assert!(matches!(raced, Err(ApiError::Conflict(message)) if message.contains("sniping")));
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fire_after_fill_is_not_released_to_scheduler() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let mexc_url = spawn_server(order_router(orders.clone())).await;
+        let dynamo = MockDynamo::start().await;
+        let state = schedule_state(&dynamo, &mexc_url).await;
+        let launch = chrono::Utc::now().timestamp_millis() + 3_600_000;
+
+        // Order gefüllt, Speichern schlägt fehl
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("NEWUSDT", launch, "detected")] }));
+        dynamo.respond_error("PutItem", "ValidationException");
+        let Json(body) = fire_scheduled(
+            State(state),
+            Path(("user-1".to_string(), "NEWUSDT-event".to_string())),
+            Json(buy()),
+        )
+        .await
+        .expect("fire failed");
+
+        assert_eq!(body["outcome"]["result"], "needs_reconcile");
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+        let updates = dynamo.requests("UpdateItem");
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1]["ExpressionAttributeValues"][":to"]["S"], "needs_reconcile");
+    }
+}
@@ -0,0 +1,142 @@
+use axum::{
+    extract::{Path, State},
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::api::error::ApiError;
+use crate::mexc::MexcClient;
+use crate::utils::RuntimeSettings;
+use crate::trading::sizing::{calculate_position_size, RoundingMode, SizingSettings};
+
+pub struct SimulateState {
+    pub mexc_client: Arc<MexcClient>,
+    pub sizing: SizingSettings,
+    pub quote_asset: String,
+    /// Hot-reloadbares Risiko pro Trade
+    pub runtime: Arc<RuntimeSettings>,
+}
+
+#[derive(Deserialize)]
+pub struct SizingRequest {
+    pub symbol: String,
+    pub confidence: f64,
+    /// Überschreibt die konfigurierte Rundung
+    #[serde(default)]
+    pub rounding: Option<RoundingMode>,
+}
+
+/// POST /api/v1/simulate/sizing/:user_id - Was-wäre-wenn Positionsgröße (ohne Order)
+pub async fn simulate_sizing(
+    State(state): State<Arc<SimulateState>>,
+    Path(user_id): Path<String>,
+    Json(payload): Json<SizingRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if !(0.0..=1.0).contains(&payload.confidence) {
+        return Err(ApiError::Validation("Confidence must be between 0 and 1".to_string()));
+    }
+
+    let (balance, ticker) = tokio::join!(
+        state.mexc_client.get_account_balance(),
+        state.mexc_client.get_ticker(&payload.symbol),
+    );
+
+    let balance = balance.map_err(|e| {
+        tracing::error!("Failed to get balance: {}", e);
+        ApiError::Upstream(e.to_string())
+    })?;
+    let ticker = ticker.map_err(|e| {
+        tracing::error!("Failed to get ticker: {}", e);
+        ApiError::Upstream(e.to_string())
+    })?;
+
+    let free_balance = balance.free_of(&state.quote_asset);
+
+    let settings = SizingSettings {
+        risk_pct: state.runtime.load().risk_per_trade_pct,
+        rounding: payload.rounding.unwrap_or(state.sizing.rounding),
+        ..state.sizing.clone()
+    };
+    let sizing = calculate_position_size(free_balance, ticker.price, payload.confidence, &settings);
+
+    Ok(Json(json!({
+        "user_id": user_id,
+        "symbol": payload.symbol,
+        "quote_asset": state.quote_asset,
+        "sizing": sizing,
+    })))
+}
+
+/// Router für Simulations-Endpunkte (unter /api/v1)
+pub fn simulate_router(state: Arc<SimulateState>) -> Router {
+    Router::new()
+        .route("/simulate/sizing/:user_id", post(simulate_sizing))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server};
+    use axum::routing::get;
+
+    #[tokio::test]
+    async fn test_simulate_sizing_is_deterministic() {
+        let router = Router::new()
+            .route(
+                "/api/v3/account",
+                get(|| async {
+                    Json(json!({
+                        "balances": [
+                            { "asset": "BTC", "free": 1.0, "locked": 0.0 },
+                            { "asset": "USDT", "free": 1000.0, "locked": 50.0 },
+                        ]
+                    }))
+                }),
+            )
+            .route(
+                "/api/v3/ticker/24hr",
+                get(|| async {
+                    Json(json!({ "symbol": "NEWUSDT", "price": 3.0, "timestamp": 0 }))
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let state = Arc::new(SimulateState {
+            mexc_client: Arc::new(mexc_client(&base_url)),
+            sizing: SizingSettings {
+                risk_pct: 10.0,
+                step_size: 0.01,
+                rounding: RoundingMode::Down,
+                base_capital: 0.0,
+                compounding: false,
+                compounding_max_multiple: 0.0,
+            },
+            quote_asset: "USDT".to_string(),
+            runtime: Arc::new(RuntimeSettings::new(&crate::utils::Config {
+                risk_per_trade_pct: 10.0,
+                ..Default::default()
+            })),
+        });
+
+        let Json(body) = simulate_sizing(
+            State(state),
+            Path("user-1".to_string()),
+            Json(SizingRequest {
+                symbol: "NEWUSDT".to_string(),
+                confidence: 0.8,
+                rounding: None,
+            }),
+        )
+        .await
+        .expect("simulation failed");
+
+        assert_eq!(body["sizing"]["free_balance"], 1000.0);
+        assert_eq!(body["sizing"]["risk_pct"], 10.0);
+        assert_eq!(body["sizing"]["price"], 3.0);
+        assert_eq!(body["sizing"]["step_size"], 0.01);
+        assert_eq!(body["sizing"]["quantity"], 26.66);
+    }
+}
@@ -6,15 +6,31 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
 
-use crate::mexc::MexcClient;
+use crate::mexc::models::MEXC_RECV_WINDOW_MS;
+use crate::mexc::{MexcClient, QueueDepth};
 
 /// Shared State für den Status-Endpunkt
 pub struct StatusState {
     pub mexc_client: Arc<MexcClient>,
     /// Unix-Timestamp beim Start des Servers
     pub started_at: u64,
+    /// Zusätzlich den signierten Write-Path prüfen (offene Orders abfragen)
+    pub check_write_path: bool,
+    /// Wie lange ein MEXC-Probe wiederverwendet wird (0 = kein Cache)
+    pub health_cache_ttl: Duration,
+    /// Ab diesem Uhr-Offset zu MEXC (ms) ist `time_sync` ungesund
+    pub time_offset_warn_ms: i64,
+    health_cache: Mutex<Option<CachedProbe>>,
+}
+
+/// Letztes MEXC-Probe-Ergebnis
+struct CachedProbe {
+    checked_at: Instant,
+    read: ComponentHealth,
+    write: Option<ComponentHealth>,
 }
 
 impl StatusState {
@@ -23,7 +39,67 @@ impl StatusState {
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
-        Self { mexc_client, started_at }
+        Self {
+            mexc_client,
+            started_at,
+            check_write_path: false,
+            health_cache_ttl: Duration::ZERO,
+            time_offset_warn_ms: 1_000,
+            health_cache: Mutex::new(None),
+        }
+    }
+
+    /// Probe-Ergebnisse für `ttl` cachen (z.B. gegen häufige K8s-Probes)
+    pub fn with_health_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.health_cache_ttl = ttl;
+        self
+    }
+
+    /// MEXC Read/Write Health, innerhalb der TTL aus dem Cache.
+    /// Gibt zusätzlich das Alter des Probes in ms zurück.
+    async fn mexc_health(&self) -> (ComponentHealth, Option<ComponentHealth>, u64) {
+        // Lock über den Probe halten, damit parallele Requests nur einmal pingen
+        let mut cache = self.health_cache.lock().await;
+        if let Some(cached) = cache.as_ref() {
+            let age = cached.checked_at.elapsed();
+            if age < self.health_cache_ttl {
+                return (cached.read.clone(), cached.write.clone(), age.as_millis() as u64);
+            }
+        }
+
+        // MEXC-Connectivity prüfen (schneller Ping via Ticker-Abfrage)
+        let start = Instant::now();
+        let read = ComponentHealth::from_result(self.mexc_client.get_ticker("BTCUSDT").await, start);
+
+        // Write-Path: signierte Abfrage ohne Order-Ausführung
+        let write = if self.check_write_path {
+            let start = Instant::now();
+            Some(ComponentHealth::from_result(
+                self.mexc_client.get_open_orders("BTCUSDT").await,
+                start,
+            ))
+        } else {
+            None
+        };
+
+        *cache = Some(CachedProbe {
+            checked_at: Instant::now(),
+            read: read.clone(),
+            write: write.clone(),
+        });
+        (read, write, 0)
+    }
+
+    /// Warnschwelle für den Uhr-Offset zu MEXC (ms)
+    pub fn with_time_offset_warn(mut self, warn_ms: i64) -> Self {
+        self.time_offset_warn_ms = warn_ms;
+        self
+    }
+
+    /// Write-Path Health Check aktivieren
+    pub fn with_write_check(mut self, enabled: bool) -> Self {
+        self.check_write_path = enabled;
+        self
     }
 }
 
@@ -36,14 +112,62 @@ pub struct BotStatus {
     pub timestamp: String,
     pub connections: ConnectionStatus,
     pub services: ServiceStatus,
+    #[serde(default)]
+    pub time_sync: TimeSyncStatus,
+}
+
+/// Gemessener Uhr-Offset zu MEXC (aus der Zeit-Synchronisation)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeSyncStatus {
+    /// Serverzeit minus lokale Zeit (ms)
+    pub offset_ms: i64,
+    /// None, solange noch nie synchronisiert wurde
+    pub last_sync_ago_secs: Option<u64>,
+    /// Offset unter der Warnschwelle
+    pub healthy: bool,
+}
+
+impl TimeSyncStatus {
+    pub fn evaluate(offset_ms: i64, last_sync: Option<i64>, now_ms: i64, warn_ms: i64) -> Self {
+        Self {
+            offset_ms,
+            last_sync_ago_secs: last_sync.map(|t| (now_ms - t).max(0) as u64 / 1000),
+            healthy: offset_ms.abs() < warn_ms,
+        }
+    }
+
+    /// Offset ab der Hälfte des recvWindow: ein kleiner Drift seit dem letzten Sync
+    /// reicht dann, damit MEXC signierte Requests ablehnt
+    pub fn is_dangerous(&self) -> bool {
+        self.offset_ms.abs() >= MEXC_RECV_WINDOW_MS / 2
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ConnectionStatus {
+    /// Alter des (ggf. gecachten) MEXC-Probes in ms
+    #[serde(default)]
+    pub probe_age_ms: u64,
+    /// Lesender Zugriff (Ticker)
     pub mexc_api: ComponentHealth,
+    /// Signierter Zugriff (Auth/Signing), nur wenn aktiviert
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mexc_api_write: Option<ComponentHealth>,
+    /// Zuletzt von MEXC gemeldetes Used-Weight (X-MBX-USED-WEIGHT)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mexc_used_weight: Option<u32>,
+    /// Wartende Requests in der MEXC Request-Queue
+    #[serde(default)]
+    pub mexc_queue_depth: QueueDepth,
+    /// MEXC-Wartung erkannt (Hintergrund-Polling pausiert)
+    #[serde(default)]
+    pub mexc_maintenance: bool,
+    /// Beginn der Wartung (Unix ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mexc_maintenance_since: Option<i64>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ComponentHealth {
     pub healthy: bool,
     pub latency_ms: Option<u64>,
@@ -57,6 +181,29 @@ pub struct ServiceStatus {
     pub storage: String,
 }
 
+impl ComponentHealth {
+    fn from_result<T>(result: anyhow::Result<T>, start: Instant) -> Self {
+        let latency_ms = Some(start.elapsed().as_millis() as u64);
+        match result {
+            Ok(_) => Self {
+                healthy: true,
+                latency_ms,
+                error: None,
+            },
+            Err(e) => Self {
+                healthy: false,
+                latency_ms,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+/// Gesamtstatus: gesund nur wenn Read und (falls geprüft) Write gesund sind
+pub fn is_overall_healthy(read: &ComponentHealth, write: Option<&ComponentHealth>) -> bool {
+    read.healthy && write.is_none_or(|w| w.healthy)
+}
+
 /// GET /api/v1/status – Vollständiger Bot-Status
 pub async fn get_status(
     State(state): State<Arc<StatusState>>,
@@ -67,27 +214,27 @@ pub async fn get_status(
         .as_secs();
     let uptime = now.saturating_sub(state.started_at);
 
-    // MEXC-Connectivity prüfen (schneller Ping via Ticker-Abfrage)
-    let mexc_health = {
-        let start = std::time::Instant::now();
-        match state.mexc_client.get_ticker("BTCUSDT").await {
-            Ok(_) => ComponentHealth {
-                healthy: true,
-                latency_ms: Some(start.elapsed().as_millis() as u64),
-                error: None,
-            },
-            Err(e) => ComponentHealth {
-                healthy: false,
-                latency_ms: Some(start.elapsed().as_millis() as u64),
-                error: Some(e.to_string()),
-            },
-        }
-    };
+    let (mexc_health, mexc_write_health, probe_age_ms) = state.mexc_health().await;
 
-    let overall_healthy = mexc_health.healthy;
+    let overall_healthy = is_overall_healthy(&mexc_health, mexc_write_health.as_ref());
+    let maintenance_since = state.mexc_client.maintenance().since();
+    let time_sync = TimeSyncStatus::evaluate(
+        state.mexc_client.time_offset_ms(),
+        state.mexc_client.last_time_sync(),
+        chrono::Utc::now().timestamp_millis(),
+        state.time_offset_warn_ms,
+    );
+    let trading_status = match &mexc_write_health {
+        _ if maintenance_since.is_some() => "paused",
+        Some(write) if !write.healthy => "degraded",
+        _ if time_sync.is_dangerous() => "degraded",
+        _ => "operational",
+    };
 
     let body = BotStatus {
-        status: if overall_healthy {
+        status: if maintenance_since.is_some() {
+            "maintenance".to_string()
+        } else if overall_healthy && !time_sync.is_dangerous() {
             "healthy".to_string()
         } else {
             "degraded".to_string()
@@ -97,13 +244,20 @@ pub async fn get_status(
         started_at: state.started_at,
         timestamp: chrono::Utc::now().to_rfc3339(),
         connections: ConnectionStatus {
+            probe_age_ms,
             mexc_api: mexc_health,
+            mexc_api_write: mexc_write_health,
+            mexc_used_weight: state.mexc_client.rate_limit().used_weight(),
+            mexc_queue_depth: state.mexc_client.queue_depth(),
+            mexc_maintenance: maintenance_since.is_some(),
+            mexc_maintenance_since: maintenance_since,
         },
         services: ServiceStatus {
-            trading: "operational".to_string(),
+            trading: trading_status.to_string(),
             market_data: "operational".to_string(),
             storage: "operational".to_string(),
         },
+        time_sync,
     };
 
     let http_status = if overall_healthy {
@@ -135,3 +289,99 @@ pub fn status_router(state: Arc<StatusState>) -> Router {
         .route("/settings", get(get_settings))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn health(healthy: bool) -> ComponentHealth {
+        ComponentHealth {
+            healthy,
+            latency_ms: Some(1),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_overall_health_combines_read_and_write() {
+        assert!(is_overall_healthy(&health(true), None));
+        assert!(is_overall_healthy(&health(true), Some(&health(true))));
+        assert!(!is_overall_healthy(&health(true), Some(&health(false))));
+        assert!(!is_overall_healthy(&health(false), Some(&health(true))));
+        assert!(!is_overall_healthy(&health(false), None));
+    }
+
+    #[test]
+    fn test_time_sync_health_from_offset() {
+        let now = 1_700_000_060_000;
+        let small = TimeSyncStatus::evaluate(-120, Some(now - 60_000), now, 1_000);
+        assert!(small.healthy);
+        assert!(!small.is_dangerous());
+        assert_eq!(small.last_sync_ago_secs, Some(60));
+
+        // Über der Warnschwelle, aber noch unter dem halben recvWindow
+        let warn = TimeSyncStatus::evaluate(1_500, Some(now), now, 1_000);
+        assert!(!warn.healthy);
+        assert!(!warn.is_dangerous());
+
+        let large = TimeSyncStatus::evaluate(-3_000, None, now, 1_000);
+        assert!(!large.healthy);
+        assert!(large.is_dangerous());
+        assert_eq!(large.last_sync_ago_secs, None);
+    }
+
+    #[tokio::test]
+    async fn test_status_reuses_cached_probe_within_ttl() {
+        let pings = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/ticker/24hr",
+            get({
+                let pings = pings.clone();
+                move || async move {
+                    pings.fetch_add(1, Ordering::SeqCst);
+                    (
+                        [("X-MBX-USED-WEIGHT", "42")],
+                        Json(serde_json::json!({ "symbol": "BTCUSDT", "price": 1.0, "timestamp": 0 })),
+                    )
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let state = Arc::new(
+            StatusState::new(Arc::new(mexc_client(&base_url)))
+                .with_health_cache_ttl(Duration::from_secs(60)),
+        );
+
+        let (status, Json(first)) = get_status(State(state.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(first.connections.probe_age_ms, 0);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let (_, Json(second)) = get_status(State(state)).await;
+
+        assert_eq!(pings.load(Ordering::SeqCst), 1);
+        assert!(second.connections.probe_age_ms >= 20);
+        assert!(second.connections.mexc_api.healthy);
+        assert_eq!(second.connections.mexc_used_weight, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_maintenance() {
+        let router = Router::new().route(
+            "/api/v3/ticker/24hr",
+            get(|| async { (StatusCode::SERVICE_UNAVAILABLE, r#"{"msg":"System maintenance"}"#) }),
+        );
+        let base_url = spawn_server(router).await;
+        let state = Arc::new(StatusState::new(Arc::new(mexc_client(&base_url))));
+
+        let (status, Json(body)) = get_status(State(state)).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body.status, "maintenance");
+        assert_eq!(body.services.trading, "paused");
+        assert!(body.connections.mexc_maintenance);
+        assert!(body.connections.mexc_maintenance_since.is_some());
+    }
+}
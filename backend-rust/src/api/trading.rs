@@ -1,90 +1,945 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::{delete, get, post},
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Json, Router,
 };
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
 
+use crate::api::auth::UserAuth;
+use crate::api::batch::{BatchFailure, BatchResult};
+use crate::api::error::ApiError;
+use crate::api::rate_limit::{rate_limit, ApiRateLimiter};
 use crate::mexc::models::OrderRequest as MexcOrderRequest;
-use crate::mexc::MexcClient;
-use crate::storage::{DynamoDBStore, OrderItem};
+use crate::mexc::{
+    Credentials, MexcClient, MexcClientPool, MexcError, RejectionCategory, SymbolState, UserClientError,
+};
+use crate::storage::{
+    audit, sanitize_note, DynamoDBStore, OrderConfirmationItem, OrderItem, OrderStatus, OrderWriteBuffer,
+    RawResponseItem, ReadConsistency, WriteSlot,
+};
+use crate::trading::{
+    clamp_reduce_only_quantity, is_closing_side, summarize_fees, BalancePreflight, FillConfirmation,
+    OrderMonitor, PlacementCooldown, PrecisionCache, PreflightError,
+};
+use crate::utils::Config;
 
 pub struct TradingState {
+    /// Globaler Client für Marktdaten
     pub mexc_client: Arc<MexcClient>,
+    /// Client pro User für Orders, Guthaben und Fills
+    pub clients: Arc<MexcClientPool>,
     pub store: Arc<DynamoDBStore>,
+    pub order_monitor: Arc<OrderMonitor>,
+    /// Globales Limit offener Orders je Symbol (0 = unbegrenzt), per User überschreibbar
+    pub max_open_orders_per_symbol: u32,
+    /// Pre-Trade-Guthabenprüfung (None = deaktiviert)
+    pub preflight: Option<Arc<BalancePreflight>>,
+    /// Kurzes Nachpollen bis zum Fill vor der Antwort
+    pub fill_confirmation: FillConfirmation,
+    /// Token-Bucket je User/IP (None = kein Limit)
+    pub rate_limiter: Option<Arc<ApiRateLimiter>>,
+    /// Zweistufige Bestätigung großer Orders
+    pub large_order_confirm: LargeOrderConfirm,
+    /// Orders auf pausierte Symbole vorab ablehnen (exchangeInfo)
+    pub symbol_state_check: bool,
+    /// Aufbewahrung roher MEXC-Order-Responses (None = nicht speichern)
+    pub raw_response_ttl: Option<Duration>,
+    /// Mindestabstand zwischen Orders auf dasselbe Symbol (teilbar mit dem Sniper)
+    pub placement_cooldown: Arc<PlacementCooldown>,
+    /// Order-Writes im Hintergrund statt synchron (None = synchron)
+    pub write_behind: Option<Arc<OrderWriteBuffer>>,
+    /// Batch-Endpoints antworten als `BatchResult` (succeeded/failed mit Fehlercode)
+    pub structured_batch_results: bool,
+    /// JWT-Prüfung für User-eigene Ressourcen (alle Routen mit User-ID)
+    pub user_auth: Arc<UserAuth>,
+    /// Preis-Präzision je Symbol (exchangeInfo), z.B. für Reprice-Preise
+    pub precision: Arc<PrecisionCache>,
+    /// Orders nur simulieren statt an MEXC senden (Fills über den Order Monitor)
+    pub paper_trading: bool,
+    /// Paper-Limit-Orders erst beim Kreuzen des Limits füllen (false = sofort zum Limitpreis)
+    pub paper_simulate_fills: bool,
+}
+
+/// Große Orders (Notional über `threshold`) werden erst nach Bestätigung
+/// platziert; der Token gilt `ttl` lang (Threshold 0 = aus)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargeOrderConfirm {
+    pub threshold: f64,
+    pub ttl: Duration,
+}
+
+impl LargeOrderConfirm {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            threshold: config.large_order_confirm_notional,
+            ttl: Duration::from_secs(config.large_order_confirm_ttl_secs),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0.0
+    }
+
+    pub fn requires_confirmation(&self, notional: f64) -> bool {
+        self.is_enabled() && notional > self.threshold
+    }
 }
 
-/// POST /api/trade/order - Erstelle neue Order
+/// POST /api/trade/order - Erstelle neue Order; über dem Notional-Threshold
+/// nur ein Bestätigungs-Token (202), platziert wird erst per /order/confirm
 pub async fn create_order(
     State(state): State<Arc<TradingState>>,
     Path(user_id): Path<String>,
+    headers: HeaderMap,
     Json(payload): Json<ApiOrderRequest>,
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
     tracing::info!("Creating order for user: {}", user_id);
 
+    if state.large_order_confirm.is_enabled() {
+        let request = serde_json::to_string(&payload).map_err(|e| ApiError::Internal(e.to_string()))?;
+        let (order, _) = prepare_order(&state, &user_id, payload.clone()).await?;
+        let notional = order_notional(&state, &order).await?;
+        if state.large_order_confirm.requires_confirmation(notional) {
+            let body = request_confirmation(&state, &user_id, request, notional).await?;
+            return Ok((StatusCode::ACCEPTED, Json(body)));
+        }
+    }
+
+    let body = place_order(&state, &user_id, payload).await?;
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+/// POST /api/trade/order/confirm - Große Order per Token bestätigen und platzieren
+pub async fn confirm_order(
+    State(state): State<Arc<TradingState>>,
+    headers: HeaderMap,
+    Json(payload): Json<ConfirmOrderRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let pending = state
+        .store
+        .take_order_confirmation(&payload.confirmation_token)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFound("Confirmation token not found".to_string()))?;
+    state.user_auth.authorize(&headers, &pending.user_id)?;
+
+    if pending.expires_at <= chrono::Utc::now().timestamp() {
+        return Err(ApiError::Validation("Confirmation token expired, submit the order again".to_string()));
+    }
+
+    let request: ApiOrderRequest =
+        serde_json::from_str(&pending.request).map_err(|e| ApiError::Internal(e.to_string()))?;
+    tracing::info!(
+        "Confirmed large order ({} {}) for user: {}",
+        pending.notional,
+        request.symbol,
+        pending.user_id
+    );
+
+    let body = place_order(&state, &pending.user_id, request).await?;
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+/// Order-Request für die Bestätigung ablegen und Token zurückgeben
+async fn request_confirmation(
+    state: &TradingState,
+    user_id: &str,
+    request: String,
+    notional: f64,
+) -> Result<serde_json::Value, ApiError> {
+    let pending = OrderConfirmationItem {
+        token: Uuid::new_v4().to_string(),
+        user_id: user_id.to_string(),
+        request,
+        notional,
+        expires_at: chrono::Utc::now().timestamp() + state.large_order_confirm.ttl.as_secs() as i64,
+    };
+    state
+        .store
+        .put_order_confirmation(&pending)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))?;
+    tracing::info!("Order with notional {} awaits confirmation (user {})", notional, user_id);
+
+    Ok(json!({
+        "status": "pending_confirmation",
+        "confirmation_token": pending.token,
+        "notional": notional,
+        "threshold": state.large_order_confirm.threshold,
+        "expires_at": pending.expires_at,
+    }))
+}
+
+/// POST /api/trade/estimate/:user_id - Order wie beim Platzieren validieren, Notional
+/// schätzen und per /api/v3/order/test gegen die MEXC-Filter prüfen (ohne Ausführung)
+pub async fn estimate_order(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<ApiOrderRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let (order, mexc_order) = prepare_order(&state, &user_id, payload).await?;
+    let notional = order_notional(&state, &order).await?;
+
+    let client = user_client(&state, &user_id).await?;
+    if let Err(e) = client.test_order(&mexc_order).await {
+        return Err(match e.downcast_ref::<MexcError>() {
+            // Von MEXC abgelehnt (Filter, Guthaben, ...) -> Fehler im Request
+            Some(rejection) if (400..500).contains(&rejection.status) && rejection.status != 429 => {
+                ApiError::Validation(format!("Order rejected by MEXC: {}", rejection.message))
+            }
+            _ => ApiError::Upstream(e.to_string()),
+        });
+    }
+
+    Ok(Json(json!({
+        "symbol": order.symbol,
+        "side": order.side,
+        "order_type": mexc_order.order_type,
+        "quantity": order.quantity,
+        "quote_order_qty": order.quote_order_qty,
+        "price": order.price,
+        "notional": notional,
+        "valid": true,
+    })))
+}
+
+/// Einzelne Order prüfen und an MEXC senden
+async fn place_order(
+    state: &TradingState,
+    user_id: &str,
+    payload: ApiOrderRequest,
+) -> Result<serde_json::Value, ApiError> {
+    let (order, mexc_order) = prepare_order(state, user_id, payload).await?;
+    check_open_order_cap(state, user_id, &order.symbol, 1).await?;
+    check_balance(state, user_id, std::slice::from_ref(&order)).await?;
+    submit_order(state, order, mexc_order).await
+}
+
+/// POST /api/trade/orders/:user_id - Mehrere Orders; Validierung und Open-Order-Limit
+/// gelten für den ganzen Batch, bevor eine Order gesendet wird
+pub async fn create_orders_batch(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<BatchOrderRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    if payload.orders.is_empty() {
+        return Err(ApiError::Validation("orders must not be empty".to_string()));
+    }
+    tracing::info!("Creating {} orders for user: {}", payload.orders.len(), user_id);
+
+    let mut prepared = Vec::with_capacity(payload.orders.len());
+    for request in payload.orders {
+        prepared.push(prepare_order(&state, &user_id, request).await?);
+    }
+
+    let mut per_symbol: BTreeMap<&str, usize> = BTreeMap::new();
+    for (order, _) in &prepared {
+        *per_symbol.entry(order.symbol.as_str()).or_default() += 1;
+    }
+    for (symbol, count) in per_symbol {
+        check_open_order_cap(&state, &user_id, symbol, count).await?;
+    }
+    // Große Orders nur einzeln mit Bestätigung
+    if state.large_order_confirm.is_enabled() {
+        for (order, _) in &prepared {
+            if state.large_order_confirm.requires_confirmation(order_notional(&state, order).await?) {
+                return Err(ApiError::Validation(format!(
+                    "{} order exceeds the confirmation threshold of {}, submit it via POST /api/trade/order",
+                    order.symbol, state.large_order_confirm.threshold
+                )));
+            }
+        }
+    }
+    let orders: Vec<OrderItem> = prepared.iter().map(|(order, _)| order.clone()).collect();
+    check_balance(&state, &user_id, &orders).await?;
+
+    let mut results = Vec::with_capacity(prepared.len());
+    for (order, mexc_order) in prepared {
+        let order_id = order.order_id.clone();
+        results.push((order_id, submit_order_with_code(&state, order, mexc_order).await));
+    }
+
+    if state.structured_batch_results {
+        let batch: BatchResult<_> = results
+            .into_iter()
+            .map(|(order_id, result)| result.map_err(|(e, code)| BatchFailure::from_api(order_id, &e, code)))
+            .collect();
+        return Ok(Json(json!(batch)));
+    }
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|(_, result)| match result {
+            Ok(body) => body,
+            Err((e, _)) => json!({ "error": { "type": e.kind(), "message": e.message() } }),
+        })
+        .collect();
+    Ok(Json(json!({ "orders": results })))
+}
+
+/// Max. gleichzeitige Cancel-and-Replace-Vorgänge je Reprice-Request
+const REPRICE_CONCURRENCY: usize = 4;
+
+/// POST /api/trade/orders/:user_id/reprice - Offene Limit Orders (optional nur
+/// eines Symbols) stornieren und mit gleicher Restmenge zum neuen Preis neu platzieren
+pub async fn reprice_orders(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<RepriceRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let target = payload.target()?;
+    let stored = state
+        .store
+        .query_orders_by_status(&user_id, OrderStatus::Open.as_str())
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let orders: Vec<OrderItem> = with_buffered(&state, &user_id, stored)
+        .into_iter()
+        .filter(|o| o.status == OrderStatus::Open.as_str())
+        .filter(|o| o.order_type.eq_ignore_ascii_case("limit") && o.price.is_some() && o.mexc_order_id.is_some())
+        .filter(|o| {
+            payload
+                .symbol
+                .as_deref()
+                .is_none_or(|symbol| o.symbol.eq_ignore_ascii_case(symbol))
+        })
+        .collect();
+    tracing::info!("Repricing {} open orders for user: {}", orders.len(), user_id);
+
+    let mut results = vec![None; orders.len()];
+    if !orders.is_empty() {
+        let client = user_client(&state, &user_id).await?;
+        let permits = Arc::new(Semaphore::new(REPRICE_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (index, order) in orders.into_iter().enumerate() {
+            let (state, client, permits) = (state.clone(), client.clone(), permits.clone());
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (index, reprice_order(&state, &client, order, target).await)
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => tracing::error!("Reprice task failed: {}", e),
+            }
+        }
+    }
+
+    if state.structured_batch_results {
+        let batch: BatchResult<_> = results.into_iter().flatten().collect();
+        return Ok(Json(json!({
+            "user_id": user_id,
+            "count": batch.len(),
+            "succeeded": batch.succeeded,
+            "failed": batch.failed,
+        })));
+    }
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|result| match result {
+            Some(Ok(body)) => body,
+            // Übersprungen (Rate Limit) statt fehlgeschlagen
+            Some(Err(failure)) => json!({
+                "order_id": failure.id,
+                "status": if failure.error_code == "rate_limited" { "skipped" } else { "error" },
+                "message": failure.message,
+            }),
+            None => serde_json::Value::Null,
+        })
+        .collect();
+    Ok(Json(json!({
+        "user_id": user_id,
+        "count": results.len(),
+        "orders": results,
+    })))
+}
+
+/// Eine Order stornieren und ersetzen; gefüllte Orders werden nur aktualisiert
+async fn reprice_order(
+    state: &TradingState,
+    client: &MexcClient,
+    mut order: OrderItem,
+    target: RepriceTarget,
+) -> Result<serde_json::Value, BatchFailure> {
+    let new_price = state
+        .precision
+        .round_price(&order.symbol, target.apply(order.price.unwrap_or_default()));
+    if new_price <= 0.0 {
+        let message = format!("new price {} must be positive", new_price);
+        return Err(BatchFailure::new(order.order_id, "validation", message));
+    }
+    if order.paper {
+        return Err(BatchFailure::new(order.order_id, "validation", "paper orders cannot be repriced"));
+    }
+    let notional = (order.quantity - order.filled_qty) * new_price;
+    if let Err(e) = check_replacement(state, &order, notional).await {
+        return Err(BatchFailure::from_api(order.order_id, &e, None));
+    }
+    // Jede ersetzte Order zählt gegen das Rate Limit des Users
+    if let Some(limiter) = &state.rate_limiter {
+        if limiter.check(&format!("user:{}", order.user_id)).is_err() {
+            return Err(BatchFailure::new(order.order_id, "rate_limited", "rate limited"));
+        }
+    }
+
+    let mexc_order_id = order.mexc_order_id.clone().unwrap_or_default();
+    let cancelled = match client.cancel_order(&order.symbol, &mexc_order_id).await {
+        Ok(cancelled) => cancelled,
+        Err(e) => {
+            // Cancel abgelehnt: Order wurde zwischenzeitlich evtl. gefüllt
+            return match client.get_order(&order.symbol, &mexc_order_id).await {
+                Ok(remote) if OrderStatus::from_mexc(&remote.status) == OrderStatus::Filled => {
+                    order.status = OrderStatus::Filled.as_str().to_string();
+                    order.filled_qty = remote.filled_qty;
+                    order.fill_price = order.price;
+                    order.updated_at = chrono::Utc::now().to_rfc3339();
+                    if let Err(e) = persist_order(state, &order).await {
+                        tracing::error!("Failed to store filled order {}: {}", order.order_id, e);
+                    }
+                    Ok(json!({ "order_id": order.order_id, "status": "filled", "filled_qty": order.filled_qty }))
+                }
+                _ => Err(BatchFailure::from_error(order.order_id, &e, format!("cancel failed: {}", e))),
+            };
+        }
+    };
+
+    order.filled_qty = order.filled_qty.max(cancelled.filled_qty);
+    order.updated_at = chrono::Utc::now().to_rfc3339();
+    let remaining = order.quantity - order.filled_qty;
+    if remaining <= 0.0 {
+        order.status = OrderStatus::Filled.as_str().to_string();
+        if let Err(e) = persist_order(state, &order).await {
+            tracing::error!("Failed to store filled order {}: {}", order.order_id, e);
+        }
+        return Ok(json!({ "order_id": order.order_id, "status": "filled", "filled_qty": order.filled_qty }));
+    }
+
+    let mut replacement = OrderItem::new(
+        order.user_id.clone(),
+        order.symbol.clone(),
+        order.side.clone(),
+        order.order_type.clone(),
+        remaining,
+        Some(new_price),
+    );
+    replacement.replaces = Some(order.order_id.clone());
+    replacement.post_only = order.post_only;
+
+    // Guthaben erst nach dem Cancel prüfen: die Mittel der alten Order sind dann wieder frei
+    if let Some(preflight) = &state.preflight {
+        preflight.invalidate(&order.user_id);
+    }
+    let balance = check_balance(state, &order.user_id, std::slice::from_ref(&replacement)).await;
+    order.status = OrderStatus::Cancelled.as_str().to_string();
+    if balance.is_ok() {
+        order.replaced_by = Some(replacement.order_id.clone());
+    }
+    if let Err(e) = persist_order(state, &order).await {
+        tracing::error!("Failed to store replaced order {}: {}", order.order_id, e);
+    }
+
+    let mexc_order = MexcOrderRequest {
+        symbol: replacement.symbol.clone(),
+        side: replacement.side.clone(),
+        order_type: mexc_order_type(&replacement.order_type, replacement.post_only),
+        quantity: remaining,
+        price: Some(new_price),
+        quote_order_qty: None,
+        client_order_id: Some(replacement.order_id.clone()),
+    };
+    let submitted = match balance {
+        Ok(()) => submit_order_with_code(state, replacement, mexc_order).await,
+        Err(e) => Err((e, None)),
+    };
+    match submitted {
+        Ok(body) => Ok(json!({
+            "order_id": order.order_id,
+            "status": "replaced",
+            "new_order_id": body["order_id"],
+            "new_status": body["status"],
+            "price": new_price,
+            "quantity": remaining,
+        })),
+        Err((e, code)) => {
+            let message = format!("cancelled, but replacement failed: {}", e.message());
+            Err(BatchFailure::new(order.order_id, code.unwrap_or_else(|| e.kind().to_string()), message))
+        }
+    }
+}
+
+/// Ersatz-Order vor dem Cancel wie eine neue Order prüfen. Große Orders brauchen die
+/// Bestätigung über POST /api/trade/order; die ersetzte Order zählt im Open-Order-Limit
+/// noch mit, der Ersatz belegt also keinen zusätzlichen Platz
+async fn check_replacement(state: &TradingState, order: &OrderItem, notional: f64) -> Result<(), ApiError> {
+    check_symbol_state(state, &order.symbol).await?;
+    if state.large_order_confirm.requires_confirmation(notional) {
+        return Err(ApiError::Validation(format!(
+            "{} replacement exceeds the confirmation threshold of {}, cancel and submit it via POST /api/trade/order",
+            order.symbol, state.large_order_confirm.threshold
+        )));
+    }
+    check_open_order_cap(state, &order.user_id, &order.symbol, 0).await
+}
+
+/// Request validieren und Order Item + MEXC Request bauen (noch nichts senden)
+async fn prepare_order(
+    state: &TradingState,
+    user_id: &str,
+    payload: ApiOrderRequest,
+) -> Result<(OrderItem, MexcOrderRequest), ApiError> {
     // Validierung
-    if payload.quantity <= 0.0 {
-        return Err((StatusCode::BAD_REQUEST, "Quantity must be positive".to_string()));
+    let amount = validate_order_amount(&payload.order_type, payload.quantity, payload.quote_order_qty)?;
+    let (mut quantity, quote_order_qty) = match amount {
+        OrderAmount::Base(quantity) => (quantity, None),
+        OrderAmount::Quote(quote_qty) => (0.0, Some(quote_qty)),
+    };
+    check_symbol_state(state, &payload.symbol).await?;
+    if payload.post_only && (!payload.order_type.eq_ignore_ascii_case("limit") || payload.price.is_none()) {
+        return Err(ApiError::Validation(
+            "post_only requires a LIMIT order with price".to_string(),
+        ));
+    }
+
+    // Reduce-only: nur gegen eine offene Position, Menge auf deren Größe begrenzt.
+    // MEXC Spot kennt kein reduceOnly, daher wird clientseitig erzwungen.
+    if payload.reduce_only {
+        if quote_order_qty.is_some() {
+            return Err(ApiError::Validation(
+                "reduce_only orders must use quantity, not quote_order_qty".to_string(),
+            ));
+        }
+
+        let position_id = payload.position_id.as_deref().ok_or_else(|| {
+            ApiError::Validation("reduce_only orders require position_id".to_string())
+        })?;
+        let position = state
+            .store
+            .get_position(user_id, position_id, ReadConsistency::Strong)
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?
+            .filter(|p| p.status == "open")
+            .ok_or_else(|| ApiError::NotFound("Open position not found".to_string()))?;
+
+        if !is_closing_side(&position.side, &payload.side) {
+            return Err(ApiError::Validation(
+                "reduce_only order must be opposite to the position side".to_string(),
+            ));
+        }
+
+        let requested = quantity;
+        quantity = clamp_reduce_only_quantity(requested, position.quantity);
+        if quantity < requested {
+            tracing::info!(
+                "Reduce-only order clamped from {} to position size {}",
+                requested,
+                quantity
+            );
+        }
     }
 
     // Erstelle Order Item
     let mut order = OrderItem::new(
-        user_id.clone(),
+        user_id.to_string(),
         payload.symbol.clone(),
         payload.side.clone(),
         payload.order_type.clone(),
-        payload.quantity,
+        quantity,
         payload.price,
     );
+    order.reduce_only = payload.reduce_only;
+    order.post_only = payload.post_only;
+    order.quote_order_qty = quote_order_qty;
+    order.strategy = payload.strategy;
 
-    // Sende zu MEXC
     let mexc_order = MexcOrderRequest {
-        symbol: payload.symbol.clone(),
-        side: payload.side.clone(),
-        order_type: payload.order_type.clone(),
-        quantity: payload.quantity,
+        symbol: payload.symbol,
+        side: payload.side,
+        order_type: mexc_order_type(&payload.order_type, payload.post_only),
+        quantity,
         price: payload.price,
+        quote_order_qty,
+        client_order_id: Some(order.order_id.clone()),
+    };
+
+    Ok((order, mexc_order))
+}
+
+/// MEXC Order-Typ; post-only Limit Orders werden zu LIMIT_MAKER
+fn mexc_order_type(order_type: &str, post_only: bool) -> String {
+    if post_only {
+        "LIMIT_MAKER".to_string()
+    } else {
+        order_type.to_string()
+    }
+}
+
+/// Orders auf pausierte Symbole ablehnen; ist exchangeInfo nicht erreichbar,
+/// entscheidet MEXC selbst beim Platzieren
+async fn check_symbol_state(state: &TradingState, symbol: &str) -> Result<(), ApiError> {
+    if !state.symbol_state_check {
+        return Ok(());
+    }
+
+    match state.mexc_client.symbol_state(symbol).await {
+        Ok(SymbolState::Halted) => Err(ApiError::Validation(format!(
+            "Symbol {} is halted, trading is currently suspended",
+            symbol
+        ))),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::warn!("Symbol state check for {} failed: {}", symbol, e);
+            Ok(())
+        }
+    }
+}
+
+/// Offene Orders des Symbols gegen das Limit prüfen (User-Setting vor globalem Wert, 0 = aus)
+async fn check_open_order_cap(
+    state: &TradingState,
+    user_id: &str,
+    symbol: &str,
+    additional: usize,
+) -> Result<(), ApiError> {
+    let user_cap = state
+        .store
+        .get_max_open_orders_per_symbol(user_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let cap = user_cap.unwrap_or(state.max_open_orders_per_symbol) as usize;
+    if cap == 0 {
+        return Ok(());
+    }
+
+    let stored = state
+        .store
+        .query_open_orders_by_symbol(user_id, symbol)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let open = with_buffered(state, user_id, stored)
+        .iter()
+        .filter(|o| o.status == OrderStatus::Open.as_str() && o.symbol == symbol)
+        .count();
+
+    if open + additional > cap {
+        tracing::warn!(
+            "Rejecting {} order(s) for {}: {} open, cap {}",
+            additional,
+            symbol,
+            open,
+            cap
+        );
+        return Err(ApiError::Conflict(format!(
+            "Open order cap for {} reached ({} open, cap {})",
+            symbol, open, cap
+        )));
+    }
+
+    Ok(())
+}
+
+/// Notional einer Order in Quote-Asset; Market Orders zum aktuellen Ticker-Preis
+async fn order_notional(state: &TradingState, order: &OrderItem) -> Result<f64, ApiError> {
+    Ok(match (order.quote_order_qty, order.price) {
+        (Some(quote_qty), _) => quote_qty,
+        (None, Some(price)) => order.quantity * price,
+        (None, None) => {
+            let ticker = state
+                .mexc_client
+                .get_ticker(&order.symbol)
+                .await
+                .map_err(|e| ApiError::Upstream(e.to_string()))?;
+            order.quantity * ticker.price
+        }
+    })
+}
+
+/// MEXC Client des Users; ohne eigene Keys (und nicht Admin) abgelehnt
+async fn user_client(state: &TradingState, user_id: &str) -> Result<Arc<MexcClient>, ApiError> {
+    state.clients.for_user(user_id).await.map_err(|e| match e {
+        UserClientError::MissingCredentials(_) => ApiError::Unauthorized(e.to_string()),
+        UserClientError::UnknownAccount(_) => ApiError::Validation(e.to_string()),
+        UserClientError::Lookup(_) => {
+            tracing::error!("{}", e);
+            ApiError::Internal(e.to_string())
+        }
+    })
+}
+
+/// Pre-Trade-Check: freies Quote-Guthaben muss alle Buy-Orders inkl. Gebühren decken;
+/// Paper-Orders brauchen weder Guthaben noch eigene MEXC Keys
+async fn check_balance(state: &TradingState, user_id: &str, orders: &[OrderItem]) -> Result<(), ApiError> {
+    let Some(preflight) = state.preflight.as_ref().filter(|_| !state.paper_trading) else {
+        return Ok(());
     };
 
-    match state.mexc_client.create_order(&mexc_order).await {
-        Ok(mexc_response) => {
+    let mut notional = 0.0;
+    for order in orders.iter().filter(|o| o.side.eq_ignore_ascii_case("buy")) {
+        notional += order_notional(state, order).await?;
+    }
+    if notional <= 0.0 {
+        return Ok(());
+    }
+
+    let client = user_client(state, user_id).await?;
+    preflight.check(&client, user_id, notional).await.map_err(|e| match e {
+        PreflightError::Insufficient { .. } => ApiError::Validation(e.to_string()),
+        PreflightError::Balance(_) => ApiError::Upstream(e.to_string()),
+    })
+}
+
+/// Order an MEXC senden und speichern
+async fn submit_order(
+    state: &TradingState,
+    order: OrderItem,
+    mexc_order: MexcOrderRequest,
+) -> Result<serde_json::Value, ApiError> {
+    submit_order_with_code(state, order, mexc_order).await.map_err(|(e, _)| e)
+}
+
+/// Wie `submit_order`, liefert bei einer Ablehnung zusätzlich den MEXC-Fehlercode
+async fn submit_order_with_code(
+    state: &TradingState,
+    mut order: OrderItem,
+    mexc_order: MexcOrderRequest,
+) -> Result<serde_json::Value, (ApiError, Option<String>)> {
+    // Write-behind-Puffer voll: nicht platzieren, was nicht gespeichert werden kann
+    let slot = reserve_write(state, &order.symbol).map_err(|e| (e, None))?;
+    if state.paper_trading {
+        return submit_paper_order(state, order, slot).await.map_err(|e| (e, None));
+    }
+    let client = user_client(state, &order.user_id).await.map_err(|e| (e, None))?;
+    state.placement_cooldown.wait(&order.symbol).await;
+    match client.create_order_with_body(&mexc_order).await {
+        Ok((mexc_response, body)) => {
+            record_raw_response(state, &client, &order, 200, &body).await;
+            let mexc_response = state
+                .fill_confirmation
+                .confirm(&client, mexc_response)
+                .await;
             order.mexc_order_id = Some(mexc_response.order_id.clone());
-            order.status = mexc_response.status.clone();
+            if order.quote_order_qty.is_some() {
+                // Basis-Menge ergibt sich erst aus der Ausführung
+                order.quantity = mexc_response.quantity;
+            }
+            order.status = OrderStatus::from_mexc(&mexc_response.status).as_str().to_string();
+            order.filled_qty = mexc_response.filled_qty;
+            order.fill_price = mexc_response.average_fill_price();
 
-            // Speichere in DynamoDB
-            if let Err(e) = state.store.put_order(&order).await {
+            // Speichere in DynamoDB (bzw. im Write-behind-Puffer)
+            if let Err(e) = store_order(state, &order, slot).await {
                 tracing::error!("Failed to store order: {}", e);
-                return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Storage error: {}", e)));
+                return Err((ApiError::Internal(format!("Storage error: {}", e)), None));
             }
 
-            Ok((
-                StatusCode::CREATED,
-                Json(json!({
-                    "order_id": order.order_id,
-                    "status": order.status,
-                    "mexc_order_id": order.mexc_order_id,
-                })),
-            ))
+            state.order_monitor.track_user(&order.user_id);
+            state.order_monitor.order_index().upsert(&order);
+            if let Some(preflight) = &state.preflight {
+                preflight.invalidate(&order.user_id);
+            }
+
+            Ok(json!({
+                "order_id": order.order_id,
+                "status": order.status,
+                "mexc_order_id": order.mexc_order_id,
+                "filled_qty": order.filled_qty,
+                "fill_price": order.fill_price,
+            }))
         }
         Err(e) => {
             tracing::error!("MEXC API error: {}", e);
             order.error_message = Some(e.to_string());
+            if let Some(rejection) = e.downcast_ref::<MexcError>() {
+                record_raw_response(state, &client, &order, rejection.status, &rejection.body).await;
+                order.rejection_code = rejection.code.clone();
+                order.rejection_category = Some(rejection.category().as_str().to_string());
+            }
             order.status = "error".to_string();
-            let _ = state.store.put_order(&order).await;
+            let _ = store_order(state, &order, slot).await;
+
+            if order.post_only && e.downcast_ref::<MexcError>().is_some_and(MexcError::would_take_liquidity) {
+                let error = ApiError::WouldTakeLiquidity(format!(
+                    "Post-only {} order at {} would take liquidity and was rejected",
+                    order.symbol,
+                    order.price.unwrap_or_default()
+                ));
+                return Err((error, order.rejection_code));
+            }
+            Err((ApiError::Upstream(e.to_string()), order.rejection_code))
+        }
+    }
+}
 
-            Err((StatusCode::BAD_GATEWAY, e.to_string()))
+/// Paper-Order statt MEXC: Market füllt sofort zum aktuellen Ticker, Limit bleibt
+/// offen, bis der Order Monitor ein Kreuzen des Limits sieht (ohne Simulation sofort
+/// zum Limitpreis)
+async fn submit_paper_order(
+    state: &TradingState,
+    mut order: OrderItem,
+    slot: Option<WriteSlot>,
+) -> Result<serde_json::Value, ApiError> {
+    order.paper = true;
+    if order.order_type.eq_ignore_ascii_case("market") {
+        let price = state
+            .mexc_client
+            .get_ticker(&order.symbol)
+            .await
+            .map_err(|e| ApiError::Upstream(e.to_string()))?
+            .price;
+        if let Some(quote_qty) = order.quote_order_qty {
+            order.quantity = if price > 0.0 { quote_qty / price } else { 0.0 };
         }
+        order.status = OrderStatus::Filled.as_str().to_string();
+        order.filled_qty = order.quantity;
+        order.fill_price = Some(price);
+    } else if !state.paper_simulate_fills {
+        order.status = OrderStatus::Filled.as_str().to_string();
+        order.filled_qty = order.quantity;
+        order.fill_price = order.price;
+    } else {
+        order.status = OrderStatus::Open.as_str().to_string();
     }
+
+    if let Err(e) = store_order(state, &order, slot).await {
+        tracing::error!("Failed to store paper order: {}", e);
+        return Err(ApiError::Internal(format!("Storage error: {}", e)));
+    }
+    state.order_monitor.track_user(&order.user_id);
+    state.order_monitor.order_index().upsert(&order);
+    tracing::info!("Paper order {} on {}: {}", order.order_id, order.symbol, order.status);
+
+    Ok(json!({
+        "order_id": order.order_id,
+        "status": order.status,
+        "mexc_order_id": null,
+        "filled_qty": order.filled_qty,
+        "fill_price": order.fill_price,
+        "paper": true,
+    }))
+}
+
+/// Mit Write-behind einen Pufferplatz für eine neue Order zusagen lassen;
+/// Err (429) wenn der Puffer voll ist, None ohne Write-behind
+fn reserve_write(state: &TradingState, symbol: &str) -> Result<Option<WriteSlot>, ApiError> {
+    let Some(buffer) = &state.write_behind else {
+        return Ok(None);
+    };
+    buffer.reserve().map(Some).map_err(|full| {
+        tracing::warn!("Rejecting order for {}: {}", symbol, full);
+        ApiError::RateLimited(format!("Storage is lagging behind ({}), retry later", full))
+    })
+}
+
+/// Neue Order synchron schreiben oder, mit zugesagtem Pufferplatz, nur puffern
+async fn store_order(state: &TradingState, order: &OrderItem, slot: Option<WriteSlot>) -> anyhow::Result<()> {
+    match slot {
+        Some(slot) => {
+            slot.enqueue(order.clone());
+            Ok(())
+        }
+        None => state.store.put_order(order).await,
+    }
+}
+
+/// Update einer bestehenden Order schreiben; mit Write-behind hinter einem noch
+/// gepufferten Stand eingereiht, damit der Flush es nicht überschreibt
+async fn persist_order(state: &TradingState, order: &OrderItem) -> anyhow::Result<()> {
+    match &state.write_behind {
+        Some(buffer) => buffer.write_through(order).await,
+        None => state.store.put_order(order).await,
+    }
+}
+
+/// Order lesen, noch nicht geschriebene Stände aus dem Write-behind-Puffer zuerst
+async fn load_order(state: &TradingState, user_id: &str, order_id: &str) -> anyhow::Result<Option<OrderItem>> {
+    if let Some(order) = state
+        .write_behind
+        .as_ref()
+        .and_then(|buffer| buffer.pending_order(user_id, order_id))
+    {
+        return Ok(Some(order));
+    }
+    state.store.get_order(user_id, order_id, ReadConsistency::Strong).await
+}
+
+/// Gespeicherte Orders mit gepufferten Ständen überlagern (ohne Write-behind unverändert)
+fn with_buffered(state: &TradingState, user_id: &str, stored: Vec<OrderItem>) -> Vec<OrderItem> {
+    match &state.write_behind {
+        Some(buffer) => buffer.overlay(user_id, stored),
+        None => stored,
+    }
+}
+
+/// Rohe MEXC-Response ohne Secrets ablegen, falls aktiviert; Fehler blockieren die Order nicht
+async fn record_raw_response(state: &TradingState, client: &MexcClient, order: &OrderItem, http_status: u16, body: &str) {
+    let Some(ttl) = state.raw_response_ttl else {
+        return;
+    };
+    let now = chrono::Utc::now();
+    let raw = RawResponseItem {
+        user_id: order.user_id.clone(),
+        order_id: order.order_id.clone(),
+        http_status,
+        body: audit::redact_secrets(body, &[&client.api_key()]),
+        recorded_at: now.timestamp_millis(),
+        expires_at: now.timestamp() + ttl.as_secs() as i64,
+    };
+    if let Err(e) = state.store.put_raw_response(&raw).await {
+        tracing::warn!("Failed to store raw MEXC response for order {}: {}", order.order_id, e);
+    }
+}
+
+/// GET /api/trade/order/:user_id/:order_id/raw - Gespeicherte MEXC-Roh-Response (Audit)
+pub async fn get_raw_response(
+    State(state): State<Arc<TradingState>>,
+    Path((user_id, order_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let raw = state
+        .store
+        .get_raw_response(&user_id, &order_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::Internal(e.to_string())
+        })?
+        .ok_or(ApiError::NotFound("No raw response stored for this order".to_string()))?;
+
+    // JSON-Bodies strukturiert ausliefern, sonst als String
+    let body = serde_json::from_str::<serde_json::Value>(&raw.body).unwrap_or(serde_json::Value::String(raw.body));
+    Ok(Json(json!({
+        "order_id": raw.order_id,
+        "http_status": raw.http_status,
+        "recorded_at": raw.recorded_at,
+        "expires_at": raw.expires_at,
+        "body": body,
+    })))
 }
 
 /// GET /api/trade/order/:order_id - Get Order Status
 pub async fn get_order(
     State(state): State<Arc<TradingState>>,
     Path((user_id, order_id)): Path<(String, String)>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    match state.store.get_order(&user_id, &order_id).await {
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    // Noch nicht geschriebene Orders aus dem Write-behind-Puffer
+    match load_order(&state, &user_id, &order_id).await {
         Ok(Some(order)) => {
             Ok(Json(json!({
                 "order_id": order.order_id,
@@ -92,36 +947,79 @@ pub async fn get_order(
                 "side": order.side,
                 "quantity": order.quantity,
                 "filled_qty": order.filled_qty,
+                "fill_price": order.fill_price,
                 "status": order.status,
                 "price": order.price,
                 "created_at": order.created_at,
+                "note": order.note,
+                "account": order.account,
+                "strategy": order.strategy,
             })))
         }
-        Ok(None) => Err((StatusCode::NOT_FOUND, "Order not found".to_string())),
+        Ok(None) => Err(ApiError::NotFound("Order not found".to_string())),
         Err(e) => {
             tracing::error!("Database error: {}", e);
-            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+            Err(ApiError::Internal(e.to_string()))
         }
     }
 }
 
+/// PATCH /api/trade/order/:user_id/:order_id/note - Journal-Notiz setzen oder löschen
+pub async fn set_order_note(
+    State(state): State<Arc<TradingState>>,
+    Path((user_id, order_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(payload): Json<NoteRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let note = payload.sanitized()?;
+    let mut order = load_order(&state, &user_id, &order_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+        .ok_or(ApiError::NotFound("Order not found".to_string()))?;
+
+    order.note = note;
+    order.updated_at = chrono::Utc::now().to_rfc3339();
+    persist_order(&state, &order)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))?;
+
+    Ok(Json(json!({
+        "order_id": order.order_id,
+        "note": order.note,
+        "updated_at": order.updated_at,
+    })))
+}
+
 /// DELETE /api/trade/order/:order_id - Cancel Order
 pub async fn cancel_order(
     State(state): State<Arc<TradingState>>,
     Path((user_id, order_id)): Path<(String, String)>,
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
     // Hole Order Informationen
-    let order = state
-        .store
-        .get_order(&user_id, &order_id)
+    let order = load_order(&state, &user_id, &order_id)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, "Order not found".to_string()))?;
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+        .ok_or(ApiError::NotFound("Order not found".to_string()))?;
+
+    if order.paper {
+        // Paper-Order: nur lokal stornieren
+        let mut order = order;
+        order.status = OrderStatus::Cancelled.as_str().to_string();
+        order.updated_at = chrono::Utc::now().to_rfc3339();
+        persist_order(&state, &order)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))?;
+        state.order_monitor.order_index().upsert(&order);
+        return Ok((StatusCode::OK, Json(json!({"status": "cancelled"}))));
+    }
 
     if let Some(mexc_order_id) = &order.mexc_order_id {
         // Storniere bei MEXC
-        match state
-            .mexc_client
+        match user_client(&state, &user_id)
+            .await?
             .cancel_order(&order.symbol, mexc_order_id)
             .await
         {
@@ -131,29 +1029,1734 @@ pub async fn cancel_order(
             }
             Err(e) => {
                 tracing::error!("Failed to cancel order: {}", e);
-                Err((StatusCode::BAD_GATEWAY, e.to_string()))
+                Err(ApiError::Upstream(e.to_string()))
             }
         }
     } else {
-        Err((StatusCode::BAD_REQUEST, "Order not yet sent to MEXC".to_string()))
+        Err(ApiError::Validation("Order not yet sent to MEXC".to_string()))
+    }
+}
+
+/// GET /api/trade/fills/:user_id - Fill-Blotter (neueste zuerst); mit `symbol`
+/// werden die Fills vorher von MEXC abgeglichen und gespeichert
+pub async fn get_fills(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<FillsQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let mut fills = match &query.symbol {
+        Some(symbol) => {
+            let fills = user_client(&state, &user_id)
+                .await?
+                .get_my_trades(symbol, query.limit.unwrap_or(100))
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get fills: {}", e);
+                    ApiError::Upstream(e.to_string())
+                })?;
+            for fill in &fills {
+                state
+                    .store
+                    .put_fill(&user_id, fill)
+                    .await
+                    .map_err(|e| ApiError::Internal(format!("Storage error: {}", e)))?;
+            }
+            fills
+        }
+        None => state
+            .store
+            .query_fills(&user_id, None)
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?,
+    };
+    fills.sort_by_key(|fill| std::cmp::Reverse(fill.time));
+
+    Ok(Json(json!({
+        "user_id": user_id,
+        "count": fills.len(),
+        "fills": fills,
+    })))
+}
+
+/// GET /api/trade/fees/:user_id?from=...&to=... - Gezahlte Gebühren der gespeicherten Fills
+/// im Zeitraum, je Asset, Symbol und Maker/Taker
+pub async fn get_fees(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<FeesQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let from = query.from.unwrap_or(0);
+    let to = query
+        .to
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    if from > to {
+        return Err(ApiError::Validation("from must be <= to".to_string()));
     }
+
+    let fills = state
+        .store
+        .query_fills_between(&user_id, from, to)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let report = summarize_fees(&fills);
+
+    Ok(Json(json!({
+        "user_id": user_id,
+        "from": from,
+        "to": to,
+        "fees": report,
+    })))
+}
+
+/// PUT /api/trade/credentials/:user_id - Eigene MEXC API Keys speichern
+/// (verschlüsselt); gilt ab der nächsten Anfrage des Users. Nur mit einem
+/// Bearer-JWT dieses Users
+pub async fn put_credentials(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<Credentials>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    if payload.api_key.trim().is_empty() || payload.secret_key.trim().is_empty() {
+        return Err(ApiError::Validation("api_key and secret_key are required".to_string()));
+    }
+
+    state
+        .store
+        .put_mexc_credentials(&user_id, &payload)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to store credentials: {}", e);
+            ApiError::Internal(e.to_string())
+        })?;
+    state.clients.invalidate(&user_id);
+    tracing::info!("Stored MEXC credentials for user: {}", user_id);
+
+    Ok(Json(json!({ "user_id": user_id, "status": "stored" })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct FeesQuery {
+    /// Unix ms, default 0
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Unix ms, default jetzt
+    #[serde(default)]
+    pub to: Option<i64>,
 }
 
 #[derive(serde::Deserialize)]
+pub struct FillsQuery {
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Max. Fills von MEXC (1..=100, Default 100)
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct ApiOrderRequest {
     pub symbol: String,
     pub side: String,
     pub order_type: String,
-    pub quantity: f64,
+    /// Basis-Menge; alternativ `quote_order_qty` (nur Market Orders)
+    #[serde(default)]
+    pub quantity: Option<f64>,
+    /// Quote-Betrag (z.B. USDT) der ausgegeben werden soll
+    #[serde(default)]
+    pub quote_order_qty: Option<f64>,
     #[serde(default)]
     pub price: Option<f64>,
+    /// Order darf die Position nur verkleinern (benötigt position_id)
+    #[serde(default)]
+    pub reduce_only: bool,
+    #[serde(default)]
+    pub position_id: Option<String>,
+    /// Maker-only Limit Order (LIMIT_MAKER); würde sie sofort matchen, lehnt MEXC ab
+    #[serde(default)]
+    pub post_only: bool,
+    /// Strategie-Label für die PnL-Attribution (`/api/v1/pnl/:user_id/by-strategy`)
+    #[serde(default)]
+    pub strategy: Option<String>,
+}
+
+/// Genau eins von `price` (absolut) und `price_delta` (relativ zum alten Preis)
+#[derive(serde::Deserialize)]
+pub struct RepriceRequest {
+    #[serde(default)]
+    pub price: Option<f64>,
+    #[serde(default)]
+    pub price_delta: Option<f64>,
+    /// Nur Orders dieses Symbols (Default: alle)
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+impl RepriceRequest {
+    fn target(&self) -> Result<RepriceTarget, ApiError> {
+        match (self.price, self.price_delta) {
+            // Ein fester Preis passt nur zu einem Symbol
+            (Some(_), None) if self.symbol.is_none() => Err(ApiError::Validation(
+                "price requires symbol, use price_delta to reprice across symbols".to_string(),
+            )),
+            (Some(price), None) if price > 0.0 => Ok(RepriceTarget::Absolute(price)),
+            (Some(_), None) => Err(ApiError::Validation("price must be positive".to_string())),
+            (None, Some(delta)) if delta.is_finite() => Ok(RepriceTarget::Delta(delta)),
+            _ => Err(ApiError::Validation(
+                "Exactly one of price or price_delta is required".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RepriceTarget {
+    Absolute(f64),
+    Delta(f64),
+}
+
+impl RepriceTarget {
+    fn apply(self, old_price: f64) -> f64 {
+        match self {
+            RepriceTarget::Absolute(price) => price,
+            RepriceTarget::Delta(delta) => old_price + delta,
+        }
+    }
+}
+
+/// Journal-Notiz; leer oder null löscht sie
+#[derive(serde::Deserialize)]
+pub struct NoteRequest {
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl NoteRequest {
+    /// Bereinigte Notiz oder Validierungsfehler (Länge)
+    pub fn sanitized(&self) -> Result<Option<String>, ApiError> {
+        match self.note.as_deref() {
+            Some(note) => sanitize_note(note).map_err(ApiError::Validation),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct ConfirmOrderRequest {
+    pub confirmation_token: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct BatchOrderRequest {
+    pub orders: Vec<ApiOrderRequest>,
+}
+
+/// Order-Menge: Basis-Menge oder Quote-Betrag
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderAmount {
+    Base(f64),
+    Quote(f64),
+}
+
+/// Genau eins von quantity / quote_order_qty; quote_order_qty nur für Market Orders
+pub fn validate_order_amount(
+    order_type: &str,
+    quantity: Option<f64>,
+    quote_order_qty: Option<f64>,
+) -> Result<OrderAmount, ApiError> {
+    match (quantity, quote_order_qty) {
+        (Some(_), Some(_)) => Err(ApiError::Validation(
+            "quantity and quote_order_qty are mutually exclusive".to_string(),
+        )),
+        (None, None) => Err(ApiError::Validation(
+            "Either quantity or quote_order_qty is required".to_string(),
+        )),
+        (Some(quantity), None) if quantity > 0.0 => Ok(OrderAmount::Base(quantity)),
+        (Some(_), None) => Err(ApiError::Validation("Quantity must be positive".to_string())),
+        (None, Some(_)) if !order_type.eq_ignore_ascii_case("market") => Err(ApiError::Validation(
+            "quote_order_qty is only supported for market orders".to_string(),
+        )),
+        (None, Some(quote_qty)) if quote_qty > 0.0 => Ok(OrderAmount::Quote(quote_qty)),
+        (None, Some(_)) => Err(ApiError::Validation(
+            "quote_order_qty must be positive".to_string(),
+        )),
+    }
+}
+
+/// GET /api/trade/rejections/:user_id/summary - Abgelehnte Orders je Kategorie
+pub async fn rejection_summary(
+    State(state): State<Arc<TradingState>>,
+    Path(user_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.user_auth.authorize(&headers, &user_id)?;
+    let orders = state
+        .store
+        .query_orders_by_status(&user_id, OrderStatus::Error.as_str())
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    let mut by_category: BTreeMap<&str, u64> = RejectionCategory::ALL
+        .iter()
+        .map(|category| (category.as_str(), 0))
+        .collect();
+    let mut by_code: BTreeMap<String, u64> = BTreeMap::new();
+    for order in &orders {
+        // Ältere Orders ohne Kategorie (z.B. Netzwerkfehler) zählen als unknown
+        let category = order
+            .rejection_category
+            .as_deref()
+            .map(RejectionCategory::parse)
+            .unwrap_or(RejectionCategory::Unknown);
+        *by_category.entry(category.as_str()).or_default() += 1;
+        if let Some(code) = &order.rejection_code {
+            *by_code.entry(code.clone()).or_default() += 1;
+        }
+    }
+
+    Ok(Json(json!({
+        "user_id": user_id,
+        "total": orders.len(),
+        "by_category": by_category,
+        "by_code": by_code,
+    })))
 }
 
 /// Router für Trading Endpoints
 pub fn trading_router(state: Arc<TradingState>) -> Router {
-    Router::new()
+    let router = Router::new()
         .route("/order", post(create_order))
+        .route("/order/confirm", post(confirm_order))
+        .route("/orders/:user_id", post(create_orders_batch))
+        .route("/orders/:user_id/reprice", post(reprice_orders))
+        .route("/estimate/:user_id", post(estimate_order))
         .route("/order/:user_id/:order_id", get(get_order))
         .route("/order/:user_id/:order_id", delete(cancel_order))
-        .with_state(state)
+        .route("/order/:user_id/:order_id/note", patch(set_order_note))
+        .route("/order/:user_id/:order_id/raw", get(get_raw_response))
+        .route("/fills/:user_id", get(get_fills))
+        .route("/fees/:user_id", get(get_fees))
+        .route("/rejections/:user_id/summary", get(rejection_summary))
+        .route("/credentials/:user_id", put(put_credentials));
+
+    let router = match &state.rate_limiter {
+        Some(limiter) => router.route_layer(middleware::from_fn_with_state(limiter.clone(), rate_limit)),
+        None => router,
+    };
+    router.with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::MAX_NOTE_CHARS;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::extract::Query;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_reduce_only_close_is_clamped_to_position() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|Query(params): Query<HashMap<String, String>>| async move {
+                Json(json!({
+                    "order_id": "mexc-1",
+                    "symbol": "ETHUSDT",
+                    "side": "SELL",
+                    "order_type": "MARKET",
+                    "quantity": params["quantity"].parse::<f64>().unwrap(),
+                    "price": 0.0,
+                    "status": "NEW",
+                    "filled_qty": 0.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 1,
+                "Items": [{
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": "POSITION#1#pos-1" },
+                    "position_id": { "S": "pos-1" },
+                    "symbol": { "S": "ETHUSDT" },
+                    "entry_price": { "N": "100" },
+                    "current_price": { "N": "100" },
+                    "quantity": { "N": "1.5" },
+                    "side": { "S": "long" },
+                    "entry_time": { "N": "1" },
+                    "status": { "S": "open" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "ttl": { "N": "0" }
+                }]
+            }),
+        );
+
+        let mexc = Arc::new(mexc_client(&base_url));
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let (status, _) = create_order(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(ApiOrderRequest {
+                symbol: "ETHUSDT".to_string(),
+                side: "SELL".to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: Some(2.0),
+                quote_order_qty: None,
+                price: None,
+                reduce_only: true,
+                position_id: Some("pos-1".to_string()),
+                post_only: false,
+                strategy: None,
+            }),
+        )
+        .await
+        .expect("order failed");
+
+        assert_eq!(status, StatusCode::CREATED);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0]["Item"]["quantity"]["N"], "1.5");
+        assert_eq!(puts[0]["Item"]["reduce_only"]["BOOL"], true);
+    }
+
+    #[tokio::test]
+    async fn test_reduce_only_requires_position() {
+        let dynamo = MockDynamo::start().await;
+        let mexc = Arc::new(mexc_client("http://127.0.0.1:9"));
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let err = create_order(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(ApiOrderRequest {
+                symbol: "ETHUSDT".to_string(),
+                side: "SELL".to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: Some(2.0),
+                quote_order_qty: None,
+                price: None,
+                reduce_only: true,
+                position_id: None,
+                post_only: false,
+                strategy: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// Authorization Header mit gültigem JWT des Users (passend zu `test_state`)
+    fn auth(user_id: &str) -> HeaderMap {
+        let token = crate::api::auth::tests::sign_jwt("jwt-secret", user_id, chrono::Utc::now().timestamp() + 60);
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+        headers
+    }
+
+    /// State mit allen optionalen Prüfungen aus; Tests überschreiben per Struct-Update
+    fn test_state(mexc: Arc<MexcClient>, store: Arc<DynamoDBStore>) -> TradingState {
+        TradingState {
+            mexc_client: mexc.clone(),
+            clients: Arc::new(MexcClientPool::single_tenant(mexc.clone())),
+            store: store.clone(),
+            order_monitor: Arc::new(OrderMonitor::new(mexc, store, Duration::from_secs(60))),
+            max_open_orders_per_symbol: 0,
+            preflight: None,
+            fill_confirmation: FillConfirmation::default(),
+            rate_limiter: None,
+            large_order_confirm: LargeOrderConfirm::default(),
+            symbol_state_check: false,
+            raw_response_ttl: None,
+            placement_cooldown: Arc::default(),
+            write_behind: None,
+            structured_batch_results: true,
+            user_auth: Arc::new(UserAuth::new(Some("jwt-secret".to_string()))),
+            precision: Arc::new(PrecisionCache::from_config(&Config::default())),
+            paper_trading: false,
+            paper_simulate_fills: true,
+        }
+    }
+
+    fn open_orders(count: usize) -> serde_json::Value {
+        let items: Vec<_> = (0..count)
+            .map(|i| {
+                json!({
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": format!("ORDER#1#open-{}", i) },
+                    "order_id": { "S": format!("open-{}", i) },
+                    "symbol": { "S": "ETHUSDT" },
+                    "side": { "S": "BUY" },
+                    "order_type": { "S": "LIMIT" },
+                    "quantity": { "N": "1" },
+                    "price": { "N": "100" },
+                    "filled_qty": { "N": "0" },
+                    "status": { "S": "open" },
+                    "timestamp": { "N": "1" },
+                    "created_at": { "S": "2024-01-01T00:00:00Z" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "ttl": { "N": "0" }
+                })
+            })
+            .collect();
+        json!({ "Count": count, "Items": items })
+    }
+
+    fn limit_order() -> ApiOrderRequest {
+        ApiOrderRequest {
+            symbol: "ETHUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "LIMIT".to_string(),
+            quantity: Some(1.0),
+            quote_order_qty: None,
+            price: Some(100.0),
+            reduce_only: false,
+            position_id: None,
+            post_only: false,
+            strategy: None,
+        }
+    }
+
+    /// Trading State mit Open-Order-Limit; zählt MEXC Order-Requests
+    async fn capped_state(dynamo: &MockDynamo, cap: u32) -> (Arc<TradingState>, Arc<AtomicUsize>) {
+        let placed = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let placed = placed.clone();
+                move || async move {
+                    let n = placed.fetch_add(1, Ordering::SeqCst);
+                    Json(json!({
+                        "order_id": format!("mexc-{}", n),
+                        "symbol": "ETHUSDT",
+                        "side": "BUY",
+                        "order_type": "LIMIT",
+                        "quantity": 1.0,
+                        "price": 100.0,
+                        "status": "NEW",
+                        "filled_qty": 0.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            max_open_orders_per_symbol: cap,
+            ..test_state(mexc, store)
+        });
+        (state, placed)
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_buffers_orders_and_rejects_over_backlog() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let calls = calls.clone();
+                move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Json(json!({
+                        "order_id": "mexc-1",
+                        "symbol": "ETHUSDT",
+                        "side": "BUY",
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 0.0,
+                        "status": "NEW",
+                        "filled_qty": 0.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        let mexc = Arc::new(mexc_client(&base_url));
+        let store = Arc::new(dynamo.store().await);
+        let buffer = Arc::new(OrderWriteBuffer::new(store.clone(), 1, Duration::from_secs(60)));
+        let state = Arc::new(TradingState {
+            write_behind: Some(buffer.clone()),
+            ..test_state(mexc, store)
+        });
+        let order = || {
+            create_order(
+                State(state.clone()),
+                Path("user-1".to_string()),
+                auth("user-1"),
+                Json(ApiOrderRequest {
+                    symbol: "ETHUSDT".to_string(),
+                    side: "BUY".to_string(),
+                    order_type: "MARKET".to_string(),
+                    quantity: Some(1.0),
+                    quote_order_qty: None,
+                    price: None,
+                    reduce_only: false,
+                    position_id: None,
+                    post_only: false,
+                    strategy: None,
+                }),
+            )
+        };
+
+        // Erste Order: platziert, aber nur gepuffert und trotzdem lesbar
+        let (status, Json(body)) = order().await.expect("order failed");
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(dynamo.requests("PutItem").is_empty());
+        let order_id = body["order_id"].as_str().unwrap().to_string();
+        let Json(stored) = get_order(State(state.clone()), Path(("user-1".to_string(), order_id)), auth("user-1"))
+            .await
+            .expect("buffered order not readable");
+        assert_eq!(stored["status"], "open");
+
+        // Backlog am Limit: zweite Order wird vor MEXC abgelehnt
+        let err = order().await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(buffer.flush().await, 1);
+        assert_eq!(dynamo.requests("PutItem").len(), 1);
+        let (status, _) = order().await.expect("order after flush failed");
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_open_order_cap_counts_buffered_orders() {
+        let dynamo = MockDynamo::start().await;
+        let (state, placed) = capped_state(&dynamo, 1).await;
+        let buffer = Arc::new(OrderWriteBuffer::new(state.store.clone(), 10, Duration::from_secs(60)));
+        let state = Arc::new(TradingState {
+            write_behind: Some(buffer.clone()),
+            ..Arc::try_unwrap(state).ok().expect("state is shared")
+        });
+
+        let (status, _) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("first order failed");
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(buffer.backlog(), 1);
+
+        // DynamoDB kennt noch keine offene Order, der Puffer schon
+        let err = create_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(limit_order()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_large_order_needs_confirmation() {
+        let dynamo = MockDynamo::start().await;
+        let (state, placed) = capped_state(&dynamo, 0).await;
+        let mut state = Arc::into_inner(state).expect("state shared");
+        state.large_order_confirm = LargeOrderConfirm {
+            threshold: 50.0,
+            ttl: Duration::from_secs(60),
+        };
+        let state = Arc::new(state);
+
+        // Unter dem Threshold sofort platziert
+        let small = ApiOrderRequest {
+            quantity: Some(0.1),
+            ..limit_order()
+        };
+        let (status, _) = create_order(State(state.clone()), Path("user-1".to_string()), auth("user-1"), Json(small))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+
+        // Darüber nur ein Token, keine Order
+        let (status, Json(body)) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body["status"], "pending_confirmation");
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+        let token = body["confirmation_token"].as_str().unwrap().to_string();
+        let pending = dynamo
+            .requests("PutItem")
+            .into_iter()
+            .map(|request| request["Item"].clone())
+            .find(|item| item["sk"]["S"] == format!("ORDER_CONFIRM#{}", token))
+            .expect("pending order not stored");
+
+        dynamo.respond("DeleteItem", json!({ "Attributes": pending.clone() }));
+        let (status, Json(body)) = confirm_order(
+            State(state.clone()),
+            auth("user-1"),
+            Json(ConfirmOrderRequest {
+                confirmation_token: token.clone(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["mexc_order_id"], "mexc-1");
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+
+        // Abgelaufener Token (TTL noch nicht von DynamoDB gelöscht) wird abgelehnt
+        let mut expired = pending;
+        expired["ttl"]["N"] = json!((chrono::Utc::now().timestamp() - 1).to_string());
+        dynamo.respond("DeleteItem", json!({ "Attributes": expired }));
+        let err = confirm_order(
+            State(state.clone()),
+            auth("user-1"),
+            Json(ConfirmOrderRequest {
+                confirmation_token: token.clone(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(err.message().contains("expired"));
+
+        // Bereits eingelöst / unbekannt
+        let err = confirm_order(State(state), auth("user-1"), Json(ConfirmOrderRequest { confirmation_token: token }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_order_on_halted_symbol_is_rejected() {
+        let placed = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/exchangeInfo",
+                get(|Query(params): Query<HashMap<String, String>>| async move {
+                    let status = if params["symbol"] == "HALTUSDT" { "HALT" } else { "TRADING" };
+                    Json(json!({ "symbols": [{ "symbol": params["symbol"], "status": status }] }))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let placed = placed.clone();
+                    move || async move {
+                        placed.fetch_add(1, Ordering::SeqCst);
+                        Json(json!({
+                            "order_id": "mexc-1",
+                            "symbol": "ETHUSDT",
+                            "side": "BUY",
+                            "order_type": "LIMIT",
+                            "quantity": 1.0,
+                            "price": 100.0,
+                            "status": "NEW",
+                            "filled_qty": 0.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            symbol_state_check: true,
+            ..test_state(mexc, store)
+        });
+
+        let halted = ApiOrderRequest {
+            symbol: "HALTUSDT".to_string(),
+            ..limit_order()
+        };
+        let err = create_order(State(state.clone()), Path("user-1".to_string()), auth("user-1"), Json(halted))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(err.message().contains("halted"));
+        assert_eq!(placed.load(Ordering::SeqCst), 0);
+
+        let (status, _) = create_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(limit_order()))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reprice_replaces_open_orders() {
+        let placed = Arc::new(std::sync::Mutex::new(Vec::<HashMap<String, String>>::new()));
+        let mexc_order = |order_id: &str, status: &str, filled_qty: f64| {
+            json!({
+                "order_id": order_id,
+                "symbol": "ETHUSDT",
+                "side": "BUY",
+                "order_type": "LIMIT",
+                "quantity": 1.0,
+                "price": 100.0,
+                "status": status,
+                "filled_qty": filled_qty,
+                "created_at": 0,
+            })
+        };
+        let router = Router::new().route(
+            "/api/v3/order",
+            delete(move |Query(params): Query<HashMap<String, String>>| async move {
+                match params["orderId"].as_str() {
+                    // Zwischen Abfrage und Cancel gefüllt
+                    "mexc-2" => (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "code": -2011, "msg": "Unknown order sent." })),
+                    ),
+                    "mexc-3" => (StatusCode::OK, Json(mexc_order("mexc-3", "CANCELED", 0.25))),
+                    id => (StatusCode::OK, Json(mexc_order(id, "CANCELED", 0.0))),
+                }
+            })
+            .get(move || async move { Json(mexc_order("mexc-2", "FILLED", 1.0)) })
+            .post({
+                let placed = placed.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    placed.lock().unwrap().push(params);
+                    Json(mexc_order("mexc-new", "NEW", 0.0))
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let mut open = open_orders(3);
+        for (i, item) in open["Items"].as_array_mut().unwrap().iter_mut().enumerate() {
+            item["mexc_order_id"] = json!({ "S": format!("mexc-{}", i + 1) });
+        }
+        dynamo.respond("Query", open);
+        let store = Arc::new(dynamo.store().await);
+        // Altes Format (flache Liste) weiterhin per BATCH_RESULTS_STRUCTURED=false
+        let state = Arc::new(TradingState {
+            structured_batch_results: false,
+            ..test_state(mexc, store)
+        });
+
+        let Json(body) = reprice_orders(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(RepriceRequest {
+                price: None,
+                price_delta: Some(-1.5),
+                symbol: None,
+            }),
+        )
+        .await
+        .expect("reprice failed");
+
+        let results = body["orders"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["status"], "replaced");
+        assert_eq!(results[0]["price"], 98.5);
+        assert_eq!(results[1]["status"], "filled");
+        assert_eq!(results[2]["status"], "replaced");
+        assert_eq!(results[2]["quantity"], 0.75);
+
+        let placed = placed.lock().unwrap();
+        assert_eq!(placed.len(), 2);
+        assert!(placed.iter().all(|p| p["price"] == "98.5"));
+
+        // Alt -> Neu verknüpft, gefüllte Order nur aktualisiert
+        let puts: Vec<_> = dynamo.requests("PutItem").into_iter().map(|p| p["Item"].clone()).collect();
+        let old = puts.iter().find(|i| i["order_id"]["S"] == "open-0").expect("old order not updated");
+        assert_eq!(old["status"]["S"], "cancelled");
+        let new_id = old["replaced_by"]["S"].as_str().unwrap();
+        let new = puts.iter().find(|i| i["order_id"]["S"] == new_id).expect("replacement not stored");
+        assert_eq!(new["replaces"]["S"], "open-0");
+        assert_eq!(new["price"]["N"], "98.5");
+        let filled = puts.iter().find(|i| i["order_id"]["S"] == "open-1").unwrap();
+        assert_eq!(filled["status"]["S"], "filled");
+        assert!(filled.get("replaced_by").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_structured_reprice_reports_typed_codes_per_order() {
+        let mexc_order = |order_id: &str, status: &str, filled_qty: f64| {
+            json!({
+                "order_id": order_id,
+                "symbol": "ETHUSDT",
+                "side": "BUY",
+                "order_type": "LIMIT",
+                "quantity": 1.0,
+                "price": 100.0,
+                "status": status,
+                "filled_qty": filled_qty,
+                "created_at": 0,
+            })
+        };
+        let router = Router::new().route(
+            "/api/v3/order",
+            delete(move |Query(params): Query<HashMap<String, String>>| async move {
+                match params["orderId"].as_str() {
+                    "mexc-2" => (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "code": -2011, "msg": "Unknown order sent." })),
+                    ),
+                    "mexc-3" => (StatusCode::OK, Json(mexc_order("mexc-3", "CANCELED", 0.25))),
+                    id => (StatusCode::OK, Json(mexc_order(id, "CANCELED", 0.0))),
+                }
+            })
+            .get(|| async { (StatusCode::BAD_REQUEST, Json(json!({ "code": -2013, "msg": "Order does not exist." }))) })
+            .post(move |Query(params): Query<HashMap<String, String>>| async move {
+                // Ersatz für die teilgefüllte Order wird abgelehnt
+                if params["quantity"] == "0.75" {
+                    (StatusCode::BAD_REQUEST, Json(json!({ "code": 30004, "msg": "Insufficient position" })))
+                } else {
+                    (StatusCode::OK, Json(mexc_order("mexc-new", "NEW", 0.0)))
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let mut open = open_orders(3);
+        for (i, item) in open["Items"].as_array_mut().unwrap().iter_mut().enumerate() {
+            item["mexc_order_id"] = json!({ "S": format!("mexc-{}", i + 1) });
+        }
+        dynamo.respond("Query", open);
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let Json(body) = reprice_orders(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(RepriceRequest {
+                price: Some(99.0),
+                price_delta: None,
+                symbol: Some("ETHUSDT".to_string()),
+            }),
+        )
+        .await
+        .expect("reprice failed");
+
+        assert_eq!(body["count"], 3);
+        let succeeded = body["succeeded"].as_array().unwrap();
+        assert_eq!(succeeded.len(), 1);
+        assert_eq!(succeeded[0]["order_id"], "open-0");
+        assert_eq!(succeeded[0]["status"], "replaced");
+
+        let failed = body["failed"].as_array().unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0]["id"], "open-1");
+        assert_eq!(failed[0]["error_code"], "-2011");
+        assert!(failed[0]["message"].as_str().unwrap().starts_with("cancel failed"));
+        assert_eq!(failed[1]["id"], "open-2");
+        assert_eq!(failed[1]["error_code"], "30004");
+        assert!(failed[1]["message"].as_str().unwrap().contains("replacement failed"));
+    }
+
+    #[tokio::test]
+    async fn test_reprice_replacement_passes_placement_checks() {
+        let cancelled = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let placed = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let mexc_order = |order_id: &str, status: &str| {
+            json!({
+                "order_id": order_id,
+                "symbol": "ETHUSDT",
+                "side": "BUY",
+                "order_type": "LIMIT",
+                "quantity": 1.0,
+                "price": 100.0,
+                "status": status,
+                "filled_qty": 0.0,
+                "created_at": 0,
+            })
+        };
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                delete({
+                    let cancelled = cancelled.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        cancelled.lock().unwrap().push(params["orderId"].clone());
+                        Json(mexc_order(&params["orderId"], "CANCELED"))
+                    }
+                })
+                .post({
+                    let placed = placed.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        placed.lock().unwrap().push(format!("{} @ {}", params["quantity"], params["price"]));
+                        Json(mexc_order("mexc-new", "NEW"))
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/account",
+                get(|| async { Json(json!({ "balances": [{ "asset": "USDT", "free": 120.0, "locked": 0.0 }] })) }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        // 1 (ok), 2 (über der Bestätigungsschwelle) und 1.4 ETH (Guthaben reicht nicht)
+        let mut open = open_orders(3);
+        let items = open["Items"].as_array_mut().unwrap().iter_mut();
+        for (i, (item, quantity)) in items.zip(["1", "2", "1.4"]).enumerate() {
+            item["mexc_order_id"] = json!({ "S": format!("mexc-{}", i + 1) });
+            item["quantity"] = json!({ "N": quantity });
+        }
+        dynamo.respond("Query", open);
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            preflight: Some(Arc::new(BalancePreflight::new(&Config::default()))),
+            large_order_confirm: LargeOrderConfirm {
+                threshold: 150.0,
+                ttl: Duration::from_secs(60),
+            },
+            ..test_state(mexc, store)
+        });
+        state.precision.update(&crate::mexc::SymbolInfo {
+            symbol: "ETHUSDT".to_string(),
+            state: SymbolState::Trading,
+            qty_precision: None,
+            price_precision: Some(2),
+        });
+
+        // Fester Preis ohne Symbol wird abgelehnt
+        let err = reprice_orders(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(RepriceRequest {
+                price: Some(99.0),
+                price_delta: None,
+                symbol: None,
+            }),
+        )
+        .await
+        .expect_err("absolute price without symbol accepted");
+        assert_eq!(err.kind(), "validation");
+
+        let Json(body) = reprice_orders(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(RepriceRequest {
+                price: None,
+                price_delta: Some(-0.123),
+                symbol: None,
+            }),
+        )
+        .await
+        .expect("reprice failed");
+
+        // Auf zwei Nachkommastellen gerundet; die große Order bleibt unangetastet
+        assert_eq!(*placed.lock().unwrap(), vec!["1 @ 99.88"]);
+        let mut cancelled = cancelled.lock().unwrap().clone();
+        cancelled.sort();
+        assert_eq!(cancelled, vec!["mexc-1", "mexc-3"]);
+
+        let failed = body["failed"].as_array().unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0]["id"], "open-1");
+        assert_eq!(failed[0]["error_code"], "validation");
+        assert!(failed[0]["message"].as_str().unwrap().contains("confirmation threshold"));
+        assert_eq!(failed[1]["id"], "open-2");
+        assert!(failed[1]["message"].as_str().unwrap().contains("replacement failed"));
+
+        // Ohne Ersatz keine Verknüpfung auf eine nie platzierte Order
+        let puts: Vec<_> = dynamo.requests("PutItem").into_iter().map(|p| p["Item"].clone()).collect();
+        let unfunded = puts.iter().find(|i| i["order_id"]["S"] == "open-2").unwrap();
+        assert_eq!(unfunded["status"]["S"], "cancelled");
+        assert!(unfunded.get("replaced_by").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejections_are_categorized_and_summarized() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|| async {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "code": 10101, "msg": "Insufficient balance" })),
+                )
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let order = OrderItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            "BUY".to_string(),
+            "MARKET".to_string(),
+            1.0,
+            None,
+        );
+        let mexc_order = MexcOrderRequest {
+            symbol: "ETHUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "MARKET".to_string(),
+            quantity: 1.0,
+            quote_order_qty: None,
+            client_order_id: None,
+            price: None,
+        };
+        let err = submit_order(&state, order, mexc_order).await.unwrap_err();
+        assert_eq!(err.kind(), "upstream");
+
+        let stored = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert_eq!(stored["status"]["S"], "error");
+        assert_eq!(stored["rejection_code"]["S"], "10101");
+        assert_eq!(stored["rejection_category"]["S"], "insufficient_funds");
+
+        let mut filter = stored.clone();
+        filter["rejection_code"] = json!({ "S": "30002" });
+        filter["rejection_category"] = json!({ "S": "filter_failure" });
+        let mut network = stored.clone();
+        network.as_object_mut().unwrap().remove("rejection_code");
+        network.as_object_mut().unwrap().remove("rejection_category");
+        dynamo.respond("Query", json!({ "Count": 3, "Items": [stored, filter, network] }));
+
+        let Json(body) = rejection_summary(State(state), Path("user-1".to_string()), auth("user-1"))
+            .await
+            .expect("summary failed");
+
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["by_category"]["insufficient_funds"], 1);
+        assert_eq!(body["by_category"]["filter_failure"], 1);
+        assert_eq!(body["by_category"]["unknown"], 1);
+        assert_eq!(body["by_category"]["rate_limited"], 0);
+        assert_eq!(body["by_code"]["10101"], 1);
+        assert_eq!(body["by_code"]["30002"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_only_uses_limit_maker_and_maps_rejection() {
+        let types = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let types = types.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    types.lock().unwrap().push(params["type"].clone());
+                    // Best Ask bei 100: Buy ab 100 würde sofort matchen
+                    if params["price"].parse::<f64>().unwrap() >= 100.0 {
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            Json(json!({ "code": -2010, "msg": "Order would immediately match and take." })),
+                        );
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(json!({
+                            "order_id": "mexc-1",
+                            "symbol": "ETHUSDT",
+                            "side": "BUY",
+                            "order_type": "LIMIT_MAKER",
+                            "quantity": 1.0,
+                            "price": 99.0,
+                            "status": "NEW",
+                            "filled_qty": 0.0,
+                            "created_at": 0,
+                        })),
+                    )
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+        let post_only = |price: f64| ApiOrderRequest {
+            price: Some(price),
+            post_only: true,
+            ..limit_order()
+        };
+
+        let (status, _) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(post_only(99.0)),
+        )
+        .await
+        .expect("resting post-only order failed");
+        assert_eq!(status, StatusCode::CREATED);
+
+        let err = create_order(State(state.clone()), Path("user-1".to_string()), auth("user-1"), Json(post_only(100.0)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), "would_take_liquidity");
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+
+        assert_eq!(*types.lock().unwrap(), vec!["LIMIT_MAKER", "LIMIT_MAKER"]);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts[0]["Item"]["post_only"]["BOOL"], true);
+        assert_eq!(puts[0]["Item"]["order_type"]["S"], "LIMIT");
+        assert_eq!(puts[1]["Item"]["rejection_category"]["S"], "would_take_liquidity");
+
+        // Nur für Limit Orders mit Preis
+        let market = ApiOrderRequest {
+            order_type: "MARKET".to_string(),
+            price: None,
+            post_only: true,
+            ..limit_order()
+        };
+        let err = create_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(market))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), "validation");
+        assert_eq!(types.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_order_note_set_update_and_length_limit() {
+        let mexc = Arc::new(mexc_client("http://127.0.0.1:9"));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+        let set_note = |note: String| {
+            set_order_note(
+                State(state.clone()),
+                Path(("user-1".to_string(), "open-0".to_string())),
+                auth("user-1"),
+                Json(NoteRequest { note: Some(note) }),
+            )
+        };
+
+        dynamo.respond("Query", open_orders(1));
+        let Json(body) = set_note("  Breakout\u{0} retest <script>\n".to_string())
+            .await
+            .expect("set note failed");
+        assert_eq!(body["note"], "Breakout retest script");
+        let stored = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert_eq!(stored["note"]["S"], "Breakout retest script");
+
+        // Update: gespeicherte Order mit Notiz zurücklesen und überschreiben
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored] }));
+        let Json(body) = set_note("Took profit early".to_string()).await.expect("update failed");
+        assert_eq!(body["note"], "Took profit early");
+        assert_eq!(dynamo.requests("PutItem")[1]["Item"]["note"]["S"], "Took profit early");
+
+        let err = set_note("x".repeat(MAX_NOTE_CHARS + 1)).await.unwrap_err();
+        assert_eq!(err.kind(), "validation");
+        assert_eq!(dynamo.requests("PutItem").len(), 2);
+        assert!(set_note("x".repeat(MAX_NOTE_CHARS)).await.is_err_and(|e| e.kind() == "not_found"));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_surfaces_filter_violation_as_validation() {
+        let tested = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/order/test",
+            post({
+                let tested = tested.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    tested.fetch_add(1, Ordering::SeqCst);
+                    let notional = params["quantity"].parse::<f64>().unwrap() * params["price"].parse::<f64>().unwrap();
+                    if notional < 5.0 {
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            Json(json!({ "code": 30002, "msg": "Minimum transaction volume cannot be less than:5USDT" })),
+                        );
+                    }
+                    (StatusCode::OK, Json(json!({})))
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let Json(body) = estimate_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("estimate failed");
+        assert_eq!(body["valid"], true);
+        assert_eq!(body["notional"], 100.0);
+
+        let tiny = ApiOrderRequest {
+            quantity: Some(0.01),
+            ..limit_order()
+        };
+        let err = estimate_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(tiny))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), "validation");
+        assert!(err.message().contains("Minimum transaction volume"));
+
+        // Nur geprüft, nichts platziert oder gespeichert
+        assert_eq!(tested.load(Ordering::SeqCst), 2);
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_fills_syncs_from_mexc() {
+        let router = Router::new().route(
+            "/api/v3/myTrades",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                assert_eq!(params["symbol"], "ETHUSDT");
+                Json(json!([
+                    {
+                        "symbol": "ETHUSDT", "id": "t-1", "orderId": "o-1",
+                        "price": "2000", "qty": "0.5", "quoteQty": "1000",
+                        "commission": "0.0005", "commissionAsset": "ETH",
+                        "time": 1_000, "isBuyer": true, "isMaker": false
+                    },
+                    {
+                        "symbol": "ETHUSDT", "id": "t-2", "orderId": "o-2",
+                        "price": "2100", "qty": "0.5", "quoteQty": "1050",
+                        "commission": "1.05", "commissionAsset": "USDT",
+                        "time": 2_000, "isBuyer": false, "isMaker": true
+                    }
+                ]))
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(test_state(mexc, store));
+
+        let Json(body) = get_fills(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Query(FillsQuery {
+                symbol: Some("ETHUSDT".to_string()),
+                limit: None,
+            }),
+        )
+        .await
+        .expect("fills failed");
+
+        assert_eq!(body["count"], 2);
+        assert_eq!(body["fills"][0]["trade_id"], "t-2");
+        assert_eq!(body["fills"][0]["fee_asset"], "USDT");
+        assert_eq!(body["fills"][0]["is_maker"], true);
+        assert_eq!(body["fills"][1]["quantity"], 0.5);
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 2);
+        assert_eq!(puts[0]["Item"]["sk"]["S"], "FILL#1000#t-1");
+    }
+
+    #[tokio::test]
+    async fn test_fill_confirmation_captures_fill() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let order = |status: &str, filled_qty: f64, price: f64| {
+            json!({
+                "order_id": "mexc-1",
+                "symbol": "ETHUSDT",
+                "side": "BUY",
+                "order_type": "LIMIT",
+                "quantity": 1.0,
+                "price": price,
+                "status": status,
+                "filled_qty": filled_qty,
+                "created_at": 0,
+            })
+        };
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(move || async move { Json(order("NEW", 0.0, 0.0)) }).get({
+                let polls = polls.clone();
+                move || async move {
+                    // Erster Poll noch NEW, zweiter gefüllt
+                    if polls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        Json(order("NEW", 0.0, 0.0))
+                    } else {
+                        Json(order("FILLED", 1.0, 99.5))
+                    }
+                }
+            }),
+        );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            fill_confirmation: FillConfirmation {
+                polls: 5,
+                interval: Duration::from_millis(5),
+            },
+            ..test_state(mexc, store)
+        });
+
+        let (_, Json(body)) = create_order(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("order failed");
+
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+        assert_eq!(body["status"], "filled");
+        assert_eq!(body["filled_qty"], 1.0);
+        assert_eq!(body["fill_price"], 99.5);
+
+        let stored = &dynamo.requests("PutItem")[0]["Item"];
+        assert_eq!(stored["status"]["S"], "filled");
+        assert_eq!(stored["fill_price"]["N"], "99.5");
+    }
+
+    #[tokio::test]
+    async fn test_paper_orders_are_simulated_instead_of_sent() {
+        let placed = Arc::new(AtomicUsize::new(0));
+        let price = Arc::new(std::sync::Mutex::new(101.0));
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                post({
+                    let placed = placed.clone();
+                    move || async move {
+                        placed.fetch_add(1, Ordering::SeqCst);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/ticker/24hr",
+                get({
+                    let price = price.clone();
+                    move || async move {
+                        Json(json!({ "symbol": "ETHUSDT", "price": *price.lock().unwrap(), "timestamp": 0 }))
+                    }
+                }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let monitor = OrderMonitor::new(mexc.clone(), store.clone(), Duration::from_secs(60))
+            .with_price_cache(Arc::new(crate::mexc::websocket::PriceCache::new(Duration::ZERO)));
+        // Preflight an, aber keine Keys für user-1: Paper-Orders brauchen kein Guthaben
+        let state = Arc::new(TradingState {
+            order_monitor: Arc::new(monitor),
+            clients: Arc::new(MexcClientPool::new(mexc.clone(), store.clone(), &Config::default())),
+            preflight: Some(Arc::new(BalancePreflight::new(&Config::default()))),
+            paper_trading: true,
+            ..test_state(mexc, store)
+        });
+
+        // Market füllt sofort zum Ticker
+        let market = ApiOrderRequest {
+            order_type: "MARKET".to_string(),
+            price: None,
+            ..limit_order()
+        };
+        let (_, Json(body)) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(market),
+        )
+        .await
+        .expect("paper market order failed");
+        assert_eq!(body["status"], "filled");
+        assert_eq!(body["fill_price"], 101.0);
+        assert_eq!(body["paper"], true);
+
+        // Limit unter dem Preis bleibt offen
+        let (_, Json(body)) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("paper limit order failed");
+        assert_eq!(body["status"], "open");
+        let stored = dynamo.requests("PutItem")[1]["Item"].clone();
+        assert_eq!(stored["paper"]["BOOL"], true);
+        assert_eq!(stored["status"]["S"], "open");
+
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored.clone()] }));
+        assert_eq!(state.order_monitor.poll_once().await.unwrap(), 0);
+
+        // Preis kreuzt das Limit: der Monitor füllt beim nächsten Poll
+        *price.lock().unwrap() = 99.5;
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored] }));
+        assert_eq!(state.order_monitor.poll_once().await.unwrap(), 1);
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 3);
+        assert_eq!(puts[2]["Item"]["status"]["S"], "filled");
+        assert_eq!(puts[2]["Item"]["filled_qty"]["N"], "1");
+        assert_eq!(puts[2]["Item"]["fill_price"]["N"], "100");
+        assert_eq!(placed.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_paper_limit_order_fills_at_limit_without_simulation() {
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            paper_trading: true,
+            paper_simulate_fills: false,
+            ..test_state(Arc::new(mexc_client("http://127.0.0.1:9")), store)
+        });
+
+        let (_, Json(body)) = create_order(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("paper limit order failed");
+
+        assert_eq!(body["status"], "filled");
+        assert_eq!(body["filled_qty"], 1.0);
+        assert_eq!(body["fill_price"], 100.0);
+        let stored = &dynamo.requests("PutItem")[0]["Item"];
+        assert_eq!(stored["status"]["S"], "filled");
+        assert_eq!(stored["fill_price"]["N"], "100");
+    }
+
+    #[tokio::test]
+    async fn test_underfunded_order_is_rejected_preflight() {
+        let placed = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                post({
+                    let placed = placed.clone();
+                    move || async move {
+                        placed.fetch_add(1, Ordering::SeqCst);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/account",
+                get(|| async {
+                    Json(json!({ "balances": [{ "asset": "USDT", "free": 100.0, "locked": 0.0 }] }))
+                }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let state = Arc::new(TradingState {
+            preflight: BalancePreflight::from_config(&crate::utils::Config::default()),
+            ..test_state(mexc, store)
+        });
+
+        // 1 x 100 USDT + 0.1% Gebühren > 100 USDT frei
+        let err = create_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(limit_order()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(err.message().contains("Insufficient USDT balance"));
+        assert_eq!(placed.load(Ordering::SeqCst), 0);
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_open_order_cap_boundary_single() {
+        let dynamo = MockDynamo::start().await;
+        let (state, placed) = capped_state(&dynamo, 2).await;
+
+        // 1 offen + 1 neu = Limit
+        dynamo.respond("Query", open_orders(1));
+        let (status, _) = create_order(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(limit_order()),
+        )
+        .await
+        .expect("order below cap failed");
+        assert_eq!(status, StatusCode::CREATED);
+
+        // 2 offen + 1 neu > Limit
+        dynamo.respond("Query", open_orders(2));
+        let err = create_order(State(state.clone()), Path("user-1".to_string()), auth("user-1"), Json(limit_order()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+
+        // User-Setting überschreibt das globale Limit
+        dynamo.respond("GetItem", json!({ "Item": { "value": { "N": "1" } } }));
+        dynamo.respond("Query", open_orders(1));
+        let err = create_order(State(state), Path("user-1".to_string()), auth("user-1"), Json(limit_order()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_open_order_cap_boundary_batch() {
+        let dynamo = MockDynamo::start().await;
+        let (state, placed) = capped_state(&dynamo, 3).await;
+
+        dynamo.respond("Query", open_orders(1));
+        let Json(body) = create_orders_batch(
+            State(state.clone()),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(BatchOrderRequest { orders: vec![limit_order(), limit_order()] }),
+        )
+        .await
+        .expect("batch at cap failed");
+        assert_eq!(body["succeeded"].as_array().unwrap().len(), 2);
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+
+        // Ganzer Batch abgelehnt, keine Order gesendet
+        dynamo.respond("Query", open_orders(1));
+        let err = create_orders_batch(
+            State(state),
+            Path("user-1".to_string()),
+            auth("user-1"),
+            Json(BatchOrderRequest { orders: vec![limit_order(), limit_order(), limit_order()] }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_order_amount_is_mutually_exclusive() {
+        assert_eq!(
+            validate_order_amount("MARKET", Some(1.0), None),
+            Ok(OrderAmount::Base(1.0))
+        );
+        assert_eq!(
+            validate_order_amount("MARKET", None, Some(50.0)),
+            Ok(OrderAmount::Quote(50.0))
+        );
+        assert!(validate_order_amount("MARKET", Some(1.0), Some(50.0)).is_err());
+        assert!(validate_order_amount("MARKET", None, None).is_err());
+        assert!(validate_order_amount("LIMIT", None, Some(50.0)).is_err());
+        assert!(validate_order_amount("MARKET", None, Some(0.0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_per_user_routes_require_the_users_own_token() {
+        let dynamo = MockDynamo::start().await;
+        let state = Arc::new(test_state(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+        ));
+
+        for headers in [HeaderMap::new(), auth("user-2")] {
+            let err = create_order(
+                State(state.clone()),
+                Path("user-1".to_string()),
+                headers.clone(),
+                Json(limit_order()),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+
+            let err = cancel_order(
+                State(state.clone()),
+                Path(("user-1".to_string(), "o-1".to_string())),
+                headers.clone(),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+
+            let err = get_fills(
+                State(state.clone()),
+                Path("user-1".to_string()),
+                headers,
+                Query(FillsQuery { symbol: None, limit: None }),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+        }
+        // Abgewiesen, bevor DynamoDB oder MEXC angefragt werden
+        assert!(dynamo.requests("Query").is_empty());
+        assert!(dynamo.requests("PutItem").is_empty());
+        assert!(dynamo.requests("GetItem").is_empty());
+
+        // Bestätigungs-Token eines anderen Users
+        let pending = json!({
+            "user_id": { "S": "SYSTEM" },
+            "sk": { "S": "ORDER_CONFIRM#t-1" },
+            "owner_id": { "S": "user-1" },
+            "request": { "S": serde_json::to_string(&limit_order()).unwrap() },
+            "notional": { "N": "100" },
+            "ttl": { "N": (chrono::Utc::now().timestamp() + 60).to_string() },
+        });
+        dynamo.respond("DeleteItem", json!({ "Attributes": pending }));
+        let err = confirm_order(
+            State(state),
+            auth("user-2"),
+            Json(ConfirmOrderRequest {
+                confirmation_token: "t-1".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_credentials_require_the_users_own_token() {
+        let dynamo = MockDynamo::start().await;
+        let mexc = Arc::new(mexc_client("http://127.0.0.1:9"));
+        let store = dynamo.store().await.with_cipher(Arc::new(crate::utils::FieldCipher::new(&[1u8; 32])));
+        let state = Arc::new(test_state(mexc, Arc::new(store)));
+        let put = |token: Option<String>| {
+            let mut headers = HeaderMap::new();
+            if let Some(token) = token {
+                headers.insert(axum::http::header::AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+            }
+            put_credentials(
+                State(state.clone()),
+                Path("user-1".to_string()),
+                headers,
+                Json(Credentials {
+                    api_key: "key".to_string(),
+                    secret_key: "secret".to_string(),
+                }),
+            )
+        };
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        let err = put(None).await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+        let err = put(Some(crate::api::auth::tests::sign_jwt("jwt-secret", "user-2", exp)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+        assert!(dynamo.requests("PutItem").is_empty());
+
+        let Json(body) = put(Some(crate::api::auth::tests::sign_jwt("jwt-secret", "user-1", exp)))
+            .await
+            .expect("own credentials rejected");
+        assert_eq!(body["status"], "stored");
+        assert_eq!(dynamo.requests("PutItem").len(), 1);
+    }
 }
@@ -0,0 +1,343 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::HeaderMap,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::auth::constant_time_eq;
+use crate::api::error::ApiError;
+use crate::storage::{CalendarEventItem, DynamoDBStore};
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::Config;
+
+/// Eindeutige ID der Delivery (Nonce für den Replay-Schutz)
+pub const DELIVERY_ID_HEADER: &str = "x-webhook-id";
+/// Zeitpunkt der Delivery in Unix ms
+pub const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+/// Hex-HMAC-SHA256 über `{timestamp}.{id}.{body}`
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Max. Body-Größe eines Listing-Webhooks
+const MAX_BODY_BYTES: usize = 256 * 1024;
+
+/// Replay-Schutz für Webhook-Deliveries: jede Delivery-ID wird nur einmal
+/// akzeptiert, Payloads älter als `max_age` werden verworfen.
+/// Ergänzt die HMAC-Prüfung, die zwar Fälschung aber keinen Replay verhindert.
+pub struct ReplayGuard {
+    store: Arc<DynamoDBStore>,
+    max_age: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl ReplayGuard {
+    pub fn new(store: Arc<DynamoDBStore>, max_age: Duration) -> Self {
+        Self {
+            store,
+            max_age,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Prüfe Delivery-ID und Payload-Timestamp (Unix ms); bei Erfolg ist die ID verbraucht
+    pub async fn check(&self, delivery_id: &str, timestamp_ms: i64) -> Result<(), ApiError> {
+        let now_ms = self.clock.now_millis();
+        let max_age_ms = self.max_age.as_millis() as i64;
+
+        if (now_ms - timestamp_ms).abs() > max_age_ms {
+            return Err(ApiError::Validation("Webhook timestamp outside allowed window".to_string()));
+        }
+
+        // Nonce muss nur so lange leben wie ein Payload noch akzeptiert würde
+        let expires_at = (timestamp_ms + max_age_ms) / 1000 + 1;
+        let claimed = self
+            .store
+            .claim_webhook_nonce(delivery_id, expires_at)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to store webhook nonce: {}", e);
+                ApiError::Internal(e.to_string())
+            })?;
+
+        if !claimed {
+            tracing::warn!("Rejected replayed webhook delivery {}", delivery_id);
+            return Err(ApiError::Conflict("Webhook delivery already processed".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Listing-Webhook: signierte Listings von außen als Calendar Events einspielen
+pub struct WebhookState {
+    pub store: Arc<DynamoDBStore>,
+    /// HMAC-Secret (LISTING_WEBHOOK_SECRET); ohne Secret wird alles abgelehnt
+    pub secret: Option<String>,
+    pub replay: ReplayGuard,
+}
+
+impl WebhookState {
+    pub fn from_config(store: Arc<DynamoDBStore>, config: &Config) -> Self {
+        Self {
+            replay: ReplayGuard::new(store.clone(), Duration::from_secs(config.webhook_max_age_secs)),
+            store,
+            secret: config.listing_webhook_secret.clone().filter(|s| !s.is_empty()),
+        }
+    }
+}
+
+/// Signatur einer Delivery (Hex), gebunden an ID und Timestamp, damit ein
+/// mitgeschnittener Body nicht unter neuer ID erneut eingespielt werden kann
+pub fn sign_delivery(secret: &str, timestamp: &str, delivery_id: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(delivery_id.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, ApiError> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| ApiError::Unauthorized(format!("Missing {} header", name)))
+}
+
+/// Middleware: HMAC-Signatur prüfen (401), danach Replay-Schutz (409 bei
+/// bekannter Delivery-ID, 400 bei zu altem Timestamp)
+pub async fn verify_delivery(
+    State(state): State<Arc<WebhookState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let (parts, body) = req.into_parts();
+    let Ok(body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::Validation("Webhook body too large".to_string()).into_response();
+    };
+
+    let checked = async {
+        let secret = state
+            .secret
+            .as_deref()
+            .ok_or_else(|| ApiError::Unauthorized("Listing webhook is not configured".to_string()))?;
+        let delivery_id = header(&parts.headers, DELIVERY_ID_HEADER)?;
+        let timestamp = header(&parts.headers, TIMESTAMP_HEADER)?;
+        let signature = header(&parts.headers, SIGNATURE_HEADER)?;
+
+        let expected = sign_delivery(secret, timestamp, delivery_id, &body);
+        if !constant_time_eq(expected.as_bytes(), signature.to_ascii_lowercase().as_bytes()) {
+            tracing::warn!("Rejected webhook delivery {} with invalid signature", delivery_id);
+            return Err(ApiError::Unauthorized("Invalid webhook signature".to_string()));
+        }
+
+        let timestamp_ms = timestamp
+            .parse::<i64>()
+            .map_err(|_| ApiError::Validation("Invalid webhook timestamp".to_string()))?;
+        state.replay.check(delivery_id, timestamp_ms).await
+    }
+    .await;
+
+    match checked {
+        Ok(()) => next.run(Request::from_parts(parts, Body::from(body))).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListingWebhook {
+    pub user_id: String,
+    pub listings: Vec<Listing>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Listing {
+    pub token_name: String,
+    pub symbol: String,
+    /// Unix ms
+    pub launch_time: i64,
+    pub pattern: String,
+    pub confidence: f64,
+}
+
+/// POST /api/v1/webhook/listing - Listings als Calendar Events einlesen
+/// (gleicher Launch = gleicher Event-Key, Status bleibt erhalten)
+pub async fn receive_listing(
+    State(state): State<Arc<WebhookState>>,
+    Json(payload): Json<ListingWebhook>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let events: Vec<CalendarEventItem> = payload
+        .listings
+        .into_iter()
+        .map(|listing| {
+            CalendarEventItem::new(
+                payload.user_id.clone(),
+                listing.token_name,
+                listing.symbol.to_ascii_uppercase(),
+                listing.launch_time,
+                listing.pattern,
+                listing.confidence,
+            )
+        })
+        .collect();
+
+    let ingested = state.store.ingest_calendar_events(&events).await.map_err(|e| {
+        tracing::error!("Failed to ingest webhook listings: {}", e);
+        ApiError::Internal(e.to_string())
+    })?;
+    tracing::info!("Listing webhook ingested {} events for {}", ingested, payload.user_id);
+
+    Ok(Json(json!({ "received": events.len(), "ingested": ingested })))
+}
+
+/// Router für eingehende Webhooks (unter /api/v1)
+pub fn webhook_router(state: Arc<WebhookState>) -> Router {
+    Router::new()
+        .route("/webhook/listing", post(receive_listing))
+        .route_layer(middleware::from_fn_with_state(state.clone(), verify_delivery))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use crate::test_support::{spawn_server, MockDynamo};
+    use crate::utils::clock::MockClock;
+
+    async fn guard(dynamo: &MockDynamo) -> ReplayGuard {
+        ReplayGuard::new(Arc::new(dynamo.store().await), Duration::from_secs(300))
+    }
+
+    #[tokio::test]
+    async fn test_first_delivery_is_accepted() {
+        let dynamo = MockDynamo::start().await;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        assert!(guard(&dynamo).await.check("delivery-1", now).await.is_ok());
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0]["Item"]["sk"]["S"], "WEBHOOK_NONCE#delivery-1");
+        assert_eq!(puts[0]["ConditionExpression"], "attribute_not_exists(sk)");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_delivery_is_rejected() {
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond_error("PutItem", "ConditionalCheckFailedException");
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let err = guard(&dynamo).await.check("delivery-1", now).await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_old_delivery_is_rejected() {
+        let dynamo = MockDynamo::start().await;
+        let ten_minutes_ago = chrono::Utc::now().timestamp_millis() - 600_000;
+
+        let err = guard(&dynamo)
+            .await
+            .check("delivery-1", ten_minutes_ago)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_window_expires_with_clock() {
+        let dynamo = MockDynamo::start().await;
+        let clock = Arc::new(MockClock::from_millis(1_700_000_000_000));
+        let guard = guard(&dynamo).await.with_clock(clock.clone());
+
+        clock.advance(Duration::from_secs(299));
+        assert!(guard.check("delivery-1", 1_700_000_000_000).await.is_ok());
+
+        clock.advance(Duration::from_secs(2));
+        let err = guard.check("delivery-2", 1_700_000_000_000).await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+
+    async fn webhook_server(dynamo: &MockDynamo) -> String {
+        let config = Config {
+            listing_webhook_secret: Some("hook-secret".to_string()),
+            ..Default::default()
+        };
+        let state = Arc::new(WebhookState::from_config(Arc::new(dynamo.store().await), &config));
+        spawn_server(webhook_router(state)).await
+    }
+
+    async fn deliver(base_url: &str, delivery_id: &str, timestamp: i64, secret: &str) -> reqwest::Response {
+        let body = serde_json::json!({
+            "user_id": "user-1",
+            "listings": [{
+                "token_name": "New Token",
+                "symbol": "newusdt",
+                "launch_time": 1_700_000_000_000i64,
+                "pattern": "sts:2",
+                "confidence": 0.9,
+            }],
+        })
+        .to_string();
+        let timestamp = timestamp.to_string();
+        reqwest::Client::new()
+            .post(format!("{}/webhook/listing", base_url))
+            .header("content-type", "application/json")
+            .header(DELIVERY_ID_HEADER, delivery_id)
+            .header(TIMESTAMP_HEADER, &timestamp)
+            .header(SIGNATURE_HEADER, sign_delivery(secret, &timestamp, delivery_id, body.as_bytes()))
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_webhook_route_rejects_replays_and_bad_signatures() {
+        let dynamo = MockDynamo::start().await;
+        let base_url = webhook_server(&dynamo).await;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let response = deliver(&base_url, "delivery-1", now, "hook-secret").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["received"], 1);
+        let nonce = &dynamo.requests("PutItem")[0];
+        assert_eq!(nonce["Item"]["sk"]["S"], "WEBHOOK_NONCE#delivery-1");
+
+        // Gleiche Delivery erneut eingespielt: Nonce existiert bereits
+        dynamo.respond_error("PutItem", "ConditionalCheckFailedException");
+        let replay = deliver(&base_url, "delivery-1", now, "hook-secret").await;
+        assert_eq!(replay.status(), StatusCode::CONFLICT);
+
+        // Zu alt
+        let stale = deliver(&base_url, "delivery-2", now - 600_000, "hook-secret").await;
+        assert_eq!(stale.status(), StatusCode::BAD_REQUEST);
+
+        // Falsches Secret: weder Nonce noch Ingestion
+        let before = dynamo.requests("PutItem").len() + dynamo.requests("BatchWriteItem").len();
+        let forged = deliver(&base_url, "delivery-3", now, "wrong-secret").await;
+        assert_eq!(forged.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(dynamo.requests("PutItem").len() + dynamo.requests("BatchWriteItem").len(), before);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod api;
+pub mod mexc;
+pub mod storage;
+pub mod trading;
+pub mod utils;
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+mod test_support;
@@ -1,21 +1,17 @@
-mod api;
-mod mexc;
-mod storage;
-mod trading;
-mod utils;
-
-#[cfg(test)]
-mod tests;
-
 use axum::{
     middleware,
     routing::get,
     Router,
 };
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 
+use mexc_sniper::{api, mexc, storage, trading, utils};
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize logging
@@ -28,37 +24,247 @@ async fn main() -> anyhow::Result<()> {
         config.rust_api_port
     );
 
+    // Optionaler Region-Check gegen MEXC-Sperrliste (nur mit GEO_CHECK_FATAL fatal)
+    utils::geo::check_egress_region(&config).await?;
+
     // Initialize storage layer
-    let store = Arc::new(storage::DynamoDBStore::new(config.dynamodb_table.clone()).await?);
+    let mut store = storage::DynamoDBStore::new(config.dynamodb_table.clone())
+        .await?
+        .with_calendar_batch_writes(config.calendar_batch_writes);
+    if let Some(cipher) = utils::FieldCipher::from_config(&config)? {
+        store = store.with_cipher(Arc::new(cipher));
+    }
+    if let Some(region) = &config.dynamodb_failover_region {
+        tracing::info!("DynamoDB failover region: {} ({:?})", region, config.dynamodb_write_policy);
+        let secondary = storage::DynamoDBStore::region_client(region).await;
+        store = store.with_failover(secondary, config.dynamodb_write_policy);
+    }
+    let store = Arc::new(store);
+
+    // Initialize metrics
+    let metrics = Arc::new(utils::Metrics::new());
+
+    // Alert-Webhook mit Deduplizierung
+    let alerts = Arc::new(utils::AlertSink::from_config(&config));
+    tokio::spawn(alerts.clone().run_flush());
 
     // Initialize MEXC client
-    let mexc_client = Arc::new(mexc::MexcClient::new(&config)?);
+    let mexc_client = Arc::new(
+        mexc::MexcClient::new(&config)?
+            .with_metrics(metrics.clone())
+            .with_alerts(alerts.clone()),
+    );
+    let mexc_clients = Arc::new(
+        mexc::MexcClientPool::new(mexc_client.clone(), store.clone(), &config).with_metrics(metrics.clone()),
+    );
 
-    // Initialize metrics
-    let _metrics = Arc::new(utils::Metrics::new());
+    // exchangeInfo-Cache (Präzisionen), u.a. für nachgezogene Limit-Snipes
+    let precision_cache = Arc::new(trading::PrecisionCache::from_config(&config));
+
+    // Optionaler Write-behind für Orders (Latenz vor Haltbarkeit)
+    let write_behind = storage::OrderWriteBuffer::from_config(store.clone(), &config)
+        .map(|buffer| Arc::new(buffer.with_metrics(&metrics)));
+
+    // Order Monitor im Hintergrund (wird beim Shutdown kontrolliert beendet)
+    let mut order_monitor = trading::OrderMonitor::new(
+        mexc_client.clone(),
+        store.clone(),
+        Duration::from_secs(config.order_monitor_interval_secs),
+    )
+    .with_maintenance_probe(Duration::from_secs(config.mexc_maintenance_probe_secs))
+    .with_client_pool(mexc_clients.clone())
+    .with_order_index(Arc::new(trading::OrderIndex::from_config(&config)))
+    .with_limit_chaser(trading::LimitChaser::from_config(&config).with_precision_cache(precision_cache.clone()));
+    if let Some(buffer) = &write_behind {
+        order_monitor = order_monitor.with_write_behind(buffer.clone());
+    }
+    if config.paper_trading {
+        tracing::warn!("PAPER_TRADING enabled: API orders are simulated, not sent to MEXC");
+    }
+    if config.paper_trading && config.paper_simulate_fills {
+        // Paper-Limit-Orders füllen gegen diesen Cache; der Monitor lädt je Poll nach
+        order_monitor = order_monitor.with_price_cache(Arc::new(mexc::websocket::PriceCache::new(Duration::from_secs(
+            config.order_monitor_interval_secs,
+        ))));
+    }
+    let order_monitor = Arc::new(order_monitor);
+
+    // Offene Orders aus der Zeit vor dem Neustart weiter überwachen
+    match order_monitor.load_tracked_users().await {
+        Ok(users) => tracing::info!("Order monitor tracking {} users with open orders", users),
+        Err(e) => tracing::warn!("Failed to load users with open orders: {}", e),
+    }
+
+    // Startup-Selbstcheck: verwaiste MEXC-Orders (Crash vor dem Speichern) übernehmen
+    if !config.order_recovery_symbols.is_empty() {
+        let recovery_user = config.mexc_admin_user_id.as_deref().unwrap_or("system");
+        let recovered = trading::OrderRecovery::new(mexc_client.clone(), store.clone())
+            .run(recovery_user, &config.order_recovery_symbols)
+            .await;
+        if recovered > 0 {
+            order_monitor.track_user(recovery_user);
+        }
+    }
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let monitor_handle = tokio::spawn({
+        let order_monitor = order_monitor.clone();
+        let shutdown_rx = shutdown_rx.clone();
+        async move { order_monitor.run(shutdown_rx).await }
+    });
+
+    // exchangeInfo-Cache (Präzisionen) periodisch aktualisieren
+    if config.exchange_info_refresh_secs > 0 {
+        tokio::spawn(precision_cache.clone().run_refresh(
+            mexc_client.clone(),
+            Duration::from_secs(config.exchange_info_refresh_secs),
+            shutdown_rx.clone(),
+        ));
+    }
+
+    // Mindestabstand je Symbol, geteilt von API-Orders und Snipes
+    let placement_cooldown = Arc::new(trading::PlacementCooldown::from_config(&config));
+
+    let write_behind_handle = write_behind.clone().map(|buffer| tokio::spawn(buffer.run(shutdown_rx.clone())));
 
     // Create application state for each router
     let trading_state = Arc::new(api::TradingState {
         mexc_client: mexc_client.clone(),
+        clients: mexc_clients.clone(),
         store: store.clone(),
+        order_monitor: order_monitor.clone(),
+        max_open_orders_per_symbol: config.max_open_orders_per_symbol,
+        preflight: trading::BalancePreflight::from_config(&config),
+        fill_confirmation: trading::FillConfirmation::from_config(&config),
+        rate_limiter: api::ApiRateLimiter::from_config(&config),
+        large_order_confirm: api::trading::LargeOrderConfirm::from_config(&config),
+        symbol_state_check: config.order_symbol_state_check,
+        raw_response_ttl: config
+            .raw_response_audit
+            .then(|| Duration::from_secs(config.raw_response_ttl_days * 86_400)),
+        placement_cooldown: placement_cooldown.clone(),
+        write_behind,
+        structured_batch_results: config.batch_results_structured,
+        user_auth: Arc::new(api::UserAuth::new(config.jwt_secret.clone())),
+        precision: precision_cache.clone(),
+        paper_trading: config.paper_trading,
+        paper_simulate_fills: config.paper_simulate_fills,
     });
 
+    // Zuletzt bekannte REST-Preise für den Lesepfad bei MEXC-Ausfall
+    let stale_prices = api::StalePriceFallback::from_config(
+        Arc::new(mexc::websocket::PriceCache::new(Duration::from_secs(config.stale_price_fallback_secs))),
+        &config,
+    );
+
     let market_state = Arc::new(api::MarketState {
         mexc_client: mexc_client.clone(),
+        fallback: stale_prices.clone(),
     });
 
-    let status_state = Arc::new(api::StatusState::new(mexc_client.clone()));
+    let status_state = Arc::new(
+        api::StatusState::new(mexc_client.clone())
+            .with_write_check(config.mexc_write_health_check)
+            .with_health_cache_ttl(Duration::from_secs(config.status_health_cache_secs))
+            .with_time_offset_warn(config.status_time_offset_warn_ms),
+    );
+
+    let pnl_state = Arc::new(api::PnlState {
+        store: store.clone(),
+        default_strategy: config.pnl_default_strategy.clone(),
+    });
+
+    let positions_state = Arc::new(api::PositionsState {
+        mexc_client: mexc_client.clone(),
+        store: store.clone(),
+        fallback: stale_prices,
+        refresh_staleness: Duration::from_secs(config.position_refresh_staleness_secs),
+    });
+
+    // Hot-reloadbare Tunables (POST /api/admin/config/reload)
+    let runtime_settings = Arc::new(utils::RuntimeSettings::new(&config));
+    let admin_auth = Arc::new(api::AdminAuth::new(config.admin_api_token.clone()));
+
+    // Sniper für geplante Launches und manuelle Overrides (POST /api/v1/schedule/:user_id/:event_id/fire)
+    let sniper = Arc::new(
+        trading::SnipingManager::new(mexc_client.clone(), store.clone(), &config)
+            .with_runtime_settings(runtime_settings.clone())
+            .with_placement_cooldown(placement_cooldown)
+            .with_client_pool(mexc_clients.clone()),
+    );
+    // Dynamische Blacklist-Einträge vor dem ersten Snipe laden und periodisch nachladen
+    if let Err(e) = sniper.reload_blacklist().await {
+        tracing::warn!("Symbol blacklist load failed: {}", e);
+    }
+    if config.blacklist_reload_secs > 0 {
+        tokio::spawn(
+            sniper
+                .clone()
+                .run_blacklist_refresh(Duration::from_secs(config.blacklist_reload_secs), shutdown_rx.clone()),
+        );
+    }
+    if !config.scheduler_user_ids.is_empty() {
+        let scheduler = Arc::new(
+            trading::LaunchScheduler::from_config(store.clone(), &config)
+                .with_sniper(sniper.clone(), mexc_clients.clone(), &config)
+                .with_runtime_settings(runtime_settings.clone()),
+        );
+        tokio::spawn(scheduler.run(config.scheduler_user_ids.clone(), shutdown_rx.clone()));
+    }
+    let schedule_state = Arc::new(api::ScheduleState::from_config(
+        store.clone(),
+        sniper,
+        admin_auth.clone(),
+        &config,
+    ));
+
+    // Positionen: Stop-Nachführung, Events und Pyramiding-Nachkäufe (PYRAMID_*) aus der Config
+    let position_manager = Arc::new(
+        trading::PositionManager::from_config(store.clone(), &config)
+            .with_pyramiding(trading::Pyramiding::from_config(&config), mexc_client.clone()),
+    );
+
+    let admin_state = Arc::new(api::AdminState {
+        mexc_client: mexc_client.clone(),
+        auth: admin_auth,
+        runtime: runtime_settings.clone(),
+        store: store.clone(),
+        config: Arc::new(config.clone()),
+        flattener: Arc::new(
+            trading::Flattener::from_config(mexc_clients.clone(), store.clone(), &config)
+                .with_alerts(alerts.clone())
+                .with_position_manager(position_manager),
+        ),
+    });
+
+    let simulate_state = Arc::new(api::SimulateState {
+        mexc_client: mexc_client.clone(),
+        sizing: trading::SizingSettings::from_config(&config),
+        quote_asset: config.quote_asset.clone(),
+        runtime: runtime_settings.clone(),
+    });
+
+    // Listing-Webhook (HMAC-signiert, mit Replay-Schutz)
+    let webhook_state = Arc::new(api::WebhookState::from_config(store.clone(), &config));
 
     // Build routers
     let app = Router::new()
         // Health & Admin Routes
-        .nest("/api/admin", api::admin_router())
+        .nest("/api/admin", api::admin_router(admin_state))
         // Trading Routes
         .nest("/api/trade", api::trading_router(trading_state))
         // Market Data Routes
         .nest("/api/market", api::market_router(market_state))
         // V1 Status & Settings Routes
-        .nest("/api/v1", api::status_router(status_state))
+        .nest(
+            "/api/v1",
+            api::status_router(status_state)
+                .merge(api::simulate_router(simulate_state))
+                .merge(api::pnl_router(pnl_state))
+                .merge(api::positions_router(positions_state))
+                .merge(api::schedule_router(schedule_state))
+                .merge(api::webhook_router(webhook_state)),
+        )
         // Root health check
         .route("/health", get(health_check))
         // Global middleware
@@ -74,11 +280,48 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Server listening on port {}", config.rust_api_port);
 
-    axum::serve(listener, app).await?;
+    // ConnectInfo für das IP-basierte Rate Limit
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // Laufenden Poll abschließen lassen, dann beenden
+    tracing::info!("Shutting down, draining order monitor");
+    shutdown_tx.send(true).ok();
+    let grace = Duration::from_secs(config.shutdown_grace_secs);
+    trading::monitor::drain(monitor_handle, grace).await;
+    if let Some(handle) = write_behind_handle {
+        tokio::time::timeout(grace, handle).await.ok();
+    }
 
     Ok(())
 }
 
+/// Warte auf SIGINT (Ctrl+C) oder SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 /// Health check endpoint
 async fn health_check() -> &'static str {
     "OK"
@@ -1 +1 @@
-use crate::mexc::models::{MexcClient, OrderRequest, OrderResponse, TickerResponse};
+pub use crate::mexc::models::{MexcClient, OrderRequest, OrderResponse, TickerResponse};
@@ -0,0 +1,211 @@
+//! Typisierte MEXC-Fehlerantworten (`{"code": ..., "msg": ...}`) und ihre
+//! Einordnung in Ablehnungskategorien für Auswertungen
+use serde::Deserialize;
+use std::fmt;
+
+/// Normalisierte Ablehnungsgründe einer Order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RejectionCategory {
+    InsufficientFunds,
+    FilterFailure,
+    RateLimited,
+    Timestamp,
+    /// Post-only (LIMIT_MAKER) Order hätte das Buch gekreuzt
+    WouldTakeLiquidity,
+    Unknown,
+}
+
+impl RejectionCategory {
+    pub const ALL: [RejectionCategory; 6] = [
+        RejectionCategory::InsufficientFunds,
+        RejectionCategory::FilterFailure,
+        RejectionCategory::RateLimited,
+        RejectionCategory::Timestamp,
+        RejectionCategory::WouldTakeLiquidity,
+        RejectionCategory::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectionCategory::InsufficientFunds => "insufficient_funds",
+            RejectionCategory::FilterFailure => "filter_failure",
+            RejectionCategory::RateLimited => "rate_limited",
+            RejectionCategory::Timestamp => "timestamp",
+            RejectionCategory::WouldTakeLiquidity => "would_take_liquidity",
+            RejectionCategory::Unknown => "unknown",
+        }
+    }
+
+    /// Gespeicherten Wert einlesen; Unbekanntes landet in `Unknown`
+    pub fn parse(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value)
+            .unwrap_or(RejectionCategory::Unknown)
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: Option<serde_json::Value>,
+    msg: Option<String>,
+}
+
+/// Fehlerantwort von MEXC (HTTP-Status, MEXC-Fehlercode, Meldung)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MexcError {
+    pub status: u16,
+    pub code: Option<String>,
+    pub message: String,
+    /// Roher Body (für Logs und `error_message`)
+    pub body: String,
+}
+
+impl MexcError {
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let parsed = serde_json::from_str::<ErrorBody>(body).ok();
+        let code = parsed.as_ref().and_then(|b| b.code.as_ref()).map(|code| match code {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        });
+        let message = parsed
+            .and_then(|b| b.msg)
+            .unwrap_or_else(|| body.to_string());
+        Self {
+            status,
+            code,
+            message,
+            body: body.to_string(),
+        }
+    }
+
+    /// Kategorie nach MEXC-Fehlercode, sonst nach HTTP-Status bzw. Meldung
+    pub fn category(&self) -> RejectionCategory {
+        match self.code.as_deref() {
+            Some("10101" | "30004" | "30005") => return RejectionCategory::InsufficientFunds,
+            Some("30002" | "30003" | "30010" | "30029" | "30032" | "30041") => {
+                return RejectionCategory::FilterFailure
+            }
+            Some("429" | "510") => return RejectionCategory::RateLimited,
+            Some("-1021" | "700003") => return RejectionCategory::Timestamp,
+            _ => {}
+        }
+        if self.status == 429 {
+            return RejectionCategory::RateLimited;
+        }
+        if self.would_take_liquidity() {
+            return RejectionCategory::WouldTakeLiquidity;
+        }
+
+        let message = self.message.to_ascii_lowercase();
+        if message.contains("insufficient") || message.contains("balance") {
+            RejectionCategory::InsufficientFunds
+        } else if message.contains("recvwindow") || message.contains("timestamp") {
+            RejectionCategory::Timestamp
+        } else if message.contains("frequency") || message.contains("too many") {
+            RejectionCategory::RateLimited
+        } else if ["filter", "lot_size", "min_notional", "precision", "minimum", "maximum"]
+            .iter()
+            .any(|needle| message.contains(needle))
+        {
+            RejectionCategory::FilterFailure
+        } else {
+            RejectionCategory::Unknown
+        }
+    }
+
+    /// Timestamp außerhalb des recvWindow (lokale Uhr weicht ab)
+    pub fn is_clock_skew(&self) -> bool {
+        matches!(self.code.as_deref(), Some("-1021" | "700003"))
+    }
+
+    /// newClientOrderId wurde schon verwendet (z.B. Retry nach Timeout). MEXC nutzt
+    /// dafür je nach Endpoint unterschiedliche Codes, daher nach Meldung.
+    pub fn is_duplicate_order(&self) -> bool {
+        let message = self.message.to_ascii_lowercase();
+        message.contains("duplicate") || (message.contains("clientorderid") && message.contains("exist"))
+    }
+
+    /// Order bei MEXC unbekannt, z.B. Lookup per Client-ID einer nie angekommenen Order
+    pub fn is_unknown_order(&self) -> bool {
+        if matches!(self.code.as_deref(), Some("-2013" | "-2011")) {
+            return true;
+        }
+        let message = self.message.to_ascii_lowercase();
+        message.contains("unknown order") || message.contains("order does not exist")
+    }
+
+    /// Eindeutige Ablehnung (4xx): die Order wurde nicht platziert. Bei 5xx
+    /// kann MEXC sie trotzdem angenommen haben.
+    pub fn is_rejection(&self) -> bool {
+        (400..500).contains(&self.status)
+    }
+
+    /// MARKET-Orders für das Symbol (noch) nicht erlaubt, z.B. direkt nach einem Listing
+    pub fn market_unavailable(&self) -> bool {
+        let message = self.message.to_ascii_lowercase();
+        message.contains("market")
+            && ["not support", "unsupported", "not allowed", "not available", "disabled"]
+                .iter()
+                .any(|phrase| message.contains(phrase))
+    }
+
+    /// LIMIT_MAKER abgelehnt, weil die Order sofort gematcht hätte
+    pub fn would_take_liquidity(&self) -> bool {
+        let message = self.message.to_ascii_lowercase();
+        message.contains("immediately match")
+            || message.contains("would take")
+            || (message.contains("maker") && message.contains("reject"))
+    }
+}
+
+impl fmt::Display for MexcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MEXC API Error: {}", self.body)
+    }
+}
+
+impl std::error::Error for MexcError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mexc_errors_map_to_categories() {
+        let cases = [
+            (400, r#"{"code":30004,"msg":"Insufficient position"}"#, "30004", RejectionCategory::InsufficientFunds),
+            (400, r#"{"code":10101,"msg":"Insufficient balance"}"#, "10101", RejectionCategory::InsufficientFunds),
+            (400, r#"{"code":30002,"msg":"Minimum transaction volume cannot be less than:5USDT"}"#, "30002", RejectionCategory::FilterFailure),
+            (429, r#"{"code":510,"msg":"Excessive frequency"}"#, "510", RejectionCategory::RateLimited),
+            (400, r#"{"code":700003,"msg":"Timestamp for this request is outside of the recvWindow."}"#, "700003", RejectionCategory::Timestamp),
+            (400, r#"{"code":"99999","msg":"LOT_SIZE filter failure"}"#, "99999", RejectionCategory::FilterFailure),
+            (400, r#"{"code":30014,"msg":"Invalid symbol."}"#, "30014", RejectionCategory::Unknown),
+            (400, r#"{"code":-2010,"msg":"Order would immediately match and take."}"#, "-2010", RejectionCategory::WouldTakeLiquidity),
+        ];
+        for (status, body, code, category) in cases {
+            let error = MexcError::from_response(status, body);
+            assert_eq!(error.code.as_deref(), Some(code), "{}", body);
+            assert_eq!(error.category(), category, "{}", body);
+        }
+
+        let plain = MexcError::from_response(429, "Too Many Requests");
+        assert_eq!(plain.code, None);
+        assert_eq!(plain.message, "Too Many Requests");
+        assert_eq!(plain.category(), RejectionCategory::RateLimited);
+        assert_eq!(plain.to_string(), "MEXC API Error: Too Many Requests");
+
+        assert_eq!(RejectionCategory::parse("timestamp"), RejectionCategory::Timestamp);
+        assert_eq!(RejectionCategory::parse("bogus"), RejectionCategory::Unknown);
+    }
+
+    #[test]
+    fn test_unknown_order_and_rejection() {
+        assert!(MexcError::from_response(400, r#"{"code":-2013,"msg":"Order does not exist."}"#).is_unknown_order());
+        assert!(MexcError::from_response(400, r#"{"msg":"Unknown order sent."}"#).is_unknown_order());
+        assert!(!MexcError::from_response(400, r#"{"code":30004,"msg":"Insufficient position"}"#).is_unknown_order());
+
+        assert!(MexcError::from_response(400, r#"{"code":30004,"msg":"Insufficient position"}"#).is_rejection());
+        assert!(!MexcError::from_response(503, "busy").is_rejection());
+    }
+}
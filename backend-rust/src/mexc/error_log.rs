@@ -0,0 +1,99 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::mexc::error::MexcError;
+
+/// Abgelehnter MEXC-Request (nur Fehlerstatus, keine Netzwerkfehler)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordedError {
+    /// Unix ms
+    pub timestamp: i64,
+    /// Pfad ohne Query, z.B. "/api/v3/order"
+    pub endpoint: String,
+    pub status: u16,
+    pub code: Option<String>,
+    pub message: String,
+    pub symbol: Option<String>,
+}
+
+/// Ringpuffer der letzten `capacity` MEXC-Fehler zum Debuggen ohne Log-Suche
+/// (capacity 0 = aus)
+#[derive(Debug)]
+pub struct ErrorLog {
+    capacity: usize,
+    entries: Mutex<VecDeque<RecordedError>>,
+}
+
+impl ErrorLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Fehlerantwort festhalten; der älteste Eintrag fällt bei vollem Puffer heraus
+    pub fn record(&self, endpoint: &str, symbol: Option<String>, error: &MexcError) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        // Zeitstempel unter dem Lock, damit die Reihenfolge im Puffer stimmt
+        entries.push_back(RecordedError {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            endpoint: endpoint.to_string(),
+            status: error.status,
+            code: error.code.clone(),
+            message: error.message.clone(),
+            symbol,
+        });
+    }
+
+    /// Gespeicherte Fehler, neueste zuerst
+    pub fn recent(&self) -> Vec<RecordedError> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().rev().cloned().collect()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_concurrent_writes_keep_the_newest_entries() {
+        let log = Arc::new(ErrorLog::new(5));
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let log = log.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        let body = format!(r#"{{"code":{},"msg":"thread {}"}}"#, i, t);
+                        log.record("/api/v3/order", None, &MexcError::from_response(400, &body));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let recent = log.recent();
+        assert_eq!(recent.len(), 5);
+        assert!(recent.windows(2).all(|pair| pair[0].timestamp >= pair[1].timestamp));
+
+        let disabled = ErrorLog::new(0);
+        disabled.record("/api/v3/order", None, &MexcError::from_response(400, "{}"));
+        assert!(disabled.recent().is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Gewicht einer neuen Messung im gleitenden Mittel
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Exponentiell gleitender Mittelwert der Round-Trip-Zeit signierter Requests
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    inner: Mutex<LatencySnapshot>,
+}
+
+/// Stand des Latenz-Mittels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LatencySnapshot {
+    /// None bis zur ersten Messung
+    pub ewma_ms: Option<f64>,
+    pub samples: u64,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.ewma_ms = Some(match inner.ewma_ms {
+            Some(current) => current + EWMA_ALPHA * (ms - current),
+            None => ms,
+        });
+        inner.samples += 1;
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        *self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ewma_starts_at_first_sample_and_smooths_spikes() {
+        let tracker = LatencyTracker::new();
+        assert_eq!(tracker.snapshot().ewma_ms, None);
+
+        tracker.record(Duration::from_millis(100));
+        assert_eq!(tracker.snapshot().ewma_ms, Some(100.0));
+
+        tracker.record(Duration::from_millis(600));
+        let snapshot = tracker.snapshot();
+        assert!((snapshot.ewma_ms.unwrap() - 200.0).abs() < 1e-9);
+        assert_eq!(snapshot.samples, 2);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::utils::AlertSink;
+use reqwest::StatusCode;
+use std::sync::{Arc, Mutex};
+
+/// Alert-Key für Wartungs-Alerts (Deduplizierung im AlertSink)
+const MAINTENANCE_ALERT: &str = "mexc_maintenance";
+
+/// Erkennt MEXC-Wartung an einer Response: Fehlerstatus (typisch 503) mit
+/// "maintenance" im Body
+pub fn is_maintenance_response(status: StatusCode, body: &str) -> bool {
+    !status.is_success() && body.to_ascii_lowercase().contains("maintenance")
+}
+
+/// Wartungszustand von MEXC. Alarmiert genau einmal beim Eintritt und einmal
+/// bei der Erholung, statt bei jedem fehlschlagenden Call.
+pub struct MaintenanceState {
+    enabled: bool,
+    /// Beginn der Wartung (Unix ms), None = keine Wartung
+    since: Mutex<Option<i64>>,
+    /// Alert-Webhook (None = nur Log)
+    alerts: Option<Arc<AlertSink>>,
+}
+
+impl MaintenanceState {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            since: Mutex::new(None),
+            alerts: None,
+        }
+    }
+
+    /// Alerts zusätzlich an den Webhook senden
+    pub fn with_alerts(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Response auswerten: Wartung betreten oder nach Erfolg verlassen
+    pub fn record(&self, status: StatusCode, body: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut since = self.since.lock().unwrap_or_else(|e| e.into_inner());
+        if is_maintenance_response(status, body) {
+            if since.is_none() {
+                self.notify(
+                    true,
+                    format!("MEXC maintenance detected ({}), pausing background polling", status),
+                );
+                *since = Some(chrono::Utc::now().timestamp_millis());
+            }
+        } else if status.is_success() {
+            if let Some(started) = since.take() {
+                let secs = (chrono::Utc::now().timestamp_millis() - started) / 1000;
+                self.notify(false, format!("MEXC recovered from maintenance after {}s, resuming", secs));
+            }
+        }
+    }
+
+    /// Alert bzw. Recovery melden; ohne Sink nur ins Log
+    fn notify(&self, alert: bool, message: String) {
+        let Some(alerts) = self.alerts.clone() else {
+            tracing::error!("ALERT: {}", message);
+            return;
+        };
+        tokio::spawn(async move {
+            if alert {
+                alerts.alert(MAINTENANCE_ALERT, &message).await;
+            } else {
+                alerts.resolve(MAINTENANCE_ALERT, &message).await;
+            }
+        });
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.since().is_some()
+    }
+
+    /// Beginn der laufenden Wartung (Unix ms)
+    pub fn since(&self) -> Option<i64> {
+        *self.since.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{"code":503,"msg":"System maintenance, please try again later"}"#;
+
+    #[test]
+    fn test_detects_maintenance_body() {
+        assert!(is_maintenance_response(StatusCode::SERVICE_UNAVAILABLE, BODY));
+        assert!(!is_maintenance_response(StatusCode::SERVICE_UNAVAILABLE, "upstream timeout"));
+        assert!(!is_maintenance_response(StatusCode::OK, BODY));
+    }
+
+    #[test]
+    fn test_enter_and_recover() {
+        let state = MaintenanceState::new(true);
+        state.record(StatusCode::SERVICE_UNAVAILABLE, BODY);
+        let since = state.since().expect("maintenance not detected");
+
+        // Weitere Fehler ändern den Beginn nicht, andere Fehler beenden die Wartung nicht
+        state.record(StatusCode::SERVICE_UNAVAILABLE, BODY);
+        state.record(StatusCode::BAD_REQUEST, "bad request");
+        assert_eq!(state.since(), Some(since));
+
+        state.record(StatusCode::OK, "{}");
+        assert!(!state.is_active());
+
+        let disabled = MaintenanceState::new(false);
+        disabled.record(StatusCode::SERVICE_UNAVAILABLE, BODY);
+        assert!(!disabled.is_active());
+    }
+}
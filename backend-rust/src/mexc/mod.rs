@@ -1,5 +1,19 @@
 pub mod client;
+pub mod error;
+pub mod error_log;
+pub mod latency;
+pub mod maintenance;
 pub mod models;
+pub mod pool;
+pub mod queue;
+pub mod rate_limit;
+pub mod signing;
 pub mod websocket;
 
-pub use models::{MexcClient, OrderRequest, OrderResponse, TickerResponse};
+pub use error::{MexcError, RejectionCategory};
+pub use error_log::{ErrorLog, RecordedError};
+pub use latency::{LatencySnapshot, LatencyTracker};
+pub use models::{Credentials, MexcClient, OrderBook, OrderRequest, OrderResponse, PriceTicker, SymbolInfo, SymbolState, TickerResponse, TradeFill};
+pub use pool::{MexcClientPool, UserClientError};
+pub use queue::{QueueDepth, RequestPriority};
+pub use signing::SigningVersion;
@@ -1,12 +1,21 @@
 use crate::utils::config::Config;
+use crate::utils::Metrics;
 use anyhow::{anyhow, Result};
-use hmac::{Hmac, Mac};
+use arc_swap::ArcSwap;
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
-use std::collections::BTreeMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Semaphore, SemaphorePermit};
 
-type HmacSha256 = Hmac<Sha256>;
+use crate::mexc::error::MexcError;
+use crate::mexc::error_log::ErrorLog;
+use crate::mexc::latency::LatencyTracker;
+use crate::mexc::maintenance::MaintenanceState;
+use crate::mexc::queue::{QueueDepth, QueueSlot, RequestPriority, RequestQueue};
+use crate::mexc::rate_limit::{RateLimitHeaders, RateLimitState};
+use crate::mexc::signing::SigningVersion;
 
 /// MEXC API Request Models
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -17,6 +26,12 @@ pub struct OrderRequest {
     pub quantity: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub price: Option<f64>,
+    /// Quote-Betrag statt Basis-Menge (quoteOrderQty, nur Market); ersetzt `quantity`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_order_qty: Option<f64>,
+    /// Eigene Order-ID (newClientOrderId); macht Retries idempotent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +45,27 @@ pub struct OrderResponse {
     pub status: String,
     pub filled_qty: f64,
     pub created_at: i64,
+    /// Ausgeführtes Quote-Volumen; bei MARKET/IOC ist `price` 0, der Fill-Preis ergibt sich nur hieraus
+    #[serde(
+        default,
+        alias = "cummulativeQuoteQty",
+        deserialize_with = "deserialize_opt_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cummulative_quote_qty: Option<f64>,
+}
+
+impl OrderResponse {
+    /// Durchschnittlicher Fill-Preis (Quote-Volumen / ausgeführte Menge), sonst der Limit-Preis
+    pub fn average_fill_price(&self) -> Option<f64> {
+        if self.filled_qty <= 0.0 {
+            return None;
+        }
+        match self.cummulative_quote_qty {
+            Some(quote) if quote > 0.0 => Some(quote / self.filled_qty),
+            _ => Some(self.price).filter(|p| *p > 0.0),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,14 +73,327 @@ pub struct TickerResponse {
     pub symbol: String,
     pub price: f64,
     pub timestamp: i64,
+    /// 24h-Volumen in Basis-Asset (None wenn nicht geliefert)
+    #[serde(default, deserialize_with = "deserialize_opt_number")]
+    pub volume: Option<f64>,
+    /// 24h-Volumen in Quote-Asset
+    #[serde(default, alias = "quoteVolume", deserialize_with = "deserialize_opt_number")]
+    pub quote_volume: Option<f64>,
+}
+
+/// /api/v3/ticker/24hr liefert je nach Anfrage ein Objekt oder ein Array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TickerPayload {
+    Single(TickerResponse),
+    List(Vec<TickerResponse>),
+}
+
+impl TickerPayload {
+    /// Ticker für `symbol`; bei Arrays den passenden (sonst den ersten) Eintrag
+    fn into_ticker(self, symbol: &str) -> Result<TickerResponse> {
+        match self {
+            TickerPayload::Single(ticker) => Ok(ticker),
+            TickerPayload::List(tickers) => {
+                let index = tickers.iter().position(|t| t.symbol == symbol).unwrap_or(0);
+                tickers
+                    .into_iter()
+                    .nth(index)
+                    .ok_or_else(|| anyhow!("Empty ticker response for {}", symbol))
+            }
+        }
+    }
+}
+
+/// Eigener Fill aus /api/v3/myTrades (Aliase = MEXC Feldnamen)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeFill {
+    pub symbol: String,
+    #[serde(alias = "id", deserialize_with = "deserialize_id")]
+    pub trade_id: String,
+    #[serde(alias = "orderId", deserialize_with = "deserialize_id")]
+    pub order_id: String,
+    #[serde(deserialize_with = "deserialize_number")]
+    pub price: f64,
+    #[serde(alias = "qty", deserialize_with = "deserialize_number")]
+    pub quantity: f64,
+    #[serde(alias = "quoteQty", deserialize_with = "deserialize_number")]
+    pub quote_qty: f64,
+    #[serde(alias = "commission", deserialize_with = "deserialize_number")]
+    pub fee: f64,
+    #[serde(alias = "commissionAsset")]
+    pub fee_asset: String,
+    pub time: i64, // Unix timestamp in Millisekunden
+    #[serde(alias = "isBuyer")]
+    pub is_buyer: bool,
+    #[serde(alias = "isMaker")]
+    pub is_maker: bool,
+}
+
+/// Eintrag aus /api/v3/ticker/price (Batch-Abfrage)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceTicker {
+    pub symbol: String,
+    #[serde(deserialize_with = "deserialize_number")]
+    pub price: f64,
+}
+
+/// Order Book (Depth) Snapshot, Level = (Preis, Menge)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBook {
+    #[serde(deserialize_with = "deserialize_levels")]
+    pub bids: Vec<(f64, f64)>,
+    #[serde(deserialize_with = "deserialize_levels")]
+    pub asks: Vec<(f64, f64)>,
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|(price, _)| *price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|(price, _)| *price)
+    }
+
+    /// Mittelkurs zwischen bestem Bid und Ask
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_ask()?) / 2.0)
+    }
+
+    /// Absoluter Spread (Ask - Bid)
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    /// Spread in Prozent des Mittelkurses; None bei einseitigem oder leerem Buch
+    pub fn spread_pct(&self) -> Option<f64> {
+        let mid = self.mid_price().filter(|mid| *mid > 0.0)?;
+        Some(self.spread()? / mid * 100.0)
+    }
+
+    /// Gewichtete Imbalance der obersten `levels` Level in [-1, 1]:
+    /// (Bid - Ask) / (Bid + Ask), Level i zählt mit 1/(i+1), damit das Top
+    /// of Book dominiert. Positiv = Kaufdruck; None bei leerem Buch.
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let weighted = |side: &[(f64, f64)]| -> f64 {
+            side.iter()
+                .take(levels)
+                .enumerate()
+                .map(|(i, (_, qty))| qty / (i + 1) as f64)
+                .sum()
+        };
+        let bids = weighted(&self.bids);
+        let asks = weighted(&self.asks);
+        let total = bids + asks;
+        (total > 0.0).then(|| (bids - asks) / total)
+    }
+}
+
+/// Handelsstatus eines Symbols laut exchangeInfo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolState {
+    /// Gelistet, Handel noch nicht eröffnet (Listing-Countdown)
+    PreTrading,
+    Trading,
+    /// Handel pausiert/beendet
+    Halted,
+    /// Symbol nicht in exchangeInfo
+    NotListed,
+    Unknown(String),
+}
+
+impl SymbolState {
+    /// MEXC liefert den Status als Text (TRADING, PRE_TRADING, HALT, ...)
+    /// oder numerisch ("1" = online, "2" = pausiert, "3" = offline)
+    pub fn from_exchange_status(status: &str) -> Self {
+        match status.trim().to_ascii_uppercase().as_str() {
+            "TRADING" | "ENABLED" | "1" => Self::Trading,
+            "PRE_TRADING" | "PENDING_TRADING" | "AUCTION_MATCH" => Self::PreTrading,
+            "HALT" | "BREAK" | "PAUSE" | "OFFLINE" | "END_OF_DAY" | "POST_TRADING" | "DELISTED" | "2" | "3" => {
+                Self::Halted
+            }
+            _ => Self::Unknown(status.to_string()),
+        }
+    }
+
+    pub fn is_trading(&self) -> bool {
+        *self == Self::Trading
+    }
+}
+
+#[derive(Deserialize)]
+struct ExchangeInfo {
+    #[serde(default)]
+    symbols: Vec<ExchangeSymbol>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeSymbol {
+    symbol: String,
+    #[serde(deserialize_with = "deserialize_id")]
+    status: String,
+    #[serde(default)]
+    base_asset_precision: Option<u32>,
+    #[serde(default)]
+    quote_precision: Option<u32>,
+}
+
+/// Symbol-Eintrag aus exchangeInfo
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    pub state: SymbolState,
+    /// Nachkommastellen der Menge (baseAssetPrecision)
+    pub qty_precision: Option<u32>,
+    /// Nachkommastellen des Preises (quotePrecision)
+    pub price_precision: Option<u32>,
+}
+
+/// Erlaubte `limit` Werte für /api/v3/depth
+pub const DEPTH_LIMITS: [u32; 8] = [5, 10, 20, 50, 100, 500, 1000, 5000];
+
+/// recvWindow, den MEXC ohne eigenen `recvWindow`-Parameter anwendet (ms)
+pub const MEXC_RECV_WINDOW_MS: i64 = 5_000;
+
+/// Runde `limit` auf den nächsten erlaubten Wert auf (max 5000)
+pub fn clamp_depth_limit(limit: u32) -> u32 {
+    DEPTH_LIMITS
+        .iter()
+        .copied()
+        .find(|allowed| *allowed >= limit)
+        .unwrap_or(DEPTH_LIMITS[DEPTH_LIMITS.len() - 1])
+}
+
+/// MEXC liefert Zahlen teils als String ("0.123"), teils als Number
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(f64),
+    String(String),
+}
+
+impl NumberOrString {
+    fn into_f64(self) -> std::result::Result<f64, String> {
+        match self {
+            NumberOrString::Number(n) => Ok(n),
+            NumberOrString::String(s) => s
+                .parse()
+                .map_err(|_| format!("invalid number: {}", s)),
+        }
+    }
+}
+
+fn deserialize_number<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    NumberOrString::deserialize(deserializer)?
+        .into_f64()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_opt_number<'de, D>(deserializer: D) -> std::result::Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<NumberOrString>::deserialize(deserializer)?
+        .map(NumberOrString::into_f64)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+/// IDs kommen je nach Endpunkt als String oder Number
+fn deserialize_id<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Id {
+        Number(i64),
+        String(String),
+    }
+
+    Ok(match Id::deserialize(deserializer)? {
+        Id::Number(n) => n.to_string(),
+        Id::String(s) => s,
+    })
+}
+
+fn deserialize_levels<'de, D>(deserializer: D) -> std::result::Result<Vec<(f64, f64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Vec<(NumberOrString, NumberOrString)> = Deserialize::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(price, qty)| {
+            Ok((
+                price.into_f64().map_err(serde::de::Error::custom)?,
+                qty.into_f64().map_err(serde::de::Error::custom)?,
+            ))
+        })
+        .collect()
+}
+
+/// MEXC API Credentials (zur Laufzeit austauschbar)
+#[derive(Clone, Deserialize)]
+pub struct Credentials {
+    pub api_key: String,
+    pub secret_key: String,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &self.api_key)
+            .field("secret_key", &"***")
+            .finish()
+    }
 }
 
 /// MEXC API Client mit HMAC-SHA256 Signing
 pub struct MexcClient {
     base_url: String,
-    api_key: String,
-    secret_key: String,
+    /// Lock-frei lesbar beim Signieren, atomar austauschbar (Key-Rotation)
+    credentials: ArcSwap<Credentials>,
     client: reqwest::Client,
+    metrics: Option<Arc<Metrics>>,
+    /// Priorisierte Queue vor allen Requests (Order vor Marktdaten)
+    queue: RequestQueue,
+    /// Permits für signierte Order-/Account-Endpunkte
+    order_permits: Semaphore,
+    /// Permits für Market-Data-Endpunkte (Ticker, Depth), getrennt damit
+    /// ein Ticker-Burst keine Order verzögert
+    market_permits: Semaphore,
+    /// Request/Response-Logging auf Debug-Level (MEXC_DEBUG_LOG)
+    debug_log: bool,
+    /// Used-Weight und Retry-After aus den Response-Headern
+    rate_limit: RateLimitState,
+    /// Erkannte MEXC-Wartung (pausiert Hintergrund-Polling)
+    maintenance: MaintenanceState,
+    /// Signatur-Variante (MEXC_SIGNING_VERSION)
+    signing_version: SigningVersion,
+    /// Offset zur MEXC-Serverzeit in ms (per `sync_time`)
+    time_offset_ms: AtomicI64,
+    /// Letzte erfolgreiche Zeit-Synchronisation (Unix ms, 0 = noch nie)
+    last_time_sync_ms: AtomicI64,
+    /// Doppelte newClientOrderId als Erfolg behandeln (MEXC_IDEMPOTENT_CREATE)
+    idempotent_create: bool,
+    /// Round-Trip-EWMA signierter Requests
+    latency: LatencyTracker,
+    /// Letzte abgelehnte Requests (MEXC_ERROR_LOG_SIZE), mit User-Clients geteilt
+    errors: Arc<ErrorLog>,
+    /// Versuche je Request bei Verbindungsfehlern und 429/5xx (1 = kein Retry)
+    retry_max_attempts: u32,
+    /// Wartezeit vor dem ersten Retry, verdoppelt sich pro Versuch
+    retry_base_delay: Duration,
+    /// Obergrenze je Wartezeit, auch für ein Retry-After von MEXC
+    retry_max_delay: Duration,
 }
 
 impl MexcClient {
@@ -57,215 +406,790 @@ impl MexcClient {
 
         Ok(Self {
             base_url: config.mexc_base_url.clone(),
-            api_key: config.mexc_api_key.clone(),
-            secret_key: config.mexc_secret_key.clone(),
+            credentials: ArcSwap::from_pointee(Credentials {
+                api_key: config.mexc_api_key.clone(),
+                secret_key: config.mexc_secret_key.clone(),
+            }),
             client,
+            metrics: None,
+            queue: RequestQueue::new(config.mexc_queue_workers),
+            order_permits: Semaphore::new(config.mexc_order_concurrency.max(1)),
+            market_permits: Semaphore::new(config.mexc_market_concurrency.max(1)),
+            debug_log: config.mexc_debug_log,
+            rate_limit: RateLimitState::new()
+                .with_max_retry_after(Duration::from_millis(config.mexc_retry_max_delay_ms)),
+            maintenance: MaintenanceState::new(config.mexc_maintenance_detection),
+            signing_version: config.mexc_signing_version,
+            time_offset_ms: AtomicI64::new(0),
+            last_time_sync_ms: AtomicI64::new(0),
+            idempotent_create: config.mexc_idempotent_create,
+            latency: LatencyTracker::new(),
+            errors: Arc::new(ErrorLog::new(config.mexc_error_log_size)),
+            retry_max_attempts: config.mexc_retry_max_attempts.max(1),
+            retry_base_delay: Duration::from_millis(config.mexc_retry_base_delay_ms),
+            retry_max_delay: Duration::from_millis(config.mexc_retry_max_delay_ms),
         })
     }
 
-    /// Erstelle signierte Request mit HMAC-SHA256
-    fn create_signature(&self, query_string: &str) -> String {
-        let mut mac = HmacSha256::new_from_slice(self.secret_key.as_bytes())
-            .expect("HMAC can take key of any size");
-        mac.update(query_string.as_bytes());
-        hex::encode(mac.finalize().into_bytes())
+    /// Fehler in einen gemeinsamen Ringpuffer schreiben (z.B. den des globalen Clients)
+    pub fn with_error_log(mut self, errors: Arc<ErrorLog>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Wartungs-Alerts an den Alert-Webhook senden
+    pub fn with_alerts(mut self, alerts: Arc<crate::utils::AlertSink>) -> Self {
+        self.maintenance = self.maintenance.with_alerts(alerts);
+        self
+    }
+
+    /// Aktiviere Latenz-Metriken für Order-Requests
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn observe_stage(&self, stage: &str, started: Instant) {
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_order_stage("create_order", stage, started.elapsed());
+        }
+    }
+
+    /// Ein aktives (gedeckeltes) Retry-After abwarten, dann Worker der Queue
+    /// und Permit der Request-Klasse holen
+    async fn acquire(&self, priority: RequestPriority) -> Result<(QueueSlot, SemaphorePermit<'_>)> {
+        self.rate_limit.wait().await;
+        let slot = self.queue.acquire(priority).await;
+        let permits = match priority {
+            RequestPriority::Order => &self.order_permits,
+            RequestPriority::Market => &self.market_permits,
+        };
+        let permit = permits.acquire().await?;
+        Ok((slot, permit))
+    }
+
+    /// Wartende Requests in der Queue
+    pub fn queue_depth(&self) -> QueueDepth {
+        self.queue.depth()
+    }
+
+    /// Serverseitiger Rate-Limit-Zustand (letztes Used-Weight, Retry-After)
+    pub fn rate_limit(&self) -> &RateLimitState {
+        &self.rate_limit
+    }
+
+    /// Wartungszustand laut letzten Responses
+    pub fn maintenance(&self) -> &MaintenanceState {
+        &self.maintenance
+    }
+
+    /// Gleitende Latenz signierter Requests (Senden bis Body gelesen)
+    pub fn latency(&self) -> &LatencyTracker {
+        &self.latency
+    }
+
+    /// Letzte MEXC-Fehlerantworten
+    pub fn error_log(&self) -> &Arc<ErrorLog> {
+        &self.errors
+    }
+
+    /// Logge ausgehenden Request (Signatur maskiert), nur mit MEXC_DEBUG_LOG
+    fn log_request(&self, method: &str, url: &str, params: &impl std::fmt::Debug) {
+        if self.debug_log {
+            tracing::debug!("MEXC request: {} {} params={:?}", method, redact_signature(url), params);
+        }
+    }
+
+    /// Lese Response-Body; mit MEXC_DEBUG_LOG wird Status + Body geloggt
+    async fn read_response(
+        &self,
+        method: &str,
+        url: &str,
+        response: reqwest::Response,
+    ) -> Result<(reqwest::StatusCode, String)> {
+        let status = response.status();
+        self.rate_limit.record(status, response.headers());
+        let request_url = response.url().clone();
+        let body = response.text().await?;
+        self.maintenance.record(status, &body);
+        if !status.is_success() {
+            let symbol = request_url
+                .query_pairs()
+                .find(|(key, _)| key == "symbol")
+                .map(|(_, value)| value.into_owned());
+            self.errors
+                .record(request_url.path(), symbol, &MexcError::from_response(status.as_u16(), &body));
+        }
+        if self.debug_log {
+            tracing::debug!(
+                "MEXC response: {} {} -> {} {}",
+                method,
+                redact_signature(url),
+                status,
+                body
+            );
+        }
+        Ok((status, body))
+    }
+
+    /// Request senden und bei vorübergehenden Fehlern (Verbindung nicht
+    /// aufgebaut, 429/500/502/503) mit exponentiellem Backoff wiederholen.
+    /// `build` erzeugt jeden Versuch neu (signierte Requests mit frischem
+    /// Timestamp). Nur `retryable` Requests werden wiederholt: GETs und Orders
+    /// mit newClientOrderId, bei denen ein doppelter Versuch keine zweite
+    /// Order erzeugt. Retry-After hat Vorrang, gedeckelt auf MEXC_RETRY_MAX_DELAY_MS.
+    async fn send_with_retry<F>(&self, retryable: bool, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> Result<reqwest::RequestBuilder>,
+    {
+        let max_attempts = if retryable { self.retry_max_attempts } else { 1 };
+        let mut attempt = 1;
+        loop {
+            let request = build()?;
+            if attempt >= max_attempts {
+                return Ok(request.send().await?);
+            }
+
+            let delay = match request.send().await {
+                Ok(response) if is_transient(response.status()) => {
+                    let status = response.status();
+                    self.rate_limit.record(status, response.headers());
+                    let retry_after = RateLimitHeaders::parse(response.headers()).retry_after;
+                    let delay = retry_after
+                        .unwrap_or_else(|| self.retry_delay(attempt))
+                        .min(self.retry_max_delay);
+                    tracing::warn!(
+                        "MEXC {} {} (attempt {}/{}), retrying in {:?}",
+                        response.url().path(),
+                        status,
+                        attempt,
+                        max_attempts,
+                        delay
+                    );
+                    delay
+                }
+                Ok(response) => return Ok(response),
+                // Nur wenn die Verbindung nie stand: der Request kann nicht angekommen sein
+                Err(e) if e.is_connect() => {
+                    let delay = self.retry_delay(attempt).min(self.retry_max_delay);
+                    tracing::warn!(
+                        "MEXC connection failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt,
+                        max_attempts,
+                        delay,
+                        e
+                    );
+                    delay
+                }
+                Err(e) => return Err(e.into()),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Exponentieller Backoff vor Versuch `attempt + 1`
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.retry_base_delay.saturating_mul(1 << (attempt - 1).min(16))
+    }
+
+    /// Aktueller API Key (für den X-MEXC-APIKEY Header)
+    pub fn api_key(&self) -> String {
+        self.credentials.load().api_key.clone()
+    }
+
+    /// Parameter mit HMAC-SHA256 gemäß MEXC_SIGNING_VERSION signieren
+    #[cfg(test)]
+    fn sign_params(&self, params: &BTreeMap<String, String>) -> crate::mexc::signing::SignedQuery {
+        self.signing_version.sign(&self.credentials.load().secret_key, params)
+    }
+
+    /// Ersetze die Credentials ohne Validierung; gibt die vorherigen zurück
+    fn swap_credentials(&self, credentials: Credentials) -> Arc<Credentials> {
+        self.credentials.swap(Arc::new(credentials))
+    }
+
+    /// Tausche die Credentials aus, nachdem sie per Account-Abfrage validiert wurden.
+    /// Schlägt die Validierung fehl, bleiben die bisherigen Keys aktiv.
+    pub async fn rotate_credentials(&self, new_credentials: Credentials) -> Result<()> {
+        self.fetch_account(&new_credentials)
+            .await
+            .map_err(|e| anyhow!("Credential validation failed: {}", e))?;
+
+        self.swap_credentials(new_credentials);
+        tracing::info!("MEXC credentials rotated");
+        Ok(())
+    }
+
+    /// Erreichbarkeit prüfen (GET /api/v3/ping), z.B. als Recovery-Probe
+    pub async fn ping(&self) -> Result<()> {
+        let _permit = self.acquire(RequestPriority::Market).await?;
+        let url = format!("{}/api/v3/ping", self.base_url);
+
+        self.log_request("GET", &url, &());
+        let response = self.client.get(&url).send().await?;
+
+        let (status, body) = self.read_response("GET", &url, response).await?;
+        if !status.is_success() {
+            return Err(anyhow!("MEXC ping failed: {} {}", status, body));
+        }
+        Ok(())
     }
 
     /// Rufe Ticker Daten ab (Real-Time Price)
     pub async fn get_ticker(&self, symbol: &str) -> Result<TickerResponse> {
+        let _permit = self.acquire(RequestPriority::Market).await?;
         let url = format!("{}/api/v3/ticker/24hr", self.base_url);
         let mut params = BTreeMap::new();
         params.insert("symbol".to_string(), symbol.to_string());
 
-        let query_string = Self::build_query_string(&params);
+        self.log_request("GET", &url, &params);
+        let response = self
+            .send_with_retry(true, || {
+                Ok(self.client.get(&url).query(&params).header("X-MEXC-APIKEY", self.api_key()))
+            })
+            .await?;
 
+        let (_, body) = self.read_response("GET", &url, response).await?;
+        let payload: TickerPayload = serde_json::from_str(&body)?;
+        payload.into_ticker(symbol)
+    }
+
+    /// Preise mehrerer Symbole mit einem Request; fehlende Symbole sind nicht im Ergebnis
+    pub async fn get_tickers(&self, symbols: &[String]) -> Result<HashMap<String, f64>> {
+        if symbols.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let _permit = self.acquire(RequestPriority::Market).await?;
+        let url = format!("{}/api/v3/ticker/price", self.base_url);
+
+        self.log_request("GET", &url, &symbols);
         let response = self
-            .client
-            .get(&url)
-            .query(&params)
-            .header("X-MEXC-APIKEY", &self.api_key)
-            .send()
+            .send_with_retry(true, || Ok(self.client.get(&url).header("X-MEXC-APIKEY", self.api_key())))
             .await?;
 
-        let ticker: TickerResponse = response.json().await?;
-        Ok(ticker)
+        let (status, body) = self.read_response("GET", &url, response).await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to get tickers: {}", status));
+        }
+
+        let wanted: HashSet<&str> = symbols.iter().map(String::as_str).collect();
+        let tickers: Vec<PriceTicker> = serde_json::from_str(&body)?;
+        Ok(tickers
+            .into_iter()
+            .filter(|t| wanted.contains(t.symbol.as_str()))
+            .map(|t| (t.symbol, t.price))
+            .collect())
     }
 
-    /// Erstelle neue Order mit Signing
-    pub async fn create_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
+    /// Rufe Order Book ab (limit wird auf erlaubte Werte gerundet)
+    pub async fn get_order_book(&self, symbol: &str, limit: u32) -> Result<OrderBook> {
+        let _permit = self.acquire(RequestPriority::Market).await?;
+        let url = format!("{}/api/v3/depth", self.base_url);
+        let limit = clamp_depth_limit(limit);
 
-        let mut params = BTreeMap::new();
-        params.insert("symbol".to_string(), order.symbol.clone());
-        params.insert("side".to_string(), order.side.clone());
-        params.insert("type".to_string(), order.order_type.clone());
-        params.insert("quantity".to_string(), order.quantity.to_string());
+        let params = [("symbol", symbol.to_string()), ("limit", limit.to_string())];
+        self.log_request("GET", &url, &params);
+        let response = self
+            .send_with_retry(true, || {
+                Ok(self.client.get(&url).query(&params).header("X-MEXC-APIKEY", self.api_key()))
+            })
+            .await?;
 
-        if let Some(price) = order.price {
-            params.insert("price".to_string(), price.to_string());
+        let (status, body) = self.read_response("GET", &url, response).await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to get order book: {}", status));
         }
 
-        params.insert("timestamp".to_string(), timestamp);
+        let mut book: OrderBook = serde_json::from_str(&body)?;
+        if book.timestamp == 0 {
+            book.timestamp = chrono::Utc::now().timestamp_millis();
+        }
+        Ok(book)
+    }
 
-        let query_string = Self::build_query_string(&params);
-        let signature = self.create_signature(&query_string);
+    /// Handelsstatus eines Symbols aus /api/v3/exchangeInfo
+    pub async fn symbol_state(&self, symbol: &str) -> Result<SymbolState> {
+        Ok(self
+            .symbol_info(symbol)
+            .await?
+            .map(|info| info.state)
+            .unwrap_or(SymbolState::NotListed))
+    }
 
-        let url = format!(
-            "{}/api/v3/order?{}&signature={}",
-            self.base_url, query_string, signature
-        );
+    /// exchangeInfo eines Symbols (None = nicht gelistet)
+    pub async fn symbol_info(&self, symbol: &str) -> Result<Option<SymbolInfo>> {
+        Ok(self
+            .fetch_exchange_info(Some(symbol))
+            .await?
+            .into_iter()
+            .find(|s| s.symbol.eq_ignore_ascii_case(symbol)))
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .header("X-MEXC-APIKEY", &self.api_key)
-            .send()
+    /// Komplette exchangeInfo aller Symbole (große Response, nur periodisch)
+    pub async fn exchange_info(&self) -> Result<Vec<SymbolInfo>> {
+        self.fetch_exchange_info(None).await
+    }
+
+    async fn fetch_exchange_info(&self, symbol: Option<&str>) -> Result<Vec<SymbolInfo>> {
+        let _permit = self.acquire(RequestPriority::Market).await?;
+        let url = format!("{}/api/v3/exchangeInfo", self.base_url);
+
+        let params: Vec<(&str, String)> = symbol.map(|s| ("symbol", s.to_string())).into_iter().collect();
+        self.log_request("GET", &url, &params);
+        let response = self.send_with_retry(true, || Ok(self.client.get(&url).query(&params))).await?;
+
+        let (status, body) = self.read_response("GET", &url, response).await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to get exchange info: {}", status));
+        }
+
+        let info: ExchangeInfo = serde_json::from_str(&body)?;
+        Ok(info
+            .symbols
+            .into_iter()
+            .map(|s| SymbolInfo {
+                state: SymbolState::from_exchange_status(&s.status),
+                symbol: s.symbol,
+                qty_precision: s.base_asset_precision,
+                price_precision: s.quote_precision,
+            })
+            .collect())
+    }
+
+    /// Erstelle neue Order mit Signing
+    pub async fn create_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
+        Ok(self.create_order_with_body(order).await?.0)
+    }
+
+    /// Wie `create_order`, zusätzlich mit dem rohen Response-Body (Audit).
+    /// Bei Ablehnung steckt der Body im `MexcError`. Meldet MEXC eine doppelte
+    /// `client_order_id`, wird die bestehende Order nachgeschlagen und geliefert.
+    pub async fn create_order_with_body(&self, order: &OrderRequest) -> Result<(OrderResponse, String)> {
+        let permit = self.acquire(RequestPriority::Order).await?;
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::POST, "/api/v3/order", order_params(order), &credentials, true)
             .await?;
 
-        if !response.status().is_success() {
-            let error_body = response.text().await?;
-            return Err(anyhow!("MEXC API Error: {}", error_body));
+        if !status.is_success() {
+            let error = MexcError::from_response(status.as_u16(), &body);
+            if let (true, true, Some(client_order_id)) =
+                (self.idempotent_create, error.is_duplicate_order(), &order.client_order_id)
+            {
+                // Permit freigeben, der Lookup holt sich ein eigenes
+                drop(permit);
+                tracing::info!(
+                    "Duplicate client order id {} for {}, returning the existing order",
+                    client_order_id,
+                    order.symbol
+                );
+                return self
+                    .query_order(order_lookup_params(&order.symbol, "origClientOrderId", client_order_id))
+                    .await;
+            }
+            return Err(error.into());
         }
 
-        let order_response: OrderResponse = response.json().await?;
-        Ok(order_response)
+        let stage_start = Instant::now();
+        let order_response: OrderResponse = serde_json::from_str(&body)?;
+        self.observe_stage("parse", stage_start);
+        Ok((order_response, body))
+    }
+
+    /// Order gegen die MEXC-Filter prüfen ohne sie auszuführen (POST /api/v3/order/test).
+    /// Ok wenn MEXC die Order annehmen würde, sonst der typisierte MexcError.
+    pub async fn test_order(&self, order: &OrderRequest) -> Result<()> {
+        let _permit = self.acquire(RequestPriority::Order).await?;
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::POST, "/api/v3/order/test", order_params(order), &credentials, false)
+            .await?;
+
+        if !status.is_success() {
+            return Err(MexcError::from_response(status.as_u16(), &body).into());
+        }
+        Ok(())
     }
 
     /// Query Order Status
     pub async fn get_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
+        Ok(self.query_order(order_lookup_params(symbol, "orderId", order_id)).await?.0)
+    }
+
+    /// Order über die eigene ID (newClientOrderId) abfragen
+    pub async fn get_order_by_client_id(&self, symbol: &str, client_order_id: &str) -> Result<OrderResponse> {
+        Ok(self
+            .query_order(order_lookup_params(symbol, "origClientOrderId", client_order_id))
+            .await?
+            .0)
+    }
+
+    /// GET /api/v3/order mit rohem Response-Body
+    async fn query_order(&self, params: BTreeMap<String, String>) -> Result<(OrderResponse, String)> {
+        let _permit = self.acquire(RequestPriority::Order).await?;
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::GET, "/api/v3/order", params, &credentials, false)
+            .await?;
+        if !status.is_success() {
+            return Err(MexcError::from_response(status.as_u16(), &body).into());
+        }
+
+        let order: OrderResponse = serde_json::from_str(&body)?;
+        Ok((order, body))
+    }
 
+    /// Offene Orders eines Symbols (signiert, auch als Write-Path Health Check genutzt)
+    pub async fn get_open_orders(&self, symbol: &str) -> Result<Vec<OrderResponse>> {
+        let _permit = self.acquire(RequestPriority::Order).await?;
         let mut params = BTreeMap::new();
         params.insert("symbol".to_string(), symbol.to_string());
-        params.insert("orderId".to_string(), order_id.to_string());
-        params.insert("timestamp".to_string(), timestamp);
 
-        let query_string = Self::build_query_string(&params);
-        let signature = self.create_signature(&query_string);
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::GET, "/api/v3/openOrders", params, &credentials, false)
+            .await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to query open orders: {}", status));
+        }
 
-        let url = format!(
-            "{}/api/v3/order?{}&signature={}",
-            self.base_url, query_string, signature
-        );
+        let orders: Vec<OrderResponse> = serde_json::from_str(&body)?;
+        Ok(orders)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .header("X-MEXC-APIKEY", &self.api_key)
-            .send()
-            .await?;
+    /// Eigene Fills eines Symbols (signiert, limit 1..=100)
+    pub async fn get_my_trades(&self, symbol: &str, limit: u32) -> Result<Vec<TradeFill>> {
+        let _permit = self.acquire(RequestPriority::Order).await?;
+        let mut params = BTreeMap::new();
+        params.insert("symbol".to_string(), symbol.to_string());
+        params.insert("limit".to_string(), limit.clamp(1, 100).to_string());
 
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to query order: {}", response.status()));
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::GET, "/api/v3/myTrades", params, &credentials, false)
+            .await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to query trades: {}", status));
         }
 
-        let order: OrderResponse = response.json().await?;
-        Ok(order)
+        let fills: Vec<TradeFill> = serde_json::from_str(&body)?;
+        Ok(fills)
     }
 
     /// Storniere Order
     pub async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
-
+        let _permit = self.acquire(RequestPriority::Order).await?;
         let mut params = BTreeMap::new();
         params.insert("symbol".to_string(), symbol.to_string());
         params.insert("orderId".to_string(), order_id.to_string());
-        params.insert("timestamp".to_string(), timestamp);
-
-        let query_string = Self::build_query_string(&params);
-        let signature = self.create_signature(&query_string);
-
-        let url = format!(
-            "{}/api/v3/order?{}&signature={}",
-            self.base_url, query_string, signature
-        );
 
-        let response = self
-            .client
-            .delete(&url)
-            .header("X-MEXC-APIKEY", &self.api_key)
-            .send()
+        let credentials = self.credentials.load_full();
+        let (status, body) = self
+            .send_signed(reqwest::Method::DELETE, "/api/v3/order", params, &credentials, false)
             .await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to cancel order: {}", response.status()));
+        if !status.is_success() {
+            return Err(MexcError::from_response(status.as_u16(), &body).into());
         }
 
-        let order: OrderResponse = response.json().await?;
+        let order: OrderResponse = serde_json::from_str(&body)?;
         Ok(order)
     }
 
     /// Get Account Balance
     pub async fn get_account_balance(&self) -> Result<AccountBalance> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
-
-        let params = vec![("timestamp".to_string(), timestamp)];
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        let signature = self.create_signature(&query_string);
-
-        let url = format!(
-            "{}/api/v3/account?{}&signature={}",
-            self.base_url, query_string, signature
-        );
+        let credentials = self.credentials.load_full();
+        self.fetch_account(&credentials).await
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .header("X-MEXC-APIKEY", &self.api_key)
-            .send()
+    /// Signierte Account-Abfrage mit expliziten Credentials
+    async fn fetch_account(&self, credentials: &Credentials) -> Result<AccountBalance> {
+        let _permit = self.acquire(RequestPriority::Order).await?;
+        let (status, body) = self
+            .send_signed(reqwest::Method::GET, "/api/v3/account", BTreeMap::new(), credentials, false)
             .await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to get account balance: {}", response.status()));
+        if !status.is_success() {
+            return Err(anyhow!("Failed to get account balance: {}", status));
         }
 
-        let balance: AccountBalance = response.json().await?;
+        let balance: AccountBalance = serde_json::from_str(&body)?;
         Ok(balance)
     }
 
-    /// Hilfsfunktion: Erstelle Query String aus BTreeMap (sortiert für Signing)
-    fn build_query_string(params: &BTreeMap<String, String>) -> String {
-        params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&")
+    /// Lokale Zeit plus Server-Offset als `timestamp` Parameter
+    fn timestamp(&self) -> Result<String> {
+        Ok((local_millis()? + self.time_offset_ms.load(Ordering::Relaxed)).to_string())
+    }
+
+    /// Abweichung der lokalen Uhr zur MEXC-Serverzeit (ms, Server minus lokal)
+    pub fn time_offset_ms(&self) -> i64 {
+        self.time_offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Zeitpunkt der letzten Zeit-Synchronisation (Unix ms, None = noch nie)
+    pub fn last_time_sync(&self) -> Option<i64> {
+        Some(self.last_time_sync_ms.load(Ordering::Relaxed)).filter(|t| *t > 0)
+    }
+
+    /// Serverzeit abfragen (GET /api/v3/time) und den Offset für künftige
+    /// Timestamps übernehmen; gibt den neuen Offset zurück
+    pub async fn sync_time(&self) -> Result<i64> {
+        let _permit = self.acquire(RequestPriority::Market).await?;
+        self.fetch_time_offset().await
+    }
+
+    /// Offset ohne Permit bestimmen (auch aus einem laufenden signierten Request heraus)
+    async fn fetch_time_offset(&self) -> Result<i64> {
+        let url = format!("{}/api/v3/time", self.base_url);
+        self.log_request("GET", &url, &());
+        let sent_at = local_millis()?;
+        let response = self.client.get(&url).send().await?;
+        let (status, body) = self.read_response("GET", &url, response).await?;
+        let received_at = local_millis()?;
+        if !status.is_success() {
+            return Err(anyhow!("MEXC server time failed: {} {}", status, body));
+        }
+
+        let server_time: ServerTime = serde_json::from_str(&body)?;
+        // Serverzeit entspricht etwa der Mitte der Round-Trip
+        let offset = server_time.server_time - (sent_at + received_at) / 2;
+        self.time_offset_ms.store(offset, Ordering::Relaxed);
+        self.last_time_sync_ms.store(received_at, Ordering::Relaxed);
+        tracing::info!("MEXC server time offset: {} ms", offset);
+        Ok(offset)
     }
+
+    /// Signierten Request mit aktuellem Timestamp senden; jeder Retry wird neu
+    /// signiert. Meldet MEXC einen Timestamp außerhalb des recvWindow
+    /// (-1021/700003), wird die Serverzeit neu synchronisiert und der Request
+    /// einmal wiederholt.
+    async fn send_signed(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        params: BTreeMap<String, String>,
+        credentials: &Credentials,
+        observe_stages: bool,
+    ) -> Result<(reqwest::StatusCode, String)> {
+        // Ein zweiter Versuch darf keine zweite Order erzeugen
+        let retryable = method == reqwest::Method::GET || params.contains_key("newClientOrderId");
+        let url = format!("{}{}", self.base_url, path);
+        let mut resynced = false;
+        loop {
+            let stage_start = Instant::now();
+            let round_trip_start = stage_start;
+            let response = self
+                .send_with_retry(retryable, || {
+                    let sign_start = Instant::now();
+                    let mut params = params.clone();
+                    params.insert("timestamp".to_string(), self.timestamp()?);
+                    let signed = self.signing_version.sign(&credentials.secret_key, &params);
+                    if observe_stages {
+                        self.observe_stage("sign", sign_start);
+                    }
+                    self.log_request(method.as_str(), &url, &params);
+                    Ok(self
+                        .client
+                        .request(method.clone(), format!("{}?{}", url, signed.query))
+                        .header("X-MEXC-APIKEY", &credentials.api_key)
+                        .headers(signed.headers))
+                })
+                .await?;
+            if observe_stages {
+                self.observe_stage("request", stage_start);
+            }
+
+            let stage_start = Instant::now();
+            let (status, body) = self.read_response(method.as_str(), &url, response).await?;
+            if observe_stages {
+                self.observe_stage("response_read", stage_start);
+            }
+            self.latency.record(round_trip_start.elapsed());
+
+            let clock_skew = !status.is_success()
+                && MexcError::from_response(status.as_u16(), &body).is_clock_skew();
+            if resynced || !clock_skew {
+                return Ok((status, body));
+            }
+            tracing::warn!("MEXC rejected timestamp for {} {}, resyncing server time", method, path);
+            self.fetch_time_offset().await?;
+            resynced = true;
+        }
+    }
+}
+
+/// Status, bei denen ein Retry sinnvoll ist (Rate Limit, kurze Ausfälle)
+fn is_transient(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 500 | 502 | 503)
+}
+
+/// Antwort von GET /api/v3/time
+#[derive(Deserialize)]
+struct ServerTime {
+    #[serde(rename = "serverTime")]
+    server_time: i64,
+}
+
+fn local_millis() -> Result<i64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64)
+}
+
+/// Parameter für /api/v3/order und /api/v3/order/test (vor Timestamp und Signatur)
+fn order_params(order: &OrderRequest) -> BTreeMap<String, String> {
+    let mut params = BTreeMap::new();
+    params.insert("symbol".to_string(), order.symbol.clone());
+    params.insert("side".to_string(), order.side.clone());
+    params.insert("type".to_string(), order.order_type.clone());
+    match order.quote_order_qty {
+        Some(quote_qty) => params.insert("quoteOrderQty".to_string(), quote_qty.to_string()),
+        None => params.insert("quantity".to_string(), order.quantity.to_string()),
+    };
+
+    if let Some(price) = order.price {
+        params.insert("price".to_string(), price.to_string());
+    }
+    if let Some(client_order_id) = &order.client_order_id {
+        params.insert("newClientOrderId".to_string(), client_order_id.clone());
+    }
+    params
+}
+
+/// Parameter für GET /api/v3/order (`id_param`: orderId oder origClientOrderId)
+fn order_lookup_params(symbol: &str, id_param: &str, id: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("symbol".to_string(), symbol.to_string()),
+        (id_param.to_string(), id.to_string()),
+    ])
+}
+
+/// Maskiere den Wert des `signature` Query-Parameters für Logs
+fn redact_signature(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some(("signature", _)) => "signature=***".to_string(),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}?{}", base, query)
 }
 
+/// Antwort von /api/v3/account; weitere Felder (canTrade, permissions, ...) werden ignoriert
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccountBalance {
+    #[serde(default)]
     pub balances: Vec<BalanceInfo>,
 }
 
+/// MEXC liefert `free`/`locked` als Strings ("100.5")
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BalanceInfo {
     pub asset: String,
+    #[serde(deserialize_with = "deserialize_number")]
     pub free: f64,
+    #[serde(deserialize_with = "deserialize_number")]
     pub locked: f64,
 }
 
+impl AccountBalance {
+    /// Balance eines Assets (case-insensitive)
+    fn find(&self, asset: &str) -> Option<&BalanceInfo> {
+        self.balances.iter().find(|b| b.asset.eq_ignore_ascii_case(asset))
+    }
+
+    /// Freie Menge eines Assets, 0 wenn nicht vorhanden
+    pub fn free_of(&self, asset: &str) -> f64 {
+        self.find(asset).map(|b| b.free).unwrap_or(0.0)
+    }
+
+    /// Gesperrte Menge eines Assets (offene Orders), 0 wenn nicht vorhanden
+    pub fn locked_of(&self, asset: &str) -> f64 {
+        self.find(asset).map(|b| b.locked).unwrap_or(0.0)
+    }
+
+    /// Frei + gesperrt
+    pub fn total_of(&self, asset: &str) -> f64 {
+        self.free_of(asset) + self.locked_of(asset)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ticker_payload_object_and_array() {
+        let object: TickerPayload =
+            serde_json::from_str(r#"{"symbol":"ETHUSDT","price":110.0,"timestamp":1}"#).unwrap();
+        assert_eq!(object.into_ticker("ETHUSDT").unwrap().price, 110.0);
+
+        let array: TickerPayload =
+            serde_json::from_str(r#"[{"symbol":"ETHUSDT","price":111.0,"timestamp":1}]"#).unwrap();
+        assert_eq!(array.into_ticker("ETHUSDT").unwrap().price, 111.0);
+
+        let empty: TickerPayload = serde_json::from_str("[]").unwrap();
+        let err = empty.into_ticker("ETHUSDT").unwrap_err();
+        assert!(err.to_string().contains("Empty ticker response"));
+    }
+
+    #[test]
+    fn test_balance_lookup_by_asset() {
+        let balance = AccountBalance {
+            balances: vec![
+                BalanceInfo { asset: "USDT".to_string(), free: 100.0, locked: 25.0 },
+                BalanceInfo { asset: "ETH".to_string(), free: 0.5, locked: 0.0 },
+            ],
+        };
+
+        assert_eq!(balance.free_of("USDT"), 100.0);
+        assert_eq!(balance.locked_of("USDT"), 25.0);
+        assert_eq!(balance.total_of("USDT"), 125.0);
+
+        assert_eq!(balance.free_of("usdt"), 100.0);
+        assert_eq!(balance.total_of("eth"), 0.5);
+
+        assert_eq!(balance.free_of("BTC"), 0.0);
+        assert_eq!(balance.locked_of("BTC"), 0.0);
+        assert_eq!(balance.total_of("BTC"), 0.0);
+    }
+
+    #[test]
+    fn test_account_response_with_string_balances() {
+        let body = r#"{
+            "makerCommission": null,
+            "takerCommission": null,
+            "buyerCommission": null,
+            "sellerCommission": null,
+            "canTrade": true,
+            "canWithdraw": true,
+            "canDeposit": true,
+            "updateTime": null,
+            "accountType": "SPOT",
+            "balances": [
+                { "asset": "USDT", "free": "1523.41900000", "locked": "76.5", "available": "1523.419" },
+                { "asset": "MX", "free": "0", "locked": "0" },
+                { "asset": "ETH", "free": 0.25, "locked": "0.05" }
+            ],
+            "permissions": ["SPOT"]
+        }"#;
+
+        let balance: AccountBalance = serde_json::from_str(body).expect("account response not parsed");
+        assert_eq!(balance.free_of("USDT"), 1523.419);
+        assert_eq!(balance.locked_of("USDT"), 76.5);
+        assert_eq!(balance.total_of("MX"), 0.0);
+        assert_eq!(balance.total_of("ETH"), 0.3);
+
+        let empty: AccountBalance = serde_json::from_str(r#"{"canTrade":false}"#).unwrap();
+        assert!(empty.balances.is_empty());
+    }
+
     #[test]
     fn test_signature_creation() {
         let config = Config {
@@ -275,14 +1199,712 @@ mod tests {
             aws_region: "ap-southeast-1".to_string(),
             dynamodb_table: "mexc_trading_data".to_string(),
             rust_api_port: 8080,
-            jwt_secret: "jwt-secret".to_string(),
+            jwt_secret: Some("jwt-secret".to_string()),
+            ..Default::default()
         };
 
         let client = MexcClient::new(&config).expect("Failed to create client");
-        let query_string = "symbol=ETHUSDT&quantity=1.0&side=BUY&type=LIMIT&price=2000.0";
-        let signature = client.create_signature(query_string);
+        let params = BTreeMap::from([
+            ("symbol".to_string(), "ETHUSDT".to_string()),
+            ("side".to_string(), "BUY".to_string()),
+        ]);
+        let signed = client.sign_params(&params);
+        let (_, signature) = signed.query.split_once("&signature=").expect("signature missing");
 
         assert!(!signature.is_empty());
         assert_eq!(signature.len(), 64); // SHA256 hex = 64 chars
     }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried_with_backoff() {
+        use crate::test_support::{spawn_server, test_config};
+        use axum::{
+            extract::RawQuery,
+            http::StatusCode,
+            response::IntoResponse,
+            routing::{get, post},
+            Json, Router,
+        };
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        let queries = Arc::new(Mutex::new(Vec::<String>::new()));
+        let tickers = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                post({
+                    let queries = queries.clone();
+                    move |RawQuery(query): RawQuery| async move {
+                        let mut queries = queries.lock().unwrap();
+                        queries.push(query.unwrap_or_default());
+                        // Zweimal 503 (einmal mit absurdem Retry-After), dann angenommen
+                        match queries.len() {
+                            1 => (StatusCode::SERVICE_UNAVAILABLE, [("retry-after", "3600")]).into_response(),
+                            2 => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+                            _ => Json(serde_json::json!({
+                                "order_id": "mexc-1",
+                                "symbol": "ETHUSDT",
+                                "side": "BUY",
+                                "order_type": "MARKET",
+                                "quantity": 1.0,
+                                "price": 0.0,
+                                "status": "FILLED",
+                                "filled_qty": 1.0,
+                                "created_at": 0,
+                            }))
+                            .into_response(),
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/ticker/24hr",
+                get({
+                    let tickers = tickers.clone();
+                    move || async move {
+                        if tickers.fetch_add(1, Ordering::SeqCst) == 0 {
+                            return StatusCode::BAD_GATEWAY.into_response();
+                        }
+                        Json(serde_json::json!({ "symbol": "ETHUSDT", "price": 100.0, "timestamp": 0 })).into_response()
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let client = MexcClient::new(&Config {
+            mexc_retry_max_attempts: 3,
+            mexc_retry_base_delay_ms: 20,
+            mexc_retry_max_delay_ms: 50,
+            ..test_config(&base_url)
+        })
+        .unwrap();
+        let order = |client_order_id: Option<&str>| OrderRequest {
+            symbol: "ETHUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "MARKET".to_string(),
+            quantity: 1.0,
+            price: None,
+            quote_order_qty: None,
+            client_order_id: client_order_id.map(str::to_string),
+        };
+
+        // Ohne newClientOrderId könnte ein Retry doppelt kaufen: genau ein Versuch
+        assert!(client.create_order(&order(None)).await.is_err());
+        assert_eq!(queries.lock().unwrap().len(), 1);
+
+        // Mit newClientOrderId: wiederholt, Retry-After auf 50 ms gedeckelt
+        queries.lock().unwrap().clear();
+        let started = Instant::now();
+        let placed = client
+            .create_order(&order(Some("snipe-1")))
+            .await
+            .expect("order should succeed on the third attempt");
+        assert_eq!(placed.order_id, "mexc-1");
+        assert!(started.elapsed() >= Duration::from_millis(90));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // Jeder Versuch mit eigenem Timestamp und eigener Signatur
+        let queries = queries.lock().unwrap().clone();
+        assert_eq!(queries.len(), 3);
+        assert!(queries.iter().all(|q| q.contains("newClientOrderId=snipe-1")));
+        let signatures: HashSet<_> = queries.iter().filter_map(|q| q.split("signature=").nth(1)).collect();
+        assert_eq!(signatures.len(), 3);
+
+        // GETs sind idempotent und werden wiederholt
+        client.get_ticker("ETHUSDT").await.expect("ticker should succeed on retry");
+        assert_eq!(tickers.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_signing_version_v2_sends_version_header() {
+        use crate::test_support::{spawn_server, test_config};
+        use axum::{extract::RawQuery, http::HeaderMap, routing::get, Json, Router};
+
+        let router = Router::new().route(
+            "/api/v3/openOrders",
+            get(|headers: HeaderMap, RawQuery(query): RawQuery| async move {
+                assert_eq!(headers["x-mexc-signature-version"], "2");
+                assert!(query.unwrap_or_default().contains("&signature="));
+                Json(serde_json::json!([]))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let client = MexcClient::new(&Config {
+            mexc_signing_version: SigningVersion::V2,
+            ..test_config(&base_url)
+        })
+        .unwrap();
+
+        assert!(client.get_open_orders("ETHUSDT").await.expect("request failed").is_empty());
+    }
+
+    #[test]
+    fn test_my_trades_deserialization() {
+        let json = r#"[{
+            "symbol": "MXUSDT",
+            "id": "fad2af9e942049b6adbda1a271f990c6",
+            "orderId": "bb41e5663e124046bd9497a3f5692f39",
+            "orderListId": -1,
+            "price": "3.4450",
+            "qty": "0.20",
+            "quoteQty": "0.689",
+            "commission": "0.000689",
+            "commissionAsset": "USDT",
+            "time": 1700000000000,
+            "isBuyer": true,
+            "isMaker": false,
+            "isBestMatch": true,
+            "isSelfTrade": false,
+            "clientOrderId": null
+        }]"#;
+
+        let fills: Vec<TradeFill> = serde_json::from_str(json).expect("parse failed");
+        assert_eq!(fills.len(), 1);
+        let fill = &fills[0];
+        assert_eq!(fill.trade_id, "fad2af9e942049b6adbda1a271f990c6");
+        assert_eq!(fill.order_id, "bb41e5663e124046bd9497a3f5692f39");
+        assert_eq!(fill.price, 3.445);
+        assert_eq!(fill.quantity, 0.2);
+        assert_eq!(fill.fee, 0.000689);
+        assert_eq!(fill.fee_asset, "USDT");
+        assert!(fill.is_buyer);
+        assert!(!fill.is_maker);
+
+        // Eigene (snake_case) Serialisierung bleibt lesbar
+        let round_trip: TradeFill = serde_json::from_value(serde_json::to_value(fill).unwrap()).unwrap();
+        assert_eq!(&round_trip, fill);
+    }
+
+    #[test]
+    fn test_average_fill_price_of_market_order() {
+        let json = r#"{
+            "order_id": "mexc-1",
+            "symbol": "NEWUSDT",
+            "side": "BUY",
+            "order_type": "MARKET",
+            "quantity": 4.0,
+            "price": 0.0,
+            "status": "FILLED",
+            "filled_qty": 4.0,
+            "cummulativeQuoteQty": "4.60",
+            "created_at": 0
+        }"#;
+        let mut response: OrderResponse = serde_json::from_str(json).expect("parse failed");
+        assert!((response.average_fill_price().unwrap() - 1.15).abs() < 1e-9);
+
+        // Ohne Quote-Volumen gilt der Limit-Preis, ohne Fill gar keiner
+        response.cummulative_quote_qty = None;
+        assert_eq!(response.average_fill_price(), None);
+        response.price = 1.2;
+        assert_eq!(response.average_fill_price(), Some(1.2));
+        response.filled_qty = 0.0;
+        assert_eq!(response.average_fill_price(), None);
+    }
+
+    #[test]
+    fn test_symbol_state_from_exchange_status() {
+        let cases = [
+            ("TRADING", SymbolState::Trading),
+            ("ENABLED", SymbolState::Trading),
+            ("1", SymbolState::Trading),
+            ("PRE_TRADING", SymbolState::PreTrading),
+            ("pending_trading", SymbolState::PreTrading),
+            ("AUCTION_MATCH", SymbolState::PreTrading),
+            ("HALT", SymbolState::Halted),
+            ("BREAK", SymbolState::Halted),
+            ("2", SymbolState::Halted),
+            ("3", SymbolState::Halted),
+            ("DELISTED", SymbolState::Halted),
+            ("SOMETHING_NEW", SymbolState::Unknown("SOMETHING_NEW".to_string())),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(SymbolState::from_exchange_status(status), expected, "{}", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_symbol_state_from_exchange_info() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{extract::Query, routing::get, Json, Router};
+
+        let router = Router::new().route(
+            "/api/v3/exchangeInfo",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                let symbols = match params["symbol"].as_str() {
+                    "NEWUSDT" => serde_json::json!([{ "symbol": "NEWUSDT", "status": "PRE_TRADING" }]),
+                    "ETHUSDT" => serde_json::json!([{ "symbol": "ETHUSDT", "status": 1 }]),
+                    _ => serde_json::json!([]),
+                };
+                Json(serde_json::json!({ "timezone": "CST", "symbols": symbols }))
+            }),
+        );
+        let client = mexc_client(&spawn_server(router).await);
+
+        assert_eq!(client.symbol_state("NEWUSDT").await.unwrap(), SymbolState::PreTrading);
+        assert_eq!(client.symbol_state("ETHUSDT").await.unwrap(), SymbolState::Trading);
+        assert_eq!(client.symbol_state("GONEUSDT").await.unwrap(), SymbolState::NotListed);
+    }
+
+    #[test]
+    fn test_order_book_deserialization() {
+        let json = r#"{
+            "lastUpdateId": 1,
+            "bids": [["1.10", "5"], ["1.00", "10"]],
+            "asks": [[1.20, 3.5]],
+            "timestamp": 1700000000000
+        }"#;
+        let book: OrderBook = serde_json::from_str(json).unwrap();
+
+        assert_eq!(book.bids, vec![(1.10, 5.0), (1.00, 10.0)]);
+        assert_eq!(book.asks, vec![(1.20, 3.5)]);
+        assert_eq!(book.best_bid(), Some(1.10));
+        assert_eq!(book.best_ask(), Some(1.20));
+    }
+
+    #[test]
+    fn test_order_book_imbalance() {
+        let book = OrderBook {
+            bids: vec![(1.00, 10.0), (0.99, 10.0)],
+            asks: vec![(1.01, 2.0), (1.02, 4.0)],
+            timestamp: 0,
+        };
+
+        // Bid 10 + 10/2 = 15, Ask 2 + 4/2 = 4
+        assert!((book.imbalance(2).unwrap() - 11.0 / 19.0).abs() < 1e-9);
+        // Nur Top-Level: (10 - 2) / 12
+        assert!((book.imbalance(1).unwrap() - 8.0 / 12.0).abs() < 1e-9);
+
+        let sell_heavy = OrderBook {
+            bids: vec![(1.0, 1.0)],
+            asks: vec![(1.1, 9.0)],
+            timestamp: 0,
+        };
+        assert!((sell_heavy.imbalance(5).unwrap() + 0.8).abs() < 1e-9);
+
+        let empty = OrderBook { bids: vec![], asks: vec![], timestamp: 0 };
+        assert_eq!(empty.imbalance(5), None);
+    }
+
+    #[test]
+    fn test_clamp_depth_limit() {
+        assert_eq!(clamp_depth_limit(1), 5);
+        assert_eq!(clamp_depth_limit(20), 20);
+        assert_eq!(clamp_depth_limit(21), 50);
+        assert_eq!(clamp_depth_limit(10_000), 5000);
+    }
+
+    #[tokio::test]
+    async fn test_quote_order_qty_replaces_quantity_param() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{extract::RawQuery, routing::post, Json, Router};
+
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|RawQuery(query): RawQuery| async move {
+                let query = query.unwrap_or_default();
+                assert!(query.contains("quoteOrderQty=25"));
+                assert!(!query.contains("quantity="));
+                assert!(query.contains("&signature="));
+                Json(serde_json::json!({
+                    "order_id": "1",
+                    "symbol": "NEWUSDT",
+                    "side": "BUY",
+                    "order_type": "MARKET",
+                    "quantity": 10.0,
+                    "price": 2.5,
+                    "status": "FILLED",
+                    "filled_qty": 10.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let response = mexc_client(&base_url)
+            .create_order(&OrderRequest {
+                symbol: "NEWUSDT".to_string(),
+                side: "BUY".to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: 0.0,
+                price: None,
+                quote_order_qty: Some(25.0),
+                client_order_id: None,
+            })
+            .await
+            .expect("order failed");
+        assert_eq!(response.quantity, 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_client_order_id_returns_existing_order() {
+        use crate::test_support::{mexc_client, spawn_server, test_config};
+        use axum::{extract::RawQuery, http::StatusCode, routing::post, Json, Router};
+
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|RawQuery(query): RawQuery| async move {
+                assert!(query.unwrap_or_default().contains("newClientOrderId=local-1"));
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "code": -2010, "msg": "Duplicate order sent." })),
+                )
+            })
+            .get(|RawQuery(query): RawQuery| async move {
+                let query = query.unwrap_or_default();
+                assert!(query.contains("origClientOrderId=local-1"));
+                Json(serde_json::json!({
+                    "order_id": "mexc-42",
+                    "symbol": "ETHUSDT",
+                    "side": "BUY",
+                    "order_type": "LIMIT",
+                    "quantity": 1.0,
+                    "price": 2000.0,
+                    "status": "NEW",
+                    "filled_qty": 0.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let order = OrderRequest {
+            symbol: "ETHUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "LIMIT".to_string(),
+            quantity: 1.0,
+            price: Some(2000.0),
+            quote_order_qty: None,
+            client_order_id: Some("local-1".to_string()),
+        };
+
+        let response = mexc_client(&base_url).create_order(&order).await.expect("duplicate not resolved");
+        assert_eq!(response.order_id, "mexc-42");
+        assert_eq!(response.status, "NEW");
+
+        // Abgeschaltet -> der Duplicate-Fehler kommt durch
+        let strict = MexcClient::new(&Config {
+            mexc_idempotent_create: false,
+            ..test_config(&base_url)
+        })
+        .unwrap();
+        let err = strict.create_order(&order).await.unwrap_err();
+        assert!(err.downcast_ref::<MexcError>().unwrap().is_duplicate_order());
+    }
+
+    #[test]
+    fn test_redact_signature() {
+        let url = "https://api.mexc.com/api/v3/order?symbol=ETHUSDT&timestamp=1&signature=deadbeef";
+        let redacted = redact_signature(url);
+        assert_eq!(
+            redacted,
+            "https://api.mexc.com/api/v3/order?symbol=ETHUSDT&timestamp=1&signature=***"
+        );
+        assert!(!redacted.contains("deadbeef"));
+
+        assert_eq!(
+            redact_signature("http://x/a?signature=abc&symbol=BTCUSDT"),
+            "http://x/a?signature=***&symbol=BTCUSDT"
+        );
+        assert_eq!(redact_signature("http://x/a"), "http://x/a");
+    }
+
+    #[tokio::test]
+    async fn test_saturated_market_permits_do_not_block_orders() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{routing::post, Json, Router};
+        use std::time::Duration;
+
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|| async {
+                Json(serde_json::json!({
+                    "order_id": "1",
+                    "symbol": "ETHUSDT",
+                    "side": "BUY",
+                    "order_type": "MARKET",
+                    "quantity": 1.0,
+                    "price": 0.0,
+                    "status": "NEW",
+                    "filled_qty": 0.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let client = mexc_client(&base_url);
+
+        let available = client.market_permits.available_permits() as u32;
+        let _held = client.market_permits.acquire_many(available).await.unwrap();
+
+        let ticker = tokio::time::timeout(Duration::from_millis(100), client.get_ticker("ETHUSDT")).await;
+        assert!(ticker.is_err(), "ticker should wait for a market permit");
+
+        let order = OrderRequest {
+            symbol: "ETHUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "MARKET".to_string(),
+            quantity: 1.0,
+            price: None,
+            quote_order_qty: None,
+            client_order_id: None,
+        };
+        let result = tokio::time::timeout(Duration::from_secs(2), client.create_order(&order))
+            .await
+            .expect("order blocked by market semaphore");
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_dispatched_ahead_of_queued_tickers() {
+        use crate::test_support::{spawn_server, test_config};
+        use axum::{routing::get, Json, Router};
+        use std::sync::Mutex;
+        use std::time::Duration;
+        use tokio::sync::Notify;
+
+        let hits = Arc::new(Mutex::new(Vec::<String>::new()));
+        let release = Arc::new(Notify::new());
+        let router = Router::new()
+            .route(
+                "/api/v3/ticker/24hr",
+                get({
+                    let (hits, release) = (hits.clone(), release.clone());
+                    move || async move {
+                        let first = {
+                            let mut hits = hits.lock().unwrap();
+                            hits.push("ticker".to_string());
+                            hits.len() == 1
+                        };
+                        if first {
+                            // Erster Ticker blockiert den einzigen Worker
+                            release.notified().await;
+                        }
+                        Json(serde_json::json!({ "symbol": "ETHUSDT", "price": 1.0, "timestamp": 1 }))
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                axum::routing::delete({
+                    let hits = hits.clone();
+                    move || async move {
+                        hits.lock().unwrap().push("cancel".to_string());
+                        Json(serde_json::json!({
+                            "order_id": "1",
+                            "symbol": "ETHUSDT",
+                            "side": "BUY",
+                            "order_type": "LIMIT",
+                            "quantity": 1.0,
+                            "price": 1.0,
+                            "status": "CANCELED",
+                            "filled_qty": 0.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let client = Arc::new(
+            MexcClient::new(&Config {
+                mexc_queue_workers: 1,
+                ..test_config(&base_url)
+            })
+            .unwrap(),
+        );
+
+        let mut tasks = Vec::new();
+        for _ in 0..6 {
+            let client = client.clone();
+            tasks.push(tokio::spawn(async move { client.get_ticker("ETHUSDT").await.map(|_| ()) }));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let cancel = tokio::spawn({
+            let client = client.clone();
+            async move { client.cancel_order("ETHUSDT", "1").await.map(|_| ()) }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(client.queue_depth(), QueueDepth { order: 1, market: 5 });
+
+        release.notify_one();
+        cancel.await.unwrap().unwrap();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let hits = hits.lock().unwrap().clone();
+        assert_eq!(hits[0], "ticker");
+        assert_eq!(hits[1], "cancel", "cancel must run before queued tickers: {:?}", hits);
+        assert_eq!(hits.len(), 7);
+    }
+
+    #[test]
+    fn test_swapped_credentials_used_for_next_signature() {
+        let client = crate::test_support::mexc_client("http://127.0.0.1:9");
+        let params = BTreeMap::from([
+            ("symbol".to_string(), "ETHUSDT".to_string()),
+            ("timestamp".to_string(), "1".to_string()),
+        ]);
+        let signed = |secret| SigningVersion::V1.sign(secret, &params).query;
+        let before = client.sign_params(&params).query;
+        assert_eq!(before, signed("test-secret"));
+
+        let previous = client.swap_credentials(Credentials {
+            api_key: "rotated-key".to_string(),
+            secret_key: "rotated-secret".to_string(),
+        });
+
+        assert_eq!(previous.api_key, "test-key");
+        assert_eq!(client.api_key(), "rotated-key");
+        assert_eq!(client.sign_params(&params).query, signed("rotated-secret"));
+        assert_ne!(client.sign_params(&params).query, before);
+    }
+
+    #[tokio::test]
+    async fn test_failed_rotation_keeps_previous_credentials() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{http::StatusCode, routing::get, Router};
+
+        let router = Router::new().route(
+            "/api/v3/account",
+            get(|| async { (StatusCode::UNAUTHORIZED, "invalid api key") }),
+        );
+        let base_url = spawn_server(router).await;
+        let client = mexc_client(&base_url);
+        let timestamp = BTreeMap::from([("timestamp".to_string(), "1".to_string())]);
+        let signature_before = client.sign_params(&timestamp).query;
+
+        let result = client
+            .rotate_credentials(Credentials {
+                api_key: "bad-key".to_string(),
+                secret_key: "bad-secret".to_string(),
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(client.api_key(), "test-key");
+        assert_eq!(client.sign_params(&timestamp).query, signature_before);
+    }
+
+    #[tokio::test]
+    async fn test_create_order_records_stage_latency() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{routing::post, Json, Router};
+
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|| async {
+                Json(serde_json::json!({
+                    "order_id": "123",
+                    "symbol": "ETHUSDT",
+                    "side": "BUY",
+                    "order_type": "MARKET",
+                    "quantity": 1.0,
+                    "price": 0.0,
+                    "status": "FILLED",
+                    "filled_qty": 1.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let metrics = Arc::new(Metrics::new());
+        let client = mexc_client(&base_url).with_metrics(metrics.clone());
+
+        client
+            .create_order(&OrderRequest {
+                symbol: "ETHUSDT".to_string(),
+                side: "BUY".to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: 1.0,
+                price: None,
+                quote_order_qty: None,
+                client_order_id: None,
+            })
+            .await
+            .expect("order failed");
+
+        for stage in ["sign", "request", "response_read", "parse"] {
+            let count = metrics
+                .order_latency
+                .with_label_values(&["create_order", stage])
+                .get_sample_count();
+            assert!(count >= 1, "no observation for stage {}", stage);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_rejection_resyncs_and_retries_once() {
+        use crate::test_support::{mexc_client, spawn_server};
+        use axum::{extract::RawQuery, http::StatusCode, routing::get, routing::post, Json, Router};
+        use std::sync::Mutex;
+
+        // Serverzeit eine Stunde vor der lokalen Uhr
+        const SKEW_MS: i64 = 3_600_000;
+        let timestamps = Arc::new(Mutex::new(Vec::new()));
+        let seen = timestamps.clone();
+        let router = Router::new()
+            .route(
+                "/api/v3/time",
+                get(|| async { Json(serde_json::json!({ "serverTime": local_millis().unwrap() + SKEW_MS })) }),
+            )
+            .route(
+                "/api/v3/order",
+                post(move |RawQuery(query): RawQuery| {
+                    let seen = seen.clone();
+                    async move {
+                        let timestamp: i64 = query
+                            .unwrap_or_default()
+                            .split('&')
+                            .find_map(|pair| pair.strip_prefix("timestamp="))
+                            .and_then(|value| value.parse().ok())
+                            .expect("timestamp missing");
+                        let mut seen = seen.lock().unwrap();
+                        seen.push(timestamp);
+                        if seen.len() == 1 {
+                            return (
+                                StatusCode::BAD_REQUEST,
+                                Json(serde_json::json!({
+                                    "code": -1021,
+                                    "msg": "Timestamp for this request is outside of the recvWindow."
+                                })),
+                            );
+                        }
+                        (
+                            StatusCode::OK,
+                            Json(serde_json::json!({
+                                "order_id": "123",
+                                "symbol": "ETHUSDT",
+                                "side": "BUY",
+                                "order_type": "MARKET",
+                                "quantity": 1.0,
+                                "price": 0.0,
+                                "status": "FILLED",
+                                "filled_qty": 1.0,
+                                "created_at": 0,
+                            })),
+                        )
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let client = mexc_client(&base_url);
+
+        let response = client
+            .create_order(&OrderRequest {
+                symbol: "ETHUSDT".to_string(),
+                side: "BUY".to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: 1.0,
+                price: None,
+                quote_order_qty: None,
+                client_order_id: None,
+            })
+            .await
+            .expect("retry after resync failed");
+        assert_eq!(response.order_id, "123");
+
+        let timestamps = timestamps.lock().unwrap().clone();
+        assert_eq!(timestamps.len(), 2);
+        assert!((client.time_offset_ms() - SKEW_MS).abs() < 5_000);
+        assert!(timestamps[1] - timestamps[0] > SKEW_MS - 5_000, "{:?}", timestamps);
+    }
 }
@@ -0,0 +1,279 @@
+use crate::mexc::{Credentials, MexcClient};
+use crate::storage::DynamoDBStore;
+use crate::utils::{Config, Metrics};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Fehler bei der Auswahl des User-Clients
+#[derive(Debug)]
+pub enum UserClientError {
+    /// User hat keine eigenen Keys und ist nicht der Admin (bzw. kein Admin gesetzt)
+    MissingCredentials(String),
+    /// Credentials konnten nicht geladen/entschlüsselt werden
+    Lookup(anyhow::Error),
+    /// Kein benanntes Konto dieses Namens konfiguriert (MEXC_ACCOUNTS)
+    UnknownAccount(String),
+}
+
+impl fmt::Display for UserClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCredentials(user_id) => {
+                write!(f, "No MEXC credentials stored for user {}", user_id)
+            }
+            Self::Lookup(e) => write!(f, "Failed to load MEXC credentials: {}", e),
+            Self::UnknownAccount(name) => write!(f, "No MEXC account named {} configured", name),
+        }
+    }
+}
+
+impl std::error::Error for UserClientError {}
+
+/// MEXC Client pro User: eigene (verschlüsselte) Keys aus DynamoDB, der globale
+/// Key nur für den Admin-User (MEXC_ADMIN_USER_ID) bzw. explizit per `single_tenant`
+pub struct MexcClientPool {
+    global: Arc<MexcClient>,
+    store: Option<Arc<DynamoDBStore>>,
+    config: Config,
+    admin_user_id: Option<String>,
+    metrics: Option<Arc<Metrics>>,
+    clients: Mutex<HashMap<String, Arc<MexcClient>>>,
+    /// Clients der benannten Konten (lazy aus `config.mexc_accounts`)
+    accounts: Mutex<HashMap<String, Arc<MexcClient>>>,
+}
+
+impl MexcClientPool {
+    pub fn new(global: Arc<MexcClient>, store: Arc<DynamoDBStore>, config: &Config) -> Self {
+        Self {
+            global,
+            store: Some(store),
+            config: config.clone(),
+            admin_user_id: config.mexc_admin_user_id.clone(),
+            metrics: None,
+            clients: Mutex::new(HashMap::new()),
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Nur der globale Client für alle User (Tests, Single-Tenant)
+    pub fn single_tenant(global: Arc<MexcClient>) -> Self {
+        Self {
+            global,
+            store: None,
+            config: Config::default(),
+            admin_user_id: None,
+            metrics: None,
+            clients: Mutex::new(HashMap::new()),
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Latenz-Metriken auch für User-Clients
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn global(&self) -> &Arc<MexcClient> {
+        &self.global
+    }
+
+    /// Client des Users (gecacht); ohne eigene Keys nur für den Admin der globale,
+    /// ohne MEXC_ADMIN_USER_ID bekommt niemand ohne Keys den globalen Client
+    pub async fn for_user(&self, user_id: &str) -> Result<Arc<MexcClient>, UserClientError> {
+        if let Some(client) = self.clients.lock().unwrap_or_else(|e| e.into_inner()).get(user_id) {
+            return Ok(client.clone());
+        }
+
+        let credentials = match &self.store {
+            Some(store) => store
+                .get_mexc_credentials(user_id)
+                .await
+                .map_err(UserClientError::Lookup)?,
+            None => None,
+        };
+
+        let Some(credentials) = credentials else {
+            let may_use_global = match (&self.store, self.admin_user_id.as_deref()) {
+                (None, _) => true,
+                (Some(_), admin) => admin == Some(user_id),
+            };
+            return if may_use_global {
+                Ok(self.global.clone())
+            } else {
+                Err(UserClientError::MissingCredentials(user_id.to_string()))
+            };
+        };
+
+        let client = self.build_client(credentials)?;
+        self.clients
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(user_id.to_string(), client.clone());
+        Ok(client)
+    }
+
+    /// Namen der konfigurierten Konten
+    pub fn account_names(&self) -> Vec<String> {
+        self.config.mexc_accounts.keys().cloned().collect()
+    }
+
+    /// Client eines benannten Kontos (gecacht), z.B. je Strategie
+    pub fn for_account(&self, name: &str) -> Result<Arc<MexcClient>, UserClientError> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = accounts.get(name) {
+            return Ok(client.clone());
+        }
+
+        let credentials = self
+            .config
+            .mexc_accounts
+            .get(name)
+            .cloned()
+            .ok_or_else(|| UserClientError::UnknownAccount(name.to_string()))?;
+        let client = self.build_client(credentials)?;
+        accounts.insert(name.to_string(), client.clone());
+        Ok(client)
+    }
+
+    fn build_client(&self, credentials: Credentials) -> Result<Arc<MexcClient>, UserClientError> {
+        let mut client = MexcClient::new(&Config {
+            mexc_api_key: credentials.api_key,
+            mexc_secret_key: credentials.secret_key,
+            ..self.config.clone()
+        })
+        .map_err(UserClientError::Lookup)?
+        .with_error_log(self.global.error_log().clone());
+        if let Some(metrics) = &self.metrics {
+            client = client.with_metrics(metrics.clone());
+        }
+        Ok(Arc::new(client))
+    }
+
+    /// Gecachten Client verwerfen (nach Änderung der Keys)
+    pub fn invalidate(&self, user_id: &str) {
+        self.clients.lock().unwrap_or_else(|e| e.into_inner()).remove(user_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mexc::Credentials;
+    use crate::test_support::{mexc_client, MockDynamo};
+    use crate::utils::FieldCipher;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_credentials_are_stored_encrypted() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await.with_cipher(Arc::new(FieldCipher::new(&[1u8; 32])));
+
+        store
+            .put_mexc_credentials(
+                "user-1",
+                &Credentials {
+                    api_key: "user-key".to_string(),
+                    secret_key: "user-secret".to_string(),
+                },
+            )
+            .await
+            .expect("put failed");
+
+        let item = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert_eq!(item["sk"]["S"], "CREDENTIALS#mexc");
+        assert!(!item.to_string().contains("user-secret"));
+
+        dynamo.respond("GetItem", json!({ "Item": item }));
+        let loaded = store.get_mexc_credentials("user-1").await.unwrap().expect("missing");
+        assert_eq!(loaded.api_key, "user-key");
+        assert_eq!(loaded.secret_key, "user-secret");
+    }
+
+    #[tokio::test]
+    async fn test_client_selection_per_user() {
+        let dynamo = MockDynamo::start().await;
+        let cipher = FieldCipher::new(&[1u8; 32]);
+        let store = Arc::new(dynamo.store().await.with_cipher(Arc::new(FieldCipher::new(&[1u8; 32]))));
+        let global = Arc::new(mexc_client("http://127.0.0.1:9"));
+        let pool = MexcClientPool::new(
+            global.clone(),
+            store,
+            &Config {
+                mexc_admin_user_id: Some("admin".to_string()),
+                ..Default::default()
+            },
+        );
+
+        // user-1 hat eigene Keys
+        dynamo.respond(
+            "GetItem",
+            json!({ "Item": {
+                "user_id": { "S": "user-1" },
+                "sk": { "S": "CREDENTIALS#mexc" },
+                "api_key": { "S": cipher.seal("user-1-key").unwrap() },
+                "secret_key": { "S": cipher.seal("user-1-secret").unwrap() }
+            }}),
+        );
+        let client = pool.for_user("user-1").await.expect("user client");
+        assert_eq!(client.api_key(), "user-1-key");
+        assert!(!Arc::ptr_eq(&client, &global));
+
+        // Zweiter Zugriff aus dem Cache, ohne DynamoDB
+        let cached = pool.for_user("user-1").await.unwrap();
+        assert!(Arc::ptr_eq(&client, &cached));
+        assert_eq!(dynamo.requests("GetItem").len(), 1);
+
+        // Admin ohne Keys -> globaler Client, andere User ohne Keys -> Fehler
+        let admin = pool.for_user("admin").await.expect("admin client");
+        assert!(Arc::ptr_eq(&admin, &global));
+        assert!(matches!(
+            pool.for_user("user-2").await,
+            Err(UserClientError::MissingCredentials(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_global_fallback_without_admin_user() {
+        let dynamo = MockDynamo::start().await;
+        let pool = MexcClientPool::new(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        );
+
+        // Keine Keys gespeichert und kein MEXC_ADMIN_USER_ID: kein globaler Key
+        assert!(matches!(
+            pool.for_user("user-1").await,
+            Err(UserClientError::MissingCredentials(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_named_accounts_get_their_own_clients() {
+        let dynamo = MockDynamo::start().await;
+        let account = |key: &str| Credentials {
+            api_key: format!("{}-key", key),
+            secret_key: format!("{}-secret", key),
+        };
+        let pool = MexcClientPool::new(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+            &Config {
+                mexc_accounts: [("alpha".to_string(), account("alpha")), ("beta".to_string(), account("beta"))]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(pool.account_names(), vec!["alpha", "beta"]);
+        let alpha = pool.for_account("alpha").expect("alpha client");
+        let beta = pool.for_account("beta").expect("beta client");
+        assert_eq!(alpha.api_key(), "alpha-key");
+        assert_eq!(beta.api_key(), "beta-key");
+        assert!(Arc::ptr_eq(&alpha, &pool.for_account("alpha").unwrap()));
+        assert!(matches!(pool.for_account("gamma"), Err(UserClientError::UnknownAccount(_))));
+    }
+}
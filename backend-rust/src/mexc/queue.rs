@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Priorität eines MEXC-Requests; Order/Cancel/Account vor Marktdaten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestPriority {
+    Market,
+    Order,
+}
+
+/// Wartende Requests je Priorität
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueDepth {
+    pub order: usize,
+    pub market: usize,
+}
+
+impl QueueDepth {
+    pub fn total(&self) -> usize {
+        self.order + self.market
+    }
+}
+
+struct Waiter {
+    priority: RequestPriority,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    /// Höhere Priorität zuerst, innerhalb einer Priorität FIFO
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Default)]
+struct QueueInner {
+    available: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+impl QueueInner {
+    /// Freien Worker an den nächsten noch wartenden Request weitergeben
+    fn release(&mut self) {
+        while let Some(waiter) = self.waiting.pop() {
+            if waiter.wake.send(()).is_ok() {
+                return;
+            }
+        }
+        self.available += 1;
+    }
+}
+
+/// Priorisierte Request-Queue vor dem MEXC Client: höchstens `workers`
+/// Requests gleichzeitig, freie Worker gehen zuerst an Order-Requests.
+/// 0 Worker = keine Queue (nur die Permits je Request-Klasse).
+#[derive(Clone)]
+pub struct RequestQueue {
+    inner: Option<Arc<Mutex<QueueInner>>>,
+}
+
+impl RequestQueue {
+    pub fn new(workers: usize) -> Self {
+        Self {
+            inner: (workers > 0).then(|| {
+                Arc::new(Mutex::new(QueueInner {
+                    available: workers,
+                    ..Default::default()
+                }))
+            }),
+        }
+    }
+
+    /// Auf einen freien Worker warten; der Slot gibt ihn beim Drop frei
+    pub async fn acquire(&self, priority: RequestPriority) -> QueueSlot {
+        let Some(inner) = &self.inner else {
+            return QueueSlot { inner: None };
+        };
+
+        let wake = {
+            let mut queue = inner.lock().unwrap_or_else(|e| e.into_inner());
+            if queue.available > 0 && queue.waiting.is_empty() {
+                queue.available -= 1;
+                return QueueSlot {
+                    inner: Some(inner.clone()),
+                };
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let seq = queue.next_seq;
+            queue.next_seq += 1;
+            queue.waiting.push(Waiter {
+                priority,
+                seq,
+                wake: tx,
+            });
+            rx
+        };
+
+        let mut pending = PendingSlot {
+            inner: inner.clone(),
+            wake: Some(wake),
+        };
+        if let Some(wake) = pending.wake.as_mut() {
+            // Sender wird nur zusammen mit dem Waiter verworfen, nie ohne Worker
+            let _ = wake.await;
+        }
+        pending.wake = None;
+
+        QueueSlot {
+            inner: Some(inner.clone()),
+        }
+    }
+
+    /// Aktuell wartende Requests
+    pub fn depth(&self) -> QueueDepth {
+        let Some(inner) = &self.inner else {
+            return QueueDepth::default();
+        };
+
+        let queue = inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut depth = QueueDepth::default();
+        for waiter in queue.waiting.iter().filter(|w| !w.wake.is_closed()) {
+            match waiter.priority {
+                RequestPriority::Order => depth.order += 1,
+                RequestPriority::Market => depth.market += 1,
+            }
+        }
+        depth
+    }
+}
+
+/// Belegter Worker der Queue
+pub struct QueueSlot {
+    inner: Option<Arc<Mutex<QueueInner>>>,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        if let Some(inner) = &self.inner {
+            inner.lock().unwrap_or_else(|e| e.into_inner()).release();
+        }
+    }
+}
+
+/// Wartender Request; wird er abgebrochen nachdem ihm schon ein Worker
+/// zugeteilt wurde, geht der Worker an den nächsten weiter
+struct PendingSlot {
+    inner: Arc<Mutex<QueueInner>>,
+    wake: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for PendingSlot {
+    fn drop(&mut self) {
+        if let Some(mut wake) = self.wake.take() {
+            wake.close();
+            if wake.try_recv().is_ok() {
+                self.inner.lock().unwrap_or_else(|e| e.into_inner()).release();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_leak_worker() {
+        let queue = RequestQueue::new(1);
+        let busy = queue.acquire(RequestPriority::Market).await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(20), queue.acquire(RequestPriority::Order)).await;
+        assert!(waiting.is_err());
+        drop(busy);
+
+        let slot = tokio::time::timeout(Duration::from_millis(100), queue.acquire(RequestPriority::Market)).await;
+        assert!(slot.is_ok(), "worker lost after cancelled waiter");
+    }
+}
@@ -0,0 +1,158 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const USED_WEIGHT: &str = "x-mbx-used-weight";
+const USED_WEIGHT_1M: &str = "x-mbx-used-weight-1m";
+
+/// Rate-Limit-Angaben einer MEXC Response
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitHeaders {
+    /// Serverseitig verbrauchtes Request-Weight (1m-Fenster bevorzugt)
+    pub used_weight: Option<u32>,
+    /// Retry-After in Sekunden (bei 429/418)
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitHeaders {
+    pub fn parse(headers: &HeaderMap) -> Self {
+        let number = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        Self {
+            used_weight: number(USED_WEIGHT_1M)
+                .or_else(|| number(USED_WEIGHT))
+                .map(|w| w.min(u32::MAX as u64) as u32),
+            retry_after: number(RETRY_AFTER.as_str()).map(Duration::from_secs),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RateLimitInner {
+    used_weight: Option<u32>,
+    retry_until: Option<Instant>,
+}
+
+/// Vom Server gemeldeter Rate-Limit-Zustand (statt Schätzung)
+#[derive(Debug, Default)]
+pub struct RateLimitState {
+    inner: Mutex<RateLimitInner>,
+    /// Obergrenze für ein gemeldetes Retry-After (None = unbegrenzt)
+    max_retry_after: Option<Duration>,
+}
+
+impl RateLimitState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retry-After auf höchstens `max` begrenzen (wie der Retry-Backoff), damit
+    /// eine einzelne Response nicht alle Requests inkl. Cancels lange sperrt
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = Some(max);
+        self
+    }
+
+    /// Header einer Response übernehmen; Retry-After gilt nur bei 429/418
+    pub fn record(&self, status: StatusCode, headers: &HeaderMap) {
+        let parsed = RateLimitHeaders::parse(headers);
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(weight) = parsed.used_weight {
+            inner.used_weight = Some(weight);
+        }
+
+        let limited = status == StatusCode::TOO_MANY_REQUESTS || status.as_u16() == 418;
+        if let (true, Some(retry_after)) = (limited, parsed.retry_after) {
+            let retry_after = self.max_retry_after.map_or(retry_after, |max| retry_after.min(max));
+            tracing::warn!("MEXC rate limit hit ({}), backing off for {:?}", status, retry_after);
+            inner.retry_until = Some(Instant::now() + retry_after);
+        }
+    }
+
+    /// Zuletzt gemeldetes Used-Weight
+    pub fn used_weight(&self) -> Option<u32> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).used_weight
+    }
+
+    /// Verbleibende Sperre laut Retry-After
+    pub fn retry_remaining(&self) -> Option<Duration> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .retry_until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Bis zum Ablauf eines Retry-After warten
+    pub async fn wait(&self) {
+        if let Some(remaining) = self.retry_remaining() {
+            tokio::time::sleep(remaining).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_parse_rate_limit_headers() {
+        let parsed = RateLimitHeaders::parse(&headers(&[
+            ("X-MBX-USED-WEIGHT", "120"),
+            ("X-MBX-USED-WEIGHT-1M", "340"),
+            ("Retry-After", "7"),
+        ]));
+        assert_eq!(parsed.used_weight, Some(340));
+        assert_eq!(parsed.retry_after, Some(Duration::from_secs(7)));
+
+        let parsed = RateLimitHeaders::parse(&headers(&[("x-mbx-used-weight", "12")]));
+        assert_eq!(parsed.used_weight, Some(12));
+        assert_eq!(parsed.retry_after, None);
+    }
+
+    #[test]
+    fn test_state_tracks_weight_and_retry_after() {
+        let state = RateLimitState::new();
+
+        state.record(StatusCode::OK, &headers(&[("X-MBX-USED-WEIGHT", "50"), ("Retry-After", "30")]));
+        assert_eq!(state.used_weight(), Some(50));
+        assert_eq!(state.retry_remaining(), None);
+
+        // Response ohne Header behält das letzte Weight
+        state.record(StatusCode::OK, &HeaderMap::new());
+        assert_eq!(state.used_weight(), Some(50));
+
+        state.record(
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("X-MBX-USED-WEIGHT", "1200"), ("Retry-After", "30")]),
+        );
+        assert_eq!(state.used_weight(), Some(1200));
+        let remaining = state.retry_remaining().expect("retry-after not recorded");
+        assert!(remaining > Duration::from_secs(29) && remaining <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_retry_after_is_capped() {
+        let state = RateLimitState::new().with_max_retry_after(Duration::from_secs(5));
+
+        state.record(StatusCode::from_u16(418).unwrap(), &headers(&[("Retry-After", "3600")]));
+        let remaining = state.retry_remaining().expect("retry-after not recorded");
+        assert!(remaining <= Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,130 @@
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header mit der Signatur-Version (nur V2)
+pub const SIGNATURE_VERSION_HEADER: &str = "X-MEXC-SIGNATURE-VERSION";
+
+/// Signatur-Variante für signierte Requests (MEXC_SIGNING_VERSION)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningVersion {
+    /// HMAC-SHA256 über den unkodierten, sortierten Query String
+    #[default]
+    V1,
+    /// HMAC-SHA256 über den prozent-kodierten, sortierten Query String
+    /// plus `X-MEXC-SIGNATURE-VERSION: 2` Header
+    V2,
+}
+
+impl FromStr for SigningVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "1" | "v1" => Ok(Self::V1),
+            "2" | "v2" => Ok(Self::V2),
+            other => Err(format!("unknown signing version: {}", other)),
+        }
+    }
+}
+
+/// Signierter Query String (inkl. `signature`) und zusätzliche Header
+#[derive(Debug, Clone)]
+pub struct SignedQuery {
+    pub query: String,
+    pub headers: HeaderMap,
+}
+
+impl SigningVersion {
+    /// Parameter (sortiert) signieren
+    pub fn sign(&self, secret_key: &str, params: &BTreeMap<String, String>) -> SignedQuery {
+        let mut headers = HeaderMap::new();
+        let query = match self {
+            Self::V1 => build_query_string(params, |v| v.to_string()),
+            Self::V2 => {
+                headers.insert(SIGNATURE_VERSION_HEADER, HeaderValue::from_static("2"));
+                build_query_string(params, percent_encode)
+            }
+        };
+
+        let signature = sign(secret_key, &query);
+        SignedQuery {
+            query: format!("{}&signature={}", query, signature),
+            headers,
+        }
+    }
+}
+
+/// HMAC-SHA256 Signatur (hex) über den Query String
+pub fn sign(secret_key: &str, query_string: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret_key.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(query_string.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Query String aus BTreeMap (sortiert für Signing)
+fn build_query_string(params: &BTreeMap<String, String>, encode: impl Fn(&str) -> String) -> String {
+    params
+        .iter()
+        .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Prozent-Kodierung nach RFC 3986 (unreserved bleibt unverändert)
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("symbol".to_string(), "ETHUSDT".to_string()),
+            ("side".to_string(), "BUY".to_string()),
+            ("clientOrderId".to_string(), "a b/c".to_string()),
+            ("timestamp".to_string(), "1700000000000".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_v1_signs_raw_sorted_query() {
+        let signed = SigningVersion::V1.sign("secret", &params());
+        let query = "clientOrderId=a b/c&side=BUY&symbol=ETHUSDT&timestamp=1700000000000";
+
+        assert_eq!(signed.query, format!("{}&signature={}", query, sign("secret", query)));
+        assert!(signed.headers.is_empty());
+    }
+
+    #[test]
+    fn test_v2_signs_encoded_query_with_version_header() {
+        let signed = SigningVersion::V2.sign("secret", &params());
+        let query = "clientOrderId=a%20b%2Fc&side=BUY&symbol=ETHUSDT&timestamp=1700000000000";
+
+        assert_eq!(signed.query, format!("{}&signature={}", query, sign("secret", query)));
+        assert_eq!(signed.headers[SIGNATURE_VERSION_HEADER], "2");
+    }
+
+    #[test]
+    fn test_parse_signing_version() {
+        assert_eq!("v1".parse::<SigningVersion>(), Ok(SigningVersion::V1));
+        assert_eq!("2".parse::<SigningVersion>(), Ok(SigningVersion::V2));
+        assert_eq!(" V2 ".parse::<SigningVersion>(), Ok(SigningVersion::V2));
+        assert!("v3".parse::<SigningVersion>().is_err());
+    }
+}
@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::utils::{Clock, Metrics, SystemClock};
 
 /// WebSocket Event Types für Real-Time Market Data
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,3 +40,146 @@ pub enum WebSocketMessage {
     Kline(KlineEvent),
     OrderBook(OrderBookUpdate),
 }
+
+/// Letzter Stream-Preis je Symbol
+#[derive(Debug, Clone)]
+struct CachedPrice {
+    price: f64,
+    updated_at_ms: i64,
+    /// Staleness bereits gemeldet (Log/Metrik nur einmal pro Ausfall)
+    stale_reported: bool,
+}
+
+/// Preis-Cache aus dem Trade-Stream mit Staleness-Erkennung.
+/// Kommt für ein Symbol länger als `stale_after` kein Update, gilt der Preis
+/// als veraltet und Aufrufer sollen auf REST zurückfallen.
+pub struct PriceCache {
+    entries: Mutex<HashMap<String, CachedPrice>>,
+    stale_after: Duration,
+    clock: Arc<dyn Clock>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl PriceCache {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            stale_after,
+            clock: Arc::new(SystemClock),
+            metrics: None,
+        }
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Preis aus einem Trade übernehmen
+    pub fn update(&self, trade: &TradeEvent) {
+        self.record(&trade.symbol, trade.price);
+    }
+
+    /// Preis aus beliebiger Quelle (Stream oder REST) übernehmen
+    pub fn record(&self, symbol: &str, price: f64) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.get(symbol).is_some_and(|p| p.stale_reported) {
+            tracing::info!("Price stream for {} recovered", symbol);
+        }
+        entries.insert(
+            symbol.to_string(),
+            CachedPrice {
+                price,
+                updated_at_ms: self.clock.now_millis(),
+                stale_reported: false,
+            },
+        );
+    }
+
+    /// Kein oder zu altes Update für das Symbol
+    pub fn is_stale(&self, symbol: &str) -> bool {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = entries.get_mut(symbol) else {
+            return true;
+        };
+
+        let age_ms = self.clock.now_millis() - entry.updated_at_ms;
+        if age_ms <= self.stale_after.as_millis() as i64 {
+            return false;
+        }
+
+        if !entry.stale_reported {
+            entry.stale_reported = true;
+            tracing::warn!("Price stream for {} stale, last update {} ms ago", symbol, age_ms);
+            if let Some(metrics) = &self.metrics {
+                metrics.price_cache_stale.with_label_values(&[symbol]).inc();
+            }
+        }
+        true
+    }
+
+    /// Frischer Preis oder None (dann REST nutzen)
+    pub fn get(&self, symbol: &str) -> Option<f64> {
+        if self.is_stale(symbol) {
+            return None;
+        }
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(symbol).map(|p| p.price)
+    }
+
+    /// Letzter bekannter Preis samt Alter, unabhängig von der Staleness
+    pub fn last_known(&self, symbol: &str) -> Option<(f64, Duration)> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get(symbol)?;
+        let age_ms = (self.clock.now_millis() - entry.updated_at_ms).max(0);
+        Some((entry.price, Duration::from_millis(age_ms as u64)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::MockClock;
+
+    fn trade(symbol: &str, price: f64) -> TradeEvent {
+        TradeEvent {
+            symbol: symbol.to_string(),
+            price,
+            quantity: 1.0,
+            timestamp: 0,
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn test_price_older_than_threshold_is_stale() {
+        let clock = Arc::new(MockClock::from_millis(1_700_000_000_000));
+        let metrics = Arc::new(Metrics::new());
+        let cache = PriceCache::new(Duration::from_secs(10))
+            .with_clock(clock.clone())
+            .with_metrics(metrics.clone());
+
+        assert!(cache.is_stale("ETHUSDT"));
+        cache.update(&trade("ETHUSDT", 100.0));
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.get("ETHUSDT"), Some(100.0));
+
+        clock.advance(Duration::from_secs(1));
+        assert!(cache.is_stale("ETHUSDT"));
+        assert_eq!(cache.get("ETHUSDT"), None);
+        assert_eq!(
+            metrics.price_cache_stale.with_label_values(&["ETHUSDT"]).get(),
+            1.0
+        );
+
+        cache.update(&trade("ETHUSDT", 101.0));
+        assert_eq!(cache.get("ETHUSDT"), Some(101.0));
+    }
+}
@@ -0,0 +1,75 @@
+//! Rohe MEXC-Responses für Audits: Secrets entfernen und gzip-komprimieren
+use anyhow::Result;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::Value;
+use std::io::{Read, Write};
+
+const REDACTED: &str = "***";
+
+/// Feldnamen (lowercase, ohne `_`), deren Werte nie gespeichert werden
+const SECRET_FIELDS: [&str; 5] = ["apikey", "secretkey", "secret", "signature", "listenkey"];
+
+/// Secret-Felder im JSON maskieren und bekannte Secrets (z.B. den API Key)
+/// auch im Fließtext ersetzen
+pub fn redact_secrets(body: &str, secrets: &[&str]) -> String {
+    let mut redacted = match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        }
+        Err(_) => body.to_string(),
+    };
+    for secret in secrets.iter().filter(|s| !s.is_empty()) {
+        redacted = redacted.replace(secret, REDACTED);
+    }
+    redacted
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                let normalized = key.to_ascii_lowercase().replace('_', "");
+                if SECRET_FIELDS.contains(&normalized.as_str()) {
+                    *field = Value::from(REDACTED);
+                } else {
+                    redact_value(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+pub fn compress(body: &str) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+pub fn decompress(bytes: &[u8]) -> Result<String> {
+    let mut body = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut body)?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_and_compress_round_trip() {
+        let body = r#"{"orderId":"1","apiKey":"key-123","nested":{"signature":"abc"},"msg":"key key-123"}"#;
+        let redacted = redact_secrets(body, &["key-123"]);
+        assert!(!redacted.contains("key-123"));
+        assert!(!redacted.contains("abc"));
+        assert!(redacted.contains(r#""orderId":"1""#));
+
+        let compressed = compress(&redacted).unwrap();
+        assert_eq!(decompress(&compressed).unwrap(), redacted);
+        assert_eq!(redact_secrets("plain key-123", &["key-123", ""]), "plain ***");
+    }
+}
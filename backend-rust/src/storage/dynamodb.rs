@@ -1,23 +1,231 @@
-use crate::storage::models::{CalendarEventItem, OrderItem, PositionItem};
+use crate::mexc::{Credentials, TradeFill};
+use crate::storage::export::ExportBundle;
+use crate::storage::models::{
+    closed_key_time, ttl_from, CalendarEventItem, OrderConfirmationItem, OrderItem, OrderStatus, PositionItem,
+    RawResponseItem,
+};
+use crate::storage::audit;
+use crate::utils::{crypto, FieldCipher, StartupRetry};
 use anyhow::{anyhow, Result};
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, PutRequest, ReturnValue, WriteRequest};
+use aws_sdk_dynamodb::error::{DisplayErrorContext, SdkError};
+use aws_sdk_dynamodb::primitives::Blob;
 use aws_sdk_dynamodb::Client;
-use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Partition Key für globale (nicht user-gebundene) Items
+pub const SYSTEM_PARTITION: &str = "SYSTEM";
+
+/// Max. Items pro BatchWriteItem (DynamoDB Limit)
+const BATCH_WRITE_LIMIT: usize = 25;
+/// Versuche für unverarbeitete Items eines Batches
+const BATCH_WRITE_ATTEMPTS: u32 = 5;
+
+/// GSI über `data_type` + `sk` (alle Items eines Typs, user-übergreifend)
+const DATA_TYPE_INDEX: &str = "data_type-index";
+/// Sparse GSI über `mexc_order_id` (nur Orders, die MEXC bereits kennt)
+const MEXC_ORDER_INDEX: &str = "mexc_order_id-index";
+
+/// Verhalten von Writes wenn eine Failover-Region konfiguriert ist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WritePolicy {
+    /// Nur Primary-Region
+    #[default]
+    Primary,
+    /// Bei Netzwerk-/5xx-Fehlern der Primary in die Failover-Region schreiben
+    Failover,
+    /// Immer in beide Regionen schreiben
+    DualWrite,
+}
+
+impl std::str::FromStr for WritePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "primary" => Ok(WritePolicy::Primary),
+            "failover" => Ok(WritePolicy::Failover),
+            "dual" | "dual_write" => Ok(WritePolicy::DualWrite),
+            other => Err(format!("unknown write policy: {}", other)),
+        }
+    }
+}
+
+/// Lesekonsistenz pro Call. `Strong` sieht eigene Writes sofort, kostet aber
+/// doppelte Read Capacity und gilt nur für die Primary-Region (ein Failover-Read
+/// aus der Replica ist wieder eventual) -> nur wo direkt nach Writes gelesen wird.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadConsistency {
+    #[default]
+    Eventual,
+    Strong,
+}
+
+impl ReadConsistency {
+    pub fn is_strong(self) -> bool {
+        self == ReadConsistency::Strong
+    }
+}
 
 /// DynamoDB Storage Layer
 pub struct DynamoDBStore {
     client: Client,
     table_name: String,
+    /// Client der replizierten Tabelle in der Failover-Region
+    secondary: Option<Client>,
+    write_policy: WritePolicy,
+    /// Verschlüsselung sensibler Attribute (User-Credentials)
+    cipher: Option<Arc<FieldCipher>>,
+    /// Neue Calendar Events beim Einlesen per BatchWriteItem schreiben (CALENDAR_BATCH_WRITES)
+    calendar_batch_writes: bool,
 }
 
 impl DynamoDBStore {
-    /// Erstelle neue DynamoDB Store Instanz
+    /// Erstelle neue DynamoDB Store Instanz; prüft, dass die Tabelle erreichbar ist
     pub async fn new(table_name: String) -> Result<Self> {
         let config = aws_config::load_from_env().await;
         let client = Client::new(&config);
 
-        Ok(Self { client, table_name })
+        let store = Self::from_client(client, table_name);
+        store.check_table().await?;
+        Ok(store)
+    }
+
+    /// Tabelle per DescribeTable prüfen; Throttling beim Start wird mit
+    /// Backoff wiederholt statt den Start abzubrechen
+    pub async fn check_table(&self) -> Result<()> {
+        StartupRetry::default()
+            .run(&format!("DynamoDB table '{}'", self.table_name), || {
+                self.client.describe_table().table_name(&self.table_name).send()
+            })
+            .await
+            .map_err(|e| {
+                anyhow!("DynamoDB table '{}' not accessible: {}", self.table_name, DisplayErrorContext(&e))
+            })?;
+        Ok(())
+    }
+
+    /// Erstelle Store mit bereits konfiguriertem Client (z.B. eigener Endpoint)
+    pub fn from_client(client: Client, table_name: String) -> Self {
+        Self {
+            client,
+            table_name,
+            secondary: None,
+            write_policy: WritePolicy::Primary,
+            cipher: None,
+            calendar_batch_writes: true,
+        }
+    }
+
+    /// Batching beim Einlesen von Calendar Events ein-/ausschalten
+    pub fn with_calendar_batch_writes(mut self, enabled: bool) -> Self {
+        self.calendar_batch_writes = enabled;
+        self
+    }
+
+    /// Schlüssel für verschlüsselte Attribute setzen (ohne Key werden keine
+    /// Credentials gespeichert, übrige sensible Felder bleiben Klartext)
+    pub fn with_cipher(mut self, cipher: Arc<FieldCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn cipher(&self) -> Result<&FieldCipher> {
+        self.cipher
+            .as_deref()
+            .ok_or_else(|| anyhow!("CREDENTIALS_ENCRYPTION_KEY not configured"))
+    }
+
+    /// Sensibles Attribut verschlüsseln, falls ein Key konfiguriert ist
+    fn seal_field(&self, value: &str) -> Result<String> {
+        match &self.cipher {
+            Some(cipher) => cipher.seal(value),
+            None => Ok(value.to_string()),
+        }
+    }
+
+    /// Sensibles Attribut lesen; Klartext (Altbestand, kein Key) bleibt unverändert
+    fn open_field(&self, value: String) -> Result<String> {
+        if crypto::is_sealed(&value) {
+            self.cipher()?.open(&value)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Client für die Failover-Region aus der Standard-AWS-Config
+    pub async fn region_client(region: &str) -> Client {
+        let config = aws_config::from_env()
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await;
+        Client::new(&config)
+    }
+
+    /// Sekundären Client (replizierte Tabelle) für Read-Failover aktivieren
+    pub fn with_failover(mut self, secondary: Client, write_policy: WritePolicy) -> Self {
+        self.secondary = Some(secondary);
+        self.write_policy = write_policy;
+        self
+    }
+
+    /// Lesen mit Fallback auf die Failover-Region bei Netzwerk-/5xx-Fehlern
+    async fn read<T, E, F, Fut>(&self, op: F) -> Result<T, SdkError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E>>>,
+    {
+        match (op(self.client.clone()).await, &self.secondary) {
+            (Err(e), Some(secondary)) if is_failover_error(&e) => {
+                tracing::warn!(
+                    "DynamoDB primary read failed, using failover region: {}",
+                    DisplayErrorContext(&e)
+                );
+                op(secondary.clone()).await
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Schreiben gemäß `write_policy` (bedingte Writes gehen immer nur an die Primary)
+    async fn write<T, E, F, Fut>(&self, op: F) -> Result<T, SdkError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E>>>,
+    {
+        let Some(secondary) = &self.secondary else {
+            return op(self.client.clone()).await;
+        };
+
+        match self.write_policy {
+            WritePolicy::Primary => op(self.client.clone()).await,
+            WritePolicy::Failover => self.read(op).await,
+            WritePolicy::DualWrite => {
+                let primary = op(self.client.clone()).await;
+                let replica = op(secondary.clone()).await;
+                match (primary, replica) {
+                    (Err(e), Ok(replica)) if is_failover_error(&e) => {
+                        tracing::warn!(
+                            "DynamoDB primary write failed, kept failover write: {}",
+                            DisplayErrorContext(&e)
+                        );
+                        Ok(replica)
+                    }
+                    (primary, Err(e)) => {
+                        tracing::warn!("DynamoDB failover write failed: {}", DisplayErrorContext(&e));
+                        primary
+                    }
+                    (primary, Ok(_)) => primary,
+                }
+            }
+        }
     }
 
     /// Speichere Order in DynamoDB
@@ -47,6 +255,9 @@ impl DynamoDBStore {
             "filled_qty".to_string(),
             AttributeValue::N(order.filled_qty.to_string()),
         );
+        if let Some(fill_price) = order.fill_price {
+            item.insert("fill_price".to_string(), AttributeValue::N(fill_price.to_string()));
+        }
         item.insert("status".to_string(), AttributeValue::S(order.status.clone()));
         item.insert(
             "timestamp".to_string(),
@@ -68,36 +279,96 @@ impl DynamoDBStore {
             );
         }
         if let Some(error) = &order.error_message {
-            item.insert("error_message".to_string(), AttributeValue::S(error.clone()));
+            // Kann rohe Upstream-Antworten enthalten -> verschlüsselt
+            item.insert("error_message".to_string(), AttributeValue::S(self.seal_field(error)?));
+        }
+
+        if let Some(code) = &order.rejection_code {
+            item.insert("rejection_code".to_string(), AttributeValue::S(code.clone()));
+        }
+        if let Some(category) = &order.rejection_category {
+            item.insert("rejection_category".to_string(), AttributeValue::S(category.clone()));
+        }
+
+        if order.reduce_only {
+            item.insert("reduce_only".to_string(), AttributeValue::Bool(true));
+        }
+        if order.post_only {
+            item.insert("post_only".to_string(), AttributeValue::Bool(true));
+        }
+        if order.paper {
+            item.insert("paper".to_string(), AttributeValue::Bool(true));
+        }
+        if order.recovered {
+            item.insert("recovered".to_string(), AttributeValue::Bool(true));
+        }
+        if let Some(quote_qty) = order.quote_order_qty {
+            item.insert(
+                "quote_order_qty".to_string(),
+                AttributeValue::N(quote_qty.to_string()),
+            );
+        }
+        if let Some(event_id) = &order.event_id {
+            item.insert("event_id".to_string(), AttributeValue::S(event_id.clone()));
+        }
+        if let Some(replaces) = &order.replaces {
+            item.insert("replaces".to_string(), AttributeValue::S(replaces.clone()));
+        }
+        if let Some(replaced_by) = &order.replaced_by {
+            item.insert("replaced_by".to_string(), AttributeValue::S(replaced_by.clone()));
+        }
+        if let Some(note) = &order.note {
+            item.insert("note".to_string(), AttributeValue::S(note.clone()));
+        }
+        if let Some(account) = &order.account {
+            item.insert("account".to_string(), AttributeValue::S(account.clone()));
+        }
+        if let Some(strategy) = &order.strategy {
+            item.insert("strategy".to_string(), AttributeValue::S(strategy.clone()));
+        }
+        if let Some(origin) = order.chase_origin_price {
+            item.insert("chase_origin_price".to_string(), AttributeValue::N(origin.to_string()));
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            item.insert("client_order_id".to_string(), AttributeValue::S(client_order_id.clone()));
         }
 
         item.insert("ttl".to_string(), AttributeValue::N(order.ttl.to_string()));
         item.insert("data_type".to_string(), AttributeValue::S("ORDER".to_string()));
 
-        self.client
-            .put_item()
-            .table_name(&self.table_name)
-            .set_item(Some(item))
-            .send()
-            .await?;
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
 
         Ok(())
     }
 
     /// Rufe Order nach user_id und order_id ab
-    pub async fn get_order(&self, user_id: &str, order_id: &str) -> Result<Option<OrderItem>> {
-        let response = self
-            .client
-            .query()
-            .table_name(&self.table_name)
-            .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
-            .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
-            .expression_attribute_values(
-                ":sk".to_string(),
-                AttributeValue::S(format!("ORDER#{}#", order_id)),
-            )
-            .send()
-            .await?;
+    pub async fn get_order(
+        &self,
+        user_id: &str,
+        order_id: &str,
+        consistency: ReadConsistency,
+    ) -> Result<Option<OrderItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .consistent_read(consistency.is_strong())
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(
+                    ":sk".to_string(),
+                    AttributeValue::S(format!("ORDER#{}#", order_id)),
+                )
+                .send()
+        })
+        .await?;
 
         if let Some(items) = response.items {
             if let Some(item) = items.first() {
@@ -114,18 +385,19 @@ impl DynamoDBStore {
         user_id: &str,
         status: &str,
     ) -> Result<Vec<OrderItem>> {
-        let response = self
-            .client
-            .query()
-            .table_name(&self.table_name)
-            .key_condition_expression("user_id = :uid")
-            .filter_expression("begins_with(sk, :sk) AND #status = :status")
-            .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
-            .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
-            .expression_attribute_values(":status".to_string(), AttributeValue::S(status.to_string()))
-            .expression_attribute_names("#status".to_string(), "status".to_string())
-            .send()
-            .await?;
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid")
+                .filter_expression("begins_with(sk, :sk) AND #status = :status")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
+                .expression_attribute_values(":status".to_string(), AttributeValue::S(status.to_string()))
+                .expression_attribute_names("#status".to_string(), "status".to_string())
+                .send()
+        })
+        .await?;
 
         let mut orders = Vec::new();
         if let Some(items) = response.items {
@@ -137,15 +409,293 @@ impl DynamoDBStore {
         Ok(orders)
     }
 
+    /// Query offene Orders eines Users für ein Symbol
+    pub async fn query_open_orders_by_symbol(
+        &self,
+        user_id: &str,
+        symbol: &str,
+    ) -> Result<Vec<OrderItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .filter_expression("#status = :status AND symbol = :symbol")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
+                .expression_attribute_values(
+                    ":status".to_string(),
+                    AttributeValue::S(OrderStatus::Open.as_str().to_string()),
+                )
+                .expression_attribute_values(":symbol".to_string(), AttributeValue::S(symbol.to_string()))
+                .expression_attribute_names("#status".to_string(), "status".to_string())
+                .send()
+        })
+        .await?;
+
+        response
+            .items()
+            .iter()
+            .map(|item| self.item_to_order(item))
+            .collect()
+    }
+
+    /// Query alle Orders eines Users für ein Symbol (beliebiger Status, seitenweise)
+    pub async fn query_orders_by_symbol(&self, user_id: &str, symbol: &str) -> Result<Vec<OrderItem>> {
+        let mut orders = Vec::new();
+        let mut start_key = None;
+
+        loop {
+            let response = self.read(|client| {
+                client
+                    .query()
+                    .table_name(&self.table_name)
+                    .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                    .filter_expression("symbol = :symbol")
+                    .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                    .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
+                    .expression_attribute_values(":symbol".to_string(), AttributeValue::S(symbol.to_string()))
+                    .set_exclusive_start_key(start_key.clone())
+                    .send()
+            })
+            .await?;
+
+            for item in response.items() {
+                orders.push(self.item_to_order(item)?);
+            }
+
+            start_key = response.last_evaluated_key;
+            if start_key.is_none() {
+                break;
+            }
+        }
+
+        Ok(orders)
+    }
+
+    /// Order zu einer MEXC Order-ID suchen, user-übergreifend über den mexc_order_id-Index
+    pub async fn find_order_by_mexc_id(&self, mexc_order_id: &str) -> Result<Option<OrderItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .index_name(MEXC_ORDER_INDEX)
+                .key_condition_expression("mexc_order_id = :mexc_id")
+                .expression_attribute_values(":mexc_id".to_string(), AttributeValue::S(mexc_order_id.to_string()))
+                .limit(1)
+                .send()
+        })
+        .await?;
+
+        response
+            .items()
+            .first()
+            .map(|item| self.item_to_order(item))
+            .transpose()
+    }
+
+    /// Query alle Orders eines Users, die ein Calendar Event ausgelöst hat
+    pub async fn query_orders_by_event(
+        &self,
+        user_id: &str,
+        event_id: &str,
+    ) -> Result<Vec<OrderItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .filter_expression("event_id = :event_id")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
+                .expression_attribute_values(":event_id".to_string(), AttributeValue::S(event_id.to_string()))
+                .send()
+        })
+        .await?;
+
+        response
+            .items()
+            .iter()
+            .map(|item| self.item_to_order(item))
+            .collect()
+    }
+
+    /// User mit mindestens einer offenen Order (user-übergreifend über den
+    /// data_type-Index, seitenweise), z.B. für den Order Monitor nach einem Neustart
+    pub async fn query_users_with_open_orders(&self) -> Result<Vec<String>> {
+        let mut users = HashSet::new();
+        let mut start_key = None;
+
+        loop {
+            let response = self.read(|client| {
+                client
+                    .query()
+                    .table_name(&self.table_name)
+                    .index_name(DATA_TYPE_INDEX)
+                    .key_condition_expression("data_type = :type AND begins_with(sk, :sk)")
+                    .filter_expression("#status = :status")
+                    .projection_expression("user_id")
+                    .expression_attribute_values(":type".to_string(), AttributeValue::S("ORDER".to_string()))
+                    .expression_attribute_values(":sk".to_string(), AttributeValue::S("ORDER#".to_string()))
+                    .expression_attribute_values(
+                        ":status".to_string(),
+                        AttributeValue::S(OrderStatus::Open.as_str().to_string()),
+                    )
+                    .expression_attribute_names("#status".to_string(), "status".to_string())
+                    .set_exclusive_start_key(start_key.clone())
+                    .send()
+            })
+            .await?;
+
+            for item in response.items() {
+                if let Some(AttributeValue::S(user_id)) = item.get("user_id") {
+                    users.insert(user_id.clone());
+                }
+            }
+
+            start_key = response.last_evaluated_key;
+            if start_key.is_none() {
+                break;
+            }
+        }
+
+        let mut users: Vec<String> = users.into_iter().collect();
+        users.sort();
+        Ok(users)
+    }
+
+    /// Speichere einen Fill (idempotent über Zeit + Trade-ID)
+    pub async fn put_fill(&self, user_id: &str, fill: &TradeFill) -> Result<()> {
+        let mut item = HashMap::new();
+        item.insert("user_id".to_string(), AttributeValue::S(user_id.to_string()));
+        item.insert(
+            "sk".to_string(),
+            AttributeValue::S(format!("FILL#{}#{}", fill.time, fill.trade_id)),
+        );
+        item.insert("symbol".to_string(), AttributeValue::S(fill.symbol.clone()));
+        item.insert("trade_id".to_string(), AttributeValue::S(fill.trade_id.clone()));
+        item.insert("order_id".to_string(), AttributeValue::S(fill.order_id.clone()));
+        item.insert("price".to_string(), AttributeValue::N(fill.price.to_string()));
+        item.insert("quantity".to_string(), AttributeValue::N(fill.quantity.to_string()));
+        item.insert("quote_qty".to_string(), AttributeValue::N(fill.quote_qty.to_string()));
+        item.insert("fee".to_string(), AttributeValue::N(fill.fee.to_string()));
+        item.insert("fee_asset".to_string(), AttributeValue::S(fill.fee_asset.clone()));
+        item.insert("time".to_string(), AttributeValue::N(fill.time.to_string()));
+        item.insert("is_buyer".to_string(), AttributeValue::Bool(fill.is_buyer));
+        item.insert("is_maker".to_string(), AttributeValue::Bool(fill.is_maker));
+        item.insert(
+            "ttl".to_string(),
+            AttributeValue::N(ttl_from(chrono::Utc::now()).to_string()),
+        );
+        item.insert("data_type".to_string(), AttributeValue::S("FILL".to_string()));
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gespeicherte Fills eines Users, optional nur für ein Symbol
+    pub async fn query_fills(&self, user_id: &str, symbol: Option<&str>) -> Result<Vec<TradeFill>> {
+        let response = self.read(|client| {
+            let query = client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("FILL#".to_string()));
+            match symbol {
+                Some(symbol) => query
+                    .filter_expression("symbol = :symbol")
+                    .expression_attribute_values(":symbol".to_string(), AttributeValue::S(symbol.to_string()))
+                    .send(),
+                None => query.send(),
+            }
+        })
+        .await?;
+
+        response
+            .items()
+            .iter()
+            .map(|item| self.item_to_fill(item))
+            .collect()
+    }
+
+    /// Gespeicherte Fills eines Users mit `from <= time <= to` (Unix ms)
+    pub async fn query_fills_between(&self, user_id: &str, from: i64, to: i64) -> Result<Vec<TradeFill>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid AND sk BETWEEN :from AND :to")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":from".to_string(), AttributeValue::S(format!("FILL#{}", from)))
+                .expression_attribute_values(":to".to_string(), AttributeValue::S(format!("FILL#{}#~", to)))
+                .send()
+        })
+        .await?;
+
+        response
+            .items()
+            .iter()
+            .map(|item| self.item_to_fill(item))
+            .collect()
+    }
+
     /// Speichere Position in DynamoDB
     pub async fn put_position(&self, position: &PositionItem) -> Result<()> {
+        let item = self.position_to_item(position, position.sort_key(), "POSITION");
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Speichere geschlossene Position zusätzlich unter CLOSED#{close_time}#{id}
+    pub async fn put_closed_position(&self, position: &PositionItem) -> Result<()> {
+        let sort_key = position
+            .closed_sort_key()
+            .ok_or_else(|| anyhow!("Position {} is not closed", position.position_id))?;
+        let item = self.position_to_item(position, sort_key, "CLOSED_POSITION");
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    fn position_to_item(
+        &self,
+        position: &PositionItem,
+        sort_key: String,
+        data_type: &str,
+    ) -> HashMap<String, AttributeValue> {
         let mut item = HashMap::new();
 
         item.insert(
             "user_id".to_string(),
             AttributeValue::S(position.partition_key()),
         );
-        item.insert("sk".to_string(), AttributeValue::S(position.sort_key()));
+        item.insert("sk".to_string(), AttributeValue::S(sort_key));
         item.insert(
             "position_id".to_string(),
             AttributeValue::S(position.position_id.clone()),
@@ -182,36 +732,120 @@ impl DynamoDBStore {
             "updated_at".to_string(),
             AttributeValue::S(position.updated_at.clone()),
         );
+        if let Some(closed_at) = position.closed_at {
+            item.insert(
+                "closed_at".to_string(),
+                AttributeValue::N(closed_at.to_string()),
+            );
+        }
+        if let Some(stop_price) = position.stop_price {
+            item.insert(
+                "stop_price".to_string(),
+                AttributeValue::N(stop_price.to_string()),
+            );
+        }
+        if let Some(note) = &position.note {
+            item.insert("note".to_string(), AttributeValue::S(note.clone()));
+        }
+        if let Some(account) = &position.account {
+            item.insert("account".to_string(), AttributeValue::S(account.clone()));
+        }
+        if let Some(strategy) = &position.strategy {
+            item.insert("strategy".to_string(), AttributeValue::S(strategy.clone()));
+        }
+        if position.add_ons > 0 {
+            item.insert("add_ons".to_string(), AttributeValue::N(position.add_ons.to_string()));
+        }
         item.insert("ttl".to_string(), AttributeValue::N(position.ttl.to_string()));
         item.insert(
             "data_type".to_string(),
-            AttributeValue::S("POSITION".to_string()),
+            AttributeValue::S(data_type.to_string()),
         );
 
-        self.client
-            .put_item()
-            .table_name(&self.table_name)
-            .set_item(Some(item))
-            .send()
-            .await?;
+        item
+    }
 
-        Ok(())
+    /// Rufe Position nach user_id und position_id ab
+    pub async fn get_position(
+        &self,
+        user_id: &str,
+        position_id: &str,
+        consistency: ReadConsistency,
+    ) -> Result<Option<PositionItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .consistent_read(consistency.is_strong())
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .filter_expression("position_id = :pid")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("POSITION#".to_string()))
+                .expression_attribute_values(":pid".to_string(), AttributeValue::S(position_id.to_string()))
+                .send()
+        })
+        .await?;
+
+        if let Some(items) = response.items {
+            if let Some(item) = items.first() {
+                return Ok(Some(self.item_to_position(item)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Query geschlossene Positionen mit Schließzeit im Bereich [from, to] (ms)
+    pub async fn query_closed_positions(
+        &self,
+        user_id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<PositionItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid AND sk BETWEEN :from AND :to")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(
+                    ":from".to_string(),
+                    AttributeValue::S(format!("CLOSED#{}", closed_key_time(from))),
+                )
+                // '~' sortiert nach allen IDs, damit Einträge mit close_time == to enthalten sind
+                .expression_attribute_values(
+                    ":to".to_string(),
+                    AttributeValue::S(format!("CLOSED#{}#~", closed_key_time(to))),
+                )
+                .send()
+        })
+        .await?;
+
+        let mut positions = Vec::new();
+        if let Some(items) = response.items {
+            for item in items {
+                positions.push(self.item_to_position(&item)?);
+            }
+        }
+
+        Ok(positions)
     }
 
     /// Query alle offenen Positionen für einen User
     pub async fn query_open_positions(&self, user_id: &str) -> Result<Vec<PositionItem>> {
-        let response = self
-            .client
-            .query()
-            .table_name(&self.table_name)
-            .key_condition_expression("user_id = :uid")
-            .filter_expression("begins_with(sk, :sk) AND #status = :status")
-            .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
-            .expression_attribute_values(":sk".to_string(), AttributeValue::S("POSITION#".to_string()))
-            .expression_attribute_values(":status".to_string(), AttributeValue::S("open".to_string()))
-            .expression_attribute_names("#status".to_string(), "status".to_string())
-            .send()
-            .await?;
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid")
+                .filter_expression("begins_with(sk, :sk) AND #status = :status")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("POSITION#".to_string()))
+                .expression_attribute_values(":status".to_string(), AttributeValue::S("open".to_string()))
+                .expression_attribute_names("#status".to_string(), "status".to_string())
+                .send()
+        })
+        .await?;
 
         let mut positions = Vec::new();
         if let Some(items) = response.items {
@@ -225,6 +859,81 @@ impl DynamoDBStore {
 
     /// Speichere Calendar Event
     pub async fn put_calendar_event(&self, event: &CalendarEventItem) -> Result<()> {
+        let item = self.calendar_event_item(event)?;
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Viele Calendar Events per BatchWriteItem schreiben (je 25, unverarbeitete Items
+    /// mit Backoff erneut). Doppelte Event-Keys im Aufruf werden auf den letzten Stand
+    /// reduziert; gibt die Anzahl geschriebener Events zurück.
+    pub async fn batch_put_calendar_events(&self, events: &[CalendarEventItem]) -> Result<usize> {
+        let mut latest: HashMap<(String, String), &CalendarEventItem> = HashMap::new();
+        let mut keys = Vec::new();
+        for event in events {
+            let key = (event.partition_key(), event.sort_key());
+            if latest.insert(key.clone(), event).is_none() {
+                keys.push(key);
+            }
+        }
+        let items = keys
+            .iter()
+            .map(|key| self.calendar_event_item(latest[key]))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.batch_put_items(&items).await?;
+        tracing::info!("Batch-wrote {} calendar events", items.len());
+        Ok(items.len())
+    }
+
+    /// Erkannte Listings einlesen: bereits gespeicherte Launches (gleicher Event-Key)
+    /// per Upsert, damit Status und Versuche erhalten bleiben; neue per Batch
+    /// (bzw. einzeln, wenn CALENDAR_BATCH_WRITES aus ist)
+    pub async fn ingest_calendar_events(&self, events: &[CalendarEventItem]) -> Result<usize> {
+        if !self.calendar_batch_writes {
+            for event in events {
+                self.upsert_calendar_event(event).await?;
+            }
+            return Ok(events.len());
+        }
+
+        let mut by_user: HashMap<&str, Vec<&CalendarEventItem>> = HashMap::new();
+        for event in events {
+            by_user.entry(event.user_id.as_str()).or_default().push(event);
+        }
+
+        let mut new_events = Vec::new();
+        for (user_id, events) in by_user {
+            let from = events.iter().map(|e| e.launch_time).min().unwrap_or_default();
+            let to = events.iter().map(|e| e.launch_time).max().unwrap_or_default();
+            let existing: HashSet<String> = self
+                .query_calendar_events_by_time(user_id, from, to)
+                .await?
+                .into_iter()
+                .map(|e| e.event_id)
+                .collect();
+            for event in events {
+                if existing.contains(&event.event_id) {
+                    self.upsert_calendar_event(event).await?;
+                } else {
+                    new_events.push(event.clone());
+                }
+            }
+        }
+        self.batch_put_calendar_events(&new_events).await?;
+        Ok(events.len())
+    }
+
+    fn calendar_event_item(&self, event: &CalendarEventItem) -> Result<HashMap<String, AttributeValue>> {
         let mut item = HashMap::new();
 
         item.insert(
@@ -270,22 +979,99 @@ impl DynamoDBStore {
             );
         }
 
+        item.insert(
+            "attempts".to_string(),
+            AttributeValue::N(event.attempts.to_string()),
+        );
+        if let Some(meta) = &event.pattern_meta {
+            item.insert("pattern_meta".to_string(), AttributeValue::S(serde_json::to_string(meta)?));
+        }
         item.insert("ttl".to_string(), AttributeValue::N(event.ttl.to_string()));
         item.insert(
             "data_type".to_string(),
             AttributeValue::S("CALENDAR".to_string()),
         );
+        Ok(item)
+    }
 
-        self.client
-            .put_item()
-            .table_name(&self.table_name)
-            .set_item(Some(item))
+    /// Calendar Event idempotent einlesen: existiert der Launch (gleicher Event-Key)
+    /// bereits, werden nur die Erkennungsdaten aktualisiert; Status, Versuche und
+    /// ausgeführte Orders bleiben erhalten
+    pub async fn upsert_calendar_event(&self, event: &CalendarEventItem) -> Result<()> {
+        let mut expression = "SET event_id = :event_id, token_name = :token_name, symbol = :symbol, \
+             launch_time = :launch_time, detected_pattern = :pattern, confidence = :confidence, \
+             data_type = :data_type, created_at = if_not_exists(created_at, :created_at), \
+             #status = if_not_exists(#status, :status), attempts = if_not_exists(attempts, :attempts), \
+             #ttl = if_not_exists(#ttl, :ttl)"
+            .to_string();
+        let pattern_meta = event.pattern_meta.as_ref().map(serde_json::to_string).transpose()?;
+        if pattern_meta.is_some() {
+            expression.push_str(", pattern_meta = :pattern_meta");
+        }
+
+        self.write(|client| {
+            let request = client
+                .update_item()
+                .table_name(&self.table_name)
+                .key("user_id", AttributeValue::S(event.partition_key()))
+                .key("sk", AttributeValue::S(event.sort_key()))
+                .update_expression(expression.clone())
+                .expression_attribute_names("#status", "status")
+                .expression_attribute_names("#ttl", "ttl")
+                .expression_attribute_values(":event_id", AttributeValue::S(event.event_id.clone()))
+                .expression_attribute_values(":token_name", AttributeValue::S(event.token_name.clone()))
+                .expression_attribute_values(":symbol", AttributeValue::S(event.symbol.clone()))
+                .expression_attribute_values(":launch_time", AttributeValue::N(event.launch_time.to_string()))
+                .expression_attribute_values(":pattern", AttributeValue::S(event.detected_pattern.clone()))
+                .expression_attribute_values(":confidence", AttributeValue::N(event.confidence.to_string()))
+                .expression_attribute_values(":data_type", AttributeValue::S("CALENDAR".to_string()))
+                .expression_attribute_values(":created_at", AttributeValue::S(event.created_at.clone()))
+                .expression_attribute_values(":status", AttributeValue::S(event.status.clone()))
+                .expression_attribute_values(":attempts", AttributeValue::N(event.attempts.to_string()))
+                .expression_attribute_values(":ttl", AttributeValue::N(event.ttl.to_string()));
+            match &pattern_meta {
+                Some(meta) => request.expression_attribute_values(":pattern_meta", AttributeValue::S(meta.clone())),
+                None => request,
+            }
             .send()
-            .await?;
+        })
+        .await?;
 
         Ok(())
     }
 
+    /// Bedingter Status-Übergang eines Calendar Events (inkl. Versuchszähler).
+    /// Gibt `false` zurück wenn der aktuelle Status nicht `from` ist.
+    pub async fn transition_calendar_event_status(
+        &self,
+        event: &CalendarEventItem,
+        from: &str,
+        to: &str,
+    ) -> Result<bool> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("user_id", AttributeValue::S(event.partition_key()))
+            .key("sk", AttributeValue::S(event.sort_key()))
+            .update_expression("SET #status = :to, attempts = :attempts")
+            .condition_expression("#status = :from")
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":from", AttributeValue::S(from.to_string()))
+            .expression_attribute_values(":to", AttributeValue::S(to.to_string()))
+            .expression_attribute_values(":attempts", AttributeValue::N(event.attempts.to_string()))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) => match e.into_service_error() {
+                UpdateItemError::ConditionalCheckFailedException(_) => Ok(false),
+                other => Err(other.into()),
+            },
+        }
+    }
+
     /// Query Calendar Events innerhalb eines Zeitfensters
     pub async fn query_calendar_events_by_time(
         &self,
@@ -293,19 +1079,20 @@ impl DynamoDBStore {
         start_time: i64,
         end_time: i64,
     ) -> Result<Vec<CalendarEventItem>> {
-        let response = self
-            .client
-            .query()
-            .table_name(&self.table_name)
-            .key_condition_expression("user_id = :uid")
-            .filter_expression("begins_with(sk, :sk) AND #launch >= :start AND #launch <= :end")
-            .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
-            .expression_attribute_values(":sk".to_string(), AttributeValue::S("CALENDAR#".to_string()))
-            .expression_attribute_values(":start".to_string(), AttributeValue::N(start_time.to_string()))
-            .expression_attribute_values(":end".to_string(), AttributeValue::N(end_time.to_string()))
-            .expression_attribute_names("#launch".to_string(), "launch_time".to_string())
-            .send()
-            .await?;
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .key_condition_expression("user_id = :uid")
+                .filter_expression("begins_with(sk, :sk) AND #launch >= :start AND #launch <= :end")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("CALENDAR#".to_string()))
+                .expression_attribute_values(":start".to_string(), AttributeValue::N(start_time.to_string()))
+                .expression_attribute_values(":end".to_string(), AttributeValue::N(end_time.to_string()))
+                .expression_attribute_names("#launch".to_string(), "launch_time".to_string())
+                .send()
+        })
+        .await?;
 
         let mut events = Vec::new();
         if let Some(items) = response.items {
@@ -317,7 +1104,326 @@ impl DynamoDBStore {
         Ok(events)
     }
 
+    /// Calendar Event über die Event-ID (konsistent gelesen, z.B. für manuelle Overrides)
+    pub async fn get_calendar_event(&self, user_id: &str, event_id: &str) -> Result<Option<CalendarEventItem>> {
+        let response = self.read(|client| {
+            client
+                .query()
+                .table_name(&self.table_name)
+                .consistent_read(true)
+                .key_condition_expression("user_id = :uid AND begins_with(sk, :sk)")
+                .filter_expression("event_id = :eid")
+                .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                .expression_attribute_values(":sk".to_string(), AttributeValue::S("CALENDAR#".to_string()))
+                .expression_attribute_values(":eid".to_string(), AttributeValue::S(event_id.to_string()))
+                .send()
+        })
+        .await?;
+
+        match response.items.as_deref().and_then(|items| items.first()) {
+            Some(item) => Ok(Some(self.item_to_calendar_event(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Lade die globale Symbol-Blacklist (Item SYSTEM / SETTINGS#symbol_blacklist)
+    pub async fn get_symbol_blacklist(&self) -> Result<Vec<String>> {
+        let response = self.read(|client| {
+            client
+                .get_item()
+                .table_name(&self.table_name)
+                .key("user_id", AttributeValue::S(SYSTEM_PARTITION.to_string()))
+                .key("sk", AttributeValue::S("SETTINGS#symbol_blacklist".to_string()))
+                .send()
+        })
+        .await?;
+
+        Ok(response
+            .item
+            .and_then(|item| self.get_optional_string_list(&item, "patterns"))
+            .unwrap_or_default())
+    }
+
+    /// User-spezifisches Open-Order-Limit je Symbol (Item user / SETTINGS#max_open_orders_per_symbol)
+    pub async fn get_max_open_orders_per_symbol(&self, user_id: &str) -> Result<Option<u32>> {
+        let response = self.read(|client| {
+            client
+                .get_item()
+                .table_name(&self.table_name)
+                .key("user_id", AttributeValue::S(user_id.to_string()))
+                .key("sk", AttributeValue::S("SETTINGS#max_open_orders_per_symbol".to_string()))
+                .send()
+        })
+        .await?;
+
+        Ok(response
+            .item
+            .and_then(|item| self.get_optional_number(&item, "value"))
+            .map(|v| v as u32))
+    }
+
+    /// MEXC Credentials eines Users verschlüsselt speichern (user / CREDENTIALS#mexc)
+    pub async fn put_mexc_credentials(&self, user_id: &str, credentials: &Credentials) -> Result<()> {
+        let cipher = self.cipher()?;
+        let mut item = HashMap::new();
+        item.insert("user_id".to_string(), AttributeValue::S(user_id.to_string()));
+        item.insert("sk".to_string(), AttributeValue::S("CREDENTIALS#mexc".to_string()));
+        item.insert("api_key".to_string(), AttributeValue::S(cipher.seal(&credentials.api_key)?));
+        item.insert("secret_key".to_string(), AttributeValue::S(cipher.seal(&credentials.secret_key)?));
+        item.insert(
+            "updated_at".to_string(),
+            AttributeValue::S(chrono::Utc::now().to_rfc3339()),
+        );
+        item.insert("data_type".to_string(), AttributeValue::S("CREDENTIALS".to_string()));
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Entschlüsselte MEXC Credentials eines Users, falls hinterlegt
+    pub async fn get_mexc_credentials(&self, user_id: &str) -> Result<Option<Credentials>> {
+        let response = self.read(|client| {
+            client
+                .get_item()
+                .table_name(&self.table_name)
+                .key("user_id", AttributeValue::S(user_id.to_string()))
+                .key("sk", AttributeValue::S("CREDENTIALS#mexc".to_string()))
+                .send()
+        })
+        .await?;
+
+        let Some(item) = response.item else {
+            return Ok(None);
+        };
+        let cipher = self.cipher()?;
+        Ok(Some(Credentials {
+            api_key: cipher.open(&self.get_string(&item, "api_key")?)?,
+            secret_key: cipher.open(&self.get_string(&item, "secret_key")?)?,
+        }))
+    }
+
+    /// Reserviere eine Webhook-Delivery-ID (SYSTEM / WEBHOOK_NONCE#id) mit TTL.
+    /// Gibt `false` zurück wenn die ID bereits verarbeitet wurde.
+    pub async fn claim_webhook_nonce(&self, delivery_id: &str, expires_at: i64) -> Result<bool> {
+        let result = self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .item("user_id", AttributeValue::S(SYSTEM_PARTITION.to_string()))
+            .item("sk", AttributeValue::S(format!("WEBHOOK_NONCE#{}", delivery_id)))
+            .item("ttl", AttributeValue::N(expires_at.to_string()))
+            .condition_expression("attribute_not_exists(sk)")
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) => match e.into_service_error() {
+                PutItemError::ConditionalCheckFailedException(_) => Ok(false),
+                other => Err(other.into()),
+            },
+        }
+    }
+
+    /// Zu bestätigende Order speichern; verfällt per TTL
+    pub async fn put_order_confirmation(&self, pending: &OrderConfirmationItem) -> Result<()> {
+        let mut item = HashMap::new();
+        item.insert("user_id".to_string(), AttributeValue::S(SYSTEM_PARTITION.to_string()));
+        item.insert("sk".to_string(), AttributeValue::S(format!("ORDER_CONFIRM#{}", pending.token)));
+        item.insert("owner_id".to_string(), AttributeValue::S(pending.user_id.clone()));
+        item.insert("request".to_string(), AttributeValue::S(pending.request.clone()));
+        item.insert("notional".to_string(), AttributeValue::N(pending.notional.to_string()));
+        item.insert("ttl".to_string(), AttributeValue::N(pending.expires_at.to_string()));
+        item.insert("data_type".to_string(), AttributeValue::S("ORDER_CONFIRM".to_string()));
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Zu bestätigende Order lesen und löschen (einmalig einlösbar).
+    /// Abgelaufene Einträge werden mitgeliefert, da DynamoDB TTL verzögert löscht.
+    pub async fn take_order_confirmation(&self, token: &str) -> Result<Option<OrderConfirmationItem>> {
+        let response = self
+            .client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("user_id", AttributeValue::S(SYSTEM_PARTITION.to_string()))
+            .key("sk", AttributeValue::S(format!("ORDER_CONFIRM#{}", token)))
+            .return_values(ReturnValue::AllOld)
+            .send()
+            .await?;
+
+        let Some(item) = response.attributes else {
+            return Ok(None);
+        };
+        Ok(Some(OrderConfirmationItem {
+            token: token.to_string(),
+            user_id: self.get_string(&item, "owner_id")?,
+            request: self.get_string(&item, "request")?,
+            notional: self.get_number(&item, "notional")?,
+            expires_at: self.get_number(&item, "ttl")? as i64,
+        }))
+    }
+
+    /// Rohe MEXC-Response einer Order gzip-komprimiert speichern; verfällt per TTL
+    pub async fn put_raw_response(&self, raw: &RawResponseItem) -> Result<()> {
+        let mut item = HashMap::new();
+        item.insert("user_id".to_string(), AttributeValue::S(raw.user_id.clone()));
+        item.insert("sk".to_string(), AttributeValue::S(format!("RAW#{}", raw.order_id)));
+        item.insert("order_id".to_string(), AttributeValue::S(raw.order_id.clone()));
+        item.insert("http_status".to_string(), AttributeValue::N(raw.http_status.to_string()));
+        item.insert("body".to_string(), AttributeValue::B(Blob::new(audit::compress(&raw.body)?)));
+        item.insert("recorded_at".to_string(), AttributeValue::N(raw.recorded_at.to_string()));
+        item.insert("ttl".to_string(), AttributeValue::N(raw.expires_at.to_string()));
+        item.insert("data_type".to_string(), AttributeValue::S("RAW_RESPONSE".to_string()));
+
+        self.write(|client| {
+            client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(item.clone()))
+                .send()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gespeicherte Roh-Response einer Order (entpackt), falls vorhanden
+    pub async fn get_raw_response(&self, user_id: &str, order_id: &str) -> Result<Option<RawResponseItem>> {
+        let response = self.read(|client| {
+            client
+                .get_item()
+                .table_name(&self.table_name)
+                .key("user_id", AttributeValue::S(user_id.to_string()))
+                .key("sk", AttributeValue::S(format!("RAW#{}", order_id)))
+                .send()
+        })
+        .await?;
+
+        let Some(item) = response.item else {
+            return Ok(None);
+        };
+        let body = item
+            .get("body")
+            .and_then(|v| v.as_b().ok())
+            .ok_or_else(|| anyhow!("Missing field: body"))?;
+        Ok(Some(RawResponseItem {
+            user_id: user_id.to_string(),
+            order_id: order_id.to_string(),
+            http_status: self.get_number(&item, "http_status")? as u16,
+            body: audit::decompress(body.as_ref())?,
+            recorded_at: self.get_number(&item, "recorded_at")? as i64,
+            expires_at: self.get_number(&item, "ttl")? as i64,
+        }))
+    }
+
     // Helper: Konvertiere AttributeValue Item zu OrderItem
+    /// Alle Items eines Users seitenweise lesen und als Backup-Bundle zurückgeben
+    pub async fn export_all(&self, user_id: &str) -> Result<ExportBundle> {
+        let mut bundle = ExportBundle::new(user_id);
+        let mut start_key = None;
+
+        loop {
+            let response = self.read(|client| {
+                client
+                    .query()
+                    .table_name(&self.table_name)
+                    .key_condition_expression("user_id = :uid")
+                    .expression_attribute_values(":uid".to_string(), AttributeValue::S(user_id.to_string()))
+                    .set_exclusive_start_key(start_key.clone())
+                    .send()
+            })
+            .await?;
+
+            for item in response.items() {
+                bundle.push(item)?;
+            }
+
+            start_key = response.last_evaluated_key;
+            if start_key.is_none() {
+                break;
+            }
+        }
+
+        tracing::info!("Exported {} items for user {}", bundle.len(), user_id);
+        Ok(bundle)
+    }
+
+    /// Backup-Bundle per BatchWriteItem (je 25 Items) zurückschreiben.
+    /// Nur Items des Bundle-Users werden akzeptiert; gibt die Anzahl geschriebener Items zurück.
+    pub async fn import_bundle(&self, bundle: &ExportBundle) -> Result<usize> {
+        let items = bundle.items()?;
+        let user_id = AttributeValue::S(bundle.user_id.clone());
+        if let Some(foreign) = items.iter().find(|item| item.get("user_id") != Some(&user_id)) {
+            return Err(anyhow!(
+                "Bundle for {} contains item of another user: {:?}",
+                bundle.user_id,
+                foreign.get("user_id")
+            ));
+        }
+
+        self.batch_put_items(&items).await?;
+
+        tracing::info!("Imported {} items for user {}", items.len(), bundle.user_id);
+        Ok(items.len())
+    }
+
+    /// Items per BatchWriteItem schreiben (je 25); unverarbeitete Items werden mit
+    /// Backoff bis zu BATCH_WRITE_ATTEMPTS mal erneut gesendet
+    async fn batch_put_items(&self, items: &[HashMap<String, AttributeValue>]) -> Result<()> {
+        for chunk in items.chunks(BATCH_WRITE_LIMIT) {
+            let mut requests = chunk
+                .iter()
+                .map(|item| {
+                    Ok(WriteRequest::builder()
+                        .put_request(PutRequest::builder().set_item(Some(item.clone())).build()?)
+                        .build())
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut attempt = 0;
+            while !requests.is_empty() {
+                attempt += 1;
+                if attempt > BATCH_WRITE_ATTEMPTS {
+                    return Err(anyhow!("{} items left unprocessed after batch write retries", requests.len()));
+                }
+                if attempt > 1 {
+                    tokio::time::sleep(std::time::Duration::from_millis(100 * 2u64.pow(attempt - 2))).await;
+                }
+
+                let response = self
+                    .client
+                    .batch_write_item()
+                    .request_items(self.table_name.clone(), requests)
+                    .send()
+                    .await?;
+
+                requests = response
+                    .unprocessed_items
+                    .and_then(|mut unprocessed| unprocessed.remove(&self.table_name))
+                    .unwrap_or_default();
+            }
+        }
+        Ok(())
+    }
+
     fn item_to_order(&self, item: &HashMap<String, AttributeValue>) -> Result<OrderItem> {
         Ok(OrderItem {
             user_id: self.get_string(item, "user_id")?,
@@ -328,16 +1434,51 @@ impl DynamoDBStore {
             quantity: self.get_number(item, "quantity")?,
             price: self.get_optional_number(item, "price"),
             filled_qty: self.get_number(item, "filled_qty")?,
+            fill_price: self.get_optional_number(item, "fill_price"),
             status: self.get_string(item, "status")?,
             timestamp: self.get_number(item, "timestamp")? as i64,
             created_at: self.get_string(item, "created_at")?,
             updated_at: self.get_string(item, "updated_at")?,
             mexc_order_id: self.get_optional_string(item, "mexc_order_id"),
-            error_message: self.get_optional_string(item, "error_message"),
+            error_message: self
+                .get_optional_string(item, "error_message")
+                .map(|error| self.open_field(error))
+                .transpose()?,
+            rejection_code: self.get_optional_string(item, "rejection_code"),
+            rejection_category: self.get_optional_string(item, "rejection_category"),
+            reduce_only: self.get_optional_bool(item, "reduce_only").unwrap_or(false),
+            post_only: self.get_optional_bool(item, "post_only").unwrap_or(false),
+            paper: self.get_optional_bool(item, "paper").unwrap_or(false),
+            quote_order_qty: self.get_optional_number(item, "quote_order_qty"),
+            event_id: self.get_optional_string(item, "event_id"),
+            recovered: self.get_optional_bool(item, "recovered").unwrap_or(false),
+            replaces: self.get_optional_string(item, "replaces"),
+            replaced_by: self.get_optional_string(item, "replaced_by"),
+            note: self.get_optional_string(item, "note"),
+            account: self.get_optional_string(item, "account"),
+            strategy: self.get_optional_string(item, "strategy"),
+            chase_origin_price: self.get_optional_number(item, "chase_origin_price"),
+            client_order_id: self.get_optional_string(item, "client_order_id"),
             ttl: self.get_number(item, "ttl")? as i64,
         })
     }
 
+    fn item_to_fill(&self, item: &HashMap<String, AttributeValue>) -> Result<TradeFill> {
+        Ok(TradeFill {
+            symbol: self.get_string(item, "symbol")?,
+            trade_id: self.get_string(item, "trade_id")?,
+            order_id: self.get_string(item, "order_id")?,
+            price: self.get_number(item, "price")?,
+            quantity: self.get_number(item, "quantity")?,
+            quote_qty: self.get_number(item, "quote_qty")?,
+            fee: self.get_number(item, "fee")?,
+            fee_asset: self.get_string(item, "fee_asset")?,
+            time: self.get_number(item, "time")? as i64,
+            is_buyer: self.get_optional_bool(item, "is_buyer").unwrap_or(false),
+            is_maker: self.get_optional_bool(item, "is_maker").unwrap_or(false),
+        })
+    }
+
     fn item_to_position(&self, item: &HashMap<String, AttributeValue>) -> Result<PositionItem> {
         Ok(PositionItem {
             user_id: self.get_string(item, "user_id")?,
@@ -352,6 +1493,12 @@ impl DynamoDBStore {
             pnl_percentage: self.get_optional_number(item, "pnl_percentage"),
             status: self.get_string(item, "status")?,
             updated_at: self.get_string(item, "updated_at")?,
+            closed_at: self.get_optional_number(item, "closed_at").map(|v| v as i64),
+            stop_price: self.get_optional_number(item, "stop_price"),
+            note: self.get_optional_string(item, "note"),
+            account: self.get_optional_string(item, "account"),
+            strategy: self.get_optional_string(item, "strategy"),
+            add_ons: self.get_optional_number(item, "add_ons").unwrap_or(0.0) as u32,
             ttl: self.get_number(item, "ttl")? as i64,
         })
     }
@@ -369,6 +1516,10 @@ impl DynamoDBStore {
             status: self.get_string(item, "status")?,
             execution_time: self.get_optional_number(item, "execution_time").map(|v| v as i64),
             executed_orders: self.get_optional_string_list(item, "executed_orders").unwrap_or_default(),
+            attempts: self.get_optional_number(item, "attempts").unwrap_or(0.0) as u32,
+            pattern_meta: self
+                .get_optional_string(item, "pattern_meta")
+                .and_then(|meta| serde_json::from_str(&meta).ok()),
             ttl: self.get_number(item, "ttl")? as i64,
         })
     }
@@ -395,9 +1546,493 @@ impl DynamoDBStore {
             .ok_or_else(|| anyhow!("Missing or invalid number field: {}", key))
     }
 
+    fn get_optional_bool(&self, item: &HashMap<String, AttributeValue>, key: &str) -> Option<bool> {
+        item.get(key).and_then(|v| v.as_bool().ok()).copied()
+    }
+
     fn get_optional_number(&self, item: &HashMap<String, AttributeValue>, key: &str) -> Option<f64> {
         item.get(key)
             .and_then(|v| v.as_n().ok())
             .and_then(|n| n.parse::<f64>().ok())
     }
 }
+
+/// Netzwerk-, Timeout- und 5xx-Fehler rechtfertigen einen Regionswechsel,
+/// Client-Fehler (Validation, ConditionalCheck) nicht
+fn is_failover_error<E>(error: &SdkError<E>) -> bool {
+    match error {
+        SdkError::DispatchFailure(_) | SdkError::TimeoutError(_) | SdkError::ResponseError(_) => true,
+        SdkError::ServiceError(e) => e.raw().status().is_server_error(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReadConsistency, WritePolicy};
+    use crate::storage::{CalendarEventItem, OrderItem, RawResponseItem};
+    use crate::test_support::MockDynamo;
+    use crate::trading::PatternDetector;
+    use crate::utils::{crypto, FieldCipher};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_read_falls_back_to_failover_region() {
+        let primary = MockDynamo::start().await;
+        let secondary = MockDynamo::start().await;
+        primary.respond_server_error("GetItem");
+        secondary.respond(
+            "GetItem",
+            json!({ "Item": { "patterns": { "SS": ["SCAM*"] } } }),
+        );
+
+        let store = primary.store().await.with_failover(secondary.client(), WritePolicy::Primary);
+        let patterns = store.get_symbol_blacklist().await.expect("failover read failed");
+
+        assert_eq!(patterns, vec!["SCAM*".to_string()]);
+        assert_eq!(primary.requests("GetItem").len(), 1);
+        assert_eq!(secondary.requests("GetItem").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_do_not_fail_over() {
+        let primary = MockDynamo::start().await;
+        let secondary = MockDynamo::start().await;
+        primary.respond_error("GetItem", "ValidationException");
+
+        let store = primary.store().await.with_failover(secondary.client(), WritePolicy::Failover);
+        assert!(store.get_symbol_blacklist().await.is_err());
+        assert!(secondary.requests("GetItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_table_check_retries_throttling_only() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+        dynamo.respond_error("DescribeTable", "ThrottlingException");
+        dynamo.respond_error("DescribeTable", "ProvisionedThroughputExceededException");
+
+        store.check_table().await.expect("table check failed");
+        assert_eq!(dynamo.requests("DescribeTable").len(), 3);
+
+        dynamo.respond_error("DescribeTable", "ResourceNotFoundException");
+        assert!(store.check_table().await.is_err());
+        assert_eq!(dynamo.requests("DescribeTable").len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_pattern_meta_round_trips() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+        let pattern = PatternDetector::new(0.8).detect_pattern("VFARM", &[1000, 2000, 3000]).unwrap();
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "VFARM".to_string(),
+            "VFARMUSDT".to_string(),
+            1_700_000_000_000,
+            pattern.pattern_type.clone(),
+            pattern.confidence,
+        )
+        .with_pattern_meta(pattern.meta.clone());
+
+        store.put_calendar_event(&event).await.unwrap();
+        store.upsert_calendar_event(&event).await.unwrap();
+        let update = &dynamo.requests("UpdateItem")[0];
+        assert!(update["UpdateExpression"].as_str().unwrap().ends_with("pattern_meta = :pattern_meta"));
+
+        let stored = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert!(stored["pattern_meta"]["S"].as_str().unwrap().contains("interval_cv"));
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored] }));
+        let events = store
+            .query_calendar_events_by_time("user-1", 0, i64::MAX)
+            .await
+            .unwrap();
+        assert_eq!(events[0].pattern_meta, Some(pattern.meta));
+    }
+
+    #[tokio::test]
+    async fn test_dual_write_policy_writes_both_regions() {
+        let primary = MockDynamo::start().await;
+        let secondary = MockDynamo::start().await;
+        let store = primary.store().await.with_failover(secondary.client(), WritePolicy::DualWrite);
+
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        store.put_calendar_event(&event).await.expect("write failed");
+
+        assert_eq!(primary.requests("PutItem").len(), 1);
+        assert_eq!(secondary.requests("PutItem").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        let order = json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": "ORDER#1#order-1" },
+            "quantity": { "N": "1.5" },
+            "reduce_only": { "BOOL": true }
+        });
+        let position = json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": "CLOSED#5#pos-1" },
+            "executed": { "SS": ["a", "b"] }
+        });
+        let setting = json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": "SETTINGS#risk" },
+            "value": { "M": { "pct": { "N": "2" } } }
+        });
+
+        // Zwei Seiten: Export folgt LastEvaluatedKey
+        dynamo.respond(
+            "Query",
+            json!({ "Items": [order.clone(), position.clone()], "LastEvaluatedKey": { "user_id": { "S": "user-1" }, "sk": { "S": "CLOSED#5#pos-1" } } }),
+        );
+        dynamo.respond("Query", json!({ "Items": [setting.clone()] }));
+
+        let bundle = store.export_all("user-1").await.expect("export failed");
+        let queries = dynamo.requests("Query");
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[1]["ExclusiveStartKey"]["sk"]["S"], "CLOSED#5#pos-1");
+        assert_eq!(bundle.orders, vec![order.clone()]);
+        assert_eq!(bundle.positions, vec![position.clone()]);
+        assert_eq!(bundle.settings, vec![setting.clone()]);
+
+        // Bundle übersteht JSON-Serialisierung und wird 1:1 zurückgeschrieben
+        let restored: crate::storage::ExportBundle =
+            serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert_eq!(store.import_bundle(&restored).await.expect("import failed"), 3);
+
+        let writes = dynamo.requests("BatchWriteItem");
+        assert_eq!(writes.len(), 1);
+        let written: Vec<_> = writes[0]["RequestItems"]["test_table"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["PutRequest"]["Item"].clone())
+            .collect();
+        assert_eq!(written, vec![order, position, setting]);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_foreign_items() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        let mut bundle = crate::storage::ExportBundle::new("user-1");
+        bundle.orders.push(json!({ "user_id": { "S": "user-2" }, "sk": { "S": "ORDER#1#x" } }));
+
+        assert!(store.import_bundle(&bundle).await.is_err());
+        assert!(dynamo.requests("BatchWriteItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reingesting_launch_targets_same_event() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        let launch = |confidence| {
+            CalendarEventItem::new(
+                "user-1".to_string(),
+                "New Token".to_string(),
+                "NEWUSDT".to_string(),
+                1_700_000_000,
+                "sts:2".to_string(),
+                confidence,
+            )
+        };
+        store.upsert_calendar_event(&launch(0.7)).await.expect("first ingest failed");
+        store.upsert_calendar_event(&launch(0.9)).await.expect("second ingest failed");
+
+        let updates = dynamo.requests("UpdateItem");
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0]["Key"], updates[1]["Key"]);
+        assert!(dynamo.requests("PutItem").is_empty());
+
+        // Zweites Einlesen aktualisiert die Erkennung, Status bleibt erhalten
+        let expression = updates[1]["UpdateExpression"].as_str().unwrap();
+        assert!(expression.contains("#status = if_not_exists(#status, :status)"));
+        assert_eq!(updates[1]["ExpressionAttributeValues"][":confidence"]["N"], "0.9");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_batches_new_events_and_retries_unprocessed() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        let events: Vec<CalendarEventItem> = (0..41)
+            .map(|i| {
+                CalendarEventItem::new(
+                    "user-1".to_string(),
+                    format!("Token {}", i),
+                    format!("TOK{}USDT", i),
+                    1_700_000_000 + i,
+                    "sts:2".to_string(),
+                    0.8,
+                )
+            })
+            .collect();
+        // Launch 0 ist schon gespeichert -> Upsert statt Batch
+        let item =
+            |i: usize| crate::storage::export::item_to_json(&store.calendar_event_item(&events[i]).unwrap());
+        let known = item(0).unwrap();
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [known] }));
+        // Erster Batch: zwei Items unverarbeitet
+        let unprocessed: Vec<_> = (1..3)
+            .map(|i| json!({ "PutRequest": { "Item": item(i).unwrap() } }))
+            .collect();
+        dynamo.respond("BatchWriteItem", json!({ "UnprocessedItems": { "test_table": unprocessed } }));
+
+        assert_eq!(store.ingest_calendar_events(&events).await.unwrap(), 41);
+
+        assert_eq!(dynamo.requests("UpdateItem").len(), 1);
+        let batches: Vec<usize> = dynamo
+            .requests("BatchWriteItem")
+            .iter()
+            .map(|b| b["RequestItems"]["test_table"].as_array().unwrap().len())
+            .collect();
+        // 40 neue Events: 25 + 15, dazwischen der Retry der 2 unverarbeiteten
+        assert_eq!(batches, vec![25, 2, 15]);
+    }
+
+    #[tokio::test]
+    async fn test_query_closed_positions_key_condition() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        store
+            .query_closed_positions("user-1", 1_000, 2_000)
+            .await
+            .expect("query failed");
+
+        let queries = dynamo.requests("Query");
+        assert_eq!(queries.len(), 1);
+        assert_eq!(
+            queries[0]["KeyConditionExpression"],
+            "user_id = :uid AND sk BETWEEN :from AND :to"
+        );
+        let values = &queries[0]["ExpressionAttributeValues"];
+        assert_eq!(values[":uid"]["S"], "user-1");
+        assert_eq!(values[":from"]["S"], "CLOSED#0000000001000");
+        assert_eq!(values[":to"]["S"], "CLOSED#0000000002000#~");
+    }
+
+    #[tokio::test]
+    async fn test_closed_position_bounds_are_fixed_width() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        // Kurze, negative und überlange Zeitstempel: untere Grenze bleibt <= obere
+        let ranges = [(2, 999), (999, 1_700_000_000_000), (-5, 3), (1_700_000_000_000, i64::MAX)];
+        for (from, to) in ranges {
+            store.query_closed_positions("user-1", from, to).await.expect("query failed");
+        }
+
+        let queries = dynamo.requests("Query");
+        let bounds: Vec<(String, String)> = queries
+            .iter()
+            .map(|q| {
+                let values = &q["ExpressionAttributeValues"];
+                (
+                    values[":from"]["S"].as_str().unwrap().to_string(),
+                    values[":to"]["S"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(bounds[0], ("CLOSED#0000000000002".to_string(), "CLOSED#0000000000999#~".to_string()));
+        assert_eq!(bounds[2].0, "CLOSED#0000000000000");
+        assert_eq!(bounds[3].1, "CLOSED#9999999999999#~");
+        for (from, to) in &bounds {
+            assert!(from < to, "{} > {}", from, to);
+        }
+
+        // Geschriebene Keys liegen innerhalb der Grenzen ihres Zeitraums
+        let mut position = crate::storage::PositionItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            2000.0,
+            1.0,
+            "BUY".to_string(),
+        );
+        position.closed_at = Some(999);
+        let key = position.closed_sort_key().unwrap();
+        assert!(key.starts_with("CLOSED#0000000000999#"));
+        assert!(bounds[0].0 <= key && key <= bounds[0].1);
+        assert!(key < bounds[1].1);
+    }
+
+    #[tokio::test]
+    async fn test_order_event_id_round_trip() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "NEWUSDT".to_string(),
+            "BUY".to_string(),
+            "market".to_string(),
+            10.0,
+            None,
+        );
+        order.event_id = Some("event-1".to_string());
+        store.put_order(&order).await.expect("put failed");
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts[0]["Item"]["event_id"]["S"], "event-1");
+
+        // Geschriebenes Item zurücklesen
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [puts[0]["Item"].clone()] }));
+        let loaded = store
+            .get_order("user-1", &order.order_id, ReadConsistency::Eventual)
+            .await
+            .expect("get failed")
+            .expect("order missing");
+        assert_eq!(loaded.event_id.as_deref(), Some("event-1"));
+
+        // Ohne event_id wird kein Attribut geschrieben
+        order.event_id = None;
+        store.put_order(&order).await.expect("put failed");
+        assert!(dynamo.requests("PutItem")[1]["Item"].get("event_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_order_error_message_encrypted_at_rest() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo
+            .store()
+            .await
+            .with_cipher(Arc::new(FieldCipher::new(&[3u8; 32])));
+
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "NEWUSDT".to_string(),
+            "BUY".to_string(),
+            "market".to_string(),
+            10.0,
+            None,
+        );
+        order.error_message = Some("signature=abc123 rejected".to_string());
+        store.put_order(&order).await.expect("put failed");
+
+        // Nur das sensible Feld ist verschlüsselt, Query-Felder bleiben Klartext
+        let mut item = dynamo.requests("PutItem")[0]["Item"].clone();
+        let stored = item["error_message"]["S"].as_str().unwrap().to_string();
+        assert!(crypto::is_sealed(&stored));
+        assert!(!stored.contains("abc123"));
+        assert_eq!(item["symbol"]["S"], "NEWUSDT");
+
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [item.clone()] }));
+        let loaded = store.get_order("user-1", &order.order_id, ReadConsistency::Eventual).await.unwrap().unwrap();
+        assert_eq!(loaded.error_message.as_deref(), Some("signature=abc123 rejected"));
+
+        // Manipulierter Ciphertext -> Lesen schlägt fehl statt Müll zu liefern
+        let mut tampered = stored.into_bytes();
+        let last = tampered.len() - 1;
+        tampered[last] = if tampered[last] == b'0' { b'1' } else { b'0' };
+        item["error_message"]["S"] = json!(String::from_utf8(tampered).unwrap());
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [item.clone()] }));
+        assert!(store.get_order("user-1", &order.order_id, ReadConsistency::Eventual).await.is_err());
+
+        // Klartext-Altbestand bleibt lesbar
+        item["error_message"]["S"] = json!("legacy error");
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [item] }));
+        let legacy = store.get_order("user-1", &order.order_id, ReadConsistency::Eventual).await.unwrap().unwrap();
+        assert_eq!(legacy.error_message.as_deref(), Some("legacy error"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_response_stored_compressed_and_round_trips() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+        let body = r#"{"orderId":"C02__1","symbol":"NEWUSDT","status":"FILLED"}"#.repeat(4);
+        let raw = RawResponseItem {
+            user_id: "user-1".to_string(),
+            order_id: "order-1".to_string(),
+            http_status: 200,
+            body: body.clone(),
+            recorded_at: 1_700_000_000_000,
+            expires_at: 1_702_592_000,
+        };
+        store.put_raw_response(&raw).await.expect("put failed");
+
+        let item = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert_eq!(item["sk"]["S"], "RAW#order-1");
+        assert_eq!(item["ttl"]["N"], "1702592000");
+        // Binär (gzip), nicht im Klartext
+        let stored = item["body"]["B"].as_str().expect("body not binary");
+        assert!(!stored.contains("NEWUSDT"));
+
+        dynamo.respond("GetItem", json!({ "Item": item }));
+        let loaded = store.get_raw_response("user-1", "order-1").await.unwrap().unwrap();
+        assert_eq!(loaded.body, body);
+        assert_eq!(loaded.http_status, 200);
+        assert_eq!(loaded.expires_at, raw.expires_at);
+
+        assert!(store.get_raw_response("user-1", "missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_orders_by_event() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 1,
+                "Items": [{
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": "ORDER#1#order-1" },
+                    "order_id": { "S": "order-1" },
+                    "symbol": { "S": "NEWUSDT" },
+                    "side": { "S": "BUY" },
+                    "order_type": { "S": "market" },
+                    "quantity": { "N": "10" },
+                    "filled_qty": { "N": "10" },
+                    "status": { "S": "filled" },
+                    "timestamp": { "N": "1" },
+                    "created_at": { "S": "2024-01-01T00:00:00Z" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "event_id": { "S": "event-1" },
+                    "ttl": { "N": "0" }
+                }]
+            }),
+        );
+
+        let orders = store.query_orders_by_event("user-1", "event-1").await.expect("query failed");
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, "order-1");
+        assert_eq!(orders[0].event_id.as_deref(), Some("event-1"));
+
+        let query = &dynamo.requests("Query")[0];
+        assert_eq!(query["FilterExpression"], "event_id = :event_id");
+        assert_eq!(query["ExpressionAttributeValues"][":event_id"]["S"], "event-1");
+    }
+
+    #[tokio::test]
+    async fn test_consistent_read_flag_per_call() {
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+
+        store.get_order("user-1", "order-1", ReadConsistency::Strong).await.expect("get failed");
+        store.get_order("user-1", "order-1", ReadConsistency::Eventual).await.expect("get failed");
+        store
+            .get_position("user-1", "pos-1", ReadConsistency::Strong)
+            .await
+            .expect("get failed");
+
+        let queries = dynamo.requests("Query");
+        assert_eq!(queries[0]["ConsistentRead"], true);
+        assert_eq!(queries[1]["ConsistentRead"], false);
+        assert_eq!(queries[2]["ConsistentRead"], true);
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::AttributeValue;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Format-Version des Backup-Bundles
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Backup aller Items eines Users. Items liegen im DynamoDB-JSON-Format vor
+/// (`{"S": ..}`, `{"N": ..}`), damit ein Import sie unverändert zurückschreibt.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportBundle {
+    pub version: u32,
+    pub user_id: String,
+    pub exported_at: String,
+    pub orders: Vec<Value>,
+    /// Offene und geschlossene Positionen (POSITION#, CLOSED#)
+    pub positions: Vec<Value>,
+    pub calendar_events: Vec<Value>,
+    pub settings: Vec<Value>,
+    /// Alle übrigen Items des Users, damit nichts verloren geht
+    #[serde(default)]
+    pub other: Vec<Value>,
+}
+
+impl ExportBundle {
+    pub fn new(user_id: &str) -> Self {
+        Self {
+            version: EXPORT_VERSION,
+            user_id: user_id.to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        }
+    }
+
+    /// Item anhand des Sort-Key-Prefix einsortieren
+    pub fn push(&mut self, item: &HashMap<String, AttributeValue>) -> Result<()> {
+        let sort_key = item.get("sk").and_then(|v| v.as_s().ok()).map(String::as_str).unwrap_or_default();
+        let value = item_to_json(item)?;
+
+        let target = match sort_key.split('#').next().unwrap_or_default() {
+            "ORDER" => &mut self.orders,
+            "POSITION" | "CLOSED" => &mut self.positions,
+            "CALENDAR" => &mut self.calendar_events,
+            "SETTINGS" => &mut self.settings,
+            _ => &mut self.other,
+        };
+        target.push(value);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+            + self.positions.len()
+            + self.calendar_events.len()
+            + self.settings.len()
+            + self.other.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Alle Items als DynamoDB Attribute-Maps (für den Import)
+    pub fn items(&self) -> Result<Vec<HashMap<String, AttributeValue>>> {
+        self.orders
+            .iter()
+            .chain(&self.positions)
+            .chain(&self.calendar_events)
+            .chain(&self.settings)
+            .chain(&self.other)
+            .map(json_to_item)
+            .collect()
+    }
+}
+
+/// Attribute-Map in DynamoDB-JSON umwandeln
+pub fn item_to_json(item: &HashMap<String, AttributeValue>) -> Result<Value> {
+    let mut map = Map::new();
+    for (key, value) in item {
+        map.insert(key.clone(), attribute_to_json(value)?);
+    }
+    Ok(Value::Object(map))
+}
+
+/// DynamoDB-JSON zurück in eine Attribute-Map umwandeln
+pub fn json_to_item(value: &Value) -> Result<HashMap<String, AttributeValue>> {
+    value
+        .as_object()
+        .ok_or_else(|| anyhow!("Export item is not an object"))?
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), json_to_attribute(value)?)))
+        .collect()
+}
+
+fn attribute_to_json(value: &AttributeValue) -> Result<Value> {
+    let (tag, inner) = match value {
+        AttributeValue::S(s) => ("S", Value::from(s.clone())),
+        AttributeValue::N(n) => ("N", Value::from(n.clone())),
+        AttributeValue::Bool(b) => ("BOOL", Value::from(*b)),
+        AttributeValue::Null(b) => ("NULL", Value::from(*b)),
+        // Binärwerte wie im DynamoDB-JSON base64-kodiert
+        AttributeValue::B(blob) => ("B", Value::from(BASE64.encode(blob.as_ref()))),
+        AttributeValue::Ss(list) => ("SS", Value::from(list.clone())),
+        AttributeValue::Ns(list) => ("NS", Value::from(list.clone())),
+        AttributeValue::L(list) => (
+            "L",
+            Value::Array(list.iter().map(attribute_to_json).collect::<Result<_>>()?),
+        ),
+        AttributeValue::M(map) => ("M", item_to_json(map)?),
+        other => return Err(anyhow!("Unsupported attribute type in export: {:?}", other)),
+    };
+    Ok(Value::Object(Map::from_iter([(tag.to_string(), inner)])))
+}
+
+fn json_to_attribute(value: &Value) -> Result<AttributeValue> {
+    let (tag, inner) = value
+        .as_object()
+        .filter(|map| map.len() == 1)
+        .and_then(|map| map.iter().next())
+        .ok_or_else(|| anyhow!("Invalid attribute value: {}", value))?;
+
+    let string = |v: &Value| {
+        v.as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Expected string in {} attribute", tag))
+    };
+    let strings = |v: &Value| -> Result<Vec<String>> {
+        v.as_array()
+            .ok_or_else(|| anyhow!("Expected list in {} attribute", tag))?
+            .iter()
+            .map(string)
+            .collect()
+    };
+
+    Ok(match tag.as_str() {
+        "S" => AttributeValue::S(string(inner)?),
+        "N" => AttributeValue::N(string(inner)?),
+        "BOOL" => AttributeValue::Bool(inner.as_bool().ok_or_else(|| anyhow!("Expected bool"))?),
+        "NULL" => AttributeValue::Null(inner.as_bool().unwrap_or(true)),
+        "B" => AttributeValue::B(Blob::new(BASE64.decode(string(inner)?)?)),
+        "SS" => AttributeValue::Ss(strings(inner)?),
+        "NS" => AttributeValue::Ns(strings(inner)?),
+        "L" => AttributeValue::L(
+            inner
+                .as_array()
+                .ok_or_else(|| anyhow!("Expected list in L attribute"))?
+                .iter()
+                .map(json_to_attribute)
+                .collect::<Result<_>>()?,
+        ),
+        "M" => AttributeValue::M(json_to_item(inner)?),
+        other => return Err(anyhow!("Unsupported attribute type in import: {}", other)),
+    })
+}
@@ -1,6 +1,14 @@
+pub mod audit;
 pub mod dynamodb;
+pub mod export;
 pub mod models;
 pub mod migration;
+pub mod write_behind;
 
-pub use dynamodb::DynamoDBStore;
-pub use models::{OrderItem, PositionItem, CalendarEventItem};
+pub use dynamodb::{DynamoDBStore, ReadConsistency, WritePolicy};
+pub use export::ExportBundle;
+pub use write_behind::{BacklogFull, OrderWriteBuffer, WriteSlot};
+pub use models::{
+    calendar_event_key, sanitize_note, CalendarEventItem, OrderConfirmationItem, OrderItem, OrderStatus, PatternMeta,
+    PositionItem, RawResponseItem,
+};
@@ -1,7 +1,34 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+/// Aufbewahrungsdauer der Items in DynamoDB (90 Tage)
+pub const ITEM_TTL_SECS: i64 = 7_776_000;
+
+/// Max. Länge einer Journal-Notiz (Zeichen)
+pub const MAX_NOTE_CHARS: usize = 1_000;
+
+/// Journal-Notiz bereinigen: Steuerzeichen (außer Zeilenumbruch/Tab) und
+/// spitze Klammern entfernen, trimmen; leer = Notiz löschen
+pub fn sanitize_note(raw: &str) -> Result<Option<String>, String> {
+    let note: String = raw
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>') && (!c.is_control() || matches!(c, '\n' | '\t')))
+        .collect();
+    let note = note.trim();
+    let chars = note.chars().count();
+    if chars > MAX_NOTE_CHARS {
+        return Err(format!("note has {} characters, at most {} allowed", chars, MAX_NOTE_CHARS));
+    }
+    Ok((!note.is_empty()).then(|| note.to_string()))
+}
+
+/// TTL-Zeitpunkt (Unix Sekunden) für ein zum Zeitpunkt `now` geschriebenes Item
+pub fn ttl_from(now: DateTime<Utc>) -> i64 {
+    now.timestamp() + ITEM_TTL_SECS
+}
+
 /// Typ für Order-Status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum OrderStatus {
@@ -27,6 +54,17 @@ impl OrderStatus {
             OrderStatus::Error => "error",
         }
     }
+
+    /// Normalisiere MEXC Order-Status (NEW, PARTIALLY_FILLED, FILLED, ...)
+    pub fn from_mexc(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "NEW" | "PARTIALLY_FILLED" | "OPEN" => OrderStatus::Open,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" | "CANCELLED" | "PARTIALLY_CANCELED" | "EXPIRED" => OrderStatus::Cancelled,
+            "PENDING" => OrderStatus::Pending,
+            _ => OrderStatus::Error,
+        }
+    }
 }
 
 /// DynamoDB Order Item
@@ -40,12 +78,42 @@ pub struct OrderItem {
     pub quantity: f64,
     pub price: Option<f64>,
     pub filled_qty: f64,
+    #[serde(default)]
+    pub fill_price: Option<f64>, // Ausführungspreis laut MEXC, sobald gefüllt
     pub status: String,
     pub timestamp: i64, // Unix timestamp in Millisekunden
     pub created_at: String, // ISO 8601
     pub updated_at: String, // ISO 8601
     pub mexc_order_id: Option<String>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub rejection_code: Option<String>, // MEXC-Fehlercode bei Ablehnung
+    #[serde(default)]
+    pub rejection_category: Option<String>, // siehe RejectionCategory
+    pub reduce_only: bool, // Order darf eine Position nur verkleinern
+    #[serde(default)]
+    pub post_only: bool, // Maker-only: bei MEXC als LIMIT_MAKER platziert
+    pub quote_order_qty: Option<f64>, // gesetzt wenn per Quote-Betrag (quoteOrderQty) platziert
+    #[serde(default)]
+    pub paper: bool, // Paper-Trading: nie an MEXC gesendet, Fills simuliert
+    #[serde(default)]
+    pub event_id: Option<String>, // Calendar Event, das die Order ausgelöst hat (Snipes)
+    #[serde(default)]
+    pub recovered: bool, // beim Start von MEXC übernommen (Order fehlte in DynamoDB)
+    #[serde(default)]
+    pub replaces: Option<String>, // Order, die diese per Cancel-and-Replace ersetzt hat
+    #[serde(default)]
+    pub replaced_by: Option<String>, // Nachfolger-Order nach Cancel-and-Replace
+    #[serde(default)]
+    pub note: Option<String>, // Journal-Notiz des Traders
+    #[serde(default)]
+    pub account: Option<String>, // Benanntes MEXC-Konto (Strategie-Attribution)
+    #[serde(default)]
+    pub strategy: Option<String>, // Strategie-Label (z.B. "trail", "oco", "ladder") für PnL-Attribution
+    #[serde(default)]
+    pub chase_origin_price: Option<f64>, // Ursprüngliches Limit einer nachgezogenen Limit-Snipe
+    #[serde(default)]
+    pub client_order_id: Option<String>, // newClientOrderId bei MEXC (Lookup nach unklarem Fehler)
     pub ttl: i64, // TTL für DynamoDB (90 Tage)
 }
 
@@ -60,7 +128,7 @@ impl OrderItem {
     ) -> Self {
         let now = Utc::now();
         let timestamp = now.timestamp_millis();
-        let ttl = (now.timestamp() + 7776000) as i64; // +90 Tage
+        let ttl = ttl_from(now);
 
         Self {
             user_id,
@@ -71,12 +139,28 @@ impl OrderItem {
             quantity,
             price,
             filled_qty: 0.0,
+            fill_price: None,
             status: OrderStatus::Pending.as_str().to_string(),
             timestamp,
             created_at: now.to_rfc3339(),
             updated_at: now.to_rfc3339(),
             mexc_order_id: None,
             error_message: None,
+            rejection_code: None,
+            rejection_category: None,
+            reduce_only: false,
+            post_only: false,
+            quote_order_qty: None,
+            paper: false,
+            event_id: None,
+            recovered: false,
+            replaces: None,
+            replaced_by: None,
+            note: None,
+            account: None,
+            strategy: None,
+            chase_origin_price: None,
+            client_order_id: None,
             ttl,
         }
     }
@@ -88,6 +172,28 @@ impl OrderItem {
     pub fn sort_key(&self) -> String {
         format!("ORDER#{}#{}", self.timestamp, self.order_id)
     }
+
+    /// Simulierter Limit-Fill: BUY wenn der Preis auf/unter das Limit fällt,
+    /// SELL wenn er auf/über das Limit steigt
+    pub fn limit_crossed(&self, price: f64) -> bool {
+        let Some(limit) = self.price.filter(|_| self.order_type.eq_ignore_ascii_case("limit")) else {
+            return false;
+        };
+        if self.side.eq_ignore_ascii_case("buy") {
+            price <= limit
+        } else {
+            price >= limit
+        }
+    }
+
+    /// Zeitstempel und TTL auf `now` setzen (z.B. aus einer injizierten Clock)
+    pub fn stamped_at(mut self, now: DateTime<Utc>) -> Self {
+        self.timestamp = now.timestamp_millis();
+        self.created_at = now.to_rfc3339();
+        self.updated_at = now.to_rfc3339();
+        self.ttl = ttl_from(now);
+        self
+    }
 }
 
 /// DynamoDB Position Item
@@ -105,6 +211,16 @@ pub struct PositionItem {
     pub pnl_percentage: Option<f64>,
     pub status: String, // "open", "closed", "liquidated"
     pub updated_at: String,
+    pub closed_at: Option<i64>, // Unix timestamp in Millisekunden
+    pub stop_price: Option<f64>, // Software-Stop (vom Bot überwacht)
+    #[serde(default)]
+    pub note: Option<String>, // Journal-Notiz des Traders
+    #[serde(default)]
+    pub account: Option<String>, // Benanntes MEXC-Konto (Strategie-Attribution)
+    #[serde(default)]
+    pub strategy: Option<String>, // Strategie-Label, übernommen von der Entry-Order
+    #[serde(default)]
+    pub add_ons: u32, // Anzahl Pyramiding-Nachkäufe
     pub ttl: i64,
 }
 
@@ -118,7 +234,7 @@ impl PositionItem {
     ) -> Self {
         let now = Utc::now();
         let timestamp = now.timestamp_millis();
-        let ttl = (now.timestamp() + 7776000) as i64;
+        let ttl = ttl_from(now);
 
         Self {
             user_id,
@@ -133,6 +249,12 @@ impl PositionItem {
             pnl_percentage: None,
             status: "open".to_string(),
             updated_at: now.to_rfc3339(),
+            closed_at: None,
+            stop_price: None,
+            note: None,
+            account: None,
+            strategy: None,
+            add_ons: 0,
             ttl,
         }
     }
@@ -145,6 +267,57 @@ impl PositionItem {
         format!("POSITION#{}#{}", self.entry_time, self.position_id)
     }
 
+    /// Zeitstempel und TTL auf `now` setzen (z.B. aus einer injizierten Clock)
+    pub fn stamped_at(mut self, now: DateTime<Utc>) -> Self {
+        self.entry_time = now.timestamp_millis();
+        self.updated_at = now.to_rfc3339();
+        self.ttl = ttl_from(now);
+        self
+    }
+
+    /// Sort Key des geschlossenen Records (nach Schließzeit abfragbar)
+    pub fn closed_sort_key(&self) -> Option<String> {
+        self.closed_at
+            .map(|closed_at| format!("CLOSED#{}#{}", closed_key_time(closed_at), self.position_id))
+    }
+
+    /// Position zum Preis schließen und realisierten PnL berechnen
+    pub fn close(&mut self, close_price: f64) {
+        self.close_at(close_price, Utc::now());
+    }
+
+    /// Wie `close`, mit explizitem Schließzeitpunkt
+    pub fn close_at(&mut self, close_price: f64, now: DateTime<Utc>) {
+        self.calculate_pnl(close_price);
+        self.status = "closed".to_string();
+        self.closed_at = Some(now.timestamp_millis());
+        self.updated_at = now.to_rfc3339();
+    }
+
+    /// Abstand vom aktuellen Preis zum Software-Stop in Prozent (None ohne Stop)
+    pub fn distance_to_stop_pct(&self) -> Option<f64> {
+        let stop = self.stop_price?;
+        if self.current_price <= 0.0 {
+            return None;
+        }
+        let diff = match self.side.as_str() {
+            "short" => stop - self.current_price,
+            _ => self.current_price - stop,
+        };
+        Some(diff / self.current_price * 100.0)
+    }
+
+    /// Nachkauf zu `price` verbuchen: Menge erhöhen, Entry gewichtet mitteln, PnL neu berechnen
+    pub fn add_on(&mut self, price: f64, quantity: f64) {
+        let total = self.quantity + quantity;
+        if total > 0.0 {
+            self.entry_price = (self.entry_price * self.quantity + price * quantity) / total;
+        }
+        self.quantity = total;
+        self.add_ons += 1;
+        self.calculate_pnl(self.current_price);
+    }
+
     pub fn calculate_pnl(&mut self, current_price: f64) {
         self.current_price = current_price;
         let price_diff = match self.side.as_str() {
@@ -158,6 +331,18 @@ impl PositionItem {
     }
 }
 
+/// Kennzahlen hinter einer Pattern-Erkennung (für Auswertung und Tuning)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PatternMeta {
+    /// Mittlerer Abstand der Launches in ms
+    pub mean_interval_ms: f64,
+    /// Variationskoeffizient der Abstände (Standardabweichung / Mittelwert)
+    pub interval_cv: f64,
+    pub sample_count: usize,
+    /// Mindest-Confidence des Detectors zum Erkennungszeitpunkt
+    pub min_confidence: f64,
+}
+
 /// DynamoDB Calendar/Launch Event Item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarEventItem {
@@ -172,6 +357,10 @@ pub struct CalendarEventItem {
     pub status: String, // "detected", "sniped", "missed"
     pub execution_time: Option<i64>,
     pub executed_orders: Vec<String>, // Order IDs
+    pub attempts: u32, // Anzahl Snipe-Versuche
+    /// Erkennungsdetails (als JSON-Attribut `pattern_meta` gespeichert)
+    #[serde(default)]
+    pub pattern_meta: Option<PatternMeta>,
     pub ttl: i64,
 }
 
@@ -185,11 +374,11 @@ impl CalendarEventItem {
         confidence: f64,
     ) -> Self {
         let now = Utc::now();
-        let ttl = (now.timestamp() + 7776000) as i64;
+        let ttl = ttl_from(now);
 
         Self {
             user_id,
-            event_id: Uuid::new_v4().to_string(),
+            event_id: calendar_event_key(&symbol, launch_time),
             token_name,
             symbol,
             launch_time,
@@ -199,6 +388,8 @@ impl CalendarEventItem {
             status: "detected".to_string(),
             execution_time: None,
             executed_orders: Vec::new(),
+            attempts: 0,
+            pattern_meta: None,
             ttl,
         }
     }
@@ -210,6 +401,62 @@ impl CalendarEventItem {
     pub fn sort_key(&self) -> String {
         format!("CALENDAR#{}#{}", self.launch_time, self.event_id)
     }
+
+    pub fn with_pattern_meta(mut self, meta: PatternMeta) -> Self {
+        self.pattern_meta = Some(meta);
+        self
+    }
+
+    /// Zeitstempel und TTL auf `now` setzen (z.B. aus einer injizierten Clock)
+    pub fn stamped_at(mut self, now: DateTime<Utc>) -> Self {
+        self.created_at = now.to_rfc3339();
+        self.ttl = ttl_from(now);
+        self
+    }
+}
+
+/// Größte Schließzeit, die in CLOSED#-Keys darstellbar ist (13 Stellen)
+const CLOSED_KEY_MAX_MS: i64 = 9_999_999_999_999;
+
+/// Zeitanteil des CLOSED#-Sort-Keys, auf 13 Stellen gepolstert: nur bei fester
+/// Breite entspricht der String-Vergleich in DynamoDB der zeitlichen Reihenfolge.
+/// Heutige ms-Zeitstempel haben bereits 13 Stellen, bestehende Keys bleiben gültig.
+pub fn closed_key_time(millis: i64) -> String {
+    format!("{:013}", millis.clamp(0, CLOSED_KEY_MAX_MS))
+}
+
+/// Deterministischer Event-Key aus Symbol + Launch-Zeit, damit erneutes
+/// Einlesen desselben Launches dasselbe Item trifft
+pub fn calendar_event_key(symbol: &str, launch_time: i64) -> String {
+    let digest = Sha256::digest(format!("{}|{}", symbol.to_ascii_uppercase(), launch_time));
+    hex::encode(&digest[..16])
+}
+
+/// Große Order, die auf Bestätigung wartet (SYSTEM / ORDER_CONFIRM#token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderConfirmationItem {
+    pub token: String,
+    pub user_id: String,
+    /// Ursprünglicher Order-Request als JSON
+    pub request: String,
+    pub notional: f64,
+    /// Ablauf als Unix timestamp in Sekunden (zugleich DynamoDB TTL)
+    pub expires_at: i64,
+}
+
+/// Rohe MEXC-Response zu einer Order für Audits (user / RAW#order_id),
+/// gzip-komprimiert gespeichert und per TTL begrenzt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawResponseItem {
+    pub user_id: String,
+    pub order_id: String,
+    /// HTTP-Status der MEXC-Antwort
+    pub http_status: u16,
+    /// Body ohne Secrets
+    pub body: String,
+    pub recorded_at: i64, // Unix timestamp in Millisekunden
+    /// Ablauf als Unix timestamp in Sekunden (zugleich DynamoDB TTL)
+    pub expires_at: i64,
 }
 
 /// GSI für Symbol-Queries
@@ -230,7 +477,35 @@ pub struct StatusIndex {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum DynamoItem {
-    Order(OrderItem),
+    Order(Box<OrderItem>),
     Position(PositionItem),
     CalendarEvent(CalendarEventItem),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::{Clock, MockClock};
+    use std::time::Duration;
+
+    #[test]
+    fn test_ttl_follows_clock() {
+        let clock = MockClock::from_millis(1_700_000_000_000);
+        let order = OrderItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            "buy".to_string(),
+            "market".to_string(),
+            1.0,
+            None,
+        )
+        .stamped_at(clock.now());
+
+        assert_eq!(order.timestamp, 1_700_000_000_000);
+        assert_eq!(order.ttl, 1_700_000_000 + ITEM_TTL_SECS);
+
+        clock.advance(Duration::from_secs(3600));
+        let later = order.clone().stamped_at(clock.now());
+        assert_eq!(later.ttl, order.ttl + 3600);
+    }
+}
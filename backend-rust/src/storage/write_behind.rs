@@ -0,0 +1,406 @@
+//! Write-behind für Orders: bei langsamer DynamoDB werden Order-Writes im
+//! Speicher gepuffert und im Hintergrund geschrieben. Spart Latenz im Order-Pfad
+//! auf Kosten der Haltbarkeit (ein Crash verliert den ungeschriebenen Puffer).
+//!
+//! Leser offener Orders (Open-Order-Limit, Reprice, Order Monitor) legen den
+//! Puffer per `overlay` über den gespeicherten Stand; spätere Updates einer noch
+//! gepufferten Order laufen per `write_through` hinter dieser her, damit der
+//! Flush sie nicht mit dem älteren Stand überschreibt.
+use anyhow::Result;
+use prometheus::IntGauge;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+
+use crate::storage::{DynamoDBStore, OrderItem};
+use crate::utils::{Config, Metrics};
+
+/// Puffer voll: neue Orders werden abgelehnt, bis der Flusher aufgeholt hat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BacklogFull {
+    pub backlog: usize,
+    pub max_backlog: usize,
+}
+
+impl std::fmt::Display for BacklogFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "order write backlog {} reached the limit of {}", self.backlog, self.max_backlog)
+    }
+}
+
+#[derive(Default)]
+struct Pending {
+    orders: VecDeque<OrderItem>,
+    /// Per `reserve` zugesagte, noch nicht eingereihte Plätze
+    reserved: usize,
+    /// Fehlgeschlagene Schreibversuche der vordersten Order
+    head_failures: u32,
+    /// Nach `max_attempts` aufgegebene Orders (bleiben lesbar, werden nicht mehr geschrieben)
+    dead_letters: Vec<OrderItem>,
+}
+
+/// Zugesagter Platz im Puffer: `enqueue` belegt ihn, Drop gibt ihn wieder frei
+pub struct WriteSlot {
+    buffer: Arc<OrderWriteBuffer>,
+    used: bool,
+}
+
+impl WriteSlot {
+    /// Order in den reservierten Platz schreiben und den Flusher wecken
+    pub fn enqueue(mut self, order: OrderItem) {
+        self.used = true;
+        let backlog = {
+            let mut pending = self.buffer.pending();
+            pending.reserved -= 1;
+            pending.orders.push_back(order);
+            pending.orders.len()
+        };
+        self.buffer.report(backlog);
+        self.buffer.notify.notify_one();
+    }
+}
+
+impl Drop for WriteSlot {
+    fn drop(&mut self) {
+        if !self.used {
+            self.buffer.pending().reserved -= 1;
+        }
+    }
+}
+
+pub struct OrderWriteBuffer {
+    store: Arc<DynamoDBStore>,
+    pending: Mutex<Pending>,
+    max_backlog: usize,
+    max_attempts: u32,
+    flush_interval: Duration,
+    notify: Notify,
+    backlog_gauge: Option<IntGauge>,
+}
+
+impl OrderWriteBuffer {
+    pub fn new(store: Arc<DynamoDBStore>, max_backlog: usize, flush_interval: Duration) -> Self {
+        Self {
+            store,
+            pending: Mutex::new(Pending::default()),
+            max_backlog,
+            max_attempts: 10,
+            flush_interval,
+            notify: Notify::new(),
+            backlog_gauge: None,
+        }
+    }
+
+    /// None wenn ORDER_WRITE_BEHIND aus ist (Orders werden synchron geschrieben)
+    pub fn from_config(store: Arc<DynamoDBStore>, config: &Config) -> Option<Self> {
+        config.order_write_behind.then(|| {
+            Self::new(
+                store,
+                config.order_write_behind_max_backlog,
+                Duration::from_millis(config.order_write_behind_flush_ms),
+            )
+            .with_max_attempts(config.order_write_behind_max_attempts)
+        })
+    }
+
+    /// Schreibversuche je Order, bevor sie als Dead Letter beiseitegelegt wird
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Backlog als Gauge `order_write_backlog` melden
+    pub fn with_metrics(mut self, metrics: &Metrics) -> Self {
+        self.backlog_gauge = Some(metrics.order_write_backlog.clone());
+        self
+    }
+
+    fn pending(&self) -> MutexGuard<'_, Pending> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn backlog(&self) -> usize {
+        self.pending().orders.len()
+    }
+
+    /// Vor dem Platzieren einen Platz zusagen lassen: Err wenn Backlog und
+    /// offene Zusagen das Limit erreicht haben. Prüfung und Zusage sind atomar,
+    /// parallele Orders können das Limit also nicht gemeinsam überschreiten.
+    pub fn reserve(self: &Arc<Self>) -> Result<WriteSlot, BacklogFull> {
+        let mut pending = self.pending();
+        let backlog = pending.orders.len() + pending.reserved;
+        if backlog >= self.max_backlog {
+            return Err(BacklogFull {
+                backlog,
+                max_backlog: self.max_backlog,
+            });
+        }
+        pending.reserved += 1;
+        Ok(WriteSlot {
+            buffer: self.clone(),
+            used: false,
+        })
+    }
+
+    /// Update einer bestehenden Order schreiben, ohne einen älteren gepufferten
+    /// Stand zu überholen: ist die Order noch gepuffert, wird das Update dahinter
+    /// eingereiht (zählt nicht gegen das Limit), sonst direkt geschrieben
+    pub async fn write_through(&self, order: &OrderItem) -> Result<()> {
+        let queued = {
+            let mut pending = self.pending();
+            let buffered = pending.orders.iter().any(|o| o.user_id == order.user_id && o.order_id == order.order_id);
+            if buffered {
+                pending.orders.push_back(order.clone());
+            }
+            buffered.then_some(pending.orders.len())
+        };
+        if let Some(backlog) = queued {
+            self.report(backlog);
+            self.notify.notify_one();
+            return Ok(());
+        }
+
+        self.store.put_order(order).await?;
+        // Der gespeicherte Stand ist neuer als ein aufgegebener
+        self.pending()
+            .dead_letters
+            .retain(|o| o.user_id != order.user_id || o.order_id != order.order_id);
+        Ok(())
+    }
+
+    /// Neuester noch nicht geschriebener Stand einer Order (für Lesezugriffe)
+    pub fn pending_order(&self, user_id: &str, order_id: &str) -> Option<OrderItem> {
+        let pending = self.pending();
+        pending
+            .orders
+            .iter()
+            .rev()
+            .chain(pending.dead_letters.iter().rev())
+            .find(|order| order.user_id == user_id && order.order_id == order_id)
+            .cloned()
+    }
+
+    /// Gepufferte Stände eines Users über gespeicherte Orders legen: gespeicherte
+    /// Orders werden durch ihren neuesten Pufferstand ersetzt, nur gepufferte
+    /// angehängt. Aufrufer filtern danach selbst (Status, Symbol).
+    pub fn overlay(&self, user_id: &str, stored: Vec<OrderItem>) -> Vec<OrderItem> {
+        let pending = self.pending();
+        let mut merged = stored;
+        for order in pending.dead_letters.iter().chain(pending.orders.iter()).filter(|o| o.user_id == user_id) {
+            match merged.iter_mut().find(|o| o.order_id == order.order_id) {
+                Some(existing) => *existing = order.clone(),
+                None => merged.push(order.clone()),
+            }
+        }
+        merged
+    }
+
+    /// Aufgegebene Orders (nach `max_attempts` fehlgeschlagenen Schreibversuchen)
+    pub fn dead_letters(&self) -> Vec<OrderItem> {
+        self.pending().dead_letters.clone()
+    }
+
+    /// Gepufferte Orders der Reihe nach schreiben. Bei einem Fehler bleibt die Order
+    /// vorne im Puffer und der nächste Flush versucht es erneut; nach `max_attempts`
+    /// Fehlschlägen wird sie als Dead Letter beiseitegelegt, damit ein dauerhafter
+    /// Fehler nicht alle folgenden Orders blockiert. Gibt die Anzahl geschriebener
+    /// Orders zurück.
+    pub async fn flush(&self) -> usize {
+        let mut written = 0;
+        loop {
+            let Some(order) = self.pending().orders.front().cloned() else {
+                break;
+            };
+            let result = self.store.put_order(&order).await;
+            let backlog = {
+                let mut pending = self.pending();
+                match &result {
+                    Ok(()) => {
+                        written += 1;
+                    }
+                    Err(e) if pending.head_failures + 1 < self.max_attempts => {
+                        pending.head_failures += 1;
+                        tracing::warn!(
+                            "Write-behind flush of order {} failed (attempt {}/{}): {}",
+                            order.order_id,
+                            pending.head_failures,
+                            self.max_attempts,
+                            e
+                        );
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "ALERT: order {} of user {} dead-lettered after {} failed writes, not persisted: {} ({:?})",
+                            order.order_id,
+                            order.user_id,
+                            self.max_attempts,
+                            e,
+                            order
+                        );
+                        pending.dead_letters.push(order);
+                    }
+                }
+                pending.head_failures = 0;
+                pending.orders.pop_front();
+                pending.orders.len()
+            };
+            self.report(backlog);
+        }
+        written
+    }
+
+    fn report(&self, backlog: usize) {
+        if let Some(gauge) = &self.backlog_gauge {
+            gauge.set(backlog as i64);
+        }
+    }
+
+    /// Im Hintergrund flushen (sofort nach `enqueue`, sonst im Intervall);
+    /// beim Shutdown wird der Rest noch geschrieben
+    pub async fn run(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        let mut ticker = tokio::time::interval(self.flush_interval);
+        loop {
+            tokio::select! {
+                _ = self.notify.notified() => {}
+                _ = ticker.tick() => {}
+                _ = shutdown.changed() => {
+                    self.flush().await;
+                    let left = self.backlog();
+                    if left > 0 {
+                        tracing::error!("Shutdown with {} unwritten order(s) in the write-behind buffer", left);
+                    }
+                    return;
+                }
+            }
+            self.flush().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockDynamo;
+
+    fn order(id: &str) -> OrderItem {
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            "BUY".to_string(),
+            "market".to_string(),
+            1.0,
+            None,
+        );
+        order.order_id = id.to_string();
+        order
+    }
+
+    #[tokio::test]
+    async fn test_orders_are_buffered_and_flushed() {
+        let dynamo = MockDynamo::start().await;
+        let metrics = Metrics::new();
+        let buffer = Arc::new(
+            OrderWriteBuffer::new(Arc::new(dynamo.store().await), 2, Duration::from_millis(50)).with_metrics(&metrics),
+        );
+
+        buffer.reserve().unwrap().enqueue(order("o-1"));
+        buffer.reserve().unwrap().enqueue(order("o-2"));
+        assert!(dynamo.requests("PutItem").is_empty());
+        assert_eq!(metrics.order_write_backlog.get(), 2);
+        assert_eq!(buffer.pending_order("user-1", "o-2").unwrap().order_id, "o-2");
+
+        // Backlog am Limit -> neue Orders abgelehnt
+        let full = buffer.reserve().err().unwrap();
+        assert_eq!(full.backlog, 2);
+
+        assert_eq!(buffer.flush().await, 2);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 2);
+        assert_eq!(puts[0]["Item"]["order_id"]["S"], "o-1");
+        assert_eq!(puts[1]["Item"]["order_id"]["S"], "o-2");
+        assert_eq!(metrics.order_write_backlog.get(), 0);
+        assert!(buffer.reserve().is_ok());
+        assert!(buffer.pending_order("user-1", "o-2").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reservations_count_against_the_backlog() {
+        let dynamo = MockDynamo::start().await;
+        let buffer = Arc::new(OrderWriteBuffer::new(Arc::new(dynamo.store().await), 2, Duration::from_millis(50)));
+
+        // Zwei parallele Orders halten ihre Plätze, bevor sie einreihen
+        let first = buffer.reserve().unwrap();
+        let second = buffer.reserve().unwrap();
+        assert_eq!(buffer.reserve().err().unwrap().backlog, 2);
+
+        first.enqueue(order("o-1"));
+        assert_eq!(buffer.reserve().err().unwrap().backlog, 2);
+
+        // Abgebrochene Order gibt ihren Platz frei
+        drop(second);
+        assert!(buffer.reserve().is_ok());
+        assert_eq!(buffer.backlog(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanently_failing_order_is_dead_lettered() {
+        let dynamo = MockDynamo::start().await;
+        let buffer = Arc::new(
+            OrderWriteBuffer::new(Arc::new(dynamo.store().await), 10, Duration::from_millis(50)).with_max_attempts(2),
+        );
+        buffer.reserve().unwrap().enqueue(order("bad"));
+        buffer.reserve().unwrap().enqueue(order("o-2"));
+        dynamo.respond_error("PutItem", "ValidationException");
+        dynamo.respond_error("PutItem", "ValidationException");
+
+        // Erster Fehlschlag: bleibt vorne, nichts dahinter wird geschrieben
+        assert_eq!(buffer.flush().await, 0);
+        assert_eq!(buffer.backlog(), 2);
+
+        // Zweiter Fehlschlag: Dead Letter, die nächste Order wird geschrieben
+        assert_eq!(buffer.flush().await, 1);
+        assert_eq!(buffer.backlog(), 0);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 3);
+        assert_eq!(puts[2]["Item"]["order_id"]["S"], "o-2");
+        let dead: Vec<_> = buffer.dead_letters().into_iter().map(|o| o.order_id).collect();
+        assert_eq!(dead, vec!["bad"]);
+        assert!(buffer.pending_order("user-1", "bad").is_some());
+
+        // Ein später gespeicherter Stand ersetzt den Dead Letter
+        buffer.write_through(&order("bad")).await.unwrap();
+        assert!(buffer.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_updates_queue_behind_buffered_state_and_overlay_reads() {
+        let dynamo = MockDynamo::start().await;
+        let buffer = Arc::new(OrderWriteBuffer::new(Arc::new(dynamo.store().await), 1, Duration::from_millis(50)));
+        let mut placed = order("o-1");
+        placed.status = "open".to_string();
+        buffer.reserve().unwrap().enqueue(placed.clone());
+
+        // Gespeichert ist nur o-stored; der Puffer ergänzt o-1
+        let merged = buffer.overlay("user-1", vec![order("o-stored")]);
+        let ids: Vec<_> = merged.iter().map(|o| o.order_id.as_str()).collect();
+        assert_eq!(ids, vec!["o-stored", "o-1"]);
+
+        // Fill-Update überholt den gepufferten Stand nicht, auch über dem Limit
+        let mut filled = placed.clone();
+        filled.status = "filled".to_string();
+        buffer.write_through(&filled).await.unwrap();
+        assert!(dynamo.requests("PutItem").is_empty());
+        assert_eq!(buffer.overlay("user-1", Vec::new())[0].status, "filled");
+
+        // Nicht gepufferte Orders werden direkt geschrieben
+        buffer.write_through(&order("o-stored")).await.unwrap();
+        assert_eq!(dynamo.requests("PutItem").len(), 1);
+
+        assert_eq!(buffer.flush().await, 2);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts[1]["Item"]["status"]["S"], "open");
+        assert_eq!(puts[2]["Item"]["status"]["S"], "filled");
+    }
+}
@@ -0,0 +1,187 @@
+//! Test-Hilfen: lokale Mock-Server für MEXC und DynamoDB
+use crate::mexc::MexcClient;
+use crate::storage::DynamoDBStore;
+use crate::utils::Config;
+use aws_sdk_dynamodb::config::{BehaviorVersion, Credentials, Region};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Starte einen Router auf einem freien lokalen Port und gib die Base-URL zurück
+pub async fn spawn_server(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind mock server");
+    let addr = listener.local_addr().expect("Mock server has no address");
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.ok();
+    });
+    format!("http://{}", addr)
+}
+
+/// Test-Config mit Dummy-Credentials gegen die angegebene MEXC Base-URL
+pub fn test_config(base_url: &str) -> Config {
+    Config {
+        mexc_api_key: "test-key".to_string(),
+        mexc_secret_key: "test-secret".to_string(),
+        mexc_base_url: base_url.to_string(),
+        ..Default::default()
+    }
+}
+
+/// MEXC Client gegen die angegebene Base-URL
+pub fn mexc_client(base_url: &str) -> MexcClient {
+    MexcClient::new(&test_config(base_url)).expect("Failed to create MEXC client")
+}
+
+type RecordedRequests = Arc<Mutex<Vec<(String, Value)>>>;
+type ScriptedResponses = Arc<Mutex<HashMap<String, VecDeque<(StatusCode, Value)>>>>;
+
+/// Mock DynamoDB Endpoint: zeichnet Requests pro Operation auf und
+/// antwortet mit vorgegebenen Bodies (Default: leeres JSON-Objekt)
+#[derive(Clone)]
+pub struct MockDynamo {
+    endpoint: String,
+    requests: RecordedRequests,
+    responses: ScriptedResponses,
+}
+
+impl MockDynamo {
+    pub async fn start() -> Self {
+        let requests: RecordedRequests = Arc::default();
+        let responses: ScriptedResponses = Arc::default();
+
+        let router = {
+            let requests = requests.clone();
+            let responses = responses.clone();
+            Router::new().route(
+                "/",
+                post(move |headers: HeaderMap, body: String| {
+                    let requests = requests.clone();
+                    let responses = responses.clone();
+                    async move {
+                        let operation = headers
+                            .get("x-amz-target")
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|v| v.split('.').nth(1))
+                            .unwrap_or_default()
+                            .to_string();
+                        let payload: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+                        requests.lock().unwrap().push((operation.clone(), payload));
+
+                        let (status, reply) = responses
+                            .lock()
+                            .unwrap()
+                            .get_mut(&operation)
+                            .and_then(|queue| queue.pop_front())
+                            .unwrap_or_else(|| (StatusCode::OK, serde_json::json!({})));
+
+                        (
+                            status,
+                            [(header::CONTENT_TYPE, "application/x-amz-json-1.0")],
+                            reply.to_string(),
+                        )
+                            .into_response()
+                    }
+                }),
+            )
+        };
+
+        let endpoint = spawn_server(router).await;
+        Self {
+            endpoint,
+            requests,
+            responses,
+        }
+    }
+
+    /// Lege die nächste Antwort für eine Operation fest (z.B. "Query")
+    pub fn respond(&self, operation: &str, body: Value) {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(operation.to_string())
+            .or_default()
+            .push_back((StatusCode::OK, body));
+    }
+
+    /// Lege einen DynamoDB-Fehler als nächste Antwort fest
+    /// (z.B. "ConditionalCheckFailedException")
+    pub fn respond_error(&self, operation: &str, error_type: &str) {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(operation.to_string())
+            .or_default()
+            .push_back((
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({
+                    "__type": format!("com.amazonaws.dynamodb.v20120810#{}", error_type),
+                    "message": "mock error",
+                }),
+            ));
+    }
+
+    /// Lege einen 5xx-Fehler (InternalServerError) als nächste Antwort fest
+    pub fn respond_server_error(&self, operation: &str) {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(operation.to_string())
+            .or_default()
+            .push_back((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({
+                    "__type": "com.amazonaws.dynamodb.v20120810#InternalServerError",
+                    "message": "mock outage",
+                }),
+            ));
+    }
+
+    /// Alle aufgezeichneten Request-Bodies einer Operation
+    pub fn requests(&self, operation: &str) -> Vec<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(op, _)| op == operation)
+            .map(|(_, body)| body.clone())
+            .collect()
+    }
+
+    /// DynamoDB Client gegen diesen Mock-Endpoint
+    pub fn client(&self) -> aws_sdk_dynamodb::Client {
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+            .endpoint_url(&self.endpoint)
+            // Keine SDK-Retries: jede gescriptete Antwort entspricht genau einem Request
+            .retry_config(aws_sdk_dynamodb::config::retry::RetryConfig::disabled())
+            .build();
+        aws_sdk_dynamodb::Client::from_conf(config)
+    }
+
+    /// SSM Client gegen diesen Mock-Endpoint (gleiches AWS-JSON-Protokoll)
+    pub fn ssm_client(&self) -> aws_sdk_ssm::Client {
+        let config = aws_sdk_ssm::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+            .endpoint_url(&self.endpoint)
+            .retry_config(aws_sdk_ssm::config::retry::RetryConfig::disabled())
+            .build();
+        aws_sdk_ssm::Client::from_conf(config)
+    }
+
+    /// DynamoDB Store gegen diesen Mock-Endpoint
+    pub async fn store(&self) -> DynamoDBStore {
+        DynamoDBStore::from_client(self.client(), "test_table".to_string())
+    }
+}
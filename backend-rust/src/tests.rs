@@ -14,7 +14,8 @@ mod integration_tests {
             aws_region: "ap-southeast-1".to_string(),
             dynamodb_table: "mexc_trading_data".to_string(),
             rust_api_port: 8080,
-            jwt_secret: "test-secret".to_string(),
+            jwt_secret: Some("test-secret".to_string()),
+            ..Default::default()
         };
 
         if config.mexc_api_key.is_empty() {
@@ -48,7 +49,8 @@ mod integration_tests {
             aws_region: "ap-southeast-1".to_string(),
             dynamodb_table: "mexc_trading_data".to_string(),
             rust_api_port: 8080,
-            jwt_secret: "test-secret".to_string(),
+            jwt_secret: Some("test-secret".to_string()),
+            ..Default::default()
         };
 
         match DynamoDBStore::new(config.dynamodb_table.clone()).await {
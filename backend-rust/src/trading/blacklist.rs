@@ -0,0 +1,90 @@
+use std::sync::RwLock;
+
+/// Symbol Blacklist für bekannte Scam/Honeypot Tokens
+/// Unterstützt exakte Symbole ("SCAMUSDT") und Wildcard-Prefixe ("PEPE*")
+pub struct SymbolBlacklist {
+    /// Statische Einträge aus der Config
+    static_patterns: Vec<String>,
+    /// Zur Laufzeit aus DynamoDB geladene Einträge
+    dynamic_patterns: RwLock<Vec<String>>,
+}
+
+impl SymbolBlacklist {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            static_patterns: normalize(patterns),
+            dynamic_patterns: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Ersetze die dynamischen Einträge (z.B. nach Reload aus DynamoDB)
+    pub fn replace_dynamic(&self, patterns: &[String]) {
+        let mut dynamic = self
+            .dynamic_patterns
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        *dynamic = normalize(patterns);
+    }
+
+    /// Prüfe ob ein Symbol geblockt ist
+    pub fn is_blocked(&self, symbol: &str) -> bool {
+        let symbol = symbol.trim().to_uppercase();
+        if self.static_patterns.iter().any(|p| matches(p, &symbol)) {
+            return true;
+        }
+
+        let dynamic = self
+            .dynamic_patterns
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        dynamic.iter().any(|p| matches(p, &symbol))
+    }
+}
+
+fn normalize(patterns: &[String]) -> Vec<String> {
+    patterns
+        .iter()
+        .map(|p| p.trim().to_uppercase())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+fn matches(pattern: &str, symbol: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => symbol.starts_with(prefix),
+        None => pattern == symbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match_block() {
+        let blacklist = SymbolBlacklist::new(&["scamusdt".to_string()]);
+        assert!(blacklist.is_blocked("SCAMUSDT"));
+        assert!(!blacklist.is_blocked("SCAMUSDC"));
+        assert!(!blacklist.is_blocked("ETHUSDT"));
+    }
+
+    #[test]
+    fn test_wildcard_prefix_block() {
+        let blacklist = SymbolBlacklist::new(&["HONEY*".to_string()]);
+        assert!(blacklist.is_blocked("HONEYPOTUSDT"));
+        assert!(blacklist.is_blocked("honeyusdt"));
+        assert!(!blacklist.is_blocked("XHONEYUSDT"));
+    }
+
+    #[test]
+    fn test_dynamic_patterns_replace() {
+        let blacklist = SymbolBlacklist::new(&[]);
+        assert!(!blacklist.is_blocked("RUGUSDT"));
+
+        blacklist.replace_dynamic(&["RUG*".to_string()]);
+        assert!(blacklist.is_blocked("RUGUSDT"));
+
+        blacklist.replace_dynamic(&[]);
+        assert!(!blacklist.is_blocked("RUGUSDT"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::mexc::OrderBook;
+use crate::storage::{OrderItem, OrderStatus};
+use crate::trading::precision::PrecisionCache;
+use crate::utils::Config;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Nächster Schritt für eine liegengebliebene Limit-Snipe
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChaseAction {
+    /// Stornieren und zum neuen Limit neu platzieren
+    Reprice { price: f64 },
+    /// Max. Abstand erreicht und weiter ungefüllt: stornieren
+    Cancel,
+}
+
+/// Ungefüllte Limit-Snipes, denen der Markt davongelaufen ist, schrittweise
+/// Richtung bestem Ask (Buy) bzw. Bid (Sell) nachziehen. Der Abstand zum
+/// ursprünglichen Limit ist gedeckelt; am Deckel wird storniert.
+#[derive(Clone, Default)]
+pub struct LimitChaser {
+    /// Alter, ab dem eine ungefüllte Limit-Snipe nachgezogen wird (0 = aus)
+    pub min_age: Duration,
+    /// Schritt je Nachziehen in Prozent des aktuellen Limits
+    pub step_pct: f64,
+    /// Max. Abstand zum ursprünglichen Limit in Prozent
+    pub max_distance_pct: f64,
+    /// Neue Limits auf die Preis-Präzision des Symbols runden
+    precision: Option<Arc<PrecisionCache>>,
+}
+
+impl LimitChaser {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            min_age: Duration::from_secs(config.snipe_chase_after_secs),
+            step_pct: config.snipe_chase_step_pct,
+            max_distance_pct: config.snipe_chase_max_pct,
+            precision: None,
+        }
+    }
+
+    pub fn with_precision_cache(mut self, precision: Arc<PrecisionCache>) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.min_age.is_zero() && self.step_pct > 0.0
+    }
+
+    /// Offene, ungefüllte Limit-Snipe, die älter als `min_age` ist
+    pub fn is_due(&self, order: &OrderItem, now_millis: i64) -> bool {
+        self.is_enabled()
+            && order.event_id.is_some()
+            && !order.paper
+            && order.mexc_order_id.is_some()
+            && order.order_type.eq_ignore_ascii_case("limit")
+            && order.status == OrderStatus::Open.as_str()
+            && order.filled_qty <= 0.0
+            && order.price.is_some_and(|p| p > 0.0)
+            && now_millis - order.timestamp >= self.min_age.as_millis() as i64
+    }
+
+    /// Nachziehen um `step_pct`, höchstens bis zum Buch und zum Deckel; None wenn
+    /// das Limit das Buch schon erreicht oder die Gegenseite leer ist
+    pub fn next_action(&self, order: &OrderItem, book: &OrderBook) -> Option<ChaseAction> {
+        let price = order.price?;
+        let origin = order.chase_origin_price.unwrap_or(price);
+        let sell = order.side.eq_ignore_ascii_case("sell");
+        let (target, step, cap) = if sell {
+            (
+                book.best_bid()?,
+                price * (1.0 - self.step_pct / 100.0),
+                origin * (1.0 - self.max_distance_pct / 100.0),
+            )
+        } else {
+            (
+                book.best_ask()?,
+                price * (1.0 + self.step_pct / 100.0),
+                origin * (1.0 + self.max_distance_pct / 100.0),
+            )
+        };
+
+        let (reached_book, at_cap, next) = if sell {
+            (price <= target, price <= cap, step.max(target).max(cap))
+        } else {
+            (price >= target, price >= cap, step.min(target).min(cap))
+        };
+        if reached_book {
+            return None;
+        }
+        if at_cap {
+            return Some(ChaseAction::Cancel);
+        }
+        let next = match &self.precision {
+            Some(precision) => precision.round_price(&order.symbol, next),
+            None => next,
+        };
+        // Rundung frisst den Schritt auf: weiteres Nachziehen wäre wirkungslos
+        let progressed = if sell { next < price } else { next > price };
+        Some(if progressed { ChaseAction::Reprice { price: next } } else { ChaseAction::Cancel })
+    }
+
+    /// Nachfolger der stornierten Order zum neuen Limit (Rest-Menge, gleiches
+    /// Event/Konto, ursprüngliches Limit bleibt für den Deckel erhalten)
+    pub fn replacement(&self, order: &OrderItem, price: f64) -> OrderItem {
+        let mut replacement = OrderItem::new(
+            order.user_id.clone(),
+            order.symbol.clone(),
+            order.side.clone(),
+            order.order_type.clone(),
+            order.quantity - order.filled_qty,
+            Some(price),
+        );
+        replacement.event_id = order.event_id.clone();
+        replacement.account = order.account.clone();
+        replacement.strategy = order.strategy.clone();
+        replacement.replaces = Some(order.order_id.clone());
+        replacement.chase_origin_price = order.chase_origin_price.or(order.price);
+        replacement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit_snipe(price: f64) -> OrderItem {
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "NEWUSDT".to_string(),
+            "buy".to_string(),
+            "limit".to_string(),
+            1.0,
+            Some(price),
+        );
+        order.event_id = Some("event-1".to_string());
+        order.mexc_order_id = Some("mexc-1".to_string());
+        order.status = OrderStatus::Open.as_str().to_string();
+        order
+    }
+
+    #[test]
+    fn test_chases_twice_then_cancels_at_max_distance() {
+        let chaser = LimitChaser {
+            min_age: Duration::from_secs(30),
+            step_pct: 1.0,
+            max_distance_pct: 2.0,
+            precision: None,
+        };
+        // Der Markt ist auf 110 davongelaufen
+        let book = OrderBook {
+            bids: vec![(109.0, 1.0)],
+            asks: vec![(110.0, 1.0)],
+            timestamp: 0,
+        };
+
+        let order = limit_snipe(100.0);
+        assert!(!chaser.is_due(&order, order.timestamp + 29_000));
+        assert!(chaser.is_due(&order, order.timestamp + 30_000));
+
+        let mut reprices = Vec::new();
+        let mut current = order;
+        loop {
+            match chaser.next_action(&current, &book) {
+                Some(ChaseAction::Reprice { price }) => {
+                    reprices.push(price);
+                    current = chaser.replacement(&current, price);
+                }
+                Some(ChaseAction::Cancel) => break,
+                None => panic!("expected a chase or cancel at {:?}", current.price),
+            }
+        }
+
+        // 100 -> 101 -> 102 (Deckel statt 102.01), dann Storno
+        assert_eq!(reprices.len(), 2);
+        assert!((reprices[0] - 101.0).abs() < 1e-9);
+        assert!((reprices[1] - 102.0).abs() < 1e-9);
+        assert_eq!(current.chase_origin_price, Some(100.0));
+        assert!(current.replaces.is_some());
+
+        // Limit schon am besten Ask: nichts zu tun
+        let at_book = limit_snipe(110.0);
+        assert_eq!(chaser.next_action(&at_book, &book), None);
+    }
+}
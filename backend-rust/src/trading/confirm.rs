@@ -0,0 +1,42 @@
+use crate::mexc::{MexcClient, OrderResponse};
+use crate::storage::OrderStatus;
+use crate::utils::Config;
+use std::time::Duration;
+
+/// Kurzes Nachpollen nach `create_order`, bis die Order gefüllt ist.
+/// Begrenzt auf `polls` Abfragen im Abstand `interval` (0 Polls = aus).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FillConfirmation {
+    pub polls: u32,
+    pub interval: Duration,
+}
+
+impl FillConfirmation {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            polls: config.fill_confirm_polls,
+            interval: Duration::from_millis(config.fill_confirm_interval_ms),
+        }
+    }
+
+    /// Letzten bekannten Stand der Order liefern; Fehler beim Pollen beenden
+    /// die Bestätigung, die ursprüngliche Response bleibt dann gültig
+    pub async fn confirm(&self, client: &MexcClient, mut response: OrderResponse) -> OrderResponse {
+        for _ in 0..self.polls {
+            if OrderStatus::from_mexc(&response.status) != OrderStatus::Open {
+                break;
+            }
+
+            tokio::time::sleep(self.interval).await;
+            match client.get_order(&response.symbol, &response.order_id).await {
+                Ok(latest) => response = latest,
+                Err(e) => {
+                    tracing::warn!("Fill confirmation for {} failed: {}", response.order_id, e);
+                    break;
+                }
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,101 @@
+use crate::utils::Config;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Mindestabstand zwischen zwei Order-Platzierungen auf demselben Symbol, damit
+/// sich eigene Orders (z.B. parallele Snipes) nicht gegenseitig den Preis
+/// hochtreiben. Weitere Orders werden nicht abgelehnt, sondern der Reihe nach
+/// im Abstand `cooldown` freigegeben. 0 = aus.
+#[derive(Debug, Default)]
+pub struct PlacementCooldown {
+    cooldown: Duration,
+    /// Frühester freier Platzierungszeitpunkt je Symbol
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl PlacementCooldown {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(Duration::from_millis(config.order_symbol_cooldown_ms))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.cooldown.is_zero()
+    }
+
+    /// Slot für `symbol` reservieren und bis dahin warten; gibt die Wartezeit zurück
+    pub async fn wait(&self, symbol: &str) -> Duration {
+        if !self.is_enabled() {
+            return Duration::ZERO;
+        }
+
+        let now = Instant::now();
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            // Abgelaufene Einträge nicht endlos sammeln
+            next_slot.retain(|_, next| *next > now);
+            let slot = next_slot.get(symbol).copied().unwrap_or(now).max(now);
+            next_slot.insert(symbol.to_string(), slot + self.cooldown);
+            slot
+        };
+
+        let delay = slot - now;
+        if !delay.is_zero() {
+            tracing::debug!("Delaying {} order by {:?} (placement cooldown)", symbol, delay);
+            tokio::time::sleep_until(slot).await;
+        }
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_same_symbol_orders_are_spaced_by_cooldown() {
+        let cooldown = Arc::new(PlacementCooldown::new(Duration::from_millis(100)));
+        let start = Instant::now();
+
+        let placed: Vec<_> = place_concurrently(&cooldown, &["NEWUSDT", "NEWUSDT", "OTHERUSDT"]).await;
+        let mut same_symbol = [placed[0], placed[1]];
+        same_symbol.sort();
+        assert!(same_symbol[0] - start < Duration::from_millis(50));
+        assert!(same_symbol[1] - same_symbol[0] >= Duration::from_millis(100));
+        // Andere Symbole warten nicht
+        assert!(placed[2] - start < Duration::from_millis(50));
+
+        let disabled = PlacementCooldown::default();
+        assert_eq!(disabled.wait("NEWUSDT").await, Duration::ZERO);
+        assert_eq!(disabled.wait("NEWUSDT").await, Duration::ZERO);
+    }
+
+    /// Alle Symbole gleichzeitig anfragen; Zeitpunkt der Freigabe je Aufruf
+    async fn place_concurrently(cooldown: &Arc<PlacementCooldown>, symbols: &[&str]) -> Vec<Instant> {
+        let handles: Vec<_> = symbols
+            .iter()
+            .map(|symbol| {
+                let cooldown = cooldown.clone();
+                let symbol = symbol.to_string();
+                tokio::spawn(async move {
+                    cooldown.wait(&symbol).await;
+                    Instant::now()
+                })
+            })
+            .collect();
+        let mut placed = Vec::new();
+        for handle in handles {
+            placed.push(handle.await.unwrap());
+        }
+        placed
+    }
+}
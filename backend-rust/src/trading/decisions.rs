@@ -0,0 +1,133 @@
+//! Entscheidungsprotokoll für Snipes: jedes ausgewertete Gate wird als
+//! strukturiertes `trading.decision`-Log ausgegeben und in einem Ringpuffer
+//! gehalten, damit sich nachvollziehen lässt, warum ein Snipe (nicht) gefeuert hat
+use crate::utils::Config;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Gates, die ein Snipe vor dem Entry durchläuft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionGate {
+    Blacklist,
+    Confidence,
+    TradingState,
+    Liquidity,
+    BookImbalance,
+    /// Bid/Ask-Spread zu breit (Manipulationsrisiko)
+    Spread,
+    Balance,
+    Validation,
+    Cooldown,
+    /// Slippage der Canary-Order vor einem großen Entry
+    Canary,
+    /// Symbol war schon einmal gelistet (Re-Listing)
+    Relisting,
+    /// MARKET abgelehnt, Entry als IOC-Limit am Orderbuch
+    MarketFallback,
+    /// Warten auf Gegenseite im Orderbuch (leeres Buch zum Launch)
+    BookLiquidity,
+}
+
+impl DecisionGate {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blacklist => "blacklist",
+            Self::Confidence => "confidence",
+            Self::TradingState => "trading_state",
+            Self::Liquidity => "liquidity",
+            Self::BookImbalance => "book_imbalance",
+            Self::Spread => "spread",
+            Self::Balance => "balance",
+            Self::Validation => "validation",
+            Self::Cooldown => "cooldown",
+            Self::Canary => "canary",
+            Self::Relisting => "relisting",
+            Self::MarketFallback => "market_fallback",
+            Self::BookLiquidity => "book_liquidity",
+        }
+    }
+}
+
+/// Ergebnis eines Gates für ein Event
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionEvent {
+    pub timestamp: i64,
+    pub user_id: String,
+    pub event_id: String,
+    pub symbol: String,
+    pub gate: DecisionGate,
+    pub passed: bool,
+    /// Ablehnungsgrund bzw. Detail (z.B. Wartezeit im Cooldown)
+    pub reason: Option<String>,
+}
+
+/// Ringpuffer der letzten Entscheidungen (Kapazität 0 = aus, auch kein Log)
+pub struct DecisionLog {
+    capacity: usize,
+    entries: Mutex<VecDeque<DecisionEvent>>,
+}
+
+impl Default for DecisionLog {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl DecisionLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.decision_log_size)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Entscheidung loggen und aufnehmen (älteste fallen raus)
+    pub fn record(&self, decision: DecisionEvent) {
+        if !self.is_enabled() {
+            return;
+        }
+        tracing::info!(
+            target: "trading.decision",
+            user_id = %decision.user_id,
+            event_id = %decision.event_id,
+            symbol = %decision.symbol,
+            gate = decision.gate.as_str(),
+            outcome = if decision.passed { "pass" } else { "reject" },
+            reason = decision.reason.as_deref().unwrap_or(""),
+            "snipe gate evaluated"
+        );
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(decision);
+    }
+
+    /// Alle gehaltenen Entscheidungen zu einem Event in Auswertungsreihenfolge
+    pub fn for_event(&self, event_id: &str) -> Vec<DecisionEvent> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|decision| decision.event_id == event_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Die letzten `limit` Entscheidungen (neueste zuletzt)
+    pub fn recent(&self, limit: usize) -> Vec<DecisionEvent> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().skip(entries.len().saturating_sub(limit)).cloned().collect()
+    }
+}
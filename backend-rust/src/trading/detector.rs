@@ -1,3 +1,5 @@
+use crate::storage::PatternMeta;
+
 /// Pattern Detector für Auto-Sniping
 /// Erkenne Patterns: sts:2, st:2, tt:4
 pub struct PatternDetector {
@@ -10,12 +12,19 @@ impl PatternDetector {
     }
 
     /// Erkenne Pattern aus Launch Kalender Daten
+    /// Liefert nur Patterns deren Confidence die Mindest-Confidence erreicht
     pub fn detect_pattern(&self, token_name: &str, time_intervals: &[i64]) -> Option<DetectedPattern> {
+        self.classify(token_name, time_intervals)
+            .filter(|pattern| pattern.confidence >= self.min_confidence)
+    }
+
+    fn classify(&self, token_name: &str, time_intervals: &[i64]) -> Option<DetectedPattern> {
         // STS:2 - Single Token, Two Spaces (3 Tokens, 2 Spaces)
         if self.is_sts_2_pattern(token_name, time_intervals) {
             return Some(DetectedPattern {
                 pattern_type: "sts:2".to_string(),
                 confidence: 0.95,
+                meta: self.meta(time_intervals),
             });
         }
 
@@ -24,6 +33,7 @@ impl PatternDetector {
             return Some(DetectedPattern {
                 pattern_type: "st:2".to_string(),
                 confidence: 0.85,
+                meta: self.meta(time_intervals),
             });
         }
 
@@ -32,25 +42,43 @@ impl PatternDetector {
             return Some(DetectedPattern {
                 pattern_type: "tt:4".to_string(),
                 confidence: 0.75,
+                meta: self.meta(time_intervals),
             });
         }
 
         None
     }
 
+    /// Abstandsstatistik der Intervalle plus aktuelle Schwelle
+    fn meta(&self, intervals: &[i64]) -> PatternMeta {
+        let count = intervals.len();
+        let mean = intervals.iter().sum::<i64>() as f64 / count.max(1) as f64;
+        let variance = intervals
+            .iter()
+            .map(|&interval| (interval as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count.max(1) as f64;
+        PatternMeta {
+            mean_interval_ms: mean,
+            interval_cv: if mean > 0.0 { variance.sqrt() / mean } else { 0.0 },
+            sample_count: count,
+            min_confidence: self.min_confidence,
+        }
+    }
+
     fn is_sts_2_pattern(&self, _token: &str, intervals: &[i64]) -> bool {
         // STS:2 = 3 Launches mit konsistenten Abständen
-        intervals.len() >= 3 && self.min_confidence >= 0.9
+        intervals.len() == 3
     }
 
     fn is_st_2_pattern(&self, _token: &str, intervals: &[i64]) -> bool {
         // ST:2 = 2 schnelle Launches desselben Tokens
-        intervals.len() >= 2 && intervals.len() < 3 && self.min_confidence >= 0.8
+        intervals.len() == 2
     }
 
     fn is_tt_4_pattern(&self, _token: &str, intervals: &[i64]) -> bool {
         // TT:4 = 4 Token Launches
-        intervals.len() == 4 && self.min_confidence >= 0.7
+        intervals.len() == 4
     }
 }
 
@@ -58,6 +86,7 @@ impl PatternDetector {
 pub struct DetectedPattern {
     pub pattern_type: String,
     pub confidence: f64,
+    pub meta: PatternMeta,
 }
 
 #[cfg(test)]
@@ -71,4 +100,31 @@ mod tests {
         let pattern = detector.detect_pattern("VFARM", &intervals);
         assert!(pattern.is_some());
     }
+
+    #[test]
+    fn test_four_launches_detect_tt_4() {
+        let detector = PatternDetector::new(0.7);
+        let pattern = detector.detect_pattern("VFARM", &[1000, 2000, 3000, 4000]).unwrap();
+        assert_eq!(pattern.pattern_type, "tt:4");
+        assert_eq!(pattern.confidence, 0.75);
+
+        // Über der tt:4-Confidence wird nichts gemeldet
+        assert!(PatternDetector::new(0.8).detect_pattern("VFARM", &[1000, 2000, 3000, 4000]).is_none());
+        assert_eq!(
+            detector.detect_pattern("VFARM", &[1000, 2000, 3000]).unwrap().pattern_type,
+            "sts:2"
+        );
+    }
+
+    #[test]
+    fn test_detection_populates_meta() {
+        let detector = PatternDetector::new(0.8);
+        let pattern = detector.detect_pattern("VFARM", &[1000, 2000, 3000]).unwrap();
+
+        assert_eq!(pattern.meta.sample_count, 3);
+        assert_eq!(pattern.meta.mean_interval_ms, 2000.0);
+        // Standardabweichung sqrt(2/3) * 1000 bei Mittelwert 2000
+        assert!((pattern.meta.interval_cv - (2.0f64 / 3.0).sqrt() / 2.0).abs() < 1e-9);
+        assert_eq!(pattern.meta.min_confidence, 0.8);
+    }
 }
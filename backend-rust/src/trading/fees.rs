@@ -0,0 +1,87 @@
+use crate::mexc::TradeFill;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Summe der Gebühren je Gebühren-Asset (Assets werden nicht ineinander umgerechnet)
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FeeTotals {
+    pub fills: usize,
+    pub by_asset: BTreeMap<String, f64>,
+}
+
+impl FeeTotals {
+    fn add(&mut self, fill: &TradeFill) {
+        self.fills += 1;
+        *self.by_asset.entry(fill.fee_asset.clone()).or_insert(0.0) += fill.fee;
+    }
+}
+
+/// Gezahlte Gebühren im Zeitraum: gesamt, pro Symbol und Maker/Taker
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FeeReport {
+    pub total: FeeTotals,
+    pub by_symbol: BTreeMap<String, FeeTotals>,
+    pub maker: FeeTotals,
+    pub taker: FeeTotals,
+}
+
+/// Aggregiere Gebühren aus eigenen Fills (`myTrades`)
+pub fn summarize_fees(fills: &[TradeFill]) -> FeeReport {
+    let mut report = FeeReport::default();
+    for fill in fills {
+        report.total.add(fill);
+        report.by_symbol.entry(fill.symbol.clone()).or_default().add(fill);
+        if fill.is_maker {
+            report.maker.add(fill);
+        } else {
+            report.taker.add(fill);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(symbol: &str, fee: f64, fee_asset: &str, is_maker: bool) -> TradeFill {
+        TradeFill {
+            symbol: symbol.to_string(),
+            trade_id: format!("t-{}-{}", symbol, fee),
+            order_id: "o-1".to_string(),
+            price: 1.0,
+            quantity: 1.0,
+            quote_qty: 1.0,
+            fee,
+            fee_asset: fee_asset.to_string(),
+            time: 1_000,
+            is_buyer: true,
+            is_maker,
+        }
+    }
+
+    #[test]
+    fn test_fees_aggregate_by_asset_symbol_and_liquidity() {
+        let fills = vec![
+            fill("ETHUSDT", 0.001, "ETH", false),
+            fill("ETHUSDT", 1.5, "USDT", true),
+            fill("BTCUSDT", 2.0, "USDT", false),
+            fill("BTCUSDT", 0.25, "MX", false),
+        ];
+
+        let report = summarize_fees(&fills);
+
+        assert_eq!(report.total.fills, 4);
+        assert_eq!(report.total.by_asset["USDT"], 3.5);
+        assert_eq!(report.total.by_asset["ETH"], 0.001);
+        assert_eq!(report.total.by_asset["MX"], 0.25);
+        assert_eq!(report.by_symbol["ETHUSDT"].fills, 2);
+        assert_eq!(report.by_symbol["ETHUSDT"].by_asset["USDT"], 1.5);
+        assert_eq!(report.by_symbol["BTCUSDT"].by_asset["USDT"], 2.0);
+        assert_eq!(report.maker.fills, 1);
+        assert_eq!(report.maker.by_asset["USDT"], 1.5);
+        assert_eq!(report.taker.fills, 3);
+        assert_eq!(report.taker.by_asset["USDT"], 2.0);
+        assert!(!report.maker.by_asset.contains_key("ETH"));
+    }
+}
@@ -0,0 +1,464 @@
+//! Notfall-Glattstellung: alle offenen Orders eines Users stornieren und alle
+//! offenen Positionen per Market-Order schließen
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::mexc::{MexcClient, MexcClientPool, OrderRequest, UserClientError};
+use crate::storage::{DynamoDBStore, OrderItem, OrderStatus, PositionItem};
+use crate::trading::PositionManager;
+use crate::utils::{AlertSink, Config};
+
+/// Fehler, die das Glattstellen als Ganzes verhindern (Einzelfehler stehen in der Summary)
+#[derive(Debug)]
+pub enum FlattenError {
+    /// Für den User läuft bereits ein Flatten
+    InProgress(String),
+    Client(UserClientError),
+    Store(anyhow::Error),
+}
+
+impl fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InProgress(user_id) => write!(f, "Flatten already in progress for user {}", user_id),
+            Self::Client(e) => write!(f, "{}", e),
+            Self::Store(e) => write!(f, "Failed to load open orders/positions: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FlattenError {}
+
+/// Eine Aktion des Flattens (Cancel einer Order oder Close einer Position)
+#[derive(Debug, Clone, Serialize)]
+pub struct FlattenAction {
+    /// "cancel_order" oder "close_position"
+    pub action: &'static str,
+    /// Lokale Order- bzw. Position-ID
+    pub id: String,
+    pub symbol: String,
+    /// "cancelled", "filled", "closed" oder "error"
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realized_pnl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl FlattenAction {
+    fn failed(action: &'static str, id: &str, symbol: &str, message: String) -> Self {
+        Self {
+            action,
+            id: id.to_string(),
+            symbol: symbol.to_string(),
+            status: "error",
+            realized_pnl: None,
+            message: Some(message),
+        }
+    }
+}
+
+/// Ergebnis eines Flattens; ein zweiter Aufruf ohne offene Orders/Positionen ist leer
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FlattenSummary {
+    pub user_id: String,
+    pub orders_cancelled: usize,
+    pub positions_closed: usize,
+    pub errors: usize,
+    pub total_realized_pnl: f64,
+    pub actions: Vec<FlattenAction>,
+}
+
+/// Stellt alle Positionen eines Users glatt: erst Orders stornieren (gibt
+/// reserviertes Guthaben frei), dann die Positionen parallel (begrenzt) schließen
+pub struct Flattener {
+    clients: Arc<MexcClientPool>,
+    store: Arc<DynamoDBStore>,
+    positions: Arc<PositionManager>,
+    concurrency: usize,
+    alerts: Option<Arc<AlertSink>>,
+    in_progress: Mutex<HashSet<String>>,
+}
+
+/// Gibt den User beim Verlassen wieder frei (auch bei Fehlern)
+struct InProgressGuard<'a> {
+    users: &'a Mutex<HashSet<String>>,
+    user_id: String,
+}
+
+impl Drop for InProgressGuard<'_> {
+    fn drop(&mut self) {
+        self.users.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.user_id);
+    }
+}
+
+impl Flattener {
+    pub fn new(clients: Arc<MexcClientPool>, store: Arc<DynamoDBStore>, concurrency: usize) -> Self {
+        Self {
+            clients,
+            positions: Arc::new(PositionManager::new(store.clone())),
+            store,
+            concurrency: concurrency.max(1),
+            alerts: None,
+            in_progress: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Concurrency aus FLATTEN_CONCURRENCY, Positions-Events wie beim PositionManager
+    pub fn from_config(clients: Arc<MexcClientPool>, store: Arc<DynamoDBStore>, config: &Config) -> Self {
+        Self::new(clients, store.clone(), config.flatten_concurrency)
+            .with_position_manager(Arc::new(PositionManager::from_config(store, config)))
+    }
+
+    pub fn with_position_manager(mut self, positions: Arc<PositionManager>) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    /// Alert je geschlossener Position
+    pub fn with_alerts(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Alle offenen Orders stornieren und alle offenen Positionen glattstellen
+    pub async fn flatten(&self, user_id: &str) -> Result<FlattenSummary, FlattenError> {
+        if !self
+            .in_progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(user_id.to_string())
+        {
+            return Err(FlattenError::InProgress(user_id.to_string()));
+        }
+        let _guard = InProgressGuard {
+            users: &self.in_progress,
+            user_id: user_id.to_string(),
+        };
+
+        let orders = self
+            .store
+            .query_orders_by_status(user_id, OrderStatus::Open.as_str())
+            .await
+            .map_err(FlattenError::Store)?;
+        let positions = self.store.query_open_positions(user_id).await.map_err(FlattenError::Store)?;
+        tracing::warn!(
+            "Flattening user {}: {} open orders, {} open positions",
+            user_id,
+            orders.len(),
+            positions.len()
+        );
+
+        let mut summary = FlattenSummary {
+            user_id: user_id.to_string(),
+            ..Default::default()
+        };
+        if orders.is_empty() && positions.is_empty() {
+            return Ok(summary);
+        }
+        let client = self.clients.for_user(user_id).await.map_err(FlattenError::Client)?;
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+
+        let mut tasks = JoinSet::new();
+        for order in orders {
+            let (client, store, permits) = (client.clone(), self.store.clone(), permits.clone());
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                cancel_order(&client, &store, order).await
+            });
+        }
+        collect(&mut tasks, &mut summary).await;
+
+        for position in positions {
+            let (client, store, permits) = (client.clone(), self.store.clone(), permits.clone());
+            let (manager, alerts) = (self.positions.clone(), self.alerts.clone());
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let action = close_position(&client, &store, &manager, position).await;
+                if let (Some(alerts), "closed") = (&alerts, action.status) {
+                    let message = format!(
+                        "Flatten closed {} position {} (realized PnL {:.4})",
+                        action.symbol,
+                        action.id,
+                        action.realized_pnl.unwrap_or(0.0)
+                    );
+                    alerts.alert(&format!("flatten:{}", action.id), &message).await;
+                }
+                action
+            });
+        }
+        collect(&mut tasks, &mut summary).await;
+
+        tracing::warn!(
+            "Flatten of user {} done: {} orders cancelled, {} positions closed, {} errors, PnL {}",
+            user_id,
+            summary.orders_cancelled,
+            summary.positions_closed,
+            summary.errors,
+            summary.total_realized_pnl
+        );
+        Ok(summary)
+    }
+}
+
+/// Ergebnisse der Tasks in die Summary übernehmen
+async fn collect(tasks: &mut JoinSet<FlattenAction>, summary: &mut FlattenSummary) {
+    while let Some(joined) = tasks.join_next().await {
+        let action = match joined {
+            Ok(action) => action,
+            Err(e) => {
+                tracing::error!("Flatten task failed: {}", e);
+                summary.errors += 1;
+                continue;
+            }
+        };
+        match (action.action, action.status) {
+            ("cancel_order", "cancelled") => summary.orders_cancelled += 1,
+            ("close_position", "closed") => {
+                summary.positions_closed += 1;
+                summary.total_realized_pnl += action.realized_pnl.unwrap_or(0.0);
+            }
+            (_, "error") => summary.errors += 1,
+            _ => {}
+        }
+        summary.actions.push(action);
+    }
+}
+
+/// Order bei MEXC stornieren; schlägt das fehl, den tatsächlichen Status übernehmen
+/// (bereits storniert oder gefüllt)
+async fn cancel_order(client: &MexcClient, store: &DynamoDBStore, mut order: OrderItem) -> FlattenAction {
+    const ACTION: &str = "cancel_order";
+    let status = match &order.mexc_order_id {
+        Some(mexc_order_id) => match client.cancel_order(&order.symbol, mexc_order_id).await {
+            Ok(_) => OrderStatus::Cancelled,
+            Err(e) => match client.get_order(&order.symbol, mexc_order_id).await {
+                Ok(current) => OrderStatus::from_mexc(&current.status),
+                Err(_) => return FlattenAction::failed(ACTION, &order.order_id, &order.symbol, e.to_string()),
+            },
+        },
+        // Nie bei MEXC angekommen: nur lokal schließen
+        None => OrderStatus::Cancelled,
+    };
+    let status = match status {
+        OrderStatus::Cancelled => "cancelled",
+        OrderStatus::Filled => "filled",
+        other => {
+            let message = format!("order still {} after cancel", other.as_str());
+            return FlattenAction::failed(ACTION, &order.order_id, &order.symbol, message);
+        }
+    };
+
+    order.status = status.to_string();
+    order.updated_at = chrono::Utc::now().to_rfc3339();
+    if let Err(e) = store.put_order(&order).await {
+        tracing::error!("Failed to store flattened order {}: {}", order.order_id, e);
+    }
+    FlattenAction {
+        action: ACTION,
+        id: order.order_id,
+        symbol: order.symbol,
+        status,
+        realized_pnl: None,
+        message: None,
+    }
+}
+
+/// Position per Market-Order auf der Gegenseite schließen. Die Client-Order-ID ist
+/// je Position fest, ein wiederholter Aufruf platziert also keine zweite Order.
+async fn close_position(
+    client: &MexcClient,
+    store: &DynamoDBStore,
+    manager: &PositionManager,
+    position: PositionItem,
+) -> FlattenAction {
+    const ACTION: &str = "close_position";
+    let failed =
+        |message: String| FlattenAction::failed(ACTION, &position.position_id, &position.symbol, message);
+    let side = if position.side == "short" { "BUY" } else { "SELL" };
+
+    let mut order = OrderItem::new(
+        position.user_id.clone(),
+        position.symbol.clone(),
+        side.to_string(),
+        "market".to_string(),
+        position.quantity,
+        None,
+    );
+    order.order_id = format!("flatten-{}", position.position_id);
+    order.account = position.account.clone();
+    order.strategy = position.strategy.clone();
+
+    let response = match client
+        .create_order(&OrderRequest {
+            symbol: position.symbol.clone(),
+            side: side.to_string(),
+            order_type: "MARKET".to_string(),
+            quantity: position.quantity,
+            price: None,
+            quote_order_qty: None,
+            client_order_id: Some(order.order_id.clone()),
+        })
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return failed(e.to_string()),
+    };
+    order.mexc_order_id = Some(response.order_id.clone());
+    order.status = OrderStatus::from_mexc(&response.status).as_str().to_string();
+    order.filled_qty = response.filled_qty;
+    if let Err(e) = store.put_order(&order).await {
+        tracing::error!("Failed to store flatten order {}: {}", order.order_id, e);
+    }
+
+    // Ohne Fill-Preis in der Antwort zum zuletzt bekannten Preis verbuchen
+    let fill_price = response.average_fill_price().unwrap_or(position.current_price);
+    match manager
+        .close_position(&position.user_id, &position.position_id, fill_price, "flatten")
+        .await
+    {
+        Ok(pnl) => FlattenAction {
+            action: ACTION,
+            id: position.position_id.clone(),
+            symbol: position.symbol.clone(),
+            status: "closed",
+            realized_pnl: Some(pnl),
+            message: None,
+        },
+        Err(e) => failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::{
+        extract::Query,
+        routing::post,
+        Json, Router,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn position(id: &str, symbol: &str) -> serde_json::Value {
+        json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": format!("POSITION#1#{}", id) },
+            "position_id": { "S": id },
+            "symbol": { "S": symbol },
+            "entry_price": { "N": "100" },
+            "current_price": { "N": "100" },
+            "quantity": { "N": "1" },
+            "side": { "S": "long" },
+            "entry_time": { "N": "1" },
+            "status": { "S": "open" },
+            "updated_at": { "S": "2024-01-01T00:00:00Z" },
+            "ttl": { "N": "0" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_flatten_cancels_orders_and_closes_positions() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|Query(params): Query<HashMap<String, String>>| async move {
+                    let price = if params["symbol"] == "ETHUSDT" { 110.0 } else { 120.0 };
+                    Json(json!({
+                        "order_id": format!("mexc-{}", params["newClientOrderId"]),
+                        "symbol": params["symbol"],
+                        "side": params["side"],
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": price,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                })
+                .delete(|| async {
+                    Json(json!({
+                        "order_id": "mexc-open-1",
+                        "symbol": "ETHUSDT",
+                        "side": "SELL",
+                        "order_type": "LIMIT",
+                        "quantity": 1.0,
+                        "price": 150.0,
+                        "status": "CANCELED",
+                        "filled_qty": 0.0,
+                        "created_at": 0,
+                    }))
+                }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 1,
+                "Items": [{
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": "ORDER#1#open-1" },
+                    "order_id": { "S": "open-1" },
+                    "symbol": { "S": "ETHUSDT" },
+                    "side": { "S": "SELL" },
+                    "order_type": { "S": "LIMIT" },
+                    "quantity": { "N": "1" },
+                    "price": { "N": "150" },
+                    "filled_qty": { "N": "0" },
+                    "status": { "S": "open" },
+                    "timestamp": { "N": "1" },
+                    "created_at": { "S": "2024-01-01T00:00:00Z" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "mexc_order_id": { "S": "mexc-open-1" },
+                    "ttl": { "N": "0" }
+                }]
+            }),
+        );
+        let open_positions = [position("pos-1", "ETHUSDT"), position("pos-2", "SOLUSDT")];
+        dynamo.respond("Query", json!({ "Count": 2, "Items": open_positions }));
+        // close_position liest jede Position noch einmal (strongly consistent)
+        for item in &open_positions {
+            dynamo.respond("Query", json!({ "Count": 1, "Items": [item] }));
+        }
+
+        let clients = Arc::new(MexcClientPool::single_tenant(Arc::new(mexc_client(&base_url))));
+        let alerts = Arc::new(AlertSink::new(None, Duration::from_secs(300), 10));
+        let flattener = Flattener::new(clients, Arc::new(dynamo.store().await), 2).with_alerts(alerts);
+
+        let summary = flattener.flatten("user-1").await.unwrap();
+        assert_eq!(summary.orders_cancelled, 1);
+        assert_eq!(summary.positions_closed, 2);
+        assert_eq!(summary.errors, 0);
+        assert!((summary.total_realized_pnl - 30.0).abs() < 1e-9);
+        assert_eq!(summary.actions.len(), 3);
+        assert_eq!(summary.actions[0].action, "cancel_order");
+        assert_eq!(summary.actions[0].status, "cancelled");
+
+        let puts = dynamo.requests("PutItem");
+        let cancelled = puts.iter().find(|p| p["Item"]["order_id"]["S"] == "open-1").unwrap();
+        assert_eq!(cancelled["Item"]["status"]["S"], "cancelled");
+        let close_orders: Vec<_> = puts
+            .iter()
+            .filter(|p| p["Item"]["order_id"]["S"].as_str().is_some_and(|id| id.starts_with("flatten-")))
+            .collect();
+        assert_eq!(close_orders.len(), 2);
+        assert!(close_orders.iter().all(|p| p["Item"]["side"]["S"] == "SELL"));
+        let closed = puts
+            .iter()
+            .filter(|p| p["Item"]["position_id"].is_object() && p["Item"]["status"]["S"] == "closed")
+            .count();
+        assert!(closed >= 2);
+
+        // Zweiter Aufruf: nichts mehr offen -> leere Summary
+        let again = flattener.flatten("user-1").await.unwrap();
+        assert!(again.actions.is_empty());
+        assert_eq!(again.total_realized_pnl, 0.0);
+    }
+}
@@ -1,18 +1,141 @@
-use crate::storage::{DynamoDBStore, PositionItem};
-use anyhow::Result;
+use crate::mexc::{MexcClient, OrderRequest};
+use crate::storage::{DynamoDBStore, OrderItem, OrderStatus, PositionItem, ReadConsistency};
+use crate::trading::pyramid::Pyramiding;
+use crate::trading::stops::StopAdjuster;
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::{Config, WebhookDelivery};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Lebenszyklus-Event einer Position für Integrationen (POSITION_WEBHOOK_URL)
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionEvent {
+    /// "opened", "updated" (Stop nachgezogen, Add-on), "closed" oder "liquidated"
+    pub event: String,
+    pub position_id: String,
+    pub user_id: String,
+    pub symbol: String,
+    pub side: String,
+    pub quantity: f64,
+    pub entry_price: f64,
+    /// Schlusskurs bei closed/liquidated
+    pub exit_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub pnl: Option<f64>,
+    pub pnl_percentage: Option<f64>,
+    pub reason: String,
+    pub account: Option<String>,
+    pub strategy: Option<String>,
+    pub timestamp: i64,
+}
+
+impl PositionEvent {
+    pub fn from_position(event: &str, position: &PositionItem, reason: &str, timestamp: i64) -> Self {
+        let finished = position.status != "open";
+        Self {
+            event: event.to_string(),
+            position_id: position.position_id.clone(),
+            user_id: position.user_id.clone(),
+            symbol: position.symbol.clone(),
+            side: position.side.clone(),
+            quantity: position.quantity,
+            entry_price: position.entry_price,
+            exit_price: finished.then_some(position.current_price),
+            stop_price: position.stop_price,
+            pnl: position.pnl,
+            pnl_percentage: position.pnl_percentage,
+            reason: reason.to_string(),
+            account: position.account.clone(),
+            strategy: position.strategy.clone(),
+            timestamp,
+        }
+    }
+}
+
+/// Zuordnung einer Position zu Konto und Strategie (beides optional)
+#[derive(Debug, Clone, Default)]
+pub struct PositionTags {
+    pub account: Option<String>,
+    pub strategy: Option<String>,
+}
 
 /// Position Manager für Open Positions Management
 pub struct PositionManager {
     store: Arc<DynamoDBStore>,
+    clock: Arc<dyn Clock>,
+    /// Break-even/Trailing-Stop bei Preis-Updates (Default: aus)
+    stops: StopAdjuster,
+    /// Nachkäufe bei laufendem Gewinn (Default: aus, braucht einen MEXC-Client)
+    pyramid: Pyramiding,
+    add_on_client: Option<Arc<MexcClient>>,
+    /// Webhook für Positions-Events (None = aus)
+    events: Option<Arc<WebhookDelivery>>,
 }
 
 impl PositionManager {
     pub fn new(store: Arc<DynamoDBStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            clock: Arc::new(SystemClock),
+            stops: StopAdjuster::default(),
+            pyramid: Pyramiding::default(),
+            add_on_client: None,
+            events: None,
+        }
+    }
+
+    /// Stop-Nachführung und Event-Webhook aus der Config
+    pub fn from_config(store: Arc<DynamoDBStore>, config: &Config) -> Self {
+        let manager = Self::new(store).with_stop_adjuster(StopAdjuster::from_config(config));
+        match &config.position_webhook_url {
+            Some(url) => manager.with_event_webhook(Arc::new(
+                WebhookDelivery::new(url.clone())
+                    .with_retries(config.position_webhook_retries, Duration::from_millis(500)),
+            )),
+            None => manager,
+        }
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Automatische Stop-Nachführung aktivieren
+    pub fn with_stop_adjuster(mut self, stops: StopAdjuster) -> Self {
+        self.stops = stops;
+        self
+    }
+
+    /// Pyramiding aktivieren; Add-ons werden als Market-Order über `client` platziert
+    pub fn with_pyramiding(mut self, pyramid: Pyramiding, client: Arc<MexcClient>) -> Self {
+        self.pyramid = pyramid;
+        self.add_on_client = Some(client);
+        self
+    }
+
+    /// Positions-Events an einen Webhook senden
+    pub fn with_event_webhook(mut self, events: Arc<WebhookDelivery>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Event fire-and-forget zustellen (blockiert den Trading-Pfad nicht)
+    fn emit(&self, event: &str, position: &PositionItem, reason: &str) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        let payload = PositionEvent::from_position(event, position, reason, self.clock.now_millis());
+        match serde_json::to_value(&payload) {
+            Ok(body) => events.spawn_send(body),
+            Err(e) => tracing::warn!("Failed to serialize position event: {}", e),
+        }
     }
 
-    /// Öffne neue Position
+    /// Öffne neue Position (optional einem benannten Konto und einer Strategie zugeordnet)
     pub async fn open_position(
         &self,
         user_id: &str,
@@ -20,33 +143,67 @@ impl PositionManager {
         entry_price: f64,
         quantity: f64,
         side: &str,
+        tags: PositionTags,
     ) -> Result<String> {
-        let position = PositionItem::new(
+        let mut position = PositionItem::new(
             user_id.to_string(),
             symbol.to_string(),
             entry_price,
             quantity,
             side.to_string(),
-        );
+        )
+        .stamped_at(self.clock.now());
+        position.account = tags.account;
+        position.strategy = tags.strategy;
 
         let position_id = position.position_id.clone();
         self.store.put_position(&position).await?;
 
         tracing::info!("Position opened: {} for user: {}", position_id, user_id);
+        self.emit("opened", &position, "entry");
 
         Ok(position_id)
     }
 
-    /// Update Position mit aktuellem Preis
+    /// Update Position mit aktuellem Preis; zieht den Stop ggf. auf Break-even bzw. nach
     pub async fn update_position_price(
         &self,
         user_id: &str,
         position_id: &str,
         current_price: f64,
     ) -> Result<()> {
-        // TODO: Query Position by ID
-        // TODO: Update price und calculate PnL
-        // TODO: Save back to store
+        let mut position = self
+            .store
+            .get_position(user_id, position_id, ReadConsistency::Strong)
+            .await?
+            .ok_or_else(|| anyhow!("Position not found: {}", position_id))?;
+
+        position.calculate_pnl(current_price);
+        let stop_moved = self.stops.adjust(&mut position);
+        if let Some(stop) = stop_moved {
+            tracing::info!(
+                "Stop of position {} raised to {} (entry {}, price {})",
+                position_id,
+                stop,
+                position.entry_price,
+                current_price
+            );
+        }
+        let added = match self.try_add_on(&mut position).await {
+            Ok(added) => added,
+            Err(e) => {
+                tracing::warn!("Add-on for position {} failed: {}", position_id, e);
+                false
+            }
+        };
+        position.updated_at = self.clock.now().to_rfc3339();
+        self.store.put_position(&position).await?;
+        if stop_moved.is_some() {
+            self.emit("updated", &position, "stop_adjusted");
+        }
+        if added {
+            self.emit("updated", &position, "add_on");
+        }
 
         tracing::debug!(
             "Position price updated: {} to {}",
@@ -57,20 +214,114 @@ impl PositionManager {
         Ok(())
     }
 
-    /// Schließe Position
+    /// Nächsten Pyramiding-Nachkauf per Market-Order platzieren und in die Position
+    /// einrechnen; true wenn nachgekauft wurde. Die Client-Order-ID hängt an Position und
+    /// Add-on-Nummer: ging die Position nach einem Fill verloren, lehnt MEXC den zweiten Versuch ab
+    async fn try_add_on(&self, position: &mut PositionItem) -> Result<bool> {
+        let Some(client) = &self.add_on_client else {
+            return Ok(false);
+        };
+        let Some(quantity) = self.pyramid.add_on_quantity(position) else {
+            return Ok(false);
+        };
+
+        let side = if position.side == "short" { "SELL" } else { "BUY" };
+        let client_order_id = add_on_client_order_id(position);
+        let mut order = OrderItem::new(
+            position.user_id.clone(),
+            position.symbol.clone(),
+            side.to_string(),
+            "market".to_string(),
+            quantity,
+            None,
+        )
+        .stamped_at(self.clock.now());
+        order.account = position.account.clone();
+        order.strategy = position.strategy.clone();
+        order.client_order_id = Some(client_order_id.clone());
+
+        let response = client
+            .create_order(&OrderRequest {
+                symbol: position.symbol.clone(),
+                side: side.to_string(),
+                order_type: "MARKET".to_string(),
+                quantity,
+                price: None,
+                quote_order_qty: None,
+                client_order_id: Some(client_order_id),
+            })
+            .await?;
+
+        // Erst in die Position einrechnen: der Fill zählt auch, wenn die Order nicht gespeichert wird
+        // (ohne Fill-Preis in der Antwort zum zuletzt bekannten Preis)
+        let fill_price = response.average_fill_price().unwrap_or(position.current_price);
+        position.add_on(fill_price, quantity);
+        tracing::info!(
+            "Add-on #{} for position {}: {} {} @ {} (avg entry {})",
+            position.add_ons,
+            position.position_id,
+            side,
+            quantity,
+            fill_price,
+            position.entry_price
+        );
+
+        order.mexc_order_id = Some(response.order_id.clone());
+        order.status = OrderStatus::from_mexc(&response.status).as_str().to_string();
+        order.filled_qty = response.filled_qty;
+        order.fill_price = response.average_fill_price();
+        if let Err(e) = self.store.put_order(&order).await {
+            tracing::error!(
+                "ALERT: add-on order {} for position {} filled but not stored: {}",
+                response.order_id,
+                position.position_id,
+                e
+            );
+        }
+        Ok(true)
+    }
+
+    /// Schließe Position, speichere sie als geschlossen und gib den realisierten PnL zurück;
+    /// `reason` (z.B. "take_profit", "stop", "manual") geht ins Close-Event
     pub async fn close_position(
         &self,
         user_id: &str,
         position_id: &str,
         close_price: f64,
+        reason: &str,
     ) -> Result<f64> {
-        // TODO: Query position
-        // TODO: Calculate final PnL
-        // TODO: Mark as closed
+        self.finish_position(user_id, position_id, close_price, "closed", reason).await
+    }
 
-        tracing::info!("Position closed: {} for user: {}", position_id, user_id);
+    /// Position als liquidiert schließen (Event "liquidated")
+    pub async fn liquidate_position(&self, user_id: &str, position_id: &str, close_price: f64) -> Result<f64> {
+        self.finish_position(user_id, position_id, close_price, "liquidated", "liquidation").await
+    }
 
-        Ok(0.0) // PnL
+    async fn finish_position(
+        &self,
+        user_id: &str,
+        position_id: &str,
+        close_price: f64,
+        status: &str,
+        reason: &str,
+    ) -> Result<f64> {
+        let mut position = self
+            .store
+            .get_position(user_id, position_id, ReadConsistency::Strong)
+            .await?
+            .ok_or_else(|| anyhow!("Position not found: {}", position_id))?;
+
+        position.close_at(close_price, self.clock.now());
+        position.status = status.to_string();
+        self.store.put_position(&position).await?;
+        self.store.put_closed_position(&position).await?;
+
+        let pnl = position.pnl.unwrap_or(0.0);
+        tracing::info!("Position {}: {} for user: {} (PnL {}, {})", status, position_id, user_id, pnl, reason);
+        self.emit(status, &position, reason);
+
+        Ok(pnl)
     }
 
     /// Rufe alle offenen Positionen ab
@@ -78,3 +329,216 @@ impl PositionManager {
         self.store.query_open_positions(user_id).await
     }
 }
+
+/// Client-Order-ID des nächsten Add-ons (Positions-Präfix + laufende Nummer)
+fn add_on_client_order_id(position: &PositionItem) -> String {
+    let prefix: String = position.position_id.chars().take(16).collect();
+    format!("{}-add-{}", prefix, position.add_ons + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use serde_json::json;
+    use axum::extract::Query;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    fn open_position_item() -> serde_json::Value {
+        json!({ "Count": 1, "Items": [{
+            "user_id": { "S": "user-1" },
+            "sk": { "S": "POSITION#1#pos-1" },
+            "position_id": { "S": "pos-1" },
+            "symbol": { "S": "ETHUSDT" },
+            "entry_price": { "N": "100" },
+            "current_price": { "N": "100" },
+            "quantity": { "N": "2" },
+            "side": { "S": "long" },
+            "entry_time": { "N": "1" },
+            "status": { "S": "open" },
+            "updated_at": { "S": "2024-01-01T00:00:00Z" },
+            "ttl": { "N": "0" }
+        }] })
+    }
+
+    #[tokio::test]
+    async fn test_close_event_carries_pnl_and_reason() {
+        // Erster Versuch schlägt fehl, der Retry kommt an
+        let received = Arc::new(Mutex::new(Vec::<serde_json::Value>::new()));
+        let router = Router::new().route(
+            "/events",
+            post({
+                let received = received.clone();
+                move |Json(body): Json<serde_json::Value>| async move {
+                    let mut received = received.lock().unwrap();
+                    received.push(body);
+                    if received.len() == 1 {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let url = format!("{}/events", spawn_server(router).await);
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond("Query", open_position_item());
+        let manager = PositionManager::new(Arc::new(dynamo.store().await)).with_event_webhook(Arc::new(
+            WebhookDelivery::new(url).with_retries(2, Duration::from_millis(10)),
+        ));
+
+        let pnl = manager.close_position("user-1", "pos-1", 110.0, "take_profit").await.unwrap();
+        assert_eq!(pnl, 20.0);
+
+        for _ in 0..100 {
+            if received.lock().unwrap().len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        let event = &received[1];
+        assert_eq!(event["event"], "closed");
+        assert_eq!(event["reason"], "take_profit");
+        assert_eq!(event["symbol"], "ETHUSDT");
+        assert_eq!(event["side"], "long");
+        assert_eq!(event["entry_price"], 100.0);
+        assert_eq!(event["exit_price"], 110.0);
+        assert_eq!(event["pnl"], 20.0);
+        assert_eq!(event["pnl_percentage"], 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_tag_is_stored_on_open_and_close() {
+        let dynamo = MockDynamo::start().await;
+        let manager = PositionManager::new(Arc::new(dynamo.store().await));
+
+        let tags = PositionTags {
+            account: None,
+            strategy: Some("trail".to_string()),
+        };
+        manager.open_position("user-1", "ETHUSDT", 100.0, 2.0, "long", tags).await.unwrap();
+        assert_eq!(dynamo.requests("PutItem")[0]["Item"]["strategy"]["S"], "trail");
+
+        let mut stored = open_position_item();
+        stored["Items"][0]["strategy"] = json!({ "S": "trail" });
+        dynamo.respond("Query", stored);
+        manager.close_position("user-1", "pos-1", 110.0, "take_profit").await.unwrap();
+
+        let puts = dynamo.requests("PutItem");
+        let closed = &puts.last().unwrap()["Item"];
+        assert_eq!(closed["data_type"]["S"], "CLOSED_POSITION");
+        assert_eq!(closed["strategy"]["S"], "trail");
+        assert_eq!(closed["pnl"]["N"], "20");
+    }
+
+    #[tokio::test]
+    async fn test_price_update_places_add_on_and_averages_entry() {
+        let orders = Arc::new(Mutex::new(Vec::<String>::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let orders = orders.clone();
+                move |Query(params): Query<HashMap<String, String>>| {
+                    let orders = orders.clone();
+                    async move {
+                        orders.lock().unwrap().push(format!(
+                            "{} {} {}",
+                            params["side"], params["quantity"], params["newClientOrderId"]
+                        ));
+                        Json(json!({
+                            "order_id": "mexc-add-1",
+                            "symbol": "ETHUSDT",
+                            "side": "BUY",
+                            "order_type": "MARKET",
+                            "quantity": 1.0,
+                            "price": 110.0,
+                            "status": "FILLED",
+                            "filled_qty": 1.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        let pyramid = Pyramiding {
+            trigger_pct: 5.0,
+            add_fraction: 0.5,
+            max_add_ons: 1,
+            max_notional: 0.0,
+        };
+        let manager = PositionManager::new(Arc::new(dynamo.store().await))
+            .with_pyramiding(pyramid, Arc::new(mexc_client(&base_url)));
+
+        // 4 % im Plus: kein Add-on
+        dynamo.respond("Query", open_position_item());
+        manager.update_position_price("user-1", "pos-1", 104.0).await.unwrap();
+        assert!(orders.lock().unwrap().is_empty());
+
+        // 10 % im Plus: halbe Menge nachkaufen
+        dynamo.respond("Query", open_position_item());
+        manager.update_position_price("user-1", "pos-1", 110.0).await.unwrap();
+        assert_eq!(*orders.lock().unwrap(), vec!["BUY 1 pos-1-add-1".to_string()]);
+
+        let puts = dynamo.requests("PutItem");
+        let position = &puts.last().unwrap()["Item"];
+        assert_eq!(position["quantity"]["N"], "3");
+        assert_eq!(position["add_ons"]["N"], "1");
+        let entry: f64 = position["entry_price"]["N"].as_str().unwrap().parse().unwrap();
+        assert!((entry - 310.0 / 3.0).abs() < 1e-9);
+        assert_eq!(puts[puts.len() - 2]["Item"]["mexc_order_id"]["S"], "mexc-add-1");
+    }
+
+    #[tokio::test]
+    async fn test_add_on_is_recorded_when_order_persist_fails() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            post(|| async {
+                Json(json!({
+                    "order_id": "mexc-add-1",
+                    "symbol": "ETHUSDT",
+                    "side": "BUY",
+                    "order_type": "MARKET",
+                    "quantity": 1.0,
+                    "price": 0.0,
+                    "status": "FILLED",
+                    "filled_qty": 1.0,
+                    "cummulativeQuoteQty": "112",
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        let pyramid = Pyramiding {
+            trigger_pct: 5.0,
+            add_fraction: 0.5,
+            max_add_ons: 2,
+            max_notional: 0.0,
+        };
+        let manager = PositionManager::new(Arc::new(dynamo.store().await))
+            .with_pyramiding(pyramid, Arc::new(mexc_client(&base_url)));
+
+        // Order-Put schlägt dauerhaft fehl, die Position zählt den Fill trotzdem
+        dynamo.respond("Query", open_position_item());
+        dynamo.respond_error("PutItem", "ValidationException");
+        manager.update_position_price("user-1", "pos-1", 110.0).await.unwrap();
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 2);
+        assert_eq!(puts[0]["Item"]["client_order_id"]["S"], "pos-1-add-1");
+        let position = &puts[1]["Item"];
+        assert_eq!(position["quantity"]["N"], "3");
+        assert_eq!(position["add_ons"]["N"], "1");
+        let entry: f64 = position["entry_price"]["N"].as_str().unwrap().parse().unwrap();
+        assert!((entry - 312.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,49 @@
+pub mod blacklist;
+pub mod chase;
+pub mod confirm;
+pub mod cooldown;
+pub mod decisions;
 pub mod detector;
+pub mod fees;
+pub mod flatten;
 pub mod manager;
+pub mod monitor;
+pub mod order_index;
+pub mod pnl;
+pub mod precision;
+pub mod preflight;
+pub mod pyramid;
+pub mod recovery;
+pub mod relisting;
+pub mod scheduler;
+pub mod sizing;
 pub mod sniper;
+pub mod stops;
 
+pub use blacklist::SymbolBlacklist;
+pub use chase::{ChaseAction, LimitChaser};
+pub use confirm::FillConfirmation;
+pub use cooldown::PlacementCooldown;
+pub use decisions::{DecisionEvent, DecisionGate, DecisionLog};
 pub use detector::{DetectedPattern, PatternDetector};
-pub use manager::PositionManager;
-pub use sniper::{SnipeOrderParams, SnipingManager};
+pub use fees::{summarize_fees, FeeReport, FeeTotals};
+pub use flatten::{FlattenAction, FlattenError, FlattenSummary, Flattener};
+pub use manager::{PositionEvent, PositionManager, PositionTags};
+pub use monitor::OrderMonitor;
+pub use order_index::{OrderIndex, OrderSummary};
+pub use pnl::{net_positions, NetPosition};
+pub use precision::{PrecisionCache, SymbolPrecision};
+pub use preflight::{BalancePreflight, PreflightError};
+pub use pyramid::Pyramiding;
+pub use recovery::OrderRecovery;
+pub use relisting::{RelistingAction, RelistingGuard};
+pub use scheduler::LaunchScheduler;
+pub use sizing::{
+    calculate_position_size, clamp_reduce_only_quantity, is_closing_side, round_quantity,
+    PositionSizing, RoundingMode, SizingSettings,
+};
+pub use stops::StopAdjuster;
+pub use sniper::{
+    CanaryOrder, EntryChain, EntryTier, EntryUncertain, ImbalanceGate, LiquidityGate, SnipeOrderParams,
+    SnipeOutcome, SnipeRetryPolicy, SnipingManager,
+};
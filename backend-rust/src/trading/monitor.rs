@@ -0,0 +1,652 @@
+use crate::mexc::websocket::PriceCache;
+use crate::mexc::{MexcClient, MexcClientPool, OrderRequest};
+use crate::storage::{DynamoDBStore, OrderItem, OrderStatus, OrderWriteBuffer};
+use crate::trading::chase::{ChaseAction, LimitChaser};
+use crate::trading::order_index::OrderIndex;
+use crate::utils::clock::{Clock, SystemClock};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Order Monitor: pollt offene Orders bei MEXC und speichert Fills.
+/// Paper-Limit-Orders werden gegen den PriceCache simuliert, liegengebliebene
+/// Limit-Snipes optional Richtung Buch nachgezogen.
+pub struct OrderMonitor {
+    mexc_client: Arc<MexcClient>,
+    store: Arc<DynamoDBStore>,
+    tracked_users: Mutex<HashSet<String>>,
+    poll_interval: Duration,
+    clock: Arc<dyn Clock>,
+    price_cache: Option<Arc<PriceCache>>,
+    /// Probe-Intervall während einer MEXC-Wartung
+    maintenance_probe: Duration,
+    /// Client pro User (None = globaler Client für alle Orders)
+    clients: Option<Arc<MexcClientPool>>,
+    /// Aktive Orders im Speicher (schnelle Lookups beim Fill-Handling)
+    order_index: Arc<OrderIndex>,
+    /// Nachziehen ungefüllter Limit-Snipes (Default: aus)
+    chaser: LimitChaser,
+    /// Noch nicht geschriebene Orders mitpollen und Updates dahinter einreihen
+    write_behind: Option<Arc<OrderWriteBuffer>>,
+}
+
+impl OrderMonitor {
+    pub fn new(
+        mexc_client: Arc<MexcClient>,
+        store: Arc<DynamoDBStore>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            mexc_client,
+            store,
+            tracked_users: Mutex::new(HashSet::new()),
+            poll_interval,
+            clock: Arc::new(SystemClock),
+            price_cache: None,
+            maintenance_probe: Duration::from_secs(30),
+            clients: None,
+            order_index: Arc::new(OrderIndex::default()),
+            chaser: LimitChaser::default(),
+            write_behind: None,
+        }
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Preisquelle für simulierte Fills von Paper-Limit-Orders
+    pub fn with_price_cache(mut self, price_cache: Arc<PriceCache>) -> Self {
+        self.price_cache = Some(price_cache);
+        self
+    }
+
+    /// Intervall der Recovery-Probes während einer MEXC-Wartung
+    pub fn with_maintenance_probe(mut self, interval: Duration) -> Self {
+        self.maintenance_probe = interval;
+        self
+    }
+
+    /// Orders mit dem MEXC Client ihres Users abfragen
+    pub fn with_client_pool(mut self, clients: Arc<MexcClientPool>) -> Self {
+        self.clients = Some(clients);
+        self
+    }
+
+    /// Gemeinsamen Order-Index setzen
+    pub fn with_order_index(mut self, order_index: Arc<OrderIndex>) -> Self {
+        self.order_index = order_index;
+        self
+    }
+
+    /// Ungefüllte Limit-Snipes nachziehen
+    pub fn with_limit_chaser(mut self, chaser: LimitChaser) -> Self {
+        self.chaser = chaser;
+        self
+    }
+
+    /// Write-behind-Puffer der API: gepufferte Orders werden mitgepollt
+    pub fn with_write_behind(mut self, buffer: Arc<OrderWriteBuffer>) -> Self {
+        self.write_behind = Some(buffer);
+        self
+    }
+
+    pub fn order_index(&self) -> &Arc<OrderIndex> {
+        &self.order_index
+    }
+
+    /// User für das Polling registrieren (z.B. nach Order-Erstellung)
+    pub fn track_user(&self, user_id: &str) {
+        self.tracked_users
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(user_id.to_string());
+    }
+
+    /// User mit offenen Orders aus DynamoDB übernehmen, damit deren Orders nach
+    /// einem Neustart weiter gepollt werden; gibt die Anzahl geladener User zurück
+    pub async fn load_tracked_users(&self) -> Result<usize> {
+        let users = self.store.query_users_with_open_orders().await?;
+        for user_id in &users {
+            self.track_user(user_id);
+        }
+        Ok(users.len())
+    }
+
+    /// Ein Poll-Durchlauf über alle offenen Orders; gibt Anzahl geänderter Orders zurück
+    pub async fn poll_once(&self) -> Result<usize> {
+        let users: Vec<String> = self
+            .tracked_users
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect();
+
+        let mut updated = 0;
+        for user_id in users {
+            let mut orders = self
+                .store
+                .query_orders_by_status(&user_id, OrderStatus::Open.as_str())
+                .await?;
+            if let Some(buffer) = &self.write_behind {
+                orders = buffer.overlay(&user_id, orders);
+                orders.retain(|order| order.status == OrderStatus::Open.as_str());
+            }
+
+            for order in orders {
+                let chase = self.chaser.is_due(&order, self.clock.now_millis()).then(|| order.clone());
+                match self.refresh_order(order).await {
+                    Ok(true) => updated += 1,
+                    // Unverändert offen: ggf. Richtung Buch nachziehen
+                    Ok(false) => {
+                        if let Some(order) = chase {
+                            match self.chase_order(order).await {
+                                Ok(true) => updated += 1,
+                                Ok(false) => {}
+                                Err(e) => tracing::warn!("Limit chase failed for user {}: {}", user_id, e),
+                            }
+                        }
+                    }
+                    Err(_) if self.mexc_client.maintenance().is_active() => return Ok(updated),
+                    Err(e) => tracing::warn!("Order refresh failed for user {}: {}", user_id, e),
+                }
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Ein Schritt der Poll-Schleife: während einer MEXC-Wartung wird nicht
+    /// gepollt, sondern nur per Ping auf Erholung geprüft
+    pub async fn step(&self) -> Result<usize> {
+        if self.mexc_client.maintenance().is_active() {
+            if let Err(e) = self.mexc_client.ping().await {
+                tracing::debug!("MEXC maintenance probe failed: {}", e);
+            }
+            return Ok(0);
+        }
+
+        self.poll_once().await
+    }
+
+    /// Order-Status bei MEXC abfragen und bei Änderung speichern
+    async fn refresh_order(&self, mut order: OrderItem) -> Result<bool> {
+        if order.paper {
+            return self.simulate_paper_fill(order).await;
+        }
+
+        let Some(mexc_order_id) = order.mexc_order_id.clone() else {
+            return Ok(false);
+        };
+
+        let remote = self
+            .client_for(&order.user_id)
+            .await?
+            .get_order(&order.symbol, &mexc_order_id)
+            .await?;
+        let status = OrderStatus::from_mexc(&remote.status).as_str().to_string();
+
+        if status == order.status && remote.filled_qty == order.filled_qty {
+            return Ok(false);
+        }
+
+        order.status = status;
+        order.filled_qty = remote.filled_qty;
+        order.updated_at = self.clock.now().to_rfc3339();
+        self.persist(&order).await?;
+        self.order_index.upsert(&order);
+
+        tracing::info!("Order updated: {} -> {}", order.order_id, order.status);
+        Ok(true)
+    }
+
+    /// Order speichern; mit Write-behind hinter einem noch gepufferten Stand
+    async fn persist(&self, order: &OrderItem) -> Result<()> {
+        match &self.write_behind {
+            Some(buffer) => buffer.write_through(order).await,
+            None => self.store.put_order(order).await,
+        }
+    }
+
+    /// Client des Users (ohne Pool der globale)
+    async fn client_for(&self, user_id: &str) -> Result<Arc<MexcClient>> {
+        Ok(match &self.clients {
+            Some(clients) => clients.for_user(user_id).await?,
+            None => self.mexc_client.clone(),
+        })
+    }
+
+    /// Limit-Snipe stornieren und einen Schritt näher am Buch neu platzieren
+    /// (bzw. am max. Abstand nur stornieren); beide Orders werden gespeichert
+    async fn chase_order(&self, mut order: OrderItem) -> Result<bool> {
+        let client = self.client_for(&order.user_id).await?;
+        let book = client.get_order_book(&order.symbol, 5).await?;
+        let Some(action) = self.chaser.next_action(&order, &book) else {
+            return Ok(false);
+        };
+
+        let mexc_order_id = order.mexc_order_id.clone().unwrap_or_default();
+        let cancelled = client.cancel_order(&order.symbol, &mexc_order_id).await?;
+        order.status = OrderStatus::Cancelled.as_str().to_string();
+        order.filled_qty = order.filled_qty.max(cancelled.filled_qty);
+        order.updated_at = self.clock.now().to_rfc3339();
+
+        let price = match action {
+            ChaseAction::Reprice { price } if order.filled_qty < order.quantity => price,
+            _ => {
+                tracing::info!(
+                    "Limit snipe {} on {} unfilled at max chase distance, cancelled",
+                    order.order_id,
+                    order.symbol
+                );
+                self.persist(&order).await?;
+                self.order_index.upsert(&order);
+                return Ok(true);
+            }
+        };
+
+        let mut replacement = self.chaser.replacement(&order, price).stamped_at(self.clock.now());
+        let request = OrderRequest {
+            symbol: replacement.symbol.clone(),
+            side: replacement.side.to_ascii_uppercase(),
+            order_type: "LIMIT".to_string(),
+            quantity: replacement.quantity,
+            price: Some(price),
+            quote_order_qty: None,
+            client_order_id: Some(replacement.order_id.clone()),
+        };
+        match client.create_order(&request).await {
+            Ok(response) => {
+                replacement.mexc_order_id = Some(response.order_id);
+                replacement.status = OrderStatus::from_mexc(&response.status).as_str().to_string();
+                replacement.filled_qty = response.filled_qty;
+            }
+            Err(e) => {
+                replacement.status = OrderStatus::Error.as_str().to_string();
+                replacement.error_message = Some(e.to_string());
+            }
+        }
+        tracing::info!(
+            "Chased limit snipe {} on {} from {} to {} (replacement {}, {})",
+            order.order_id,
+            order.symbol,
+            order.price.unwrap_or_default(),
+            price,
+            replacement.order_id,
+            replacement.status
+        );
+
+        order.replaced_by = Some(replacement.order_id.clone());
+        self.persist(&replacement).await?;
+        self.persist(&order).await?;
+        self.order_index.upsert(&replacement);
+        self.order_index.upsert(&order);
+        Ok(true)
+    }
+
+    /// Paper-Limit-Order voll zum Limitpreis füllen, sobald der (frische) Cache-Preis das Limit kreuzt;
+    /// ohne frischen Preis wird der Ticker per REST nachgeladen und gecacht
+    async fn simulate_paper_fill(&self, mut order: OrderItem) -> Result<bool> {
+        let Some(cache) = &self.price_cache else {
+            return Ok(false);
+        };
+        let price = match cache.get(&order.symbol) {
+            Some(price) => price,
+            None => {
+                let price = self.mexc_client.get_ticker(&order.symbol).await?.price;
+                cache.record(&order.symbol, price);
+                price
+            }
+        };
+        if !order.limit_crossed(price) {
+            return Ok(false);
+        }
+
+        order.status = OrderStatus::Filled.as_str().to_string();
+        order.filled_qty = order.quantity;
+        order.fill_price = order.price;
+        order.updated_at = self.clock.now().to_rfc3339();
+        self.persist(&order).await?;
+        self.order_index.upsert(&order);
+
+        tracing::info!("Paper order filled: {} at {}", order.order_id, price);
+        Ok(true)
+    }
+
+    /// Poll-Schleife bis zum Shutdown-Signal. Ein laufender Poll wird nicht
+    /// abgebrochen, sondern inkl. Speichern der Fills zu Ende geführt.
+    pub async fn run(&self, mut shutdown: watch::Receiver<bool>) {
+        loop {
+            if *shutdown.borrow() {
+                break;
+            }
+
+            if let Err(e) = self.step().await {
+                tracing::warn!("Order monitor poll failed: {}", e);
+            }
+            self.order_index.evict_expired();
+
+            let interval = if self.mexc_client.maintenance().is_active() {
+                self.maintenance_probe
+            } else {
+                self.poll_interval
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                changed = shutdown.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        tracing::info!("Order monitor drained");
+    }
+}
+
+/// Warte bis zur Grace Period auf das Ende der Monitor-Task, danach Abbruch.
+/// Gibt `true` zurück wenn sauber beendet wurde.
+pub async fn drain(mut handle: JoinHandle<()>, grace: Duration) -> bool {
+    match tokio::time::timeout(grace, &mut handle).await {
+        Ok(_) => true,
+        Err(_) => {
+            tracing::warn!("Order monitor did not drain within {:?}, aborting", grace);
+            handle.abort();
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::{routing::get, Json, Router};
+    use tokio::sync::Notify;
+
+    fn paper_limit_order() -> serde_json::Value {
+        serde_json::json!({
+            "Count": 1,
+            "Items": [{
+                "user_id": { "S": "user-1" },
+                "sk": { "S": "ORDER#1#paper-1" },
+                "order_id": { "S": "paper-1" },
+                "symbol": { "S": "ETHUSDT" },
+                "side": { "S": "buy" },
+                "order_type": { "S": "limit" },
+                "quantity": { "N": "2" },
+                "price": { "N": "2000" },
+                "filled_qty": { "N": "0" },
+                "status": { "S": "open" },
+                "timestamp": { "N": "1" },
+                "created_at": { "S": "2024-01-01T00:00:00Z" },
+                "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                "paper": { "BOOL": true },
+                "ttl": { "N": "0" }
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_paper_limit_order_fills_only_after_price_cross() {
+        let dynamo = MockDynamo::start().await;
+        let cache = Arc::new(PriceCache::new(Duration::from_secs(60)));
+        let monitor = OrderMonitor::new(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+            Duration::from_secs(60),
+        )
+        .with_price_cache(cache.clone());
+        monitor.track_user("user-1");
+
+        let trade = |price| crate::mexc::websocket::TradeEvent {
+            symbol: "ETHUSDT".to_string(),
+            price,
+            quantity: 1.0,
+            timestamp: 0,
+            is_buyer_maker: false,
+        };
+
+        // Preis über dem Buy-Limit: bleibt offen
+        cache.update(&trade(2010.0));
+        dynamo.respond("Query", paper_limit_order());
+        assert_eq!(monitor.poll_once().await.unwrap(), 0);
+        assert!(dynamo.requests("PutItem").is_empty());
+
+        // Preis kreuzt das Limit: Fill
+        cache.update(&trade(1999.5));
+        dynamo.respond("Query", paper_limit_order());
+        assert_eq!(monitor.poll_once().await.unwrap(), 1);
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0]["Item"]["status"]["S"], "filled");
+        assert_eq!(puts[0]["Item"]["filled_qty"]["N"], "2");
+        assert_eq!(puts[0]["Item"]["fill_price"]["N"], "2000");
+        assert_eq!(puts[0]["Item"]["paper"]["BOOL"], true);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_order_is_polled_and_fill_queued_behind_it() {
+        let router = Router::new().route(
+            "/api/v3/order",
+            get(|| async {
+                Json(serde_json::json!({
+                    "order_id": "mexc-1",
+                    "symbol": "ETHUSDT",
+                    "side": "BUY",
+                    "order_type": "LIMIT",
+                    "quantity": 1.0,
+                    "price": 2000.0,
+                    "status": "FILLED",
+                    "filled_qty": 1.0,
+                    "created_at": 0,
+                }))
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let buffer = Arc::new(OrderWriteBuffer::new(store.clone(), 10, Duration::from_secs(60)));
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            "BUY".to_string(),
+            "LIMIT".to_string(),
+            1.0,
+            Some(2000.0),
+        );
+        order.status = OrderStatus::Open.as_str().to_string();
+        order.mexc_order_id = Some("mexc-1".to_string());
+        buffer.reserve().unwrap().enqueue(order);
+
+        let monitor = OrderMonitor::new(Arc::new(mexc_client(&base_url)), store, Duration::from_secs(60))
+            .with_write_behind(buffer.clone());
+        monitor.track_user("user-1");
+
+        // DynamoDB liefert keine offene Order, der Puffer schon
+        assert_eq!(monitor.poll_once().await.unwrap(), 1);
+        assert!(dynamo.requests("PutItem").is_empty());
+
+        assert_eq!(buffer.flush().await, 2);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts[0]["Item"]["status"]["S"], "open");
+        assert_eq!(puts[1]["Item"]["status"]["S"], "filled");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_during_poll_persists_fill() {
+        let poll_started = Arc::new(Notify::new());
+        let router = Router::new().route(
+            "/api/v3/order",
+            get({
+                let poll_started = poll_started.clone();
+                move || async move {
+                    poll_started.notify_one();
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Json(serde_json::json!({
+                        "order_id": "mexc-1",
+                        "symbol": "ETHUSDT",
+                        "side": "BUY",
+                        "order_type": "LIMIT",
+                        "quantity": 1.0,
+                        "price": 2000.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "Query",
+            serde_json::json!({
+                "Count": 1,
+                "Items": [{
+                    "user_id": { "S": "user-1" },
+                    "sk": { "S": "ORDER#1#order-1" },
+                    "order_id": { "S": "order-1" },
+                    "symbol": { "S": "ETHUSDT" },
+                    "side": { "S": "BUY" },
+                    "order_type": { "S": "LIMIT" },
+                    "quantity": { "N": "1" },
+                    "price": { "N": "2000" },
+                    "filled_qty": { "N": "0" },
+                    "status": { "S": "open" },
+                    "timestamp": { "N": "1" },
+                    "created_at": { "S": "2024-01-01T00:00:00Z" },
+                    "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                    "mexc_order_id": { "S": "mexc-1" },
+                    "ttl": { "N": "0" }
+                }]
+            }),
+        );
+
+        let monitor = Arc::new(OrderMonitor::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            Duration::from_secs(60),
+        ));
+        monitor.track_user("user-1");
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = tokio::spawn({
+            let monitor = monitor.clone();
+            async move { monitor.run(shutdown_rx).await }
+        });
+
+        poll_started.notified().await;
+        shutdown_tx.send(true).unwrap();
+
+        assert!(drain(handle, Duration::from_secs(5)).await);
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0]["Item"]["status"]["S"], "filled");
+        assert_eq!(puts[0]["Item"]["filled_qty"]["N"], "1");
+        assert_eq!(dynamo.requests("Query").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_users_with_open_orders_are_tracked_after_restart() {
+        let dynamo = MockDynamo::start().await;
+        // Zwei Seiten aus dem data_type-Index, user-1 doppelt
+        dynamo.respond(
+            "Query",
+            serde_json::json!({
+                "Count": 2,
+                "Items": [{ "user_id": { "S": "user-1" } }, { "user_id": { "S": "user-2" } }],
+                "LastEvaluatedKey": { "user_id": { "S": "user-2" }, "sk": { "S": "ORDER#1#b" } }
+            }),
+        );
+        dynamo.respond(
+            "Query",
+            serde_json::json!({ "Count": 1, "Items": [{ "user_id": { "S": "user-1" } }] }),
+        );
+        let monitor = OrderMonitor::new(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(monitor.load_tracked_users().await.unwrap(), 2);
+        let queries = dynamo.requests("Query");
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0]["IndexName"], "data_type-index");
+        assert_eq!(queries[0]["ExpressionAttributeValues"][":status"]["S"], "open");
+        assert_eq!(queries[1]["ExclusiveStartKey"]["sk"]["S"], "ORDER#1#b");
+
+        // Der nächste Poll fragt die offenen Orders beider User ab
+        monitor.poll_once().await.unwrap();
+        let users: Vec<_> = dynamo.requests("Query")[2..]
+            .iter()
+            .map(|q| q["ExpressionAttributeValues"][":uid"]["S"].as_str().unwrap().to_string())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(users, ["user-1", "user-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_pauses_polling_until_recovery() {
+        use axum::http::StatusCode;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const MAINTENANCE: &str = r#"{"code":503,"msg":"System maintenance"}"#;
+        let pings = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                get(|| async { (StatusCode::SERVICE_UNAVAILABLE, MAINTENANCE) }),
+            )
+            .route(
+                "/api/v3/ping",
+                get({
+                    let pings = pings.clone();
+                    move || async move {
+                        // Erste Probe noch in Wartung, zweite erholt
+                        if pings.fetch_add(1, Ordering::SeqCst) == 0 {
+                            (StatusCode::SERVICE_UNAVAILABLE, MAINTENANCE)
+                        } else {
+                            (StatusCode::OK, "{}")
+                        }
+                    }
+                }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let mut open_order = paper_limit_order();
+        open_order["Items"][0]["paper"] = serde_json::json!({ "BOOL": false });
+        open_order["Items"][0]["mexc_order_id"] = serde_json::json!({ "S": "mexc-1" });
+        dynamo.respond("Query", open_order);
+
+        let monitor = OrderMonitor::new(mexc.clone(), Arc::new(dynamo.store().await), Duration::from_secs(60));
+        monitor.track_user("user-1");
+
+        monitor.step().await.expect("poll failed");
+        assert!(mexc.maintenance().is_active());
+        assert_eq!(dynamo.requests("Query").len(), 1);
+
+        // Pausiert: nur Probes, keine Order-Queries
+        monitor.step().await.unwrap();
+        assert!(mexc.maintenance().is_active());
+        monitor.step().await.unwrap();
+        assert!(!mexc.maintenance().is_active());
+        assert_eq!(pings.load(Ordering::SeqCst), 2);
+        assert_eq!(dynamo.requests("Query").len(), 1);
+
+        // Nach Erholung wird wieder gepollt
+        monitor.step().await.unwrap();
+        assert_eq!(dynamo.requests("Query").len(), 2);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::storage::{DynamoDBStore, OrderItem, OrderStatus, ReadConsistency};
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::Config;
+use anyhow::Result;
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Kompakter Stand einer Order für schnelle Lookups beim Fill-Handling
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderSummary {
+    pub user_id: String,
+    pub order_id: String,
+    pub mexc_order_id: Option<String>,
+    pub symbol: String,
+    pub side: String,
+    pub status: String,
+    pub quantity: f64,
+    pub filled_qty: f64,
+    pub fill_price: Option<f64>,
+    /// Zeitpunkt (Unix ms), ab dem die Order terminal ist (gefüllt, storniert, Fehler)
+    terminal_since: Option<i64>,
+}
+
+impl OrderSummary {
+    pub fn is_terminal(&self) -> bool {
+        self.terminal_since.is_some()
+    }
+}
+
+/// In-Memory-Index aktiver Orders nach order_id (plus MEXC-ID), O(1) und ohne
+/// globales Lock. DynamoDB bleibt die Quelle der Wahrheit: bei einem Miss wird
+/// dort gelesen. Terminale Orders fliegen nach `grace` raus.
+pub struct OrderIndex {
+    orders: DashMap<String, OrderSummary>,
+    by_mexc_id: DashMap<String, String>,
+    grace: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for OrderIndex {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+impl OrderIndex {
+    pub fn new(grace: Duration) -> Self {
+        Self {
+            orders: DashMap::new(),
+            by_mexc_id: DashMap::new(),
+            grace,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(Duration::from_secs(config.order_index_grace_secs))
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Order nach Erstellung oder Update (Fill, Cancel) übernehmen
+    pub fn upsert(&self, order: &OrderItem) {
+        let terminal = !matches!(
+            OrderStatus::from_mexc(&order.status),
+            OrderStatus::Open | OrderStatus::Pending
+        );
+        let now = self.clock.now_millis();
+
+        let mut entry = self.orders.entry(order.order_id.clone()).or_insert_with(|| OrderSummary {
+            user_id: order.user_id.clone(),
+            order_id: order.order_id.clone(),
+            mexc_order_id: None,
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            status: String::new(),
+            quantity: 0.0,
+            filled_qty: 0.0,
+            fill_price: None,
+            terminal_since: None,
+        });
+        let summary = entry.value_mut();
+        summary.mexc_order_id = order.mexc_order_id.clone();
+        summary.status = order.status.clone();
+        summary.quantity = order.quantity;
+        summary.filled_qty = order.filled_qty;
+        summary.fill_price = order.fill_price;
+        summary.terminal_since = match (terminal, summary.terminal_since) {
+            (false, _) => None,
+            (true, since) => since.or(Some(now)),
+        };
+        drop(entry);
+
+        if let Some(mexc_order_id) = &order.mexc_order_id {
+            self.by_mexc_id.insert(mexc_order_id.clone(), order.order_id.clone());
+        }
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<OrderSummary> {
+        self.orders.get(order_id).map(|entry| entry.value().clone())
+    }
+
+    /// Lookup über die MEXC Order-ID (z.B. aus Exchange-Events)
+    pub fn get_by_mexc_id(&self, mexc_order_id: &str) -> Option<OrderSummary> {
+        let order_id = self.by_mexc_id.get(mexc_order_id)?.value().clone();
+        self.get(&order_id)
+    }
+
+    /// Index-Treffer oder (bei Miss) konsistent aus DynamoDB lesen und aufnehmen
+    pub async fn lookup(&self, store: &DynamoDBStore, user_id: &str, order_id: &str) -> Result<Option<OrderSummary>> {
+        if let Some(summary) = self.get(order_id).filter(|s| s.user_id == user_id) {
+            return Ok(Some(summary));
+        }
+        let Some(order) = store.get_order(user_id, order_id, ReadConsistency::Strong).await? else {
+            return Ok(None);
+        };
+        self.upsert(&order);
+        Ok(self.get(order_id))
+    }
+
+    /// Terminale Orders nach Ablauf der Grace Period entfernen; gibt Anzahl zurück
+    pub fn evict_expired(&self) -> usize {
+        let cutoff = self.clock.now_millis() - self.grace.as_millis() as i64;
+        let before = self.orders.len();
+        self.orders
+            .retain(|_, summary| summary.terminal_since.is_none_or(|since| since > cutoff));
+        let order_ids = &self.orders;
+        self.by_mexc_id.retain(|_, order_id| order_ids.contains_key(order_id));
+        before - self.orders.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockDynamo;
+    use crate::utils::clock::MockClock;
+    use serde_json::json;
+
+    fn order(status: &str) -> OrderItem {
+        let mut order = OrderItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            "BUY".to_string(),
+            "LIMIT".to_string(),
+            1.0,
+            Some(100.0),
+        );
+        order.order_id = "order-1".to_string();
+        order.mexc_order_id = Some("mexc-1".to_string());
+        order.status = status.to_string();
+        order
+    }
+
+    #[tokio::test]
+    async fn test_index_population_lookup_and_eviction() {
+        let clock = Arc::new(MockClock::from_millis(0));
+        let index = OrderIndex::new(Duration::from_secs(60)).with_clock(clock.clone());
+
+        let mut open = order("open");
+        index.upsert(&open);
+        assert_eq!(index.get("order-1").unwrap().status, "open");
+        assert_eq!(index.get_by_mexc_id("mexc-1").unwrap().order_id, "order-1");
+
+        open.status = "filled".to_string();
+        open.filled_qty = 1.0;
+        open.fill_price = Some(99.5);
+        index.upsert(&open);
+        let filled = index.get("order-1").unwrap();
+        assert!(filled.is_terminal());
+        assert_eq!(filled.fill_price, Some(99.5));
+
+        // Terminal, aber innerhalb der Grace Period noch abrufbar
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(index.evict_expired(), 0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(index.evict_expired(), 1);
+        assert!(index.get("order-1").is_none());
+        assert!(index.get_by_mexc_id("mexc-1").is_none());
+
+        // Miss -> DynamoDB (konsistent gelesen) und wieder im Index
+        let dynamo = MockDynamo::start().await;
+        let store = dynamo.store().await;
+        dynamo.respond(
+            "Query",
+            json!({ "Count": 1, "Items": [{
+                "user_id": { "S": "user-1" },
+                "sk": { "S": "ORDER#1#order-2" },
+                "order_id": { "S": "order-2" },
+                "symbol": { "S": "ETHUSDT" },
+                "side": { "S": "BUY" },
+                "order_type": { "S": "LIMIT" },
+                "quantity": { "N": "1" },
+                "price": { "N": "100" },
+                "filled_qty": { "N": "0" },
+                "status": { "S": "open" },
+                "timestamp": { "N": "1" },
+                "created_at": { "S": "2024-01-01T00:00:00Z" },
+                "updated_at": { "S": "2024-01-01T00:00:00Z" },
+                "ttl": { "N": "0" }
+            }] }),
+        );
+        let summary = index.lookup(&store, "user-1", "order-2").await.unwrap().unwrap();
+        assert_eq!(summary.status, "open");
+        assert!(index.lookup(&store, "user-1", "order-2").await.unwrap().is_some());
+        assert_eq!(dynamo.requests("Query").len(), 1);
+        assert_eq!(dynamo.requests("Query")[0]["ConsistentRead"], true);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::storage::PositionItem;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Realisierter PnL über geschlossene Positionen
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PnlReport {
+    pub total_realized_pnl: f64,
+    pub closed_positions: usize,
+    pub by_symbol: BTreeMap<String, f64>,
+    pub series: Vec<PnlPoint>,
+}
+
+/// Ein Punkt der kumulierten PnL-Zeitreihe (pro Schließung)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PnlPoint {
+    pub closed_at: i64,
+    pub symbol: String,
+    pub pnl: f64,
+    pub cumulative_pnl: f64,
+}
+
+/// Aggregiere realisierten PnL gesamt, pro Symbol und als Zeitreihe
+pub fn summarize_realized_pnl(positions: &[PositionItem]) -> PnlReport {
+    let mut closed: Vec<&PositionItem> = positions
+        .iter()
+        .filter(|p| p.closed_at.is_some())
+        .collect();
+    closed.sort_by_key(|p| p.closed_at);
+
+    let mut by_symbol = BTreeMap::new();
+    let mut series = Vec::with_capacity(closed.len());
+    let mut cumulative = 0.0;
+
+    for position in &closed {
+        let pnl = position.pnl.unwrap_or(0.0);
+        cumulative += pnl;
+        *by_symbol.entry(position.symbol.clone()).or_insert(0.0) += pnl;
+        series.push(PnlPoint {
+            closed_at: position.closed_at.unwrap_or_default(),
+            symbol: position.symbol.clone(),
+            pnl,
+            cumulative_pnl: cumulative,
+        });
+    }
+
+    PnlReport {
+        total_realized_pnl: cumulative,
+        closed_positions: closed.len(),
+        by_symbol,
+        series,
+    }
+}
+
+/// Performance einer Strategie über ihre geschlossenen Positionen
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct StrategyPnl {
+    pub closed_positions: usize,
+    pub wins: usize,
+    /// Anteil der Positionen mit PnL > 0 (0..1)
+    pub win_rate: f64,
+    pub realized_pnl: f64,
+    /// Durchschnittliche Rendite in Prozent pro Position
+    pub avg_return_pct: f64,
+}
+
+/// Realisierten PnL pro Strategie-Label aggregieren; Positionen ohne Label
+/// landen unter `default_strategy`
+pub fn summarize_by_strategy(
+    positions: &[PositionItem],
+    default_strategy: &str,
+) -> BTreeMap<String, StrategyPnl> {
+    let mut by_strategy: BTreeMap<String, StrategyPnl> = BTreeMap::new();
+
+    for position in positions.iter().filter(|p| p.closed_at.is_some()) {
+        let strategy = position.strategy.as_deref().unwrap_or(default_strategy).to_string();
+        let pnl = position.pnl.unwrap_or(0.0);
+        let stats = by_strategy.entry(strategy).or_default();
+        stats.closed_positions += 1;
+        stats.realized_pnl += pnl;
+        if pnl > 0.0 {
+            stats.wins += 1;
+        }
+        // Erst Summe, unten durch die Anzahl geteilt
+        stats.avg_return_pct += position.pnl_percentage.unwrap_or(0.0);
+    }
+
+    for stats in by_strategy.values_mut() {
+        let closed = stats.closed_positions as f64;
+        stats.win_rate = stats.wins as f64 / closed;
+        stats.avg_return_pct /= closed;
+    }
+    by_strategy
+}
+
+/// Netto-Exposure eines Symbols über alle offenen Legs (long und short)
+#[derive(Debug, Clone, Serialize)]
+pub struct NetPosition {
+    pub symbol: String,
+    /// Long minus Short
+    pub net_quantity: f64,
+    /// "long", "short" oder "flat"
+    pub side: String,
+    /// Entry, zu dem die Netto-Menge denselben PnL hätte (None wenn flat)
+    pub blended_entry: Option<f64>,
+    pub current_price: f64,
+    /// Summe der Leg-PnLs zum aktuellen Preis
+    pub net_pnl: f64,
+    pub legs: Vec<PositionItem>,
+}
+
+/// Offene Positionen pro Symbol zu Netto-Exposure zusammenfassen
+pub fn net_positions(positions: &[PositionItem]) -> Vec<NetPosition> {
+    let mut by_symbol: BTreeMap<&str, Vec<&PositionItem>> = BTreeMap::new();
+    for position in positions.iter().filter(|p| p.status == "open") {
+        by_symbol.entry(position.symbol.as_str()).or_default().push(position);
+    }
+
+    by_symbol
+        .into_iter()
+        .map(|(symbol, legs)| {
+            let current_price = legs.last().map(|p| p.current_price).unwrap_or_default();
+            let signed_qty = |p: &PositionItem| match p.side.as_str() {
+                "long" => p.quantity,
+                "short" => -p.quantity,
+                _ => 0.0,
+            };
+            let net_quantity: f64 = legs.iter().map(|p| signed_qty(p)).sum();
+            let entry_notional: f64 = legs.iter().map(|p| p.entry_price * signed_qty(p)).sum();
+            // Alle Legs zum selben Preis bewerten: (Preis - Entry) * signierte Menge
+            let net_pnl = current_price * net_quantity - entry_notional;
+
+            let flat = net_quantity.abs() < f64::EPSILON;
+            NetPosition {
+                symbol: symbol.to_string(),
+                net_quantity: if flat { 0.0 } else { net_quantity },
+                side: match net_quantity {
+                    _ if flat => "flat",
+                    q if q > 0.0 => "long",
+                    _ => "short",
+                }
+                .to_string(),
+                blended_entry: (!flat).then(|| entry_notional / net_quantity),
+                current_price,
+                net_pnl,
+                legs: legs.into_iter().cloned().collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closed_position(symbol: &str, entry: f64, exit: f64, qty: f64, closed_at: i64) -> PositionItem {
+        let mut position = PositionItem::new(
+            "user-1".to_string(),
+            symbol.to_string(),
+            entry,
+            qty,
+            "long".to_string(),
+        );
+        position.close(exit);
+        position.closed_at = Some(closed_at);
+        position
+    }
+
+    #[test]
+    fn test_summarize_two_closed_positions() {
+        let positions = vec![
+            closed_position("BTCUSDT", 100.0, 90.0, 1.0, 2_000),
+            closed_position("ETHUSDT", 10.0, 15.0, 4.0, 1_000),
+        ];
+
+        let report = summarize_realized_pnl(&positions);
+
+        assert_eq!(report.total_realized_pnl, 10.0);
+        assert_eq!(report.closed_positions, 2);
+        assert_eq!(report.by_symbol["ETHUSDT"], 20.0);
+        assert_eq!(report.by_symbol["BTCUSDT"], -10.0);
+        assert_eq!(report.series[0].closed_at, 1_000);
+        assert_eq!(report.series[0].cumulative_pnl, 20.0);
+        assert_eq!(report.series[1].cumulative_pnl, 10.0);
+    }
+
+    #[test]
+    fn test_closes_aggregate_by_strategy() {
+        let tagged = |strategy: Option<&str>, entry: f64, exit: f64| {
+            let mut position = closed_position("ETHUSDT", entry, exit, 1.0, 1_000);
+            position.strategy = strategy.map(str::to_string);
+            position
+        };
+        let positions = vec![
+            tagged(Some("trail"), 100.0, 110.0),
+            tagged(Some("trail"), 100.0, 95.0),
+            tagged(Some("trail"), 100.0, 130.0),
+            tagged(Some("ladder"), 50.0, 40.0),
+            tagged(None, 10.0, 11.0),
+        ];
+
+        let report = summarize_by_strategy(&positions, "untagged");
+
+        assert_eq!(report.len(), 3);
+        let trail = &report["trail"];
+        assert_eq!(trail.closed_positions, 3);
+        assert_eq!(trail.wins, 2);
+        assert!((trail.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(trail.realized_pnl, 35.0);
+        assert!((trail.avg_return_pct - 35.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report["ladder"].win_rate, 0.0);
+        assert_eq!(report["ladder"].avg_return_pct, -20.0);
+        assert_eq!(report["untagged"].closed_positions, 1);
+        assert!((report["untagged"].realized_pnl - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::mexc::models::SymbolInfo;
+use crate::mexc::MexcClient;
+use crate::trading::sizing::round_down_to_step;
+use crate::utils::Config;
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Nachkommastellen für Menge und Preis eines Symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolPrecision {
+    pub qty_precision: u32,
+    pub price_precision: u32,
+}
+
+/// Präzisionen aus exchangeInfo je Symbol. Solange ein (neu gelistetes)
+/// Symbol noch nicht geladen ist, gelten die konfigurierten Fallbacks,
+/// damit ein Snipe nicht an verspäteter exchangeInfo scheitert.
+pub struct PrecisionCache {
+    fallback: SymbolPrecision,
+    /// Wird bei jedem Refresh komplett ersetzt (Leser sehen nie einen Zwischenstand)
+    symbols: ArcSwap<HashMap<String, SymbolPrecision>>,
+}
+
+impl PrecisionCache {
+    pub fn new(fallback: SymbolPrecision) -> Self {
+        Self {
+            fallback,
+            symbols: ArcSwap::from_pointee(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(SymbolPrecision {
+            qty_precision: config.default_qty_precision,
+            price_precision: config.default_price_precision,
+        })
+    }
+
+    fn precision_of(&self, info: &SymbolInfo) -> SymbolPrecision {
+        SymbolPrecision {
+            qty_precision: info.qty_precision.unwrap_or(self.fallback.qty_precision),
+            price_precision: info.price_precision.unwrap_or(self.fallback.price_precision),
+        }
+    }
+
+    /// Präzision aus exchangeInfo übernehmen; fehlende Werte bleiben beim Fallback
+    pub fn update(&self, info: &SymbolInfo) {
+        let precision = self.precision_of(info);
+        self.symbols.rcu(|symbols| {
+            let mut symbols = HashMap::clone(symbols);
+            symbols.insert(info.symbol.to_ascii_uppercase(), precision);
+            symbols
+        });
+    }
+
+    /// Cache durch eine komplette exchangeInfo ersetzen
+    pub fn replace_all(&self, infos: &[SymbolInfo]) {
+        let symbols = infos
+            .iter()
+            .map(|info| (info.symbol.to_ascii_uppercase(), self.precision_of(info)))
+            .collect();
+        self.symbols.store(Arc::new(symbols));
+    }
+
+    /// Gecachte Präzision des Symbols (ohne Fallback)
+    pub fn cached(&self, symbol: &str) -> Option<SymbolPrecision> {
+        self.symbols.load().get(&symbol.to_ascii_uppercase()).copied()
+    }
+
+    /// Präzision des Symbols, sonst der Fallback (mit Warnung)
+    pub fn get(&self, symbol: &str) -> SymbolPrecision {
+        self.cached(symbol).unwrap_or_else(|| {
+            tracing::warn!(
+                "No exchange info precision cached for {}, using fallback {:?}",
+                symbol,
+                self.fallback
+            );
+            self.fallback
+        })
+    }
+
+    /// Menge auf die Präzision des Symbols abrunden
+    pub fn round_quantity(&self, symbol: &str, quantity: f64) -> f64 {
+        round_down_to_step(quantity, step(self.get(symbol).qty_precision))
+    }
+
+    /// Preis auf die Präzision des Symbols runden
+    pub fn round_price(&self, symbol: &str, price: f64) -> f64 {
+        let factor = 10f64.powi(self.get(symbol).price_precision as i32);
+        (price * factor).round() / factor
+    }
+
+    /// Komplette exchangeInfo laden und den Cache atomar ersetzen
+    pub async fn refresh_all(&self, client: &MexcClient) -> Result<usize> {
+        let infos = client.exchange_info().await?;
+        self.replace_all(&infos);
+        Ok(infos.len())
+    }
+
+    /// Nur ein Symbol nachladen (z.B. frisch gelistet, kurz vor dem Snipe)
+    pub async fn refresh_symbol(&self, client: &MexcClient, symbol: &str) -> Result<Option<SymbolPrecision>> {
+        let Some(info) = client.symbol_info(symbol).await? else {
+            return Ok(None);
+        };
+        self.update(&info);
+        Ok(Some(self.precision_of(&info)))
+    }
+
+    /// Hintergrund-Refresh im festen Intervall bis zum Shutdown
+    pub async fn run_refresh(
+        self: Arc<Self>,
+        client: Arc<MexcClient>,
+        interval: Duration,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => match self.refresh_all(&client).await {
+                    Ok(count) => tracing::debug!("Exchange info refreshed: {} symbols", count),
+                    Err(e) => tracing::warn!("Exchange info refresh failed: {}", e),
+                },
+                _ = shutdown.changed() => return,
+            }
+        }
+    }
+}
+
+fn step(precision: u32) -> f64 {
+    10f64.powi(-(precision as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mexc::SymbolState;
+    use crate::test_support::{mexc_client, spawn_server};
+    use axum::{extract::RawQuery, routing::get, Json, Router};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_rounding_falls_back_for_uncached_symbol() {
+        let cache = PrecisionCache::new(SymbolPrecision {
+            qty_precision: 2,
+            price_precision: 4,
+        });
+
+        assert_eq!(cache.round_quantity("NEWUSDT", 12.3456), 12.34);
+        assert_eq!(cache.round_price("NEWUSDT", 0.123456), 0.1235);
+
+        cache.update(&SymbolInfo {
+            symbol: "NEWUSDT".to_string(),
+            state: SymbolState::Trading,
+            qty_precision: Some(0),
+            price_precision: None,
+        });
+        assert_eq!(cache.round_quantity("newusdt", 12.3456), 12.0);
+        assert_eq!(cache.round_price("NEWUSDT", 0.123456), 0.1235);
+    }
+
+    #[tokio::test]
+    async fn test_new_listing_available_after_refresh() {
+        let listed = Arc::new(AtomicBool::new(false));
+        let router = Router::new().route(
+            "/api/v3/exchangeInfo",
+            get({
+                let listed = listed.clone();
+                move |RawQuery(query): RawQuery| async move {
+                    let mut symbols = vec![json!({ "symbol": "ETHUSDT", "status": "1", "baseAssetPrecision": 4 })];
+                    if listed.load(Ordering::SeqCst) {
+                        symbols.push(json!({ "symbol": "NEWUSDT", "status": "2", "baseAssetPrecision": 0 }));
+                    }
+                    // Gezielter Refresh: nur das angefragte Symbol
+                    if let Some(symbol) = query.as_deref().and_then(|q| q.strip_prefix("symbol=")) {
+                        symbols.retain(|s| s["symbol"] == symbol);
+                    }
+                    Json(json!({ "symbols": symbols }))
+                }
+            }),
+        );
+        let client = mexc_client(&spawn_server(router).await);
+        let cache = PrecisionCache::new(SymbolPrecision {
+            qty_precision: 2,
+            price_precision: 4,
+        });
+
+        assert_eq!(cache.refresh_all(&client).await.unwrap(), 1);
+        assert!(cache.cached("NEWUSDT").is_none());
+        assert_eq!(cache.refresh_symbol(&client, "NEWUSDT").await.unwrap(), None);
+
+        listed.store(true, Ordering::SeqCst);
+        assert_eq!(
+            cache.refresh_symbol(&client, "NEWUSDT").await.unwrap(),
+            Some(SymbolPrecision { qty_precision: 0, price_precision: 4 })
+        );
+        assert_eq!(cache.round_quantity("NEWUSDT", 12.7), 12.0);
+
+        assert_eq!(cache.refresh_all(&client).await.unwrap(), 2);
+        assert_eq!(cache.cached("ETHUSDT").unwrap().qty_precision, 4);
+        assert_eq!(cache.cached("NEWUSDT").unwrap().qty_precision, 0);
+    }
+}
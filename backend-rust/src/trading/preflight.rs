@@ -0,0 +1,183 @@
+use crate::mexc::MexcClient;
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::Config;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Fehler des Pre-Trade-Checks
+#[derive(Debug)]
+pub enum PreflightError {
+    /// Freies Guthaben deckt Notional + Gebühren nicht
+    Insufficient {
+        asset: String,
+        required: f64,
+        available: f64,
+        fee_pct: f64,
+    },
+    /// Guthaben konnte nicht abgefragt werden
+    Balance(anyhow::Error),
+}
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Insufficient {
+                asset,
+                required,
+                available,
+                fee_pct,
+            } => write!(
+                f,
+                "Insufficient {asset} balance: order needs {required:.8} {asset} incl. {fee_pct}% fees, \
+                 but only {available:.8} {asset} is free. Deposit funds or reduce the order size."
+            ),
+            Self::Balance(e) => write!(f, "Balance pre-check failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+/// Benötigtes Quote-Guthaben: Notional plus geschätzte Gebühren
+pub fn required_quote(notional: f64, fee_pct: f64) -> f64 {
+    notional * (1.0 + fee_pct.max(0.0) / 100.0)
+}
+
+/// Pre-Trade-Check: freies Quote-Guthaben (pro User kurz gecacht) muss die Order decken
+pub struct BalancePreflight {
+    quote_asset: String,
+    fee_pct: f64,
+    cache_ttl: Duration,
+    clock: Arc<dyn Clock>,
+    /// user_id -> (Abfragezeit in ms, freies Guthaben)
+    cached: Mutex<HashMap<String, (i64, f64)>>,
+}
+
+impl BalancePreflight {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            quote_asset: config.quote_asset.clone(),
+            fee_pct: config.trading_fee_pct,
+            cache_ttl: Duration::from_millis(config.balance_cache_ms),
+            clock: Arc::new(SystemClock),
+            cached: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Nur wenn PRETRADE_BALANCE_CHECK aktiv ist
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        config.pretrade_balance_check.then(|| Arc::new(Self::new(config)))
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+
+    /// Freies Quote-Guthaben des Users, innerhalb der Cache-Dauer ohne erneuten Request
+    pub async fn free_quote(&self, client: &MexcClient, user_id: &str) -> anyhow::Result<f64> {
+        let now = self.clock.now_millis();
+        if let Some(&(fetched_at, free)) = self.cached.lock().unwrap_or_else(|e| e.into_inner()).get(user_id) {
+            if now - fetched_at < self.cache_ttl.as_millis() as i64 {
+                return Ok(free);
+            }
+        }
+
+        let free = client.get_account_balance().await?.free_of(&self.quote_asset);
+        self.cached
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(user_id.to_string(), (now, free));
+        Ok(free)
+    }
+
+    /// Cache des Users verwerfen (nach einer gesendeten Order)
+    pub fn invalidate(&self, user_id: &str) {
+        self.cached.lock().unwrap_or_else(|e| e.into_inner()).remove(user_id);
+    }
+
+    /// Prüfe ob das freie Guthaben des Users `notional` plus Gebühren deckt
+    pub async fn check(&self, client: &MexcClient, user_id: &str, notional: f64) -> Result<(), PreflightError> {
+        let required = required_quote(notional, self.fee_pct);
+        let available = self
+            .free_quote(client, user_id)
+            .await
+            .map_err(PreflightError::Balance)?;
+
+        if available < required {
+            tracing::warn!(
+                "Pre-trade check failed: {} {} required, {} free",
+                required,
+                self.quote_asset,
+                available
+            );
+            return Err(PreflightError::Insufficient {
+                asset: self.quote_asset.clone(),
+                required,
+                available,
+                fee_pct: self.fee_pct,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server};
+    use crate::utils::clock::MockClock;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_required_quote_includes_fees() {
+        assert!((required_quote(100.0, 0.1) - 100.1).abs() < 1e-9);
+        assert_eq!(required_quote(100.0, 0.0), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_balance_is_cached_within_ttl() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/account",
+            get({
+                let calls = calls.clone();
+                move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Json(json!({ "balances": [{ "asset": "USDT", "free": 50.0, "locked": 0.0 }] }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let clock = Arc::new(MockClock::from_millis(0));
+        let client = mexc_client(&base_url);
+        let preflight = BalancePreflight::new(&Config::default()).with_clock(clock.clone());
+
+        assert!(preflight.check(&client, "user-1", 40.0).await.is_ok());
+        let err = preflight
+            .check(&client, "user-1", 50.0)
+            .await
+            .expect_err("fees not covered");
+        assert!(matches!(err, PreflightError::Insufficient { .. }));
+        assert!(err.to_string().contains("Deposit funds or reduce the order size"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Cache ist pro User
+        preflight.check(&client, "user-2", 10.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        clock.advance(Duration::from_secs(3));
+        preflight.check(&client, "user-1", 10.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
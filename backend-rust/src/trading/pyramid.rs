@@ -0,0 +1,119 @@
+use crate::storage::PositionItem;
+use crate::utils::Config;
+
+/// Pyramiding: liegt eine offene Position `trigger_pct` über ihrem (gewichteten)
+/// Entry, wird `add_fraction` der aktuellen Menge nachgekauft, höchstens
+/// `max_add_ons` Mal. Da der Durchschnitts-Entry mitwandert, entsteht eine Stufenleiter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pyramiding {
+    /// Gewinn in Prozent über dem Entry, ab dem nachgelegt wird (0 = aus)
+    pub trigger_pct: f64,
+    /// Nachkauf als Anteil der aktuellen Positionsmenge (0.5 = +50 %)
+    pub add_fraction: f64,
+    /// Maximale Anzahl Add-ons pro Position
+    pub max_add_ons: u32,
+    /// Obergrenze des Positionswerts (Menge * Preis) in Quote (0 = unbegrenzt)
+    pub max_notional: f64,
+}
+
+impl Pyramiding {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            trigger_pct: config.pyramid_trigger_pct,
+            add_fraction: config.pyramid_add_fraction,
+            max_add_ons: config.pyramid_max_add_ons,
+            max_notional: config.pyramid_max_notional,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.trigger_pct > 0.0 && self.add_fraction > 0.0 && self.max_add_ons > 0
+    }
+
+    /// Menge des nächsten Add-ons beim aktuellen Preis der Position; None wenn
+    /// die Schwelle nicht erreicht, das Limit ausgeschöpft oder der Notional-Cap voll ist
+    pub fn add_on_quantity(&self, position: &PositionItem) -> Option<f64> {
+        if !self.is_enabled() || position.status != "open" || position.add_ons >= self.max_add_ons {
+            return None;
+        }
+        if position.pnl_percentage? < self.trigger_pct || position.current_price <= 0.0 {
+            return None;
+        }
+
+        let mut quantity = position.quantity * self.add_fraction;
+        if self.max_notional > 0.0 {
+            let headroom = self.max_notional / position.current_price - position.quantity;
+            quantity = quantity.min(headroom);
+        }
+        (quantity > 0.0).then_some(quantity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pyramiding(max_notional: f64) -> Pyramiding {
+        Pyramiding {
+            trigger_pct: 5.0,
+            add_fraction: 0.5,
+            max_add_ons: 2,
+            max_notional,
+        }
+    }
+
+    #[test]
+    fn test_add_on_fires_at_threshold_and_moves_average_entry() {
+        let pyramid = pyramiding(0.0);
+        let mut position = PositionItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            100.0,
+            2.0,
+            "long".to_string(),
+        );
+
+        position.calculate_pnl(104.9);
+        assert_eq!(pyramid.add_on_quantity(&position), None);
+
+        position.calculate_pnl(105.0);
+        let quantity = pyramid.add_on_quantity(&position).expect("add-on not triggered");
+        assert_eq!(quantity, 1.0);
+        position.add_on(105.0, quantity);
+        assert_eq!(position.quantity, 3.0);
+        assert!((position.entry_price - 305.0 / 3.0).abs() < 1e-9);
+        assert_eq!(position.add_ons, 1);
+
+        // Nächste Stufe erst 5 % über dem neuen Durchschnitt
+        position.calculate_pnl(106.0);
+        assert_eq!(pyramid.add_on_quantity(&position), None);
+
+        position.calculate_pnl(110.0);
+        let quantity = pyramid.add_on_quantity(&position).unwrap();
+        position.add_on(110.0, quantity);
+        assert_eq!(position.add_ons, 2);
+
+        // max_add_ons erreicht
+        position.calculate_pnl(200.0);
+        assert_eq!(pyramid.add_on_quantity(&position), None);
+    }
+
+    #[test]
+    fn test_add_on_respects_notional_cap() {
+        let mut position = PositionItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            100.0,
+            2.0,
+            "long".to_string(),
+        );
+        position.calculate_pnl(110.0);
+
+        // Cap 275 bei Preis 110 -> höchstens 2.5 Stück insgesamt
+        let quantity = pyramiding(275.0).add_on_quantity(&position).unwrap();
+        assert!((quantity - 0.5).abs() < 1e-9);
+
+        // Position schon am Cap -> kein Add-on
+        assert_eq!(pyramiding(220.0).add_on_quantity(&position), None);
+    }
+}
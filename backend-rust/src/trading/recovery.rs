@@ -0,0 +1,258 @@
+use crate::mexc::{MexcClient, OrderResponse};
+use crate::storage::{DynamoDBStore, OrderItem, OrderStatus};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Anzahl der zuletzt abgefragten Trades je Symbol
+const RECENT_TRADES_LIMIT: u32 = 100;
+
+/// Order Item für eine MEXC Order ohne lokalen Datensatz
+pub fn recovered_order(user_id: &str, remote: &OrderResponse) -> OrderItem {
+    let price = (remote.price > 0.0).then_some(remote.price);
+    let mut order = OrderItem::new(
+        user_id.to_string(),
+        remote.symbol.clone(),
+        remote.side.clone(),
+        remote.order_type.clone(),
+        remote.quantity,
+        price,
+    );
+
+    // Zeitpunkt der Platzierung bei MEXC statt Zeitpunkt des Imports
+    if remote.created_at > 0 {
+        if let Some(created) = DateTime::<Utc>::from_timestamp_millis(remote.created_at) {
+            order.timestamp = remote.created_at;
+            order.created_at = created.to_rfc3339();
+        }
+    }
+    order.mexc_order_id = Some(remote.order_id.clone());
+    order.status = OrderStatus::from_mexc(&remote.status).as_str().to_string();
+    order.filled_qty = remote.filled_qty;
+    if remote.filled_qty > 0.0 {
+        order.fill_price = price;
+    }
+    order.recovered = true;
+    order
+}
+
+/// Startup-Abgleich: Orders, die bei MEXC existieren aber nach einem Crash
+/// zwischen Platzierung und Speicherung in DynamoDB fehlen, werden übernommen
+pub struct OrderRecovery {
+    mexc_client: Arc<MexcClient>,
+    store: Arc<DynamoDBStore>,
+}
+
+impl OrderRecovery {
+    pub fn new(mexc_client: Arc<MexcClient>, store: Arc<DynamoDBStore>) -> Self {
+        Self { mexc_client, store }
+    }
+
+    /// Offene Orders und letzte Trades eines Symbols abgleichen; gibt die
+    /// Anzahl importierter Orders zurück
+    pub async fn recover_symbol(&self, user_id: &str, symbol: &str) -> Result<usize> {
+        let known: HashSet<String> = self
+            .store
+            .query_orders_by_symbol(user_id, symbol)
+            .await?
+            .into_iter()
+            .filter_map(|order| order.mexc_order_id)
+            .collect();
+
+        let mut orphans = Vec::new();
+        let mut seen = HashSet::new();
+        for remote in self.mexc_client.get_open_orders(symbol).await? {
+            if seen.insert(remote.order_id.clone()) && self.is_orphan(&known, &remote.order_id).await? {
+                orphans.push(remote);
+            }
+        }
+
+        // Bereits gefüllte Orders tauchen nur noch in den Trades auf
+        for fill in self.mexc_client.get_my_trades(symbol, RECENT_TRADES_LIMIT).await? {
+            if seen.insert(fill.order_id.clone()) && self.is_orphan(&known, &fill.order_id).await? {
+                orphans.push(self.mexc_client.get_order(symbol, &fill.order_id).await?);
+            }
+        }
+
+        for remote in &orphans {
+            let order = recovered_order(user_id, remote);
+            tracing::warn!(
+                "Recovered orphaned MEXC order {} ({} {} {}, {})",
+                remote.order_id,
+                order.side,
+                order.quantity,
+                order.symbol,
+                order.status
+            );
+            self.store.put_order(&order).await?;
+        }
+
+        Ok(orphans.len())
+    }
+
+    /// Weder beim User noch (geteiltes MEXC-Konto) bei einem anderen User gespeichert
+    async fn is_orphan(&self, known: &HashSet<String>, mexc_order_id: &str) -> Result<bool> {
+        if known.contains(mexc_order_id) {
+            return Ok(false);
+        }
+        Ok(self.store.find_order_by_mexc_id(mexc_order_id).await?.is_none())
+    }
+
+    /// Alle Symbole abgleichen; Fehler einzelner Symbole werden geloggt und
+    /// brechen den Start nicht ab
+    pub async fn run(&self, user_id: &str, symbols: &[String]) -> usize {
+        let mut recovered = 0;
+        for symbol in symbols {
+            match self.recover_symbol(user_id, symbol).await {
+                Ok(count) => recovered += count,
+                Err(e) => tracing::error!("Order recovery for {} failed: {}", symbol, e),
+            }
+        }
+
+        if recovered > 0 {
+            tracing::error!("ALERT: recovered {} orphaned MEXC order(s) for {}", recovered, user_id);
+        }
+        recovered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    fn mexc_order(order_id: &str, status: &str, filled_qty: f64) -> serde_json::Value {
+        json!({
+            "order_id": order_id,
+            "symbol": "ETHUSDT",
+            "side": "BUY",
+            "order_type": "LIMIT",
+            "quantity": 2.0,
+            "price": 100.0,
+            "status": status,
+            "filled_qty": filled_qty,
+            "created_at": 1_700_000_000_000i64,
+        })
+    }
+
+    fn stored_order(user_id: &str, mexc_order_id: &str) -> serde_json::Value {
+        json!({
+            "user_id": { "S": user_id },
+            "sk": { "S": format!("ORDER#1#{}", mexc_order_id) },
+            "order_id": { "S": format!("order-{}", mexc_order_id) },
+            "symbol": { "S": "ETHUSDT" },
+            "side": { "S": "BUY" },
+            "order_type": { "S": "LIMIT" },
+            "quantity": { "N": "2" },
+            "filled_qty": { "N": "0" },
+            "status": { "S": "open" },
+            "timestamp": { "N": "1" },
+            "created_at": { "S": "2024-01-01T00:00:00Z" },
+            "updated_at": { "S": "2024-01-01T00:00:00Z" },
+            "mexc_order_id": { "S": mexc_order_id },
+            "ttl": { "N": "1" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_exchange_orders_are_imported() {
+        let router = Router::new()
+            .route(
+                "/api/v3/openOrders",
+                get(|| async { Json(json!([mexc_order("known-1", "NEW", 0.0), mexc_order("orphan-1", "NEW", 0.0)])) }),
+            )
+            .route(
+                "/api/v3/myTrades",
+                get(|| async {
+                    Json(json!([{
+                        "symbol": "ETHUSDT", "id": "t-1", "orderId": "orphan-2", "price": "100",
+                        "qty": "2", "quoteQty": "200", "commission": "0", "commissionAsset": "USDT",
+                        "time": 1_700_000_000_500i64, "isBuyer": true, "isMaker": false
+                    }]))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                get(|| async { Json(mexc_order("orphan-2", "FILLED", 2.0)) }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored_order("admin", "known-1")] }));
+        let recovery = OrderRecovery::new(mexc, Arc::new(dynamo.store().await));
+
+        let recovered = recovery.run("admin", &["ETHUSDT".to_string()]).await;
+
+        assert_eq!(recovered, 2);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 2);
+        let open = &puts[0]["Item"];
+        assert_eq!(open["mexc_order_id"]["S"], "orphan-1");
+        assert_eq!(open["recovered"]["BOOL"], true);
+        assert_eq!(open["status"]["S"], "open");
+        assert_eq!(open["timestamp"]["N"], "1700000000000");
+        let filled = &puts[1]["Item"];
+        assert_eq!(filled["mexc_order_id"]["S"], "orphan-2");
+        assert_eq!(filled["status"]["S"], "filled");
+        assert_eq!(filled["fill_price"]["N"], "100");
+    }
+
+    #[tokio::test]
+    async fn test_second_recovery_run_imports_nothing() {
+        let router = Router::new()
+            .route(
+                "/api/v3/openOrders",
+                get(|| async { Json(json!([mexc_order("orphan-1", "NEW", 0.0), mexc_order("other-1", "NEW", 0.0)])) }),
+            )
+            .route(
+                "/api/v3/myTrades",
+                get(|| async {
+                    Json(json!([{
+                        "symbol": "ETHUSDT", "id": "t-1", "orderId": "orphan-2", "price": "100",
+                        "qty": "2", "quoteQty": "200", "commission": "0", "commissionAsset": "USDT",
+                        "time": 1_700_000_000_500i64, "isBuyer": true, "isMaker": false
+                    }]))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                get(|| async { Json(mexc_order("orphan-2", "FILLED", 2.0)) }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let recovery = OrderRecovery::new(mexc, Arc::new(dynamo.store().await));
+
+        // Erster Start: nichts gespeichert; other-1 gehört auf dem geteilten Konto einem anderen User
+        let empty = json!({ "Count": 0, "Items": [] });
+        dynamo.respond("Query", empty.clone());
+        dynamo.respond("Query", empty.clone());
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored_order("user-2", "other-1")] }));
+        dynamo.respond("Query", empty);
+        assert_eq!(recovery.run("admin", &["ETHUSDT".to_string()]).await, 2);
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 2);
+
+        // Neustart: die importierten Orders kommen über zwei Seiten zurück
+        dynamo.respond(
+            "Query",
+            json!({
+                "Count": 1,
+                "Items": [puts[0]["Item"]],
+                "LastEvaluatedKey": { "user_id": puts[0]["Item"]["user_id"], "sk": puts[0]["Item"]["sk"] }
+            }),
+        );
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [puts[1]["Item"]] }));
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [stored_order("user-2", "other-1")] }));
+        assert_eq!(recovery.run("admin", &["ETHUSDT".to_string()]).await, 0);
+        assert_eq!(dynamo.requests("PutItem").len(), 2);
+
+        let queries = dynamo.requests("Query");
+        assert_eq!(queries.len(), 7);
+        assert_eq!(queries[1]["IndexName"], "mexc_order_id-index");
+        assert_eq!(queries[1]["ExpressionAttributeValues"][":mexc_id"]["S"], "orphan-1");
+        assert_eq!(queries[5]["ExclusiveStartKey"]["sk"], puts[0]["Item"]["sk"]);
+        assert_eq!(queries[6]["ExpressionAttributeValues"][":mexc_id"]["S"], "other-1");
+    }
+}
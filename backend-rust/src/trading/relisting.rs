@@ -0,0 +1,77 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::storage::{CalendarEventItem, DynamoDBStore};
+use crate::utils::Config;
+
+/// Reaktion auf ein Symbol, das wir schon einmal gelistet/gehandelt gesehen haben
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelistingAction {
+    /// Confidence um `confidence_penalty` senken, dann normal gaten
+    #[default]
+    Downgrade,
+    /// Nicht automatisch feuern; nur per manuellem Override (`/schedule/.../fire`)
+    Confirm,
+}
+
+impl std::str::FromStr for RelistingAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "downgrade" => Ok(RelistingAction::Downgrade),
+            "confirm" => Ok(RelistingAction::Confirm),
+            other => Err(format!("unknown relisting action: {}", other)),
+        }
+    }
+}
+
+/// Erkennt Re-Listings: taucht das Symbol in älteren Calendar Events oder Orders
+/// des Users auf, die mindestens `min_age` zurückliegen, ist es kein neues Listing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RelistingGuard {
+    /// Mindestalter des früheren Auftretens (0 = Guard aus)
+    pub min_age: Duration,
+    pub action: RelistingAction,
+    /// Abzug von der Confidence bei `Downgrade`
+    pub confidence_penalty: f64,
+}
+
+impl RelistingGuard {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            min_age: Duration::from_secs(config.relisting_min_age_hours * 3600),
+            action: config.relisting_action,
+            confidence_penalty: config.relisting_confidence_penalty,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.min_age.is_zero()
+    }
+
+    /// Frühester Zeitpunkt (Unix ms), zu dem das Symbol vor mindestens `min_age`
+    /// schon einmal auftauchte; None bei einem echten Neu-Listing
+    pub async fn first_seen(
+        &self,
+        store: &DynamoDBStore,
+        user_id: &str,
+        event: &CalendarEventItem,
+        now: i64,
+    ) -> Result<Option<i64>> {
+        let cutoff = now - self.min_age.as_millis() as i64;
+
+        let events = store.query_calendar_events_by_time(user_id, 0, cutoff).await?;
+        let earlier_events = events
+            .iter()
+            .filter(|e| e.event_id != event.event_id && e.symbol.eq_ignore_ascii_case(&event.symbol))
+            .map(|e| e.launch_time);
+
+        let orders = store.query_orders_by_symbol(user_id, &event.symbol).await?;
+        let earlier_orders = orders.iter().map(|o| o.timestamp).filter(|t| *t <= cutoff);
+
+        Ok(earlier_events.chain(earlier_orders).min())
+    }
+}
@@ -0,0 +1,396 @@
+use crate::mexc::{MexcClientPool, UserClientError};
+use crate::storage::{CalendarEventItem, DynamoDBStore};
+use crate::trading::sizing::{calculate_position_size, SizingSettings};
+use crate::trading::sniper::{SnipeOrderParams, SnipeOutcome, SnipingManager};
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::{Config, RuntimeSettings};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Wie oft `run` auf fällige Launches prüft
+const FIRE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Fällige Launches über den Sniper feuern; die Menge ergibt sich aus der
+/// Positionsgröße (freie Quote-Balance des Users * Risiko% * Confidence) zum aktuellen Preis
+struct LaunchFiring {
+    sniper: Arc<SnipingManager>,
+    /// Guthaben je User über dessen Client, Preise über den globalen
+    clients: Arc<MexcClientPool>,
+    sizing: SizingSettings,
+    quote_asset: String,
+    runtime: Option<Arc<RuntimeSettings>>,
+}
+
+/// Plant Launches aus dem Kalender. Pro Zyklus werden nur Events innerhalb von
+/// `lookahead` geladen (begrenzt Speicher und Query-Kosten); Events, die erst
+/// später ins Fenster rücken, kommen im nächsten Zyklus dazu.
+pub struct LaunchScheduler {
+    store: Arc<DynamoDBStore>,
+    lookahead: Duration,
+    reload_interval: Duration,
+    clock: Arc<dyn Clock>,
+    /// Geplante Events nach (launch_time, user_id, event_id); die event_id
+    /// enthält keinen User, mehrere User können denselben Launch snipen
+    scheduled: Mutex<BTreeMap<(i64, String, String), CalendarEventItem>>,
+    /// Ohne Sniper wird nur geplant, nicht gefeuert
+    firing: Option<LaunchFiring>,
+}
+
+impl LaunchScheduler {
+    pub fn new(store: Arc<DynamoDBStore>, lookahead: Duration, reload_interval: Duration) -> Self {
+        Self {
+            store,
+            lookahead,
+            reload_interval,
+            clock: Arc::new(SystemClock),
+            scheduled: Mutex::new(BTreeMap::new()),
+            firing: None,
+        }
+    }
+
+    pub fn from_config(store: Arc<DynamoDBStore>, config: &Config) -> Self {
+        Self::new(
+            store,
+            Duration::from_secs(config.scheduler_lookahead_hours * 3600),
+            Duration::from_secs(config.scheduler_reload_secs),
+        )
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Fällige Launches automatisch snipen (Sizing und Quote-Asset aus der Config);
+    /// der Sniper sollte denselben Client-Pool nutzen, damit jeder User über seine Keys handelt
+    pub fn with_sniper(mut self, sniper: Arc<SnipingManager>, clients: Arc<MexcClientPool>, config: &Config) -> Self {
+        self.firing = Some(LaunchFiring {
+            sniper,
+            clients,
+            sizing: SizingSettings::from_config(config),
+            quote_asset: config.quote_asset.clone(),
+            runtime: None,
+        });
+        self
+    }
+
+    /// Risiko% beim Feuern aus den hot-reloadbaren Settings
+    pub fn with_runtime_settings(mut self, runtime: Arc<RuntimeSettings>) -> Self {
+        if let Some(firing) = &mut self.firing {
+            firing.runtime = Some(runtime);
+        }
+        self
+    }
+
+    /// Events im Fenster [jetzt, jetzt + lookahead] laden und als Batch einplanen;
+    /// gibt die Anzahl neu geplanter Events zurück
+    pub async fn reload(&self, user_ids: &[String]) -> Result<usize> {
+        let now = self.clock.now_millis();
+        let window_end = now + self.lookahead.as_millis() as i64;
+
+        let mut batch = Vec::new();
+        for user_id in user_ids {
+            let events = self
+                .store
+                .query_calendar_events_by_time(user_id, now, window_end)
+                .await?;
+            // Fenster auch clientseitig erzwingen: nichts vorzeitig einplanen
+            batch.extend(
+                events
+                    .into_iter()
+                    .filter(|e| e.status == "detected" && e.launch_time >= now && e.launch_time <= window_end),
+            );
+        }
+
+        let mut scheduled = self.scheduled.lock().unwrap_or_else(|e| e.into_inner());
+        let before = scheduled.len();
+        for event in batch {
+            scheduled
+                .entry((event.launch_time, event.user_id.clone(), event.event_id.clone()))
+                .or_insert(event);
+        }
+        let added = scheduled.len() - before;
+        if added > 0 {
+            tracing::info!(
+                "Scheduled {} new launch(es) within {}h ({} total)",
+                added,
+                self.lookahead.as_secs() / 3600,
+                scheduled.len()
+            );
+        }
+        Ok(added)
+    }
+
+    /// Fällige Events (launch_time <= jetzt) entnehmen
+    pub fn take_due(&self) -> Vec<CalendarEventItem> {
+        let now = self.clock.now_millis();
+        let mut scheduled = self.scheduled.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = scheduled.split_off(&(now + 1, String::new(), String::new()));
+        std::mem::replace(&mut *scheduled, pending).into_values().collect()
+    }
+
+    /// Aktuell geplante Events in Launch-Reihenfolge
+    pub fn scheduled(&self) -> Vec<CalendarEventItem> {
+        self.scheduled
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Fällige Launches entnehmen und je Event im Hintergrund feuern; gibt die Anzahl zurück
+    pub fn fire_due(self: &Arc<Self>) -> usize {
+        if self.firing.is_none() {
+            return 0;
+        }
+        let due = self.take_due();
+        for event in &due {
+            let scheduler = self.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                match scheduler.fire(&event).await {
+                    Ok(outcome) => tracing::info!("Scheduled snipe {} for {}: {:?}", event.event_id, event.symbol, outcome),
+                    Err(e) => tracing::error!("Scheduled snipe {} for {} failed: {}", event.event_id, event.symbol, e),
+                }
+            });
+        }
+        due.len()
+    }
+
+    /// Menge zum aktuellen Preis bestimmen und mit Retry snipen; das Event wird
+    /// dabei per detected -> sniping beansprucht (kein Doppelkauf mit `/fire`)
+    async fn fire(&self, event: &CalendarEventItem) -> Result<SnipeOutcome> {
+        let Some(firing) = &self.firing else {
+            return Ok(SnipeOutcome::Skipped {
+                reason: "scheduler has no sniper".to_string(),
+            });
+        };
+        // Ohne eigene Keys wird nicht über das Admin-Konto gehandelt
+        let client = match firing.clients.for_user(&event.user_id).await {
+            Ok(client) => client,
+            Err(e @ UserClientError::MissingCredentials(_)) => {
+                tracing::warn!("Not sniping {} for user {}: {}", event.symbol, event.user_id, e);
+                return Ok(SnipeOutcome::Skipped { reason: e.to_string() });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let (balance, ticker) = tokio::join!(
+            client.get_account_balance(),
+            firing.clients.global().get_ticker(&event.symbol),
+        );
+        let price = ticker?.price;
+        let settings = SizingSettings {
+            risk_pct: firing
+                .runtime
+                .as_ref()
+                .map_or(firing.sizing.risk_pct, |runtime| runtime.load().risk_per_trade_pct),
+            ..firing.sizing.clone()
+        };
+        let sizing = calculate_position_size(balance?.free_of(&firing.quote_asset), price, event.confidence, &settings);
+        if sizing.quantity <= 0.0 {
+            return Ok(SnipeOutcome::Skipped {
+                reason: format!("position size for {} rounds to zero", event.symbol),
+            });
+        }
+
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: sizing.quantity,
+            expected_price: Some(price),
+            confirmed: false,
+        };
+        firing.sniper.execute_snipe_with_retry(&event.user_id, event, params).await
+    }
+
+    /// Periodisch neu laden und fällige Launches feuern bis zum Shutdown
+    pub async fn run(self: Arc<Self>, user_ids: Vec<String>, mut shutdown: watch::Receiver<bool>) {
+        let mut ticker = tokio::time::interval(self.reload_interval);
+        let mut fire_ticker = tokio::time::interval(FIRE_POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if let Err(e) = self.reload(&user_ids).await {
+                        tracing::warn!("Launch scheduler reload failed: {}", e);
+                    }
+                }
+                _ = fire_ticker.tick() => {
+                    self.fire_due();
+                }
+                _ = shutdown.changed() => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mexc_client, spawn_server, MockDynamo};
+    use crate::utils::clock::MockClock;
+    use axum::extract::Query;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn event_item(symbol: &str, launch_time: i64) -> serde_json::Value {
+        json!({
+            "user_id": { "S": "user-1" },
+            "sk": { "S": format!("CALENDAR#{}", launch_time) },
+            "event_id": { "S": format!("{}#{}", symbol, launch_time) },
+            "token_name": { "S": symbol },
+            "symbol": { "S": symbol },
+            "launch_time": { "N": launch_time.to_string() },
+            "detected_pattern": { "S": "sts:2" },
+            "confidence": { "N": "0.9" },
+            "created_at": { "S": "2024-01-01T00:00:00Z" },
+            "status": { "S": "detected" },
+            "ttl": { "N": "0" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_only_in_window_events_are_scheduled() {
+        let start = 1_700_000_000_000;
+        let dynamo = MockDynamo::start().await;
+        let clock = Arc::new(MockClock::from_millis(start));
+        let scheduler = LaunchScheduler::new(
+            Arc::new(dynamo.store().await),
+            Duration::from_secs(24 * 3600),
+            Duration::from_secs(60),
+        )
+        .with_clock(clock.clone());
+        let users = vec!["user-1".to_string()];
+        let events = [
+            event_item("SOONUSDT", start + HOUR_MS),
+            event_item("EDGEUSDT", start + 24 * HOUR_MS),
+            event_item("LATERUSDT", start + 25 * HOUR_MS),
+        ];
+
+        dynamo.respond("Query", json!({ "Count": 3, "Items": events }));
+        assert_eq!(scheduler.reload(&users).await.unwrap(), 2);
+        let query = &dynamo.requests("Query")[0];
+        assert_eq!(query["ExpressionAttributeValues"][":end"]["N"], (start + 24 * HOUR_MS).to_string());
+        let symbols: Vec<_> = scheduler.scheduled().into_iter().map(|e| e.symbol).collect();
+        assert_eq!(symbols, vec!["SOONUSDT", "EDGEUSDT"]);
+
+        // Zwei Stunden später rückt LATERUSDT ins Fenster, Bekanntes wird nicht doppelt geplant
+        clock.advance(Duration::from_secs(2 * 3600));
+        dynamo.respond("Query", json!({ "Count": 2, "Items": [events[1].clone(), events[2].clone()] }));
+        assert_eq!(scheduler.reload(&users).await.unwrap(), 1);
+
+        let due: Vec<_> = scheduler.take_due().into_iter().map(|e| e.symbol).collect();
+        assert_eq!(due, vec!["SOONUSDT"]);
+        assert_eq!(scheduler.scheduled().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_due_launch_is_sniped_with_sized_quantity() {
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new()
+            .route(
+                "/api/v3/account",
+                get(|| async { Json(json!({ "balances": [{ "asset": "USDT", "free": 1000.0, "locked": 0.0 }] })) }),
+            )
+            .route(
+                "/api/v3/ticker/24hr",
+                get(|| async { Json(json!({ "symbol": "SOONUSDT", "price": 2.0, "timestamp": 0 })) }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        orders.lock().unwrap().push(params.clone());
+                        let quantity: f64 = params["quantity"].parse().unwrap();
+                        Json(json!({
+                            "order_id": "sched-1",
+                            "symbol": params["symbol"],
+                            "side": "BUY",
+                            "order_type": "MARKET",
+                            "quantity": quantity,
+                            "price": 2.0,
+                            "status": "FILLED",
+                            "filled_qty": quantity,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let client = Arc::new(mexc_client(&base_url));
+        // user-1 ist Admin (globaler Key), user-2 hat keine Keys
+        let config = Config {
+            risk_per_trade_pct: 10.0,
+            qty_step_size: 0.01,
+            mexc_admin_user_id: Some("user-1".to_string()),
+            ..Default::default()
+        };
+        let clients = Arc::new(MexcClientPool::new(client.clone(), store.clone(), &config));
+        let sniper = Arc::new(SnipingManager::new(client, store.clone(), &config).with_client_pool(clients.clone()));
+        let scheduler = LaunchScheduler::from_config(store, &config).with_sniper(sniper, clients, &config);
+
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "Soon Token".to_string(),
+            "SOONUSDT".to_string(),
+            chrono::Utc::now().timestamp_millis(),
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        let outcome = scheduler.fire(&event).await.expect("scheduled snipe failed");
+
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }), "{:?}", outcome);
+        // 1000 USDT * 10% * 0.9 Confidence / 2.0 = 45
+        let placed = orders.lock().unwrap().clone();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0]["side"], "BUY");
+        assert_eq!(placed[0]["quantity"].parse::<f64>().unwrap(), 45.0);
+        // Event vor dem Kauf beansprucht
+        let updates = dynamo.requests("UpdateItem");
+        assert_eq!(updates[0]["ExpressionAttributeValues"][":to"]["S"], "sniping");
+
+        // User ohne Keys: übersprungen statt über das Admin-Konto gehandelt
+        let foreign = CalendarEventItem {
+            user_id: "user-2".to_string(),
+            ..event
+        };
+        let outcome = scheduler.fire(&foreign).await.expect("scheduled snipe failed");
+        assert!(matches!(outcome, SnipeOutcome::Skipped { .. }), "{:?}", outcome);
+        assert_eq!(orders.lock().unwrap().len(), 1);
+        assert_eq!(dynamo.requests("UpdateItem").len(), updates.len());
+    }
+
+    #[tokio::test]
+    async fn test_same_launch_is_scheduled_for_every_user() {
+        let start = 1_700_000_000_000;
+        let dynamo = MockDynamo::start().await;
+        let scheduler = LaunchScheduler::new(
+            Arc::new(dynamo.store().await),
+            Duration::from_secs(24 * 3600),
+            Duration::from_secs(60),
+        )
+        .with_clock(Arc::new(MockClock::from_millis(start)));
+        let users = vec!["user-1".to_string(), "user-2".to_string()];
+        let mut other_user = event_item("SOONUSDT", start + HOUR_MS);
+        other_user["user_id"] = json!({ "S": "user-2" });
+
+        // Gleiche event_id (Symbol + Launch-Zeit), aber zwei User
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [event_item("SOONUSDT", start + HOUR_MS)] }));
+        dynamo.respond("Query", json!({ "Count": 1, "Items": [other_user] }));
+        assert_eq!(scheduler.reload(&users).await.unwrap(), 2);
+
+        let scheduled: Vec<_> = scheduler.scheduled().into_iter().map(|e| e.user_id).collect();
+        assert_eq!(scheduled, vec!["user-1", "user-2"]);
+    }
+}
@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+
+/// Rundung der Order-Menge auf die Schrittweite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Immer abrunden (nie über der Allocation)
+    #[default]
+    Down,
+    /// Zum nächsten Step runden, sofern bezahlbar
+    Nearest,
+    /// Aufrunden, sofern bezahlbar
+    UpIfAffordable,
+}
+
+impl std::str::FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "down" => Ok(RoundingMode::Down),
+            "nearest" => Ok(RoundingMode::Nearest),
+            "up" | "up_if_affordable" => Ok(RoundingMode::UpIfAffordable),
+            other => Err(format!("unknown rounding mode: {}", other)),
+        }
+    }
+}
+
+/// Einstellungen für die Positionsgrößen-Berechnung
+#[derive(Debug, Clone)]
+pub struct SizingSettings {
+    /// Anteil der freien Quote-Balance pro Trade in Prozent
+    pub risk_pct: f64,
+    /// Schrittweite der Order-Menge (LOT_SIZE)
+    pub step_size: f64,
+    /// Default-Rundung, pro Request überschreibbar
+    pub rounding: RoundingMode,
+    /// Fester Kapitalstock als Sizing-Basis (0 = freie Balance)
+    pub base_capital: f64,
+    /// Basis ist die freie Balance inkl. realisierter Gewinne statt des Kapitalstocks
+    pub compounding: bool,
+    /// Compounding-Basis höchstens `base_capital` mal diesem Faktor (0 = unbegrenzt)
+    pub compounding_max_multiple: f64,
+}
+
+impl SizingSettings {
+    pub fn from_config(config: &crate::utils::Config) -> Self {
+        Self {
+            risk_pct: config.risk_per_trade_pct,
+            step_size: config.qty_step_size,
+            rounding: config.qty_rounding_mode,
+            base_capital: config.sizing_base_capital,
+            compounding: config.sizing_compounding,
+            compounding_max_multiple: config.sizing_compounding_max_multiple,
+        }
+    }
+
+    /// Betrag, auf den Risiko% angewendet wird; nie mehr als die freie Balance
+    pub fn sizing_base(&self, free_balance: f64) -> f64 {
+        let free_balance = free_balance.max(0.0);
+        if self.base_capital <= 0.0 {
+            return free_balance;
+        }
+        if !self.compounding {
+            return free_balance.min(self.base_capital);
+        }
+        if self.compounding_max_multiple > 0.0 {
+            free_balance.min(self.base_capital * self.compounding_max_multiple)
+        } else {
+            free_balance
+        }
+    }
+}
+
+/// Ergebnis inkl. Zwischenwerten (für Simulation/Debugging)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PositionSizing {
+    pub free_balance: f64,
+    /// Basis für die Allocation (Kapitalstock bzw. Compounding-Basis)
+    pub sizing_base: f64,
+    pub risk_pct: f64,
+    pub confidence: f64,
+    /// Quote-Betrag der eingesetzt werden soll
+    pub allocation: f64,
+    pub price: f64,
+    /// Menge vor Rundung auf die Schrittweite
+    pub raw_quantity: f64,
+    pub step_size: f64,
+    pub rounding: RoundingMode,
+    /// Finale Order-Menge (auf step_size gerundet, nie über der freien Balance)
+    pub quantity: f64,
+}
+
+/// Berechne Positionsgröße: Sizing-Basis * Risiko% * Confidence / Preis,
+/// gerundet auf die Schrittweite gemäß `settings.rounding`. Die Basis ist die
+/// freie Balance, ein fester Kapitalstock oder (Compounding) die gedeckelte Balance.
+pub fn calculate_position_size(
+    free_balance: f64,
+    price: f64,
+    confidence: f64,
+    settings: &SizingSettings,
+) -> PositionSizing {
+    let confidence = confidence.clamp(0.0, 1.0);
+    let sizing_base = settings.sizing_base(free_balance);
+    let allocation = sizing_base * settings.risk_pct / 100.0 * confidence;
+    let raw_quantity = if price > 0.0 { allocation / price } else { 0.0 };
+
+    PositionSizing {
+        free_balance,
+        sizing_base,
+        risk_pct: settings.risk_pct,
+        confidence,
+        allocation,
+        price,
+        raw_quantity,
+        step_size: settings.step_size,
+        rounding: settings.rounding,
+        quantity: round_quantity(
+            raw_quantity,
+            settings.step_size,
+            settings.rounding,
+            price,
+            free_balance,
+        ),
+    }
+}
+
+/// Runde eine Menge gemäß `mode` auf die Schrittweite.
+/// Nearest/Up fallen auf Abrunden zurück, wenn `quantity * price` das Budget übersteigt.
+pub fn round_quantity(
+    quantity: f64,
+    step_size: f64,
+    mode: RoundingMode,
+    price: f64,
+    budget: f64,
+) -> f64 {
+    let down = round_down_to_step(quantity, step_size);
+    if step_size <= 0.0 || down >= quantity {
+        return down;
+    }
+
+    let up = round_down_to_step(down + step_size, step_size);
+    let candidate = match mode {
+        RoundingMode::Down => return down,
+        RoundingMode::Nearest if quantity - down < up - quantity => return down,
+        RoundingMode::Nearest | RoundingMode::UpIfAffordable => up,
+    };
+
+    if candidate * price <= budget.max(0.0) {
+        candidate
+    } else {
+        down
+    }
+}
+
+/// Runde eine Menge auf ein Vielfaches der Schrittweite ab
+pub fn round_down_to_step(quantity: f64, step_size: f64) -> f64 {
+    if step_size <= 0.0 {
+        return quantity;
+    }
+    // Kleiner Epsilon-Aufschlag gegen Float-Artefakte (z.B. 2.9999999 / 0.01)
+    let steps = (quantity / step_size + 1e-9).floor();
+    let decimals = (-step_size.log10()).ceil().max(0.0) as i32;
+    let factor = 10f64.powi(decimals);
+    (steps * step_size * factor).round() / factor
+}
+
+/// Reduce-only: Menge auf die offene Positionsgröße begrenzen, damit ein
+/// Close nie in die Gegenrichtung dreht
+pub fn clamp_reduce_only_quantity(requested: f64, position_quantity: f64) -> f64 {
+    requested.min(position_quantity.max(0.0))
+}
+
+/// Prüfe ob eine Order-Seite die Position verkleinert (long -> SELL, short -> BUY)
+pub fn is_closing_side(position_side: &str, order_side: &str) -> bool {
+    match position_side {
+        "long" => order_side.eq_ignore_ascii_case("SELL"),
+        "short" => order_side.eq_ignore_ascii_case("BUY"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_position_size() {
+        let settings = SizingSettings {
+            risk_pct: 10.0,
+            step_size: 0.01,
+            rounding: RoundingMode::Down,
+            base_capital: 0.0,
+            compounding: false,
+            compounding_max_multiple: 0.0,
+        };
+
+        // 1000 USDT * 10% * 0.8 = 80 USDT / 3.0 = 26.666.. -> 26.66
+        let sizing = calculate_position_size(1000.0, 3.0, 0.8, &settings);
+        assert_eq!(sizing.allocation, 80.0);
+        assert_eq!(sizing.quantity, 26.66);
+    }
+
+    #[test]
+    fn test_compounding_grows_size_up_to_cap() {
+        let fixed = SizingSettings {
+            risk_pct: 10.0,
+            step_size: 0.01,
+            rounding: RoundingMode::Down,
+            base_capital: 1000.0,
+            compounding: false,
+            compounding_max_multiple: 1.5,
+        };
+        let compounding = SizingSettings {
+            compounding: true,
+            ..fixed.clone()
+        };
+
+        let before = calculate_position_size(1000.0, 1.0, 1.0, &compounding);
+        assert_eq!(before.allocation, 100.0);
+
+        // Nach profitablem Close (+200 USDT realisiert) wächst nur die Compounding-Größe
+        let after = calculate_position_size(1200.0, 1.0, 1.0, &compounding);
+        assert_eq!(after.sizing_base, 1200.0);
+        assert_eq!(after.quantity, 120.0);
+        assert_eq!(calculate_position_size(1200.0, 1.0, 1.0, &fixed).quantity, 100.0);
+
+        // Glückssträhne: Basis bei 1.5x Kapitalstock gedeckelt
+        let capped = calculate_position_size(5000.0, 1.0, 1.0, &compounding);
+        assert_eq!(capped.sizing_base, 1500.0);
+        assert_eq!(capped.quantity, 150.0);
+
+        // Verluste verkleinern die Basis weiterhin
+        assert_eq!(calculate_position_size(800.0, 1.0, 1.0, &compounding).quantity, 80.0);
+    }
+
+    #[test]
+    fn test_reduce_only_never_exceeds_position() {
+        assert_eq!(clamp_reduce_only_quantity(1.5, 1.0), 1.0);
+        assert_eq!(clamp_reduce_only_quantity(0.4, 1.0), 0.4);
+        assert_eq!(clamp_reduce_only_quantity(1.0, -1.0), 0.0);
+
+        assert!(is_closing_side("long", "SELL"));
+        assert!(is_closing_side("short", "buy"));
+        assert!(!is_closing_side("long", "BUY"));
+    }
+
+    #[test]
+    fn test_round_down_to_step() {
+        assert_eq!(round_down_to_step(3.0, 0.01), 3.0);
+        assert_eq!(round_down_to_step(1.23456, 0.001), 1.234);
+        assert_eq!(round_down_to_step(7.9, 1.0), 7.0);
+    }
+
+    #[test]
+    fn test_round_quantity_modes() {
+        // Step 0.1, Preis 10 -> 0.1 Menge kostet 1 USDT
+        assert_eq!(round_quantity(2.37, 0.1, RoundingMode::Down, 10.0, 100.0), 2.3);
+        assert_eq!(round_quantity(2.37, 0.1, RoundingMode::Nearest, 10.0, 100.0), 2.4);
+        assert_eq!(round_quantity(2.32, 0.1, RoundingMode::Nearest, 10.0, 100.0), 2.3);
+        assert_eq!(round_quantity(2.32, 0.1, RoundingMode::UpIfAffordable, 10.0, 100.0), 2.4);
+        // Exakte Vielfache bleiben unverändert
+        assert_eq!(round_quantity(2.3, 0.1, RoundingMode::UpIfAffordable, 10.0, 100.0), 2.3);
+    }
+
+    #[test]
+    fn test_round_quantity_up_never_exceeds_budget() {
+        // 2.4 * 10 = 24 > 23.9 Budget -> abrunden
+        assert_eq!(round_quantity(2.37, 0.1, RoundingMode::Nearest, 10.0, 23.9), 2.3);
+        assert_eq!(round_quantity(2.32, 0.1, RoundingMode::UpIfAffordable, 10.0, 23.9), 2.3);
+        assert_eq!(round_quantity(2.32, 0.1, RoundingMode::UpIfAffordable, 10.0, 24.0), 2.4);
+
+        // Sizing mit voller Balance: Aufrunden wäre nicht bezahlbar
+        let settings = SizingSettings {
+            risk_pct: 100.0,
+            step_size: 1.0,
+            rounding: RoundingMode::UpIfAffordable,
+            base_capital: 0.0,
+            compounding: false,
+            compounding_max_multiple: 0.0,
+        };
+        let sizing = calculate_position_size(100.0, 3.0, 1.0, &settings);
+        assert_eq!(sizing.quantity, 33.0);
+    }
+
+    #[test]
+    fn test_rounding_mode_from_str() {
+        assert_eq!("nearest".parse(), Ok(RoundingMode::Nearest));
+        assert_eq!("UP".parse(), Ok(RoundingMode::UpIfAffordable));
+        assert!("sideways".parse::<RoundingMode>().is_err());
+    }
+}
@@ -1,101 +1,3052 @@
-use crate::mexc::MexcClient;
-use crate::storage::{CalendarEventItem, DynamoDBStore, OrderItem};
+use crate::mexc::{MexcClient, MexcClientPool, MexcError, OrderBook, OrderResponse, SymbolState, UserClientError};
+use crate::storage::{CalendarEventItem, DynamoDBStore, OrderItem, OrderStatus};
+use crate::trading::blacklist::SymbolBlacklist;
+use crate::trading::confirm::FillConfirmation;
+use crate::trading::cooldown::PlacementCooldown;
+use crate::trading::decisions::{DecisionEvent, DecisionGate, DecisionLog};
+use crate::trading::precision::PrecisionCache;
+use crate::trading::preflight::{BalancePreflight, PreflightError};
+use crate::trading::relisting::{RelistingAction, RelistingGuard};
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::{Config, RuntimeSettings};
 use anyhow::Result;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
 
 /// Auto-Sniping Manager für Automatische Order Execution
 pub struct SnipingManager {
     mexc_client: Arc<MexcClient>,
     store: Arc<DynamoDBStore>,
+    blacklist: SymbolBlacklist,
+    clock: Arc<dyn Clock>,
+    runtime: Arc<RuntimeSettings>,
+    unwind_on_slippage: bool,
+    retry: SnipeRetryPolicy,
+    /// Pre-Trade-Guthabenprüfung (None = Ultra-Low-Latency)
+    preflight: Option<Arc<BalancePreflight>>,
+    /// Order-Book-Imbalance-Gate für Buy-Snipes
+    imbalance_gate: ImbalanceGate,
+    /// Mindest-24h-Volumen (keine toten Märkte)
+    liquidity_gate: LiquidityGate,
+    /// Max. Bid/Ask-Spread (toxische Launches)
+    spread_gate: SpreadGate,
+    /// Warten auf Handelsstatus TRADING vor dem Feuern
+    trading_state_gate: TradingStateGate,
+    /// Warten auf Liquidität im (zum Launch evtl. leeren) Orderbuch
+    book_wait: BookLiquidityWait,
+    /// Mengen-Präzision je Symbol (Fallback vor exchangeInfo)
+    precision: Arc<PrecisionCache>,
+    fill_confirmation: FillConfirmation,
+    /// Mindestabstand zwischen Entries auf dasselbe Symbol
+    placement_cooldown: Arc<PlacementCooldown>,
+    /// Benanntes Konto, über das gehandelt wird (None = übergebener Client)
+    account: Option<String>,
+    /// Client pro User für Orders und Guthaben (None = übergebener Client für alle)
+    clients: Option<Arc<MexcClientPool>>,
+    /// Entry-Fallback-Kette (z.B. FOK -> IOC -> Market)
+    entry_chain: EntryChain,
+    /// Entry vor dem Feuern per Test-Order validieren
+    validate_before_fire: bool,
+    /// Protokoll der Gate-Entscheidungen
+    decisions: Arc<DecisionLog>,
+    /// Canary-Order vor großen Entries
+    canary: CanaryOrder,
+    /// Re-Listings erkennen (Default: aus)
+    relisting: RelistingGuard,
+}
+
+/// Stufe der Entry-Fallback-Kette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryTier {
+    /// Alles oder nichts zum Limit
+    FillOrKill,
+    /// Sofort so viel wie möglich zum Limit, Rest verfällt
+    ImmediateOrCancel,
+    /// Liegenbleibendes Limit; ungefüllt zieht der `LimitChaser` es Richtung Buch nach
+    Limit,
+    Market,
+    /// MARKET abgelehnt, ersatzweise IOC-Limit am Orderbuch (nicht konfigurierbar)
+    MarketFallback,
+}
+
+impl EntryTier {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fok" | "fill_or_kill" => Some(Self::FillOrKill),
+            "ioc" | "immediate_or_cancel" => Some(Self::ImmediateOrCancel),
+            "limit" | "gtc" => Some(Self::Limit),
+            "market" => Some(Self::Market),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FillOrKill => "fok",
+            Self::ImmediateOrCancel => "ioc",
+            Self::Limit => "limit",
+            Self::Market => "market",
+            Self::MarketFallback => "market_fallback",
+        }
+    }
+
+    /// MEXC Order-Typ der Stufe
+    pub fn mexc_order_type(&self) -> &'static str {
+        match self {
+            Self::FillOrKill => "FILL_OR_KILL",
+            Self::ImmediateOrCancel | Self::MarketFallback => "IMMEDIATE_OR_CANCEL",
+            Self::Limit => "LIMIT",
+            Self::Market => "MARKET",
+        }
+    }
+}
+
+/// Reihenfolge der Entry-Versuche: die erste angenommene (bei FOK/IOC:
+/// zumindest teilweise gefüllte) Stufe gewinnt; ein angenommenes LIMIT bleibt
+/// auch ungefüllt liegen. Limit-Stufen brauchen einen Schätzpreis und werden sonst übersprungen.
+#[derive(Debug, Clone)]
+pub struct EntryChain {
+    pub tiers: Vec<EntryTier>,
+    /// Limit für FOK/IOC/LIMIT: Schätzpreis plus (Buy) bzw. minus (Sell) N Prozent
+    pub limit_offset_pct: f64,
+    /// Gesamtbudget über alle Stufen; danach wird keine weitere Stufe versucht
+    pub deadline: Duration,
+    /// Lehnt MEXC MARKET ab: IOC-Limit N% über dem besten Ask bzw. unter dem besten Bid (0 = aus)
+    pub market_fallback_pct: f64,
+}
+
+impl Default for EntryChain {
+    fn default() -> Self {
+        Self {
+            tiers: vec![EntryTier::Market],
+            limit_offset_pct: 1.0,
+            deadline: Duration::from_secs(3),
+            market_fallback_pct: 0.0,
+        }
+    }
+}
+
+impl EntryChain {
+    pub fn from_config(config: &Config) -> Self {
+        let mut tiers = Vec::new();
+        for name in &config.snipe_entry_tiers {
+            match EntryTier::parse(name) {
+                Some(tier) if !tiers.contains(&tier) => tiers.push(tier),
+                Some(_) => {}
+                None => tracing::warn!("Ignoring unknown snipe entry tier: {}", name),
+            }
+        }
+        if tiers.is_empty() {
+            tiers.push(EntryTier::Market);
+        }
+        Self {
+            tiers,
+            limit_offset_pct: config.snipe_entry_limit_offset_pct,
+            deadline: Duration::from_millis(config.snipe_entry_deadline_ms),
+            market_fallback_pct: config.snipe_market_fallback_pct,
+        }
+    }
+
+    fn limit_price(&self, side: &str, expected_price: f64) -> f64 {
+        if side.eq_ignore_ascii_case("SELL") {
+            expected_price * (1.0 - self.limit_offset_pct / 100.0)
+        } else {
+            expected_price * (1.0 + self.limit_offset_pct / 100.0)
+        }
+    }
+}
+
+/// Zweistufiger Entry für große Allocations: erst eine kleine Market-Canary,
+/// deren Slippage entscheidet, ob und wie groß der Haupt-Entry wird
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanaryOrder {
+    /// Ab diesem Notional (Menge * Schätzpreis) wird eine Canary vorausgeschickt (0 = aus)
+    pub min_notional: f64,
+    /// Canary-Menge als Anteil der Snipe-Menge
+    pub fraction: f64,
+    /// Max. Slippage der Canary in Prozent
+    pub max_slippage_pct: f64,
+}
+
+impl CanaryOrder {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            min_notional: config.snipe_canary_min_notional,
+            fraction: config.snipe_canary_fraction,
+            max_slippage_pct: config.snipe_canary_max_slippage_pct,
+        }
+    }
+
+    /// Schätzpreis, falls der Snipe groß genug für eine Canary ist
+    fn applies(&self, quantity: f64, expected_price: Option<f64>) -> Option<f64> {
+        let expected = expected_price.filter(|p| *p > 0.0)?;
+        let enabled = self.min_notional > 0.0 && self.fraction > 0.0 && self.fraction < 1.0;
+        (enabled && quantity * expected >= self.min_notional).then_some(expected)
+    }
+}
+
+/// Mindest-Kaufdruck im Order Book vor einem Buy-Snipe
+#[derive(Debug, Clone, Copy)]
+pub struct ImbalanceGate {
+    /// Anzahl Level (0 = Gate aus)
+    pub levels: usize,
+    /// Mindest-Imbalance in [-1, 1]
+    pub min_imbalance: f64,
+}
+
+impl ImbalanceGate {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            levels: config.snipe_imbalance_levels,
+            min_imbalance: config.snipe_min_book_imbalance,
+        }
+    }
+}
+
+/// Max. Bid/Ask-Spread vor einem Snipe; extrem breite Spreads zum Launch
+/// deuten auf Manipulation hin
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpreadGate {
+    /// In Prozent des Mittelkurses (0 = Gate aus)
+    pub max_spread_pct: f64,
+    /// Snipe für manuelle Bestätigung zurückhalten statt verwerfen
+    pub require_confirmation: bool,
+}
+
+impl SpreadGate {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_spread_pct: config.snipe_max_spread_pct,
+            require_confirmation: config.snipe_spread_confirm,
+        }
+    }
+
+    /// Some(Grund) wenn der Spread über dem Limit liegt; ein einseitiges
+    /// Buch (frisches Listing) zählt als unendlicher Spread
+    pub fn check(&self, symbol: &str, book: &OrderBook) -> Option<String> {
+        match book.spread_pct() {
+            None => Some(format!("order book for {} is one-sided, spread is unbounded", symbol)),
+            Some(spread_pct) if spread_pct > self.max_spread_pct => Some(format!(
+                "spread {:.2}% for {} exceeds maximum {:.2}%",
+                spread_pct, symbol, self.max_spread_pct
+            )),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Mindest-24h-Quote-Volumen vor einem Snipe
+#[derive(Debug, Clone, Copy)]
+pub struct LiquidityGate {
+    /// 0 = Gate aus
+    pub min_quote_volume: f64,
+    /// Symbole ohne 24h-Historie (frisch gelistet) durchlassen
+    pub allow_new_listings: bool,
+}
+
+impl LiquidityGate {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            min_quote_volume: config.snipe_min_quote_volume,
+            allow_new_listings: config.snipe_allow_new_listings,
+        }
+    }
+}
+
+/// Vor dem Snipe per exchangeInfo auf Status TRADING warten
+#[derive(Debug, Clone, Copy)]
+pub struct TradingStateGate {
+    /// Poll-Intervall (0 = Gate aus)
+    pub poll: Duration,
+    /// Max. Wartezeit, danach schlägt der Versuch fehl (Retry-Policy)
+    pub max_wait: Duration,
+}
+
+impl TradingStateGate {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            poll: Duration::from_millis(config.snipe_trading_state_poll_ms),
+            max_wait: Duration::from_millis(config.snipe_trading_state_max_wait_ms),
+        }
+    }
+}
+
+/// Direkt vor dem Entry auf Liquidität im Orderbuch warten: zum Launch liegen
+/// oft noch keine Asks, dann kann weder MARKET noch der IOC-Fallback füllen
+#[derive(Debug, Clone, Copy)]
+pub struct BookLiquidityWait {
+    /// Poll-Intervall (0 = aus); gewartet wird höchstens bis zur Entry-Deadline
+    pub poll: Duration,
+    /// Mindestanzahl Level auf der Gegenseite (Asks für Buy, Bids für Sell)
+    pub min_levels: usize,
+}
+
+impl BookLiquidityWait {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            poll: Duration::from_millis(config.snipe_book_wait_poll_ms),
+            min_levels: config.snipe_book_min_levels.max(1),
+        }
+    }
+}
+
+/// Retry-Policy für fehlgeschlagene Snipes
+#[derive(Debug, Clone)]
+pub struct SnipeRetryPolicy {
+    pub max_attempts: u32,
+    /// Wartezeit vor dem ersten Retry, verdoppelt sich pro Versuch
+    pub backoff: Duration,
+    /// Retries nur bis launch_time + launch_window
+    pub launch_window: Duration,
+}
+
+impl SnipeRetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.snipe_max_attempts.max(1),
+            backoff: Duration::from_millis(config.snipe_retry_backoff_ms),
+            launch_window: Duration::from_secs(config.snipe_retry_window_secs),
+        }
+    }
 }
 
 impl SnipingManager {
-    pub fn new(mexc_client: Arc<MexcClient>, store: Arc<DynamoDBStore>) -> Self {
+    pub fn new(mexc_client: Arc<MexcClient>, store: Arc<DynamoDBStore>, config: &Config) -> Self {
         Self {
             mexc_client,
             store,
+            blacklist: SymbolBlacklist::new(&config.symbol_blacklist),
+            clock: Arc::new(SystemClock),
+            runtime: Arc::new(RuntimeSettings::new(config)),
+            unwind_on_slippage: config.unwind_on_slippage,
+            retry: SnipeRetryPolicy::from_config(config),
+            preflight: None,
+            imbalance_gate: ImbalanceGate::from_config(config),
+            liquidity_gate: LiquidityGate::from_config(config),
+            spread_gate: SpreadGate::from_config(config),
+            trading_state_gate: TradingStateGate::from_config(config),
+            book_wait: BookLiquidityWait::from_config(config),
+            precision: Arc::new(PrecisionCache::from_config(config)),
+            fill_confirmation: FillConfirmation::from_config(config),
+            placement_cooldown: Arc::new(PlacementCooldown::from_config(config)),
+            account: None,
+            clients: None,
+            entry_chain: EntryChain::from_config(config),
+            validate_before_fire: config.snipe_validate_before_fire,
+            decisions: Arc::new(DecisionLog::from_config(config)),
+            canary: CanaryOrder::from_config(config),
+            relisting: RelistingGuard::from_config(config),
+        }
+    }
+
+    /// Canary-Order vor großen Entries setzen
+    pub fn with_canary(mut self, canary: CanaryOrder) -> Self {
+        self.canary = canary;
+        self
+    }
+
+    /// Re-Listing-Guard setzen
+    pub fn with_relisting_guard(mut self, relisting: RelistingGuard) -> Self {
+        self.relisting = relisting;
+        self
+    }
+
+    /// Geteiltes Entscheidungsprotokoll verwenden
+    pub fn with_decision_log(mut self, decisions: Arc<DecisionLog>) -> Self {
+        self.decisions = decisions;
+        self
+    }
+
+    pub fn decision_log(&self) -> &Arc<DecisionLog> {
+        &self.decisions
+    }
+
+    /// Gate-Ergebnis protokollieren (`rejection` = None heißt bestanden)
+    fn decide(&self, user_id: &str, event: &CalendarEventItem, gate: DecisionGate, rejection: Option<&str>) {
+        self.record_decision(user_id, event, gate, rejection.is_none(), rejection.map(str::to_string));
+    }
+
+    fn record_decision(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        gate: DecisionGate,
+        passed: bool,
+        reason: Option<String>,
+    ) {
+        self.decisions.record(DecisionEvent {
+            timestamp: self.clock.now_millis(),
+            user_id: user_id.to_string(),
+            event_id: event.event_id.clone(),
+            symbol: event.symbol.clone(),
+            gate,
+            passed,
+            reason,
+        });
+    }
+
+    /// Test-Order vor dem Live-Entry an-/abschalten
+    pub fn with_validate_before_fire(mut self, enabled: bool) -> Self {
+        self.validate_before_fire = enabled;
+        self
+    }
+
+    /// Entry-Fallback-Kette setzen
+    pub fn with_entry_chain(mut self, chain: EntryChain) -> Self {
+        self.entry_chain = chain;
+        self
+    }
+
+    /// Über ein benanntes Konto handeln (eine Strategie je Konto);
+    /// Orders werden mit dem Kontonamen getaggt
+    pub fn with_account(mut self, pool: &MexcClientPool, account: &str) -> Result<Self, UserClientError> {
+        self.mexc_client = pool.for_account(account)?;
+        self.account = Some(account.to_string());
+        Ok(self)
+    }
+
+    /// Orders und Guthaben über den Client des Users (ohne eigene Keys kein
+    /// Rückfall auf den globalen Key außer für MEXC_ADMIN_USER_ID); ein benanntes
+    /// Konto (`with_account`) hat Vorrang
+    pub fn with_client_pool(mut self, clients: Arc<MexcClientPool>) -> Self {
+        self.clients = Some(clients);
+        self
+    }
+
+    /// Client für Orders und Guthaben des Users
+    async fn client_for(&self, user_id: &str) -> Result<Arc<MexcClient>> {
+        match &self.clients {
+            Some(clients) if self.account.is_none() => Ok(clients.for_user(user_id).await?),
+            _ => Ok(self.mexc_client.clone()),
+        }
+    }
+
+    /// Placement-Cooldown mit der Trading-API teilen, damit API-Orders und
+    /// Snipes auf dasselbe Symbol gemeinsam gestaffelt werden
+    pub fn with_placement_cooldown(mut self, cooldown: Arc<PlacementCooldown>) -> Self {
+        self.placement_cooldown = cooldown;
+        self
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Geteilte Hot-Reload-Einstellungen verwenden (z.B. vom Admin-Reload)
+    pub fn with_runtime_settings(mut self, runtime: Arc<RuntimeSettings>) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Freies Guthaben vor jedem Snipe prüfen (benötigt expected_price)
+    pub fn with_balance_preflight(mut self, preflight: Option<Arc<BalancePreflight>>) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
+    /// Imbalance-Gate ersetzen (z.B. Tests)
+    pub fn with_imbalance_gate(mut self, gate: ImbalanceGate) -> Self {
+        self.imbalance_gate = gate;
+        self
+    }
+
+    /// Liquiditäts-Gate ersetzen (z.B. Tests)
+    pub fn with_liquidity_gate(mut self, gate: LiquidityGate) -> Self {
+        self.liquidity_gate = gate;
+        self
+    }
+
+    /// Spread-Gate ersetzen (z.B. Tests)
+    pub fn with_spread_gate(mut self, gate: SpreadGate) -> Self {
+        self.spread_gate = gate;
+        self
+    }
+
+    /// Trading-State-Gate ersetzen (z.B. Tests)
+    pub fn with_trading_state_gate(mut self, gate: TradingStateGate) -> Self {
+        self.trading_state_gate = gate;
+        self
+    }
+
+    /// Warten auf Orderbuch-Liquidität ersetzen (z.B. Tests)
+    pub fn with_book_wait(mut self, wait: BookLiquidityWait) -> Self {
+        self.book_wait = wait;
+        self
+    }
+
+    /// Geteilten Präzisions-Cache verwenden
+    pub fn with_precision_cache(mut self, precision: Arc<PrecisionCache>) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Lade die Blacklist-Einträge aus DynamoDB neu (Update ohne Redeploy)
+    pub async fn reload_blacklist(&self) -> Result<()> {
+        let patterns = self.store.get_symbol_blacklist().await?;
+        tracing::info!("Symbol blacklist reloaded: {} dynamic entries", patterns.len());
+        self.blacklist.replace_dynamic(&patterns);
+        Ok(())
+    }
+
+    /// Blacklist im Intervall nachladen (der erste Load passiert beim Start)
+    pub async fn run_blacklist_refresh(self: Arc<Self>, interval: Duration, mut shutdown: watch::Receiver<bool>) {
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => if let Err(e) = self.reload_blacklist().await {
+                    tracing::warn!("Symbol blacklist reload failed: {}", e);
+                },
+                _ = shutdown.changed() => return,
+            }
+        }
+    }
+
+    /// Prüfe ob ein Symbol auf der Blacklist steht
+    pub fn is_blacklisted(&self, symbol: &str) -> bool {
+        self.blacklist.is_blocked(symbol)
+    }
+
+    /// Führe automatischen Snipe aus basierend auf Calendar Event
+    pub async fn execute_snipe(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        order_params: SnipeOrderParams,
+    ) -> Result<SnipeOutcome> {
+        tracing::info!("Executing snipe for user: {}, token: {}", user_id, event.token_name);
+
+        if self.is_blacklisted(&event.symbol) {
+            tracing::warn!("Skipping blacklisted symbol: {}", event.symbol);
+            let reason = format!("symbol {} is blacklisted", event.symbol);
+            self.decide(user_id, event, DecisionGate::Blacklist, Some(&reason));
+            let mut skipped_event = event.clone();
+            skipped_event.status = "skipped".to_string();
+            self.store.put_calendar_event(&skipped_event).await?;
+
+            return Ok(SnipeOutcome::Skipped { reason });
+        }
+        self.decide(user_id, event, DecisionGate::Blacklist, None);
+
+        let mut confidence = event.confidence;
+        if self.relisting.is_enabled() && !order_params.confirmed {
+            let first_seen = self
+                .relisting
+                .first_seen(&self.store, user_id, event, self.clock.now_millis())
+                .await?;
+            match (first_seen, self.relisting.action) {
+                (None, _) => self.decide(user_id, event, DecisionGate::Relisting, None),
+                (Some(seen), RelistingAction::Downgrade) => {
+                    confidence -= self.relisting.confidence_penalty;
+                    let detail = format!(
+                        "{} already seen at {}, confidence downgraded to {:.2}",
+                        event.symbol, seen, confidence
+                    );
+                    self.record_decision(user_id, event, DecisionGate::Relisting, true, Some(detail));
+                }
+                (Some(seen), RelistingAction::Confirm) => {
+                    let reason = format!(
+                        "{} already seen at {}, re-listing requires manual confirmation",
+                        event.symbol, seen
+                    );
+                    tracing::warn!("Holding snipe {}: {}", event.event_id, reason);
+                    self.decide(user_id, event, DecisionGate::Relisting, Some(&reason));
+                    // Zurück in die Queue, damit der Override-Endpoint es feuern kann
+                    let mut held_event = event.clone();
+                    held_event.status = "detected".to_string();
+                    self.store.put_calendar_event(&held_event).await?;
+
+                    return Ok(SnipeOutcome::Skipped { reason });
+                }
+            }
+        }
+
+        let confident = self.should_execute_snipe(&event.detected_pattern, confidence);
+        let mut gate_reason = (!confident).then(|| {
+            format!(
+                "confidence {:.2} below minimum {:.2} for pattern {}",
+                confidence,
+                self.runtime.load().min_confidence_for(&event.detected_pattern),
+                event.detected_pattern
+            )
+        });
+        self.decide(user_id, event, DecisionGate::Confidence, gate_reason.as_deref());
+
+        let mut held = false;
+
+        for gate in [
+            DecisionGate::TradingState,
+            DecisionGate::Liquidity,
+            DecisionGate::BookImbalance,
+            DecisionGate::Spread,
+            DecisionGate::Balance,
+        ] {
+            if gate_reason.is_some() {
+                break;
+            }
+            let result = match gate {
+                DecisionGate::TradingState => self.wait_until_trading(&event.symbol).await,
+                DecisionGate::Liquidity => self.check_liquidity(&event.symbol).await,
+                DecisionGate::BookImbalance => self.check_book_imbalance(&event.symbol, &order_params).await,
+                DecisionGate::Spread => self.check_spread(&event.symbol, &order_params).await,
+                _ => self.check_balance(user_id, &order_params).await,
+            };
+            gate_reason = match result {
+                Ok(reason) => reason,
+                Err(e) => {
+                    self.decide(user_id, event, gate, Some(&format!("error: {}", e)));
+                    return Err(e);
+                }
+            };
+            self.decide(user_id, event, gate, gate_reason.as_deref());
+            held = gate == DecisionGate::Spread && gate_reason.is_some() && self.spread_gate.require_confirmation;
+        }
+        if let Some(reason) = gate_reason {
+            let mut skipped_event = event.clone();
+            // Zurückgehalten: zurück in die Queue, damit der Override-Endpoint es feuern kann
+            skipped_event.status = if held { "detected" } else { "skipped" }.to_string();
+            self.store.put_calendar_event(&skipped_event).await?;
+
+            return Ok(SnipeOutcome::Skipped { reason });
+        }
+
+        // Frisch gelistet und noch nicht im Cache: nur dieses Symbol nachladen
+        if self.precision.cached(&event.symbol).is_none() {
+            if let Err(e) = self.precision.refresh_symbol(&self.mexc_client, &event.symbol).await {
+                tracing::warn!("Exchange info refresh for {} failed: {}", event.symbol, e);
+            }
+        }
+        let quantity = self.precision.round_quantity(&event.symbol, order_params.quantity);
+        if quantity <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "quantity {} for {} rounds to zero",
+                order_params.quantity,
+                event.symbol
+            ));
+        }
+
+        if self.validate_before_fire {
+            let validation = self
+                .validate_entry(user_id, event, &order_params.side, quantity, order_params.expected_price)
+                .await;
+            let rejection = validation.as_ref().err().map(|e| e.to_string());
+            self.decide(user_id, event, DecisionGate::Validation, rejection.as_deref());
+            validation?;
+        }
+
+        // Sende zu MEXC über die Entry-Kette (Unwinds unten warten nicht auf den Cooldown)
+        // Cooldown lehnt nie ab, sondern verzögert; die Wartezeit geht ins Protokoll
+        let waited = self.placement_cooldown.wait(&event.symbol).await;
+        let detail = (!waited.is_zero()).then(|| format!("waited {} ms", waited.as_millis()));
+        self.record_decision(user_id, event, DecisionGate::Cooldown, true, detail);
+
+        // Großer Entry: erst Canary, Haupt-Order nach deren Fill dimensionieren (gleiche Deadline)
+        let started = tokio::time::Instant::now();
+        self.wait_for_book(user_id, event, &order_params.side, started).await?;
+        let mut quantity = quantity;
+        let mut canary_order_id = None;
+        if let Some(expected) = self.canary.applies(quantity, order_params.expected_price) {
+            match self.fire_canary(user_id, event, &order_params.side, quantity, expected).await? {
+                CanaryResult::Proceed { order_id, main_quantity } => {
+                    canary_order_id = order_id;
+                    quantity = main_quantity;
+                }
+                CanaryResult::Abort(outcome) => return Ok(outcome),
+            }
+        }
+        let deadline = self.entry_chain.deadline.saturating_sub(started.elapsed());
+        let (order, mexc_response, entry_tier) = self
+            .place_entry(user_id, event, &order_params.side, quantity, order_params.expected_price, deadline)
+            .await
+            .map_err(|e| match &canary_order_id {
+                // Canary liegt schon im Markt: kein neuer Versuch, der sie wiederholt
+                Some(_) if e.downcast_ref::<EntryUncertain>().is_none() => {
+                    EntryUncertain::placed(e, &event.symbol, &snipe_client_order_id(event, "canary"))
+                }
+                _ => e,
+            })?;
+
+        // Ab hier ist der Entry gefüllt: Fehler beim Speichern lösen keinen zweiten Kauf aus
+        let client_order_id = order.client_order_id.clone().unwrap_or_default();
+        self.record_entry(user_id, event, &order_params, canary_order_id, order, mexc_response, entry_tier)
+            .await
+            .map_err(|e| EntryUncertain::placed(e, &event.symbol, &client_order_id))
+    }
+
+    /// Gefüllten Entry speichern, Slippage prüfen (ggf. glattstellen) und das Event abschließen
+    #[allow(clippy::too_many_arguments)]
+    async fn record_entry(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        order_params: &SnipeOrderParams,
+        canary_order_id: Option<String>,
+        order: OrderItem,
+        mexc_response: OrderResponse,
+        entry_tier: EntryTier,
+    ) -> Result<SnipeOutcome> {
+        let mut updated_order = order;
+        updated_order.mexc_order_id = Some(mexc_response.order_id.clone());
+        updated_order.status = OrderStatus::from_mexc(&mexc_response.status).as_str().to_string();
+        updated_order.filled_qty = mexc_response.filled_qty;
+        updated_order.fill_price = mexc_response.average_fill_price();
+
+        // Speichere Order
+        self.store.put_order(&updated_order).await?;
+
+        let mut updated_event = event.clone();
+        updated_event.executed_orders.extend(canary_order_id);
+        updated_event.executed_orders.push(updated_order.order_id.clone());
+        updated_event.execution_time = Some(self.clock.now_millis());
+
+        // Entry zu weit vom Schätzpreis entfernt: keine Exits, ggf. sofort glattstellen
+        if let Some(slippage_pct) = entry_slippage_pct(
+            &updated_order.side,
+            order_params.expected_price,
+            updated_order.fill_price.unwrap_or(0.0),
+        ) {
+            let max_slippage_pct = self.runtime.load().max_entry_slippage_pct;
+            if slippage_pct > max_slippage_pct {
+                tracing::error!(
+                    "ALERT: entry slippage {:.2}% for {} exceeds {:.2}%, aborting strategy",
+                    slippage_pct,
+                    updated_order.symbol,
+                    max_slippage_pct
+                );
+
+                let unwind_order_id = if self.unwind_on_slippage {
+                    let filled_qty = if mexc_response.filled_qty > 0.0 {
+                        mexc_response.filled_qty
+                    } else {
+                        updated_order.quantity
+                    };
+                    let unwind = self.unwind_entry(user_id, &updated_order, filled_qty).await?;
+                    updated_event.executed_orders.push(unwind.clone());
+                    Some(unwind)
+                } else {
+                    None
+                };
+
+                updated_event.status = "aborted".to_string();
+                self.store.put_calendar_event(&updated_event).await?;
+
+                return Ok(SnipeOutcome::Aborted {
+                    order_id: updated_order.order_id,
+                    slippage_pct,
+                    unwind_order_id,
+                });
+            }
+        }
+
+        // Update Calendar Event
+        updated_event.status = "sniped".to_string();
+
+        self.store.put_calendar_event(&updated_event).await?;
+
+        Ok(SnipeOutcome::Executed {
+            order_id: updated_order.order_id,
+            entry_tier,
+        })
+    }
+
+    /// Entry-Stufen der Reihe nach versuchen bis eine angenommen bzw. gefüllt
+    /// ist; Ablehnungen und ungefüllte FOK/IOC fallen auf die nächste Stufe zurück
+    async fn place_entry(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        expected_price: Option<f64>,
+        deadline: Duration,
+    ) -> Result<(OrderItem, OrderResponse, EntryTier)> {
+        let chain = &self.entry_chain;
+        let started = tokio::time::Instant::now();
+        let mut last_failure = None;
+
+        for &tier in &chain.tiers {
+            let Some(price) = self.tier_price(tier, &event.symbol, side, expected_price) else {
+                tracing::debug!("Skipping {} entry for {}: no expected price", tier.as_str(), event.symbol);
+                continue;
+            };
+            if started.elapsed() >= deadline {
+                tracing::warn!(
+                    "Entry deadline {:?} for {} reached before {} tier",
+                    deadline,
+                    event.symbol,
+                    tier.as_str()
+                );
+                break;
+            }
+
+            let (order, response) = match self.send_entry(user_id, event, side, quantity, tier, price).await {
+                Ok(placed) => placed,
+                // Nur echte Ablehnungen fallen zurück; Netzwerkfehler und 5xx könnten platziert haben
+                Err(e)
+                    if tier != EntryTier::Market
+                        && e.downcast_ref::<MexcError>().is_some_and(MexcError::is_rejection) =>
+                {
+                    tracing::warn!(
+                        "{} entry for {} rejected, falling back: {}",
+                        tier.as_str(),
+                        event.symbol,
+                        e
+                    );
+                    last_failure = Some(e);
+                    continue;
+                }
+                Err(e)
+                    if self.entry_chain.market_fallback_pct > 0.0
+                        && e.downcast_ref::<MexcError>().is_some_and(MexcError::market_unavailable) =>
+                {
+                    return self.market_fallback(user_id, event, side, quantity, e).await;
+                }
+                Err(e) => return Err(e),
+            };
+
+            // Ein ungefülltes LIMIT bleibt im Buch (Nachziehen übernimmt der Order Monitor)
+            if !matches!(tier, EntryTier::Market | EntryTier::Limit) && response.filled_qty <= 0.0 {
+                tracing::warn!(
+                    "{} entry for {} not filled (status {}), falling back",
+                    tier.as_str(),
+                    event.symbol,
+                    response.status
+                );
+                last_failure = Some(anyhow::anyhow!("{} entry for {} not filled", tier.as_str(), event.symbol));
+                continue;
+            }
+
+            tracing::info!("Entry for {} filled via {} tier", event.symbol, tier.as_str());
+            return Ok((order, response, tier));
+        }
+
+        Err(last_failure
+            .unwrap_or_else(|| anyhow::anyhow!("no entry tier could be attempted for {}", event.symbol)))
+    }
+
+    /// Entry-Order einer Stufe senden und den Fill bestätigen
+    async fn send_entry(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        tier: EntryTier,
+        price: Option<f64>,
+    ) -> Result<(OrderItem, OrderResponse)> {
+        let mut order = OrderItem::new(
+            user_id.to_string(),
+            event.symbol.clone(),
+            side.to_string(),
+            tier.mexc_order_type().to_ascii_lowercase(),
+            quantity,
+            price,
+        )
+        .stamped_at(self.clock.now());
+        order.event_id = Some(event.event_id.clone());
+        order.account = self.account.clone();
+        let client_order_id = snipe_client_order_id(event, tier.as_str());
+        order.client_order_id = Some(client_order_id.clone());
+
+        let request = crate::mexc::OrderRequest {
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: tier.mexc_order_type().to_string(),
+            quantity,
+            price,
+            quote_order_qty: None,
+            client_order_id: Some(client_order_id.clone()),
+        };
+        let client = self.client_for(user_id).await?;
+        let response = client
+            .create_order(&request)
+            .await
+            .map_err(|e| unless_rejected(e, &event.symbol, &client_order_id))?;
+        let response = self.fill_confirmation.confirm(&client, response).await;
+        Ok((order, response))
+    }
+
+    /// MARKET für das Symbol (noch) nicht erlaubt: einmal als aggressives IOC-Limit
+    /// `market_fallback_pct` über dem besten Ask (Sell: unter dem besten Bid) versuchen
+    async fn market_fallback(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        rejection: anyhow::Error,
+    ) -> Result<(OrderItem, OrderResponse, EntryTier)> {
+        let pct = self.entry_chain.market_fallback_pct;
+        let book = self.mexc_client.get_order_book(&event.symbol, 5).await?;
+        let price = if side.eq_ignore_ascii_case("SELL") {
+            book.best_bid().map(|bid| bid * (1.0 - pct / 100.0))
+        } else {
+            book.best_ask().map(|ask| ask * (1.0 + pct / 100.0))
+        };
+        let Some(price) = price.filter(|p| *p > 0.0) else {
+            let context = format!("no order book to price the market fallback for {}", event.symbol);
+            return Err(rejection.context(context));
+        };
+        let price = self.precision.round_price(&event.symbol, price);
+        tracing::warn!(
+            "MARKET entry for {} rejected ({}), falling back to IOC limit {}",
+            event.symbol,
+            rejection,
+            price
+        );
+        self.record_decision(
+            user_id,
+            event,
+            DecisionGate::MarketFallback,
+            true,
+            Some(format!("MARKET rejected, IOC limit {} ({}% through the book)", price, pct)),
+        );
+
+        let (order, response) = self
+            .send_entry(user_id, event, side, quantity, EntryTier::MarketFallback, Some(price))
+            .await?;
+        if response.filled_qty <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "market fallback for {} not filled (status {})",
+                event.symbol,
+                response.status
+            ));
+        }
+        tracing::info!("Entry for {} filled via market fallback", event.symbol);
+        Ok((order, response, EntryTier::MarketFallback))
+    }
+
+    /// Canary als Market-Order senden und auswerten: zu hohe Slippage bricht den
+    /// Snipe ab (Canary ggf. glattgestellt), sonst wird das restliche Budget
+    /// (Menge * Schätzpreis) zum beobachteten Fill-Preis in die Haupt-Menge umgerechnet
+    async fn fire_canary(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        expected_price: f64,
+    ) -> Result<CanaryResult> {
+        let canary_quantity = self.precision.round_quantity(&event.symbol, quantity * self.canary.fraction);
+        if canary_quantity <= 0.0 {
+            tracing::warn!("Canary for {} rounds to zero, firing main entry directly", event.symbol);
+            return Ok(CanaryResult::Proceed {
+                order_id: None,
+                main_quantity: quantity,
+            });
+        }
+
+        let mut order = OrderItem::new(
+            user_id.to_string(),
+            event.symbol.clone(),
+            side.to_string(),
+            "market".to_string(),
+            canary_quantity,
+            None,
+        )
+        .stamped_at(self.clock.now());
+        order.event_id = Some(event.event_id.clone());
+        order.account = self.account.clone();
+        let client_order_id = snipe_client_order_id(event, "canary");
+        order.client_order_id = Some(client_order_id.clone());
+
+        let client = self.client_for(user_id).await?;
+        let response = client
+            .create_order(&crate::mexc::OrderRequest {
+                symbol: event.symbol.clone(),
+                side: side.to_string(),
+                order_type: "MARKET".to_string(),
+                quantity: canary_quantity,
+                price: None,
+                quote_order_qty: None,
+                client_order_id: Some(client_order_id.clone()),
+            })
+            .await
+            .map_err(|e| unless_rejected(e, &event.symbol, &client_order_id))?;
+        let response = self.fill_confirmation.confirm(&client, response).await;
+        // Ab hier existiert die Canary bei MEXC: jeder Fehler ist kein Grund für einen neuen Versuch
+        self.evaluate_canary(user_id, event, side, quantity, expected_price, order, response)
+            .await
+            .map_err(|e| EntryUncertain::placed(e, &event.symbol, &client_order_id))
+    }
+
+    /// Canary-Fill speichern und auswerten (Abbruch oder Haupt-Menge)
+    #[allow(clippy::too_many_arguments)]
+    async fn evaluate_canary(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        expected_price: f64,
+        mut order: OrderItem,
+        response: OrderResponse,
+    ) -> Result<CanaryResult> {
+        let canary_quantity = order.quantity;
+        order.mexc_order_id = Some(response.order_id.clone());
+        order.status = OrderStatus::from_mexc(&response.status).as_str().to_string();
+        order.filled_qty = response.filled_qty;
+        order.fill_price = response.average_fill_price();
+        self.store.put_order(&order).await?;
+
+        let fill_price = order.fill_price.unwrap_or(expected_price);
+        let slippage_pct = entry_slippage_pct(side, Some(expected_price), fill_price).unwrap_or(0.0);
+        if slippage_pct > self.canary.max_slippage_pct {
+            let reason = format!(
+                "canary slippage {:.2}% exceeds {:.2}%",
+                slippage_pct, self.canary.max_slippage_pct
+            );
+            tracing::error!("ALERT: {} for {}, aborting main entry", reason, event.symbol);
+            self.decide(user_id, event, DecisionGate::Canary, Some(&reason));
+
+            let mut aborted_event = event.clone();
+            aborted_event.executed_orders.push(order.order_id.clone());
+            aborted_event.execution_time = Some(self.clock.now_millis());
+            let unwind_order_id = if self.unwind_on_slippage && response.filled_qty > 0.0 {
+                let unwind = self.unwind_entry(user_id, &order, response.filled_qty).await?;
+                aborted_event.executed_orders.push(unwind.clone());
+                Some(unwind)
+            } else {
+                None
+            };
+            aborted_event.status = "aborted".to_string();
+            self.store.put_calendar_event(&aborted_event).await?;
+
+            return Ok(CanaryResult::Abort(SnipeOutcome::Aborted {
+                order_id: order.order_id,
+                slippage_pct,
+                unwind_order_id,
+            }));
+        }
+
+        let filled = if response.filled_qty > 0.0 { response.filled_qty } else { canary_quantity };
+        let remaining_budget = quantity * expected_price - filled * fill_price;
+        let main_quantity = self.precision.round_quantity(&event.symbol, remaining_budget / fill_price);
+        self.record_decision(
+            user_id,
+            event,
+            DecisionGate::Canary,
+            true,
+            Some(format!("slippage {:.2}%, main quantity {}", slippage_pct, main_quantity)),
+        );
+        if main_quantity <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "no budget left for the main entry after the canary on {}",
+                event.symbol
+            ));
+        }
+        Ok(CanaryResult::Proceed {
+            order_id: Some(order.order_id),
+            main_quantity,
+        })
+    }
+
+    /// Limit der Stufe (None = Market); äußeres None wenn eine Limit-Stufe keinen Schätzpreis hat
+    fn tier_price(
+        &self,
+        tier: EntryTier,
+        symbol: &str,
+        side: &str,
+        expected_price: Option<f64>,
+    ) -> Option<Option<f64>> {
+        match (tier, expected_price.filter(|p| *p > 0.0)) {
+            (EntryTier::Market, _) => Some(None),
+            (_, Some(expected)) => Some(Some(
+                self.precision.round_price(symbol, self.entry_chain.limit_price(side, expected)),
+            )),
+            (_, None) => None,
+        }
+    }
+
+    /// Erste ausführbare Entry-Stufe per Test-Order prüfen; lehnt MEXC ab
+    /// (Filter, Auth, Symbol), bricht der Snipe vor jeder Live-Order ab
+    async fn validate_entry(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        quantity: f64,
+        expected_price: Option<f64>,
+    ) -> Result<()> {
+        let symbol = event.symbol.as_str();
+        let Some((tier, price)) = self
+            .entry_chain
+            .tiers
+            .iter()
+            .find_map(|&tier| Some((tier, self.tier_price(tier, symbol, side, expected_price)?)))
+        else {
+            return Ok(());
+        };
+
+        let request = crate::mexc::OrderRequest {
+            symbol: symbol.to_string(),
+            side: side.to_string(),
+            order_type: tier.mexc_order_type().to_string(),
+            quantity,
+            price,
+            quote_order_qty: None,
+            client_order_id: Some(snipe_client_order_id(event, tier.as_str())),
+        };
+        match self.client_for(user_id).await?.test_order(&request).await {
+            Ok(()) => Ok(()),
+            Err(e) => match e.downcast_ref::<MexcError>() {
+                Some(rejection) => {
+                    tracing::error!("Pre-fire validation for {} rejected: {}", symbol, rejection.message);
+                    Err(anyhow::anyhow!(
+                        "pre-fire validation of {} {} {} rejected by MEXC ({}): {}",
+                        tier.as_str(),
+                        side,
+                        symbol,
+                        rejection.category().as_str(),
+                        rejection.message
+                    ))
+                }
+                None => Err(e.context(format!("pre-fire validation for {} failed", symbol))),
+            },
+        }
+    }
+
+    /// Warten bis das Symbol laut exchangeInfo handelbar ist; Some(Grund) bei
+    /// pausiertem Symbol, Err wenn es nach `max_wait` noch nicht handelbar ist
+    async fn wait_until_trading(&self, symbol: &str) -> Result<Option<String>> {
+        let gate = self.trading_state_gate;
+        if gate.poll.is_zero() {
+            return Ok(None);
+        }
+
+        let started = tokio::time::Instant::now();
+        loop {
+            let info = self.mexc_client.symbol_info(symbol).await?;
+            if let Some(info) = &info {
+                self.precision.update(info);
+            }
+            let state = info.map(|info| info.state).unwrap_or(SymbolState::NotListed);
+            match state {
+                SymbolState::Trading => return Ok(None),
+                SymbolState::Halted => {
+                    tracing::warn!("Skipping snipe: {} is halted", symbol);
+                    return Ok(Some(format!("symbol {} is halted", symbol)));
+                }
+                _ if started.elapsed() + gate.poll > gate.max_wait => {
+                    return Err(anyhow::anyhow!(
+                        "symbol {} not trading after {:?} (state {:?})",
+                        symbol,
+                        gate.max_wait,
+                        state
+                    ));
+                }
+                _ => {
+                    tracing::debug!("Waiting for {} to start trading (state {:?})", symbol, state);
+                    tokio::time::sleep(gate.poll).await;
+                }
+            }
         }
     }
 
-    /// Führe automatischen Snipe aus basierend auf Calendar Event
-    pub async fn execute_snipe(
+    /// Orderbuch pollen bis auf der Gegenseite genug Level liegen; Err wenn es
+    /// bis zur Entry-Deadline (ab `started`) leer bleibt
+    async fn wait_for_book(
+        &self,
+        user_id: &str,
+        event: &CalendarEventItem,
+        side: &str,
+        started: tokio::time::Instant,
+    ) -> Result<()> {
+        let wait = self.book_wait;
+        if wait.poll.is_zero() {
+            return Ok(());
+        }
+
+        let deadline = self.entry_chain.deadline;
+        let mut checks = 0;
+        loop {
+            let book = self.mexc_client.get_order_book(&event.symbol, wait.min_levels as u32).await?;
+            checks += 1;
+            let levels = if side.eq_ignore_ascii_case("SELL") { book.bids.len() } else { book.asks.len() };
+            if levels >= wait.min_levels {
+                let detail = (checks > 1).then(|| {
+                    format!("liquidity after {} checks ({} ms)", checks, started.elapsed().as_millis())
+                });
+                self.record_decision(user_id, event, DecisionGate::BookLiquidity, true, detail);
+                return Ok(());
+            }
+            if started.elapsed() + wait.poll > deadline {
+                let reason = format!(
+                    "order book for {} has {} of {} levels after {:?}",
+                    event.symbol, levels, wait.min_levels, deadline
+                );
+                tracing::warn!("Not firing snipe: {}", reason);
+                self.decide(user_id, event, DecisionGate::BookLiquidity, Some(&reason));
+                return Err(anyhow::anyhow!(reason));
+            }
+            tracing::debug!("Waiting for liquidity on {} ({} levels)", event.symbol, levels);
+            tokio::time::sleep(wait.poll).await;
+        }
+    }
+
+    /// 24h-Quote-Volumen-Gate; Some(Grund) bei zu wenig Umsatz. Ohne Volumen
+    /// (frisches Listing) entscheidet `allow_new_listings`.
+    async fn check_liquidity(&self, symbol: &str) -> Result<Option<String>> {
+        let gate = self.liquidity_gate;
+        if gate.min_quote_volume <= 0.0 {
+            return Ok(None);
+        }
+
+        let ticker = self.mexc_client.get_ticker(symbol).await?;
+        Ok(match ticker.quote_volume.filter(|v| *v > 0.0) {
+            None if gate.allow_new_listings => None,
+            None => Some(format!("no 24h volume for {}", symbol)),
+            Some(volume) if volume < gate.min_quote_volume => {
+                tracing::warn!(
+                    "24h quote volume {:.2} for {} below minimum {:.2}",
+                    volume,
+                    symbol,
+                    gate.min_quote_volume
+                );
+                Some(format!(
+                    "24h quote volume {:.2} for {} below minimum {:.2}",
+                    volume, symbol, gate.min_quote_volume
+                ))
+            }
+            Some(_) => None,
+        })
+    }
+
+    /// Order-Book-Gate für Buy-Snipes; Some(Grund) bei zu dünnem oder
+    /// verkaufslastigem Buch
+    async fn check_book_imbalance(
+        &self,
+        symbol: &str,
+        order_params: &SnipeOrderParams,
+    ) -> Result<Option<String>> {
+        let gate = self.imbalance_gate;
+        if gate.levels == 0 || !order_params.side.eq_ignore_ascii_case("buy") {
+            return Ok(None);
+        }
+
+        let book = self.mexc_client.get_order_book(symbol, gate.levels as u32).await?;
+        Ok(match book.imbalance(gate.levels) {
+            None => Some(format!("order book for {} is empty", symbol)),
+            Some(imbalance) if imbalance < gate.min_imbalance => {
+                tracing::warn!(
+                    "Order book imbalance {:.2} for {} below minimum {:.2}",
+                    imbalance,
+                    symbol,
+                    gate.min_imbalance
+                );
+                Some(format!(
+                    "order book imbalance {:.2} for {} below minimum {:.2}",
+                    imbalance, symbol, gate.min_imbalance
+                ))
+            }
+            Some(_) => None,
+        })
+    }
+
+    /// Spread-Gate; Some(Grund) bei zu breitem oder einseitigem Buch. Manuell
+    /// bestätigte Snipes passieren, wenn das Gate auf Bestätigung steht.
+    async fn check_spread(&self, symbol: &str, order_params: &SnipeOrderParams) -> Result<Option<String>> {
+        let gate = self.spread_gate;
+        if gate.max_spread_pct <= 0.0 || (gate.require_confirmation && order_params.confirmed) {
+            return Ok(None);
+        }
+
+        let book = self.mexc_client.get_order_book(symbol, 5).await?;
+        let reason = gate.check(symbol, &book);
+        if let Some(reason) = &reason {
+            tracing::warn!("Spread gate for {}: {}", symbol, reason);
+        }
+        Ok(reason)
+    }
+
+    /// Pre-Trade-Check für Buy-Snipes; Some(Grund) wenn das Guthaben nicht reicht
+    async fn check_balance(&self, user_id: &str, order_params: &SnipeOrderParams) -> Result<Option<String>> {
+        let Some(preflight) = &self.preflight else {
+            return Ok(None);
+        };
+        if !order_params.side.eq_ignore_ascii_case("buy") {
+            return Ok(None);
+        }
+        let Some(price) = order_params.expected_price.filter(|p| *p > 0.0) else {
+            tracing::debug!("Pre-trade check skipped: no expected price");
+            return Ok(None);
+        };
+
+        let client = self.client_for(user_id).await?;
+        match preflight.check(&client, user_id, order_params.quantity * price).await {
+            Ok(()) => Ok(None),
+            Err(e @ PreflightError::Insufficient { .. }) => Ok(Some(e.to_string())),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Snipe mit Retry bei Fehlern: das Event wird pro Versuch per bedingtem
+    /// Übergang detected -> sniping beansprucht, damit kein paralleler oder
+    /// wiederholter Versuch doppelt kauft. Nach dem letzten Fehlschlag -> missed.
+    pub async fn execute_snipe_with_retry(
         &self,
         user_id: &str,
         event: &CalendarEventItem,
         order_params: SnipeOrderParams,
-    ) -> Result<String> {
-        tracing::info!("Executing snipe for user: {}, token: {}", user_id, event.token_name);
+    ) -> Result<SnipeOutcome> {
+        let mut event = event.clone();
+        let deadline = event.launch_time + self.retry.launch_window.as_millis() as i64;
+        let mut backoff = self.retry.backoff;
+
+        loop {
+            event.attempts += 1;
+            if !self
+                .store
+                .transition_calendar_event_status(&event, "detected", "sniping")
+                .await?
+            {
+                return Ok(SnipeOutcome::Skipped {
+                    reason: format!("event {} is no longer detected", event.event_id),
+                });
+            }
+
+            let error = match self.execute_snipe(user_id, &event, order_params.clone()).await {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) => e,
+            };
+
+            // Order evtl. schon bei MEXC: nicht neu platzieren, sondern abgleichen lassen
+            if let Some(outcome) = self.reconcile_if_placed(&event, &error).await? {
+                return Ok(outcome);
+            }
+
+            let retry = event.attempts < self.retry.max_attempts
+                && self.clock.now_millis() + backoff.as_millis() as i64 <= deadline;
+            let next_status = if retry { "detected" } else { "missed" };
+            self.store
+                .transition_calendar_event_status(&event, "sniping", next_status)
+                .await?;
+
+            if !retry {
+                tracing::error!(
+                    "Snipe for {} missed after {} attempts: {}",
+                    event.symbol,
+                    event.attempts,
+                    error
+                );
+                return Ok(SnipeOutcome::Missed {
+                    attempts: event.attempts,
+                    reason: error.to_string(),
+                });
+            }
+
+            tracing::warn!(
+                "Snipe attempt {} for {} failed, retrying in {:?}: {}",
+                event.attempts,
+                event.symbol,
+                backoff,
+                error
+            );
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    /// Nach einem Fehler klären, ob ein neuer Versuch doppelt kaufen könnte. Fehler
+    /// vor dem Senden sind unkritisch; bei unklarem Ausgang wird die Order per
+    /// Client-ID gesucht. Nur wenn MEXC sie nicht kennt, darf neu versucht werden,
+    /// sonst -> needs_reconcile (Some).
+    pub async fn reconcile_if_placed(
+        &self,
+        event: &CalendarEventItem,
+        error: &anyhow::Error,
+    ) -> Result<Option<SnipeOutcome>> {
+        let Some(uncertain) = error.downcast_ref::<EntryUncertain>() else {
+            return Ok(None);
+        };
+
+        let reason = if uncertain.placed {
+            format!("order {} was placed: {}", uncertain.client_order_id, error)
+        } else {
+            let lookup = match self.client_for(&event.user_id).await {
+                Ok(client) => client.get_order_by_client_id(&uncertain.symbol, &uncertain.client_order_id).await,
+                Err(e) => Err(e),
+            };
+            match lookup {
+                Ok(order) => format!(
+                    "order {} exists on MEXC (status {}): {}",
+                    uncertain.client_order_id, order.status, error
+                ),
+                Err(e) if e.downcast_ref::<MexcError>().is_some_and(MexcError::is_unknown_order) => {
+                    tracing::info!(
+                        "Order {} for {} never reached MEXC, snipe may be retried",
+                        uncertain.client_order_id,
+                        uncertain.symbol
+                    );
+                    return Ok(None);
+                }
+                Err(e) => format!(
+                    "lookup of order {} failed ({}): {}",
+                    uncertain.client_order_id, e, error
+                ),
+            }
+        };
+
+        tracing::error!("ALERT: snipe {} for {} needs reconciliation: {}", event.event_id, event.symbol, reason);
+        self.store
+            .transition_calendar_event_status(event, "sniping", "needs_reconcile")
+            .await?;
+        Ok(Some(SnipeOutcome::NeedsReconcile {
+            client_order_id: uncertain.client_order_id.clone(),
+            reason,
+        }))
+    }
 
-        // Erstelle Order
-        let order = OrderItem::new(
+    /// Entry per Gegen-Market-Order glattstellen; gibt die Order-ID zurück
+    async fn unwind_entry(&self, user_id: &str, entry: &OrderItem, quantity: f64) -> Result<String> {
+        let side = if entry.side.eq_ignore_ascii_case("SELL") { "BUY" } else { "SELL" };
+        let mut order = OrderItem::new(
             user_id.to_string(),
-            event.symbol.clone(),
-            order_params.side,
+            entry.symbol.clone(),
+            side.to_string(),
             "market".to_string(),
-            order_params.quantity,
+            quantity,
             None,
-        );
+        )
+        .stamped_at(self.clock.now());
+        order.event_id = entry.event_id.clone();
+        order.account = self.account.clone();
+        // Client-ID vom Entry abgeleitet (Event + Versuch), damit der Abgleich den Unwind zuordnen kann
+        order.client_order_id = entry
+            .client_order_id
+            .as_deref()
+            .and_then(|id| id.rsplit_once('-'))
+            .map(|(prefix, _)| format!("{}-unwind", prefix));
 
-        // Sende zu MEXC
-        let mexc_response = self
-            .mexc_client
+        let response = self
+            .client_for(user_id)
+            .await?
             .create_order(&crate::mexc::OrderRequest {
                 symbol: order.symbol.clone(),
-                side: order.side.clone(),
+                side: side.to_string(),
                 order_type: "MARKET".to_string(),
-                quantity: order.quantity,
+                quantity,
                 price: None,
+                quote_order_qty: None,
+                client_order_id: order.client_order_id.clone(),
             })
             .await?;
 
-        let mut updated_order = order;
-        updated_order.mexc_order_id = Some(mexc_response.order_id.clone());
-        updated_order.status = mexc_response.status;
+        order.mexc_order_id = Some(response.order_id);
+        order.status = OrderStatus::from_mexc(&response.status).as_str().to_string();
+        self.store.put_order(&order).await?;
 
-        // Speichere Order
-        self.store.put_order(&updated_order).await?;
+        tracing::warn!("Unwound entry {} with order {}", entry.order_id, order.order_id);
+        Ok(order.order_id)
+    }
 
-        // Update Calendar Event
-        let mut updated_event = event.clone();
-        updated_event.status = "sniped".to_string();
-        updated_event.executed_orders.push(updated_order.order_id.clone());
-        updated_event.execution_time = Some(chrono::Utc::now().timestamp_millis());
+    /// Prüfe ob automatischer Snipe für ein Event ausgeführt werden soll
+    /// (Schwelle je Pattern-Typ, sonst global)
+    pub fn should_execute_snipe(&self, pattern: &str, pattern_confidence: f64) -> bool {
+        pattern_confidence >= self.runtime.load().min_confidence_for(pattern)
+    }
+}
 
-        self.store.put_calendar_event(&updated_event).await?;
+/// Ergebnis eines Snipe-Versuchs
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnipeOutcome {
+    /// Entry platziert; `entry_tier` ist die Stufe der Fallback-Kette, die gegriffen hat
+    Executed { order_id: String, entry_tier: EntryTier },
+    Skipped { reason: String },
+    /// Alle Versuche fehlgeschlagen, Event als missed markiert
+    Missed { attempts: u32, reason: String },
+    /// Entry gefüllt, aber Slippage zu hoch: keine Exits, ggf. glattgestellt
+    Aborted {
+        order_id: String,
+        slippage_pct: f64,
+        unwind_order_id: Option<String>,
+    },
+    /// Fehler nach dem Senden einer Order, die bei MEXC existiert (oder deren
+    /// Status unklar ist): kein neuer Versuch, Event als needs_reconcile markiert
+    NeedsReconcile { client_order_id: String, reason: String },
+}
 
-        Ok(updated_order.order_id)
+/// Fehler nach dem Senden einer Entry-Order: ein neuer Versuch könnte doppelt kaufen
+#[derive(Debug)]
+pub struct EntryUncertain {
+    pub symbol: String,
+    pub client_order_id: String,
+    /// Order existiert sicher (MEXC hat geantwortet); sonst ist nur unklar, ob sie ankam
+    pub placed: bool,
+    source: anyhow::Error,
+}
+
+impl EntryUncertain {
+    /// Fehler nach einer Antwort von MEXC: die Order existiert
+    fn placed(source: anyhow::Error, symbol: &str, client_order_id: &str) -> anyhow::Error {
+        if source.downcast_ref::<EntryUncertain>().is_some() {
+            return source;
+        }
+        Self {
+            symbol: symbol.to_string(),
+            client_order_id: client_order_id.to_string(),
+            placed: true,
+            source,
+        }
+        .into()
     }
+}
 
-    /// Prüfe ob automatischer Snipe für ein Event ausgeführt werden soll
-    pub fn should_execute_snipe(&self, pattern_confidence: f64) -> bool {
-        // Minimum Confidence 70% für automatischen Snipe
-        pattern_confidence >= 0.7
+impl std::fmt::Display for EntryUncertain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#}", self.source)
     }
 }
 
-#[derive(Debug, Clone)]
+impl std::error::Error for EntryUncertain {}
+
+/// Fehler beim Senden einer Order: eindeutige Ablehnung (4xx) bleibt wie sie ist,
+/// alles andere (Timeout, 5xx) kann trotzdem platziert haben
+fn unless_rejected(error: anyhow::Error, symbol: &str, client_order_id: &str) -> anyhow::Error {
+    if error.downcast_ref::<MexcError>().is_some_and(MexcError::is_rejection) {
+        return error;
+    }
+    EntryUncertain {
+        symbol: symbol.to_string(),
+        client_order_id: client_order_id.to_string(),
+        placed: false,
+        source: error,
+    }
+    .into()
+}
+
+/// Deterministische newClientOrderId je User, Event, Versuch und Order (z.B. Stufe,
+/// "canary"): nach einem unklaren Fehler lässt sich genau diese Order nachschlagen.
+/// Der User gehört in den Hash, weil die event_id nur aus Symbol und Launch-Zeit
+/// besteht; sonst bekäme ein zweiter User auf demselben Konto (idempotentes
+/// Create) die Order des ersten als eigene zurück.
+pub fn snipe_client_order_id(event: &CalendarEventItem, leg: &str) -> String {
+    let digest = Sha256::digest(format!("{}|{}", event.user_id, event.event_id));
+    format!("{}-{}-{}", hex::encode(&digest[..8]), event.attempts, leg)
+}
+
+/// Ergebnis der Canary-Stufe
+enum CanaryResult {
+    /// Haupt-Entry mit angepasster Menge feuern (`order_id` der Canary, None wenn keine gesendet wurde)
+    Proceed { order_id: Option<String>, main_quantity: f64 },
+    /// Snipe beendet (Slippage zu hoch)
+    Abort(SnipeOutcome),
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct SnipeOrderParams {
     pub side: String,      // "BUY", "SELL"
     pub quantity: f64,
+    #[serde(default)]
+    pub expected_price: Option<f64>, // Schätzpreis vor dem Trade
+    /// Manuell bestätigt (Override-Endpoint): der Re-Listing-Guard greift nicht
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+/// Slippage des Fills ggü. Schätzpreis in Prozent (positiv = schlechter als erwartet)
+fn entry_slippage_pct(side: &str, expected_price: Option<f64>, fill_price: f64) -> Option<f64> {
+    let expected = expected_price.filter(|p| *p > 0.0)?;
+    if fill_price <= 0.0 {
+        return None;
+    }
+    let diff = if side.eq_ignore_ascii_case("SELL") {
+        expected - fill_price
+    } else {
+        fill_price - expected
+    };
+    Some(diff / expected * 100.0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{mexc_client, spawn_server, test_config, MockDynamo};
+    use crate::trading::{LimitChaser, OrderMonitor};
+    use axum::{extract::Query, response::IntoResponse, routing::post, Json, Router};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    async fn manager(dynamo: &MockDynamo, blacklist: &[&str]) -> SnipingManager {
+        let config = Config {
+            symbol_blacklist: blacklist.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        SnipingManager::new(
+            Arc::new(mexc_client("http://127.0.0.1:9")),
+            Arc::new(dynamo.store().await),
+            &config,
+        )
+    }
+
+    #[test]
+    fn test_client_order_id_differs_per_user() {
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let other = CalendarEventItem {
+            user_id: "user-2".to_string(),
+            ..event.clone()
+        };
+
+        // Gleicher Launch, gleiche event_id, aber verschiedene User
+        assert_eq!(event.event_id, other.event_id);
+        let id = snipe_client_order_id(&event, "market");
+        assert_ne!(id, snipe_client_order_id(&other, "market"));
+        assert_eq!(id, snipe_client_order_id(&event.clone(), "market"));
+        assert!(id.len() <= 32);
+    }
+
+    #[tokio::test]
+    async fn test_should_execute_snipe() {
+        let dynamo = MockDynamo::start().await;
+        let sniper = manager(&dynamo, &[]).await;
+
+        assert!(sniper.should_execute_snipe("sts:2", 0.7));
+        assert!(!sniper.should_execute_snipe("sts:2", 0.69));
+    }
+
+    #[tokio::test]
+    async fn test_min_confidence_depends_on_pattern() {
+        let dynamo = MockDynamo::start().await;
+        let runtime = Arc::new(RuntimeSettings::new(&Config {
+            min_snipe_confidence_by_pattern: [("sts:2".to_string(), 0.6), ("tt:4".to_string(), 0.85)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }));
+        let sniper = manager(&dynamo, &[]).await.with_runtime_settings(runtime);
+
+        // Gleiche Confidence, unterschiedliches Ergebnis je Pattern
+        assert!(sniper.should_execute_snipe("sts:2", 0.7));
+        assert!(sniper.should_execute_snipe("STS:2", 0.62));
+        assert!(!sniper.should_execute_snipe("tt:4", 0.7));
+        assert!(sniper.should_execute_snipe("tt:4", 0.85));
+        // Unbekanntes Pattern -> globaler Default 0.7
+        assert!(sniper.should_execute_snipe("st:2", 0.7));
+        assert!(!sniper.should_execute_snipe("st:2", 0.65));
+    }
+
+    #[tokio::test]
+    async fn test_reload_changes_min_confidence() {
+        let dynamo = MockDynamo::start().await;
+        let runtime = Arc::new(RuntimeSettings::new(&Config::default()));
+        let sniper = manager(&dynamo, &[]).await.with_runtime_settings(runtime.clone());
+        assert!(sniper.should_execute_snipe("sts:2", 0.75));
+
+        runtime.reload(&Config {
+            min_snipe_confidence: 0.8,
+            ..Default::default()
+        });
+        assert!(!sniper.should_execute_snipe("sts:2", 0.75));
+        assert!(sniper.should_execute_snipe("sts:2", 0.8));
+    }
+
+    #[tokio::test]
+    async fn test_blacklisted_symbol_is_skipped() {
+        let dynamo = MockDynamo::start().await;
+        let sniper = manager(&dynamo, &["SCAM*"]).await;
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "Scam Token".to_string(),
+            "SCAMUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.95,
+        );
+
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("skip should not fail");
+
+        assert!(matches!(outcome, SnipeOutcome::Skipped { .. }));
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0]["Item"]["status"]["S"], "skipped");
+    }
+
+    #[tokio::test]
+    async fn test_reload_blacklist_from_store() {
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond(
+            "GetItem",
+            serde_json::json!({ "Item": { "patterns": { "SS": ["RUG*"] } } }),
+        );
+        let sniper = manager(&dynamo, &[]).await;
+
+        assert!(!sniper.is_blacklisted("RUGUSDT"));
+        sniper.reload_blacklist().await.expect("reload failed");
+        assert!(sniper.is_blacklisted("RUGUSDT"));
+
+        // Der gespeicherte Eintrag blockiert den Snipe, bevor MEXC angefragt wird
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "Rug Token".to_string(),
+            "RUGUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.95,
+        );
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("skip should not fail");
+        assert!(matches!(outcome, SnipeOutcome::Skipped { reason } if reason.contains("blacklisted")));
+        assert_eq!(dynamo.requests("PutItem")[0]["Item"]["status"]["S"], "skipped");
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_refresh_picks_up_new_entries() {
+        let dynamo = MockDynamo::start().await;
+        let sniper = Arc::new(manager(&dynamo, &[]).await);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = tokio::spawn(sniper.clone().run_blacklist_refresh(Duration::from_millis(20), shutdown_rx));
+
+        // Eintrag erst nach dem Start hinzufügen (eine Antwort je Tick)
+        assert!(!sniper.is_blacklisted("RUGUSDT"));
+        for _ in 0..50 {
+            dynamo.respond(
+                "GetItem",
+                serde_json::json!({ "Item": { "patterns": { "SS": ["RUG*"] } } }),
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!dynamo.requests("GetItem").is_empty());
+        assert!(sniper.is_blacklisted("RUGUSDT"));
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+
+    #[test]
+    fn test_entry_slippage_pct() {
+        assert_eq!(entry_slippage_pct("BUY", Some(100.0), 110.0), Some(10.0));
+        assert_eq!(entry_slippage_pct("SELL", Some(100.0), 90.0), Some(10.0));
+        assert_eq!(entry_slippage_pct("BUY", None, 110.0), None);
+        assert_eq!(entry_slippage_pct("BUY", Some(100.0), 0.0), None);
+    }
+
+    #[tokio::test]
+    async fn test_bad_entry_fill_unwinds_position() {
+        let sides = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let sides = sides.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    let side = params["side"].clone();
+                    sides.lock().unwrap().push(side.clone());
+                    // MARKET-Fill wie von MEXC: price 0, Fill-Preis nur über das Quote-Volumen
+                    Json(serde_json::json!({
+                        "order_id": format!("mexc-{}", side),
+                        "symbol": "NEWUSDT",
+                        "side": side,
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 0.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "cummulativeQuoteQty": "1.10",
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        );
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: Some(1.0),
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("snipe failed");
+
+        let SnipeOutcome::Aborted {
+            slippage_pct,
+            unwind_order_id,
+            ..
+        } = outcome
+        else {
+            panic!("expected aborted outcome, got {:?}", outcome);
+        };
+        assert!((slippage_pct - 10.0).abs() < 1e-9);
+        assert!(unwind_order_id.is_some());
+        assert_eq!(*sides.lock().unwrap(), vec!["BUY", "SELL"]);
+
+        let puts = dynamo.requests("PutItem");
+        assert_eq!(puts.len(), 3);
+        assert_eq!(puts[1]["Item"]["side"]["S"], "SELL");
+        assert_eq!(puts[2]["Item"]["status"]["S"], "aborted");
+    }
+
+    #[tokio::test]
+    async fn test_snipe_on_named_account_tags_order() {
+        let api_keys = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let api_keys = api_keys.clone();
+                move |headers: axum::http::HeaderMap| async move {
+                    let api_key = headers["X-MEXC-APIKEY"].to_str().unwrap().to_string();
+                    api_keys.lock().unwrap().push(api_key);
+                    Json(serde_json::json!({
+                        "order_id": "mexc-1",
+                        "symbol": "NEWUSDT",
+                        "side": "BUY",
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 1.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let config = Config {
+            mexc_accounts: [(
+                "momentum".to_string(),
+                crate::mexc::Credentials {
+                    api_key: "momentum-key".to_string(),
+                    secret_key: "momentum-secret".to_string(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..crate::test_support::test_config(&base_url)
+        };
+        let pool = MexcClientPool::new(Arc::new(mexc_client(&base_url)), store.clone(), &config);
+        let sniper = SnipingManager::new(Arc::new(mexc_client(&base_url)), store, &config)
+            .with_account(&pool, "momentum")
+            .expect("account missing");
+        assert!(SnipingManager::new(Arc::new(mexc_client(&base_url)), Arc::new(dynamo.store().await), &config)
+            .with_account(&pool, "unknown")
+            .is_err());
+
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("snipe failed");
+
+        assert_eq!(*api_keys.lock().unwrap(), vec!["momentum-key"]);
+        let order = &dynamo.requests("PutItem")[0]["Item"];
+        assert_eq!(order["account"]["S"], "momentum");
+    }
+
+    #[tokio::test]
+    async fn test_entry_chain_falls_back_from_fok_to_ioc() {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let attempts = attempts.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    let order_type = params["type"].clone();
+                    attempts.lock().unwrap().push((order_type.clone(), params.get("price").cloned()));
+                    if order_type == "FILL_OR_KILL" {
+                        return (
+                            axum::http::StatusCode::BAD_REQUEST,
+                            Json(serde_json::json!({ "code": 30010, "msg": "FOK order could not be filled" })),
+                        )
+                            .into_response();
+                    }
+                    // IOC: 0.4 von 1.0 gefüllt, Rest verfallen
+                    Json(serde_json::json!({
+                        "order_id": "mexc-ioc",
+                        "symbol": "NEWUSDT",
+                        "side": "BUY",
+                        "order_type": order_type,
+                        "quantity": 1.0,
+                        "price": 1.005,
+                        "status": "EXPIRED",
+                        "filled_qty": 0.4,
+                        "created_at": 0,
+                    }))
+                    .into_response()
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let chain = EntryChain {
+            tiers: vec![EntryTier::FillOrKill, EntryTier::ImmediateOrCancel, EntryTier::Market],
+            limit_offset_pct: 1.0,
+            deadline: Duration::from_secs(5),
+            market_fallback_pct: 0.0,
+        };
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_entry_chain(chain.clone());
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: Some(1.0),
+            confirmed: false,
+        };
+
+        let outcome = sniper.execute_snipe("user-1", &event, params.clone()).await.expect("snipe failed");
+        let SnipeOutcome::Executed { entry_tier, .. } = outcome else {
+            panic!("expected executed outcome, got {:?}", outcome);
+        };
+        assert_eq!(entry_tier, EntryTier::ImmediateOrCancel);
+
+        let attempts = attempts.lock().unwrap().clone();
+        let types: Vec<_> = attempts.iter().map(|(order_type, _)| order_type.as_str()).collect();
+        assert_eq!(types, vec!["FILL_OR_KILL", "IMMEDIATE_OR_CANCEL"]);
+        let limit: f64 = attempts[0].1.as_deref().unwrap().parse().unwrap();
+        assert!((limit - 1.01).abs() < 1e-9);
+
+        let order = &dynamo.requests("PutItem")[0]["Item"];
+        assert_eq!(order["order_type"]["S"], "immediate_or_cancel");
+        assert_eq!(order["filled_qty"]["N"], "0.4");
+
+        // Deadline bereits verbraucht: keine weitere Stufe, Fehler für die Retry-Policy
+        let expired = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_entry_chain(EntryChain {
+            deadline: Duration::ZERO,
+            ..chain
+        });
+        assert!(expired.execute_snipe("user-1", &event, params).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_market_entry_falls_back_to_ioc_above_best_ask() {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new()
+            .route(
+                "/api/v3/depth",
+                axum::routing::get(|| async {
+                    Json(serde_json::json!({
+                        "bids": [["0.98", "10"]],
+                        "asks": [["1.00", "10"], ["1.05", "20"]]
+                    }))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let attempts = attempts.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        let order_type = params["type"].clone();
+                        attempts.lock().unwrap().push((order_type.clone(), params.get("price").cloned()));
+                        if order_type == "MARKET" {
+                            return (
+                                axum::http::StatusCode::BAD_REQUEST,
+                                Json(serde_json::json!({
+                                    "code": 30016,
+                                    "msg": "Market orders are not supported for this symbol yet"
+                                })),
+                            )
+                                .into_response();
+                        }
+                        Json(serde_json::json!({
+                            "order_id": "mexc-fallback",
+                            "symbol": "NEWUSDT",
+                            "side": "BUY",
+                            "order_type": order_type,
+                            "quantity": 1.0,
+                            "price": 1.0,
+                            "status": "FILLED",
+                            "filled_qty": 1.0,
+                            "created_at": 0,
+                        }))
+                        .into_response()
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_entry_chain(EntryChain {
+            market_fallback_pct: 2.0,
+            ..EntryChain::default()
+        });
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: None,
+            confirmed: false,
+        };
+
+        let outcome = sniper.execute_snipe("user-1", &event, params).await.expect("snipe failed");
+        let SnipeOutcome::Executed { entry_tier, .. } = outcome else {
+            panic!("expected executed outcome, got {:?}", outcome);
+        };
+        assert_eq!(entry_tier, EntryTier::MarketFallback);
+
+        // Bester Ask 1.00 + 2% -> IOC-Limit 1.02
+        let attempts = attempts.lock().unwrap().clone();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].0, "MARKET");
+        assert_eq!(attempts[1].0, "IMMEDIATE_OR_CANCEL");
+        let limit: f64 = attempts[1].1.as_deref().unwrap().parse().unwrap();
+        assert!((limit - 1.02).abs() < 1e-9);
+
+        let order = &dynamo.requests("PutItem")[0]["Item"];
+        assert_eq!(order["order_type"]["S"], "immediate_or_cancel");
+        let decisions = sniper.decision_log().recent(10);
+        assert!(decisions.iter().any(|d| d.gate == DecisionGate::MarketFallback && d.passed));
+    }
+
+    #[tokio::test]
+    async fn test_empty_book_at_launch_waits_for_liquidity() {
+        let depth_checks = Arc::new(AtomicUsize::new(0));
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/depth",
+                axum::routing::get({
+                    let depth_checks = depth_checks.clone();
+                    move || async move {
+                        // Erste Abfrage: noch keine Asks
+                        if depth_checks.fetch_add(1, Ordering::SeqCst) == 0 {
+                            Json(serde_json::json!({ "bids": [], "asks": [] }))
+                        } else {
+                            Json(serde_json::json!({ "bids": [["0.98", "10"]], "asks": [["1.00", "10"]] }))
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move || async move {
+                        orders.fetch_add(1, Ordering::SeqCst);
+                        Json(serde_json::json!({
+                            "order_id": "mexc-1",
+                            "symbol": "NEWUSDT",
+                            "side": "BUY",
+                            "order_type": "MARKET",
+                            "quantity": 1.0,
+                            "price": 1.0,
+                            "status": "FILLED",
+                            "filled_qty": 1.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_book_wait(BookLiquidityWait {
+            poll: Duration::from_millis(10),
+            min_levels: 1,
+        });
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: None,
+            confirmed: false,
+        };
+
+        let outcome = sniper.execute_snipe("user-1", &event, params).await.expect("snipe failed");
+
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }), "got {:?}", outcome);
+        assert_eq!(depth_checks.load(Ordering::SeqCst), 2);
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+        let decisions = sniper.decision_log().recent(10);
+        let waited = decisions.iter().find(|d| d.gate == DecisionGate::BookLiquidity).unwrap();
+        assert!(waited.passed);
+        assert!(waited.reason.as_deref().unwrap().starts_with("liquidity after 2 checks"));
+    }
+
+    #[tokio::test]
+    async fn test_canary_slippage_decides_main_entry() {
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let orders = orders.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    let side = params["side"].clone();
+                    orders.lock().unwrap().push(format!("{} {}", side, params["quantity"]));
+                    // Dünnes Buch: jeder Fill 25 % über dem Schätzpreis
+                    Json(serde_json::json!({
+                        "order_id": format!("mexc-{}", side),
+                        "symbol": "NEWUSDT",
+                        "side": side,
+                        "order_type": "MARKET",
+                        "quantity": params["quantity"].parse::<f64>().unwrap(),
+                        "price": 1.25,
+                        "status": "FILLED",
+                        "filled_qty": params["quantity"].parse::<f64>().unwrap(),
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = |max_slippage_pct: f64| {
+            let store = dynamo.store();
+            let base_url = base_url.clone();
+            async move {
+                SnipingManager::new(
+                    Arc::new(mexc_client(&base_url)),
+                    Arc::new(store.await),
+                    &Config {
+                        max_entry_slippage_pct: 50.0,
+                        ..Default::default()
+                    },
+                )
+                .with_canary(CanaryOrder {
+                    min_notional: 50.0,
+                    fraction: 0.05,
+                    max_slippage_pct,
+                })
+            }
+        };
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 100.0,
+            expected_price: Some(1.0),
+            confirmed: false,
+        };
+
+        // Canary-Slippage 25 % > 2 %: Abbruch, Canary glattgestellt, kein Haupt-Entry
+        let outcome = sniper(2.0).await.execute_snipe("user-1", &event, params.clone()).await.unwrap();
+        let SnipeOutcome::Aborted { slippage_pct, unwind_order_id, .. } = outcome else {
+            panic!("expected aborted outcome, got {:?}", outcome);
+        };
+        assert!((slippage_pct - 25.0).abs() < 1e-9);
+        assert!(unwind_order_id.is_some());
+        assert_eq!(*orders.lock().unwrap(), vec!["BUY 5", "SELL 5"]);
+
+        // Toleranz 30 %: Rest-Budget 100 - 5 * 1.25 = 93.75 zum Fill-Preis 1.25 -> 75
+        orders.lock().unwrap().clear();
+        let outcome = sniper(30.0).await.execute_snipe("user-1", &event, params).await.unwrap();
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }), "got {:?}", outcome);
+        assert_eq!(*orders.lock().unwrap(), vec!["BUY 5", "BUY 75"]);
+        let puts = dynamo.requests("PutItem");
+        let sniped = &puts.last().unwrap()["Item"];
+        assert_eq!(sniped["status"]["S"], "sniped");
+        assert_eq!(sniped["executed_orders"]["SS"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_validation_aborts_before_live_order() {
+        let live_orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/order/test",
+                post(|| async {
+                    (
+                        axum::http::StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({ "code": 30002, "msg": "Minimum transaction volume cannot be less than:5USDT" })),
+                    )
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let live_orders = live_orders.clone();
+                    move || async move {
+                        live_orders.fetch_add(1, Ordering::SeqCst);
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_validate_before_fire(true);
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: Some(1.0),
+            confirmed: false,
+        };
+
+        let error = sniper.execute_snipe("user-1", &event, params).await.unwrap_err();
+        assert!(error.to_string().contains("filter_failure"), "{}", error);
+        assert!(error.to_string().contains("Minimum transaction volume"), "{}", error);
+        assert_eq!(live_orders.load(Ordering::SeqCst), 0);
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sell_heavy_book_blocks_snipe() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/depth",
+                axum::routing::get(|| async {
+                    Json(serde_json::json!({
+                        "bids": [["1.00", "1"], ["0.99", "1"]],
+                        "asks": [["1.01", "5"], ["1.02", "8"]]
+                    }))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move || async move {
+                        orders.fetch_add(1, Ordering::SeqCst);
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_imbalance_gate(ImbalanceGate {
+            levels: 2,
+            min_imbalance: 0.2,
+        });
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("gate should not fail");
+
+        let SnipeOutcome::Skipped { reason } = outcome else {
+            panic!("expected skipped outcome, got {:?}", outcome);
+        };
+        assert!(reason.contains("imbalance"));
+        assert_eq!(orders.load(Ordering::SeqCst), 0);
+        assert_eq!(dynamo.requests("PutItem")[0]["Item"]["status"]["S"], "skipped");
+    }
 
     #[test]
-    fn test_should_execute_snipe() {
-        let config = crate::utils::Config {
-            mexc_api_key: "test".to_string(),
-            mexc_secret_key: "test".to_string(),
-            mexc_base_url: "https://api.mexc.com".to_string(),
-            aws_region: "ap-southeast-1".to_string(),
-            dynamodb_table: "test".to_string(),
-            rust_api_port: 8080,
-            jwt_secret: "test".to_string(),
-        };
-
-        // Test würde mit Mock DynamoDBStore funktionieren
-        assert!(true); // Placeholder
+    fn test_spread_gate_on_synthetic_books() {
+        let gate = SpreadGate {
+            max_spread_pct: 5.0,
+            require_confirmation: false,
+        };
+        let book = |bids: Vec<(f64, f64)>, asks: Vec<(f64, f64)>| OrderBook { bids, asks, timestamp: 0 };
+
+        // Bid 0.98, Ask 1.02 -> 0.04 / 1.00 = 4%
+        let tight = book(vec![(0.98, 10.0)], vec![(1.02, 10.0)]);
+        assert!((tight.spread_pct().unwrap() - 4.0).abs() < 1e-9);
+        assert_eq!(gate.check("NEWUSDT", &tight), None);
+
+        // Bid 0.90, Ask 1.10 -> 20%
+        let wide = book(vec![(0.90, 10.0)], vec![(1.10, 10.0)]);
+        assert!((wide.spread_pct().unwrap() - 20.0).abs() < 1e-9);
+        assert!(gate.check("NEWUSDT", &wide).unwrap().contains("spread 20.00%"));
+
+        let one_sided = book(vec![], vec![(1.10, 10.0)]);
+        assert_eq!(one_sided.spread_pct(), None);
+        assert!(gate.check("NEWUSDT", &one_sided).unwrap().contains("one-sided"));
+    }
+
+    #[tokio::test]
+    async fn test_wide_spread_holds_snipe_for_confirmation() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/depth",
+                axum::routing::get(|| async {
+                    Json(serde_json::json!({ "bids": [["0.50", "10"]], "asks": [["1.50", "10"]] }))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move || async move {
+                        orders.fetch_add(1, Ordering::SeqCst);
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_spread_gate(SpreadGate {
+            max_spread_pct: 10.0,
+            require_confirmation: true,
+        });
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("gate should not fail");
+
+        let SnipeOutcome::Skipped { reason } = outcome else {
+            panic!("expected skipped outcome, got {:?}", outcome);
+        };
+        assert!(reason.contains("spread 100.00%"));
+        assert_eq!(orders.load(Ordering::SeqCst), 0);
+        assert_eq!(dynamo.requests("PutItem")[0]["Item"]["status"]["S"], "detected");
+    }
+
+    #[tokio::test]
+    async fn test_low_volume_symbol_is_gated() {
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/ticker/24hr",
+                axum::routing::get(|Query(params): Query<HashMap<String, String>>| async move {
+                    let symbol = params["symbol"].clone();
+                    let quote_volume = if symbol == "LOWUSDT" { "500.0" } else { "2500000.0" };
+                    Json(serde_json::json!({
+                        "symbol": symbol,
+                        "price": 1.0,
+                        "timestamp": 0,
+                        "volume": "1000.0",
+                        "quoteVolume": quote_volume,
+                    }))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        orders.fetch_add(1, Ordering::SeqCst);
+                        Json(serde_json::json!({
+                            "order_id": "mexc-1",
+                            "symbol": params["symbol"],
+                            "side": "BUY",
+                            "order_type": "MARKET",
+                            "quantity": 1.0,
+                            "price": 1.0,
+                            "status": "FILLED",
+                            "filled_qty": 1.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_liquidity_gate(LiquidityGate {
+            min_quote_volume: 100_000.0,
+            allow_new_listings: false,
+        });
+        let snipe = |symbol: &str| {
+            let event = CalendarEventItem::new(
+                "user-1".to_string(),
+                "Token".to_string(),
+                symbol.to_string(),
+                1_700_000_000_000,
+                "sts:2".to_string(),
+                0.9,
+            );
+            let sniper = &sniper;
+            async move {
+                sniper
+                    .execute_snipe(
+                        "user-1",
+                        &event,
+                        SnipeOrderParams {
+                            side: "BUY".to_string(),
+                            quantity: 1.0,
+                            expected_price: None,
+                            confirmed: false,
+                        },
+                    )
+                    .await
+                    .expect("snipe failed")
+            }
+        };
+
+        let SnipeOutcome::Skipped { reason } = snipe("LOWUSDT").await else {
+            panic!("low volume symbol should be skipped");
+        };
+        assert!(reason.contains("24h quote volume"));
+        assert_eq!(orders.load(Ordering::SeqCst), 0);
+
+        // Entscheidungsprotokoll: alle Gates bis zum ablehnenden, danach keines mehr
+        let decisions = sniper
+            .decision_log()
+            .for_event(&crate::storage::calendar_event_key("LOWUSDT", 1_700_000_000_000));
+        let gates: Vec<_> = decisions.iter().map(|d| (d.gate, d.passed)).collect();
+        assert_eq!(
+            gates,
+            vec![
+                (DecisionGate::Blacklist, true),
+                (DecisionGate::Confidence, true),
+                (DecisionGate::TradingState, true),
+                (DecisionGate::Liquidity, false),
+            ]
+        );
+        assert!(decisions[3].reason.as_deref().unwrap().contains("below minimum"));
+
+        let outcome = snipe("HIGHUSDT").await;
+        assert!(!matches!(outcome, SnipeOutcome::Skipped { .. }), "{:?}", outcome);
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_previously_seen_symbol_is_held_as_relisting() {
+        const NOW: i64 = 1_700_000_000_000;
+        const DAY: i64 = 24 * 3600 * 1000;
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let orders = orders.clone();
+                move || async move {
+                    orders.fetch_add(1, Ordering::SeqCst);
+                    Json(serde_json::json!({
+                        "order_id": "mexc-1",
+                        "symbol": "NEWUSDT",
+                        "side": "BUY",
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 1.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_clock(Arc::new(crate::utils::MockClock::from_millis(NOW)))
+        .with_relisting_guard(RelistingGuard {
+            min_age: Duration::from_secs(7 * 24 * 3600),
+            action: RelistingAction::Confirm,
+            confidence_penalty: 0.2,
+        });
+        let event = |symbol: &str| {
+            CalendarEventItem::new(
+                "user-1".to_string(),
+                "Token".to_string(),
+                symbol.to_string(),
+                NOW + 60_000,
+                "sts:2".to_string(),
+                0.9,
+            )
+        };
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: None,
+            confirmed: false,
+        };
+
+        // OLDUSDT wurde vor 30 Tagen schon einmal gelistet -> zurückgehalten
+        let old_event = event("OLDUSDT");
+        dynamo.respond(
+            "Query",
+            serde_json::json!({ "Count": 1, "Items": [{
+                "user_id": { "S": "user-1" },
+                "sk": { "S": format!("CALENDAR#{}#OLDUSDT", NOW - 30 * DAY) },
+                "event_id": { "S": "old-listing" },
+                "token_name": { "S": "Old" },
+                "symbol": { "S": "OLDUSDT" },
+                "launch_time": { "N": (NOW - 30 * DAY).to_string() },
+                "detected_pattern": { "S": "sts:2" },
+                "confidence": { "N": "0.9" },
+                "created_at": { "S": "2023-10-01T00:00:00Z" },
+                "status": { "S": "sniped" },
+                "ttl": { "N": "0" }
+            }] }),
+        );
+        let outcome = sniper.execute_snipe("user-1", &old_event, params.clone()).await.unwrap();
+        let SnipeOutcome::Skipped { reason } = outcome else {
+            panic!("re-listed symbol should be held");
+        };
+        assert!(reason.contains("manual confirmation"), "{}", reason);
+        assert_eq!(orders.load(Ordering::SeqCst), 0);
+        assert_eq!(dynamo.requests("PutItem")[0]["Item"]["status"]["S"], "detected");
+        let decisions = sniper.decision_log().for_event(&old_event.event_id);
+        assert_eq!(decisions.last().map(|d| (d.gate, d.passed)), Some((DecisionGate::Relisting, false)));
+
+        // Manuelle Bestätigung (Override) umgeht den Guard
+        let confirmed = SnipeOrderParams {
+            confirmed: true,
+            ..params.clone()
+        };
+        let outcome = sniper.execute_snipe("user-1", &old_event, confirmed).await.unwrap();
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }), "{:?}", outcome);
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+
+        // Echtes Neu-Listing: keine Historie -> Gate bestanden, Snipe läuft
+        let new_event = event("NEWUSDT");
+        let outcome = sniper.execute_snipe("user-1", &new_event, params).await.unwrap();
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }), "{:?}", outcome);
+        assert_eq!(orders.load(Ordering::SeqCst), 2);
+        let decisions = sniper.decision_log().for_event(&new_event.event_id);
+        assert_eq!(decisions[1].gate, DecisionGate::Relisting);
+        assert!(decisions[1].passed);
+    }
+
+    #[tokio::test]
+    async fn test_snipe_waits_for_trading_state() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let orders = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route(
+                "/api/v3/exchangeInfo",
+                axum::routing::get({
+                    let polls = polls.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        let symbol = params["symbol"].clone();
+                        let status = if symbol == "HALTUSDT" {
+                            "HALT"
+                        } else if polls.fetch_add(1, Ordering::SeqCst) < 2 {
+                            "PRE_TRADING"
+                        } else {
+                            "TRADING"
+                        };
+                        Json(serde_json::json!({ "symbols": [{ "symbol": symbol, "status": status }] }))
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move || async move {
+                        orders.fetch_add(1, Ordering::SeqCst);
+                        Json(serde_json::json!({
+                            "order_id": "mexc-1",
+                            "symbol": "NEWUSDT",
+                            "side": "BUY",
+                            "order_type": "MARKET",
+                            "quantity": 1.0,
+                            "price": 1.0,
+                            "status": "FILLED",
+                            "filled_qty": 1.0,
+                            "created_at": 0,
+                        }))
+                    }
+                }),
+            );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let sniper = SnipingManager::new(
+            Arc::new(mexc_client(&base_url)),
+            Arc::new(dynamo.store().await),
+            &Config::default(),
+        )
+        .with_trading_state_gate(TradingStateGate {
+            poll: Duration::from_millis(10),
+            max_wait: Duration::from_secs(5),
+        });
+        let params = SnipeOrderParams {
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            expected_price: None,
+            confirmed: false,
+        };
+        let event = |symbol: &str| {
+            CalendarEventItem::new(
+                "user-1".to_string(),
+                "New Token".to_string(),
+                symbol.to_string(),
+                1_700_000_000_000,
+                "sts:2".to_string(),
+                0.9,
+            )
+        };
+
+        let outcome = sniper
+            .execute_snipe("user-1", &event("NEWUSDT"), params.clone())
+            .await
+            .expect("snipe failed");
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }));
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+
+        // Pausiertes Symbol: kein Warten, keine Order
+        let outcome = sniper
+            .execute_snipe("user-1", &event("HALTUSDT"), params)
+            .await
+            .expect("gate should not fail");
+        let SnipeOutcome::Skipped { reason } = outcome else {
+            panic!("expected skipped outcome, got {:?}", outcome);
+        };
+        assert!(reason.contains("halted"));
+        assert_eq!(orders.load(Ordering::SeqCst), 1);
+        assert_eq!(dynamo.requests("PutItem").last().unwrap()["Item"]["status"]["S"], "skipped");
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_then_success_buys_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let lookups = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let calls = calls.clone();
+                move || async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return (axum::http::StatusCode::SERVICE_UNAVAILABLE, "busy").into_response();
+                    }
+                    Json(serde_json::json!({
+                        "order_id": "mexc-1",
+                        "symbol": "NEWUSDT",
+                        "side": "BUY",
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 1.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                    .into_response()
+                }
+            })
+            // Der 503 lässt offen, ob die Order ankam: MEXC kennt sie nicht
+            .get({
+                let lookups = lookups.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    lookups.lock().unwrap().push(params);
+                    (
+                        axum::http::StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({ "code": -2013, "msg": "Order does not exist." })),
+                    )
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        let config = Config {
+            snipe_retry_backoff_ms: 10,
+            ..Default::default()
+        };
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            chrono::Utc::now().timestamp_millis(),
+            "sts:2".to_string(),
+            0.9,
+        );
+        // Ohne HTTP-Retry im Client, damit der 503 den Snipe-Retry auslöst
+        let client = MexcClient::new(&Config {
+            mexc_retry_max_attempts: 1,
+            ..test_config(&base_url)
+        })
+        .unwrap();
+        let sniper = SnipingManager::new(Arc::new(client), Arc::new(dynamo.store().await), &config);
+
+        let outcome = sniper
+            .execute_snipe_with_retry(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("snipe failed");
+
+        assert!(matches!(outcome, SnipeOutcome::Executed { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let lookups = lookups.lock().unwrap().clone();
+        assert_eq!(lookups.len(), 1);
+        let first_attempt = CalendarEventItem {
+            attempts: 1,
+            ..event.clone()
+        };
+        assert_eq!(lookups[0]["origClientOrderId"], snipe_client_order_id(&first_attempt, "market"));
+
+        let puts = dynamo.requests("PutItem");
+        let orders: Vec<_> = puts
+            .iter()
+            .filter(|p| p["Item"]["sk"]["S"].as_str().unwrap().starts_with("ORDER#"))
+            .collect();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0]["Item"]["event_id"]["S"], event.event_id.as_str());
+        assert_eq!(puts.last().unwrap()["Item"]["status"]["S"], "sniped");
+        assert_eq!(puts.last().unwrap()["Item"]["attempts"]["N"], "2");
+
+        // claim, release (-> detected), claim
+        let updates = dynamo.requests("UpdateItem");
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[1]["ExpressionAttributeValues"][":to"]["S"], "detected");
+        assert_eq!(updates[2]["ExpressionAttributeValues"][":attempts"]["N"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_claimed_event_is_not_sniped_again() {
+        let dynamo = MockDynamo::start().await;
+        dynamo.respond_error("UpdateItem", "ConditionalCheckFailedException");
+        let sniper = manager(&dynamo, &[]).await;
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            chrono::Utc::now().timestamp_millis(),
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        let outcome = sniper
+            .execute_snipe_with_retry(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("claim check failed");
+
+        assert!(matches!(outcome, SnipeOutcome::Skipped { .. }));
+        assert!(dynamo.requests("PutItem").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_persist_after_fill_is_not_retried() {
+        let posts = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::new().route(
+            "/api/v3/order",
+            post({
+                let posts = posts.clone();
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    posts.lock().unwrap().push(params);
+                    Json(serde_json::json!({
+                        "order_id": "mexc-1",
+                        "symbol": "NEWUSDT",
+                        "side": "BUY",
+                        "order_type": "MARKET",
+                        "quantity": 1.0,
+                        "price": 1.0,
+                        "status": "FILLED",
+                        "filled_qty": 1.0,
+                        "created_at": 0,
+                    }))
+                }
+            }),
+        );
+        let base_url = spawn_server(router).await;
+        let dynamo = MockDynamo::start().await;
+        // Order gefüllt, aber das Speichern schlägt fehl
+        dynamo.respond_error("PutItem", "ValidationException");
+        let config = Config {
+            snipe_retry_backoff_ms: 10,
+            ..Default::default()
+        };
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            chrono::Utc::now().timestamp_millis(),
+            "sts:2".to_string(),
+            0.9,
+        );
+        let sniper = SnipingManager::new(Arc::new(mexc_client(&base_url)), Arc::new(dynamo.store().await), &config);
+
+        let outcome = sniper
+            .execute_snipe_with_retry(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 1.0,
+                    expected_price: None,
+                    confirmed: false,
+                },
+            )
+            .await
+            .expect("snipe failed");
+
+        let SnipeOutcome::NeedsReconcile { client_order_id, .. } = outcome else {
+            panic!("expected needs_reconcile, got {:?}", outcome);
+        };
+        let posts = posts.lock().unwrap().clone();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0]["newClientOrderId"], client_order_id);
+
+        // claim, dann sniping -> needs_reconcile statt zurück an den Scheduler
+        let updates = dynamo.requests("UpdateItem");
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1]["ExpressionAttributeValues"][":to"]["S"], "needs_reconcile");
+    }
+
+    #[tokio::test]
+    async fn test_unfilled_limit_snipe_is_chased_by_the_monitor() {
+        let orders = Arc::new(Mutex::new(Vec::<String>::new()));
+        let mexc_order = |order_id: &str, status: &str| {
+            serde_json::json!({
+                "order_id": order_id,
+                "symbol": "NEWUSDT",
+                "side": "BUY",
+                "order_type": "LIMIT",
+                "quantity": 10.0,
+                "price": 1.0,
+                "status": status,
+                "filled_qty": 0.0,
+                "created_at": 0,
+            })
+        };
+        let router = Router::new()
+            .route(
+                "/api/v3/order",
+                post({
+                    let orders = orders.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        let mut orders = orders.lock().unwrap();
+                        orders.push(format!("{} {}", params["type"], params["price"]));
+                        let placed = orders.iter().filter(|o| !o.starts_with("CANCEL")).count();
+                        Json(mexc_order(&format!("mexc-{}", placed), "NEW"))
+                    }
+                })
+                .get(move || async move { Json(mexc_order("mexc-1", "NEW")) })
+                .delete({
+                    let orders = orders.clone();
+                    move |Query(params): Query<HashMap<String, String>>| async move {
+                        orders.lock().unwrap().push(format!("CANCEL {}", params["orderId"]));
+                        Json(mexc_order(&params["orderId"], "CANCELED"))
+                    }
+                }),
+            )
+            .route(
+                "/api/v3/depth",
+                axum::routing::get(|| async {
+                    Json(serde_json::json!({ "bids": [["1.08", "10"]], "asks": [["1.10", "10"]] }))
+                }),
+            );
+        let mexc = Arc::new(mexc_client(&spawn_server(router).await));
+        let dynamo = MockDynamo::start().await;
+        let store = Arc::new(dynamo.store().await);
+        let sniper = SnipingManager::new(mexc.clone(), store.clone(), &Config::default()).with_entry_chain(EntryChain {
+            tiers: vec![EntryTier::Limit],
+            limit_offset_pct: 0.0,
+            ..EntryChain::default()
+        });
+        let event = CalendarEventItem::new(
+            "user-1".to_string(),
+            "New Token".to_string(),
+            "NEWUSDT".to_string(),
+            1_700_000_000_000,
+            "sts:2".to_string(),
+            0.9,
+        );
+
+        // Ungefülltes LIMIT ist ein gültiger Entry und bleibt liegen
+        let outcome = sniper
+            .execute_snipe(
+                "user-1",
+                &event,
+                SnipeOrderParams {
+                    side: "BUY".to_string(),
+                    quantity: 10.0,
+                    expected_price: Some(1.0),
+                    confirmed: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(
+            matches!(outcome, SnipeOutcome::Executed { entry_tier: EntryTier::Limit, .. }),
+            "got {:?}",
+            outcome
+        );
+        let stored = dynamo.requests("PutItem")[0]["Item"].clone();
+        assert_eq!(stored["order_type"]["S"], "limit");
+        assert_eq!(stored["status"]["S"], "open");
+
+        // Eine Minute später zieht der Monitor das Limit Richtung Ask nach
+        let timestamp: i64 = stored["timestamp"]["N"].as_str().unwrap().parse().unwrap();
+        let monitor = OrderMonitor::new(mexc, store, Duration::from_secs(60))
+            .with_clock(Arc::new(crate::utils::MockClock::from_millis(timestamp + 60_000)))
+            .with_limit_chaser(LimitChaser::from_config(&Config {
+                snipe_chase_after_secs: 30,
+                snipe_chase_step_pct: 5.0,
+                snipe_chase_max_pct: 20.0,
+                ..Default::default()
+            }));
+        monitor.track_user("user-1");
+        dynamo.respond("Query", serde_json::json!({ "Count": 1, "Items": [stored] }));
+        assert_eq!(monitor.poll_once().await.unwrap(), 1);
+
+        assert_eq!(*orders.lock().unwrap(), vec!["LIMIT 1", "CANCEL mexc-1", "LIMIT 1.05"]);
+        let puts = dynamo.requests("PutItem");
+        let replacement = puts
+            .iter()
+            .map(|p| &p["Item"])
+            .find(|item| item["replaces"]["S"] == stored["order_id"]["S"])
+            .expect("replacement not stored");
+        assert_eq!(replacement["event_id"]["S"], event.event_id.as_str());
+        assert_eq!(replacement["mexc_order_id"]["S"], "mexc-2");
     }
 }
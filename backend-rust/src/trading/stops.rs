@@ -0,0 +1,137 @@
+use crate::storage::PositionItem;
+use crate::utils::Config;
+
+/// Automatische Stop-Nachführung: ab `activation_pct` Gewinn wird der Stop auf
+/// Break-even (Entry plus Gebühren) gezogen, danach optional im Abstand
+/// `trailing_pct` zum aktuellen Preis nachgezogen. Der Stop wird nie gelockert.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StopAdjuster {
+    /// Gewinn in Prozent, ab dem auf Break-even gezogen wird (0 = aus)
+    pub activation_pct: f64,
+    /// Gebühren (Ein- und Ausstieg) in Prozent, um die der Break-even-Stop über dem Entry liegt
+    pub fee_pct: f64,
+    /// Abstand des Trailing-Stops zum Preis in Prozent (0 = kein Trailing)
+    pub trailing_pct: f64,
+}
+
+impl StopAdjuster {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            activation_pct: config.stop_break_even_activation_pct,
+            fee_pct: config.stop_break_even_fee_pct,
+            trailing_pct: config.stop_trailing_pct,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.activation_pct > 0.0
+    }
+
+    /// Stop der Position anhand von `current_price`/`pnl_percentage` nachziehen;
+    /// Some(neuer Stop) wenn er verschoben wurde
+    pub fn adjust(&self, position: &mut PositionItem) -> Option<f64> {
+        if !self.is_enabled() || position.status != "open" || position.entry_price <= 0.0 {
+            return None;
+        }
+        if position.pnl_percentage? < self.activation_pct {
+            return None;
+        }
+
+        let short = position.side == "short";
+        let break_even = if short {
+            position.entry_price * (1.0 - self.fee_pct / 100.0)
+        } else {
+            position.entry_price * (1.0 + self.fee_pct / 100.0)
+        };
+        let mut target = break_even;
+        if self.trailing_pct > 0.0 {
+            let trail = if short {
+                position.current_price * (1.0 + self.trailing_pct / 100.0)
+            } else {
+                position.current_price * (1.0 - self.trailing_pct / 100.0)
+            };
+            // Trailing erst jenseits von Break-even
+            target = if short { target.min(trail) } else { target.max(trail) };
+        }
+
+        let tighter = match position.stop_price {
+            None => true,
+            Some(stop) if short => target < stop,
+            Some(stop) => target > stop,
+        };
+        if !tighter {
+            return None;
+        }
+        position.stop_price = Some(target);
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn long_position(stop: f64) -> PositionItem {
+        let mut position = PositionItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            100.0,
+            1.0,
+            "long".to_string(),
+        );
+        position.stop_price = Some(stop);
+        position
+    }
+
+    #[test]
+    fn test_stop_moves_to_break_even_at_activation() {
+        let adjuster = StopAdjuster {
+            activation_pct: 2.0,
+            fee_pct: 0.2,
+            trailing_pct: 0.0,
+        };
+        let mut position = long_position(95.0);
+
+        position.calculate_pnl(101.9);
+        assert_eq!(adjuster.adjust(&mut position), None);
+        assert_eq!(position.stop_price, Some(95.0));
+
+        position.calculate_pnl(102.0);
+        let stop = adjuster.adjust(&mut position).expect("stop not moved");
+        assert!((stop - 100.2).abs() < 1e-9);
+
+        // Nie zurück, auch wenn der Preis wieder fällt
+        position.calculate_pnl(101.0);
+        assert_eq!(adjuster.adjust(&mut position), None);
+        assert!((position.stop_price.unwrap() - 100.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trailing_continues_after_break_even() {
+        let adjuster = StopAdjuster {
+            activation_pct: 2.0,
+            fee_pct: 0.0,
+            trailing_pct: 5.0,
+        };
+        let mut position = long_position(95.0);
+
+        // Trail (102 * 0.95) liegt unter Entry -> Break-even
+        position.calculate_pnl(102.0);
+        assert_eq!(adjuster.adjust(&mut position), Some(100.0));
+
+        position.calculate_pnl(110.0);
+        assert!((adjuster.adjust(&mut position).unwrap() - 104.5).abs() < 1e-9);
+
+        let mut short = PositionItem::new(
+            "user-1".to_string(),
+            "ETHUSDT".to_string(),
+            100.0,
+            1.0,
+            "short".to_string(),
+        );
+        short.calculate_pnl(97.0);
+        assert_eq!(adjuster.adjust(&mut short), Some(100.0));
+        short.calculate_pnl(90.0);
+        assert!((adjuster.adjust(&mut short).unwrap() - 94.5).abs() < 1e-9);
+    }
+}
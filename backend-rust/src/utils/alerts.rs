@@ -0,0 +1,311 @@
+//! Alert-Webhook mit Deduplizierung: identische Alerts (gleicher Key) werden
+//! innerhalb eines Fensters zusammengefasst, die Gesamtzahl pro Minute begrenzt
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::Config;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Zeitraum für das Rate-Limit aller Alerts
+const RATE_WINDOW_MS: i64 = 60_000;
+
+/// Offener Vorfall eines Alert-Keys (bis zur Recovery)
+#[derive(Debug)]
+struct Incident {
+    message: String,
+    window_start: i64,
+    /// Im aktuellen Fenster unterdrückte Wiederholungen
+    suppressed: u32,
+}
+
+#[derive(Debug, Default)]
+struct AlertState {
+    incidents: HashMap<String, Incident>,
+    /// Zustellzeitpunkte der letzten Minute (Unix ms)
+    sent: VecDeque<i64>,
+}
+
+impl AlertState {
+    /// Slot im Rate-Limit belegen (0 = unbegrenzt)
+    fn take_slot(&mut self, now: i64, max_per_minute: u32) -> bool {
+        while self.sent.front().is_some_and(|sent| now - sent >= RATE_WINDOW_MS) {
+            self.sent.pop_front();
+        }
+        if max_per_minute > 0 && self.sent.len() >= max_per_minute as usize {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+/// POST von JSON an einen Webhook mit begrenztem Retry (exponentieller Backoff).
+/// Basis für Alerts und Positions-Events.
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    url: String,
+    http: reqwest::Client,
+    max_retries: u32,
+    /// Wartezeit vor dem ersten Retry, verdoppelt sich pro Versuch
+    base_delay: Duration,
+}
+
+impl WebhookDelivery {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            http: reqwest::Client::new(),
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Zustellen; gibt zurück, ob ein Versuch erfolgreich war
+    pub async fn send(&self, body: &serde_json::Value) -> bool {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .http
+                .post(&self.url)
+                .json(body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            let error = match result {
+                Ok(_) => return true,
+                Err(e) => e,
+            };
+            if attempt >= self.max_retries {
+                tracing::warn!("Webhook delivery to {} failed: {}", self.url, error);
+                return false;
+            }
+            let delay = self.base_delay.saturating_mul(1 << attempt.min(16));
+            tracing::debug!("Webhook delivery failed ({}), retrying in {:?}", error, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Fire-and-forget im Hintergrund (inkl. Retries)
+    pub fn spawn_send(self: &Arc<Self>, body: serde_json::Value) {
+        let delivery = self.clone();
+        tokio::spawn(async move {
+            delivery.send(&body).await;
+        });
+    }
+}
+
+/// Zustellt Alerts an ALERT_WEBHOOK_URL (Slack-kompatibles `text`) und loggt
+/// sie immer als `ALERT:`. Erstes Auftreten und Recovery gehen immer raus,
+/// Wiederholungen nur als Zusammenfassung mit Anzahl.
+pub struct AlertSink {
+    webhook: Option<WebhookDelivery>,
+    dedup_window: Duration,
+    max_per_minute: u32,
+    clock: Arc<dyn Clock>,
+    state: Mutex<AlertState>,
+}
+
+impl AlertSink {
+    pub fn new(webhook_url: Option<String>, dedup_window: Duration, max_per_minute: u32) -> Self {
+        Self {
+            webhook: webhook_url.map(WebhookDelivery::new),
+            dedup_window,
+            max_per_minute,
+            clock: Arc::new(SystemClock),
+            state: Mutex::new(AlertState::default()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.alert_webhook_url.clone(),
+            Duration::from_secs(config.alert_dedup_window_secs),
+            config.alert_max_per_minute,
+        )
+    }
+
+    /// Zeitquelle ersetzen (Tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Alert für `key` melden; Wiederholungen im Fenster werden nur gezählt
+    pub async fn alert(&self, key: &str, message: &str) {
+        tracing::error!("ALERT: {}", message);
+
+        let now = self.clock.now_millis();
+        let text = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let window_ms = self.dedup_window.as_millis() as i64;
+            match state.incidents.get(key).map(|incident| now - incident.window_start < window_ms) {
+                // Erstes Auftreten seit der letzten Recovery: immer zustellen
+                None => {
+                    state.take_slot(now, self.max_per_minute);
+                    state.incidents.insert(
+                        key.to_string(),
+                        Incident {
+                            message: message.to_string(),
+                            window_start: now,
+                            suppressed: 0,
+                        },
+                    );
+                    Some(message.to_string())
+                }
+                Some(true) => {
+                    if let Some(incident) = state.incidents.get_mut(key) {
+                        incident.suppressed += 1;
+                    }
+                    None
+                }
+                // Fenster abgelaufen ohne Flush: neues Fenster, Anzahl anhängen
+                Some(false) => {
+                    let allowed = state.take_slot(now, self.max_per_minute);
+                    let incident = state.incidents.get_mut(key).expect("incident checked above");
+                    incident.message = message.to_string();
+                    if allowed {
+                        let text = repeated_text(message, incident.suppressed + 1, self.dedup_window);
+                        incident.window_start = now;
+                        incident.suppressed = 0;
+                        Some(text)
+                    } else {
+                        incident.suppressed += 1;
+                        None
+                    }
+                }
+            }
+        };
+
+        if let Some(text) = text {
+            self.deliver(key, &text).await;
+        }
+    }
+
+    /// Vorfall beenden; die Recovery-Meldung geht immer raus (auch über dem Rate-Limit)
+    pub async fn resolve(&self, key: &str, message: &str) {
+        let incident = self
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .incidents
+            .remove(key);
+        let Some(incident) = incident else {
+            tracing::info!("{}", message);
+            return;
+        };
+
+        tracing::error!("ALERT: {}", message);
+        let text = match incident.suppressed {
+            0 => message.to_string(),
+            n => format!("{} ({} further alert(s) suppressed)", message, n),
+        };
+        self.deliver(key, &text).await;
+    }
+
+    /// Zusammenfassungen für abgelaufene Fenster mit unterdrückten Wiederholungen zustellen
+    pub async fn flush(&self) {
+        let now = self.clock.now_millis();
+        let summaries: Vec<(String, String)> = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let window_ms = self.dedup_window.as_millis() as i64;
+            let due: Vec<String> = state
+                .incidents
+                .iter()
+                .filter(|(_, incident)| incident.suppressed > 0 && now - incident.window_start >= window_ms)
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            let mut summaries = Vec::new();
+            for key in due {
+                if !state.take_slot(now, self.max_per_minute) {
+                    break;
+                }
+                if let Some(incident) = state.incidents.get_mut(&key) {
+                    summaries.push((key, repeated_text(&incident.message, incident.suppressed, self.dedup_window)));
+                    incident.window_start = now;
+                    incident.suppressed = 0;
+                }
+            }
+            summaries
+        };
+
+        for (key, text) in summaries {
+            self.deliver(&key, &text).await;
+        }
+    }
+
+    /// Periodisch flushen (Hintergrund-Task)
+    pub async fn run_flush(self: Arc<Self>) {
+        let period = (self.dedup_window / 4).max(Duration::from_secs(1));
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            self.flush().await;
+        }
+    }
+
+    async fn deliver(&self, key: &str, text: &str) {
+        if let Some(webhook) = &self.webhook {
+            webhook.send(&json!({ "text": text, "alert": key })).await;
+        }
+    }
+}
+
+fn repeated_text(message: &str, count: u32, window: Duration) -> String {
+    format!("{} (repeated {} time(s) in the last {}s)", message, count, window.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_server;
+    use crate::utils::clock::MockClock;
+    use axum::{routing::post, Json, Router};
+
+    #[tokio::test]
+    async fn test_identical_alerts_are_collapsed() {
+        let delivered = Arc::new(Mutex::new(Vec::<String>::new()));
+        let router = Router::new().route(
+            "/hook",
+            post({
+                let delivered = delivered.clone();
+                move |Json(body): Json<serde_json::Value>| async move {
+                    delivered.lock().unwrap().push(body["text"].as_str().unwrap_or_default().to_string());
+                }
+            }),
+        );
+        let url = format!("{}/hook", spawn_server(router).await);
+        let clock = Arc::new(MockClock::from_millis(0));
+        let sink = AlertSink::new(Some(url), Duration::from_secs(60), 5).with_clock(clock.clone());
+
+        for _ in 0..10 {
+            sink.alert("mexc_down", "MEXC unreachable").await;
+            clock.advance(Duration::from_secs(1));
+        }
+        sink.flush().await;
+        assert_eq!(*delivered.lock().unwrap(), vec!["MEXC unreachable".to_string()]);
+
+        clock.advance(Duration::from_secs(60));
+        sink.flush().await;
+        sink.flush().await;
+        {
+            let delivered = delivered.lock().unwrap();
+            assert_eq!(delivered.len(), 2);
+            assert_eq!(delivered[1], "MEXC unreachable (repeated 9 time(s) in the last 60s)");
+        }
+
+        sink.alert("mexc_down", "MEXC unreachable").await;
+        sink.resolve("mexc_down", "MEXC recovered").await;
+        let delivered = delivered.lock().unwrap();
+        assert_eq!(delivered.len(), 3);
+        assert_eq!(delivered[2], "MEXC recovered (1 further alert(s) suppressed)");
+    }
+}
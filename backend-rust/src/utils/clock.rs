@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use std::sync::Mutex;
+#[cfg(test)]
+use std::time::Duration;
+
+/// Zeitquelle; in Tests durch `MockClock` ersetzbar
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Aktuelle Zeit als Unix timestamp in Millisekunden
+    fn now_millis(&self) -> i64 {
+        self.now().timestamp_millis()
+    }
+}
+
+/// Echte Systemzeit
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manuell gesteuerte Zeit für deterministische Tests (nur im Test-Build)
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Starte bei einem Unix timestamp in Millisekunden
+    pub fn from_millis(millis: i64) -> Self {
+        Self::new(DateTime::from_timestamp_millis(millis).expect("timestamp out of range"))
+    }
+
+    /// Zeit um `duration` vorstellen
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += chrono::Duration::from_std(duration).expect("duration out of range");
+    }
+
+    /// Zeit auf einen festen Zeitpunkt setzen
+    pub fn set(&self, at: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = at;
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advances() {
+        let clock = MockClock::from_millis(1_700_000_000_000);
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now_millis(), 1_700_000_090_000);
+    }
+}
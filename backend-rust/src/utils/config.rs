@@ -1,23 +1,316 @@
 use aws_config::BehaviorVersion;
+use aws_sdk_ssm::error::{DisplayErrorContext, SdkError};
+use aws_sdk_ssm::operation::get_parameter::{GetParameterError, GetParameterOutput};
 use aws_sdk_ssm::Client as SsmClient;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::mexc::{Credentials, SigningVersion};
+use crate::storage::WritePolicy;
+use crate::trading::{RelistingAction, RoundingMode};
+use crate::utils::{RuntimeConfig, StartupRetry};
 
 /// Hauptkonfiguration für Rust Backend
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub mexc_api_key: String,
     pub mexc_secret_key: String,
     pub mexc_base_url: String,
     pub aws_region: String,
     pub dynamodb_table: String,
+    /// Region der replizierten Tabelle für Read-Failover (optional)
+    pub dynamodb_failover_region: Option<String>,
+    /// Writes bei Failover-Region: primary, failover oder dual
+    pub dynamodb_write_policy: WritePolicy,
     pub rust_api_port: u16,
     pub jwt_secret: Option<String>,
     pub clerk_secret_key: Option<String>,
     pub supabase_url: Option<String>,
     pub supabase_service_role_key: Option<String>,
     pub openai_api_key: Option<String>,
+    /// Bearer Token für /api/admin Routen mit Auth
+    pub admin_api_token: Option<String>,
+    /// HMAC-Secret des Listing-Webhooks (ohne Secret werden Deliveries abgelehnt)
+    pub listing_webhook_secret: Option<String>,
+    /// AES-256 Key (64 Hex-Zeichen) für gespeicherte User-Credentials
+    pub credentials_encryption_key: Option<String>,
+    /// Benannte MEXC-Konten (Name -> Credentials), z.B. für A/B-Strategien
+    #[serde(skip)]
+    pub mexc_accounts: BTreeMap<String, Credentials>,
+    /// User, der ohne eigene Keys den globalen MEXC Key nutzen darf
+    /// (nicht gesetzt = nur User mit eigenen Keys können handeln)
+    pub mexc_admin_user_id: Option<String>,
+    /// Symbole die nie gesnipet werden (exakt oder Prefix mit `*`)
+    pub symbol_blacklist: Vec<String>,
+    /// Intervall (Sekunden) für das Nachladen der Blacklist aus DynamoDB (0 = nur beim Start)
+    pub blacklist_reload_secs: u64,
+    /// Anteil der freien Quote-Balance pro Trade in Prozent
+    pub risk_per_trade_pct: f64,
+    /// Quote-Asset für Position Sizing (z.B. USDT)
+    pub quote_asset: String,
+    /// Schrittweite für Order-Mengen
+    pub qty_step_size: f64,
+    /// Nachkommastellen der Menge, solange exchangeInfo für ein Symbol fehlt
+    pub default_qty_precision: u32,
+    /// Nachkommastellen des Preises, solange exchangeInfo für ein Symbol fehlt
+    pub default_price_precision: u32,
+    /// Intervall (Sekunden) für den Refresh des exchangeInfo-Caches (0 = aus)
+    pub exchange_info_refresh_secs: u64,
+    /// Rundung der Order-Menge (down, nearest, up)
+    pub qty_rounding_mode: RoundingMode,
+    /// Fester Sizing-Kapitalstock in Quote (0 = immer die freie Balance)
+    pub sizing_base_capital: f64,
+    /// Gewinne fließen in die Positionsgröße ein (Basis = freie Balance statt Kapitalstock)
+    pub sizing_compounding: bool,
+    /// Obergrenze der Compounding-Basis als Vielfaches des Kapitalstocks (0 = unbegrenzt)
+    pub sizing_compounding_max_multiple: f64,
+    /// Max. offene Orders je User und Symbol (0 = unbegrenzt)
+    pub max_open_orders_per_symbol: u32,
+    /// Requests pro Sekunde je User/IP auf /api/trade (0 = kein Limit)
+    pub api_rate_limit_rps: f64,
+    /// Kurzfristiger Burst über dem Rate Limit
+    pub api_rate_limit_burst: u32,
+    /// Orders mit höherem Notional brauchen eine Bestätigung (0 = aus)
+    pub large_order_confirm_notional: f64,
+    /// Gültigkeit eines Bestätigungs-Tokens in Sekunden
+    pub large_order_confirm_ttl_secs: u64,
+    /// Freies Quote-Guthaben vor jeder Order prüfen (aus = Ultra-Low-Latency)
+    pub pretrade_balance_check: bool,
+    /// Geschätzte Handelsgebühr in Prozent für den Pre-Trade-Check
+    pub trading_fee_pct: f64,
+    /// Cache-Dauer des Guthabens für den Pre-Trade-Check
+    pub balance_cache_ms: u64,
+    /// Mindest-Confidence für automatische Snipes
+    pub min_snipe_confidence: f64,
+    /// Mindest-Confidence je Pattern-Typ (z.B. `sts:2` -> 0.6), sonst `min_snipe_confidence`
+    pub min_snipe_confidence_by_pattern: BTreeMap<String, f64>,
+    /// Anzahl gehaltener Gate-Entscheidungen (`trading.decision`-Log, 0 = aus)
+    pub decision_log_size: usize,
+    /// Max. Snipe-Versuche pro Event (1 = kein Retry)
+    pub snipe_max_attempts: u32,
+    /// Wartezeit vor dem ersten Retry in ms (verdoppelt sich pro Versuch)
+    pub snipe_retry_backoff_ms: u64,
+    /// Retries nur bis launch_time + Fenster (Sekunden)
+    pub snipe_retry_window_secs: u64,
+    /// Max. Slippage des Entry-Fills ggü. Schätzpreis in Prozent, darüber Abbruch
+    pub max_entry_slippage_pct: f64,
+    /// Position bei zu hoher Entry-Slippage sofort wieder schließen
+    pub unwind_on_slippage: bool,
+    /// Order-Book-Level für den Imbalance-Check vor Snipes (0 = aus)
+    pub snipe_imbalance_levels: usize,
+    /// Mindest-Imbalance (Kaufdruck, -1..1) damit ein Snipe feuert
+    pub snipe_min_book_imbalance: f64,
+    /// Max. Bid/Ask-Spread in Prozent vor einem Snipe (0 = aus); einseitiges Buch gilt als unendlich
+    pub snipe_max_spread_pct: f64,
+    /// Zu breiter Spread: Snipe für manuelle Bestätigung zurückhalten statt verwerfen
+    pub snipe_spread_confirm: bool,
+    /// Mindest-24h-Quote-Volumen für Snipes (0 = aus)
+    pub snipe_min_quote_volume: f64,
+    /// Neue Listings ohne 24h-Historie trotz Volumen-Gate snipen
+    pub snipe_allow_new_listings: bool,
+    /// Entry-Fallback-Kette für Snipes (fok, ioc, limit, market), in Reihenfolge
+    pub snipe_entry_tiers: Vec<String>,
+    /// Limit der FOK/IOC/LIMIT-Stufen in Prozent über (Buy) bzw. unter (Sell) dem Schätzpreis
+    pub snipe_entry_limit_offset_pct: f64,
+    /// Gesamtbudget (ms) über alle Entry-Stufen
+    pub snipe_entry_deadline_ms: u64,
+    /// MARKET abgelehnt (neues Listing): IOC-Limit N% über dem besten Ask bzw. unter dem besten Bid (0 = aus)
+    pub snipe_market_fallback_pct: f64,
+    /// Leeres Orderbuch zum Launch: alle N ms erneut prüfen bis zur Entry-Deadline (0 = aus)
+    pub snipe_book_wait_poll_ms: u64,
+    /// Mindestanzahl Level auf der Gegenseite (Asks für Buy, Bids für Sell) bevor gefeuert wird
+    pub snipe_book_min_levels: usize,
+    /// Ungefüllte Limit-Snipes (Entry-Stufe `limit`) ab diesem Alter (s) Richtung Buch nachziehen (0 = aus)
+    pub snipe_chase_after_secs: u64,
+    /// Schritt je Nachziehen in Prozent des aktuellen Limits
+    pub snipe_chase_step_pct: f64,
+    /// Max. Abstand zum ursprünglichen Limit in Prozent, danach Storno
+    pub snipe_chase_max_pct: f64,
+    /// Entry vor dem Feuern per /api/v3/order/test prüfen (kostet einen Roundtrip)
+    pub snipe_validate_before_fire: bool,
+    /// Ab diesem Notional (Menge * Schätzpreis) geht eine Canary-Order voraus (0 = aus)
+    pub snipe_canary_min_notional: f64,
+    /// Größe der Canary als Anteil der Snipe-Menge
+    pub snipe_canary_fraction: f64,
+    /// Max. Slippage (%) der Canary, darüber wird der Haupt-Entry abgebrochen
+    pub snipe_canary_max_slippage_pct: f64,
+    /// Symbol schon vor mindestens N Stunden gesehen -> Re-Listing (0 = aus)
+    pub relisting_min_age_hours: u64,
+    /// Re-Listing: Confidence senken (downgrade) oder manuelle Bestätigung verlangen (confirm)
+    pub relisting_action: RelistingAction,
+    /// Confidence-Abzug bei `downgrade`
+    pub relisting_confidence_penalty: f64,
+    /// Max. gleichzeitige Market-Closes beim Glattstellen (`/api/admin/flatten`)
+    pub flatten_concurrency: usize,
+    /// Poll-Intervall (ms) beim Warten auf Handelsstatus TRADING vor Snipes (0 = aus)
+    pub snipe_trading_state_poll_ms: u64,
+    /// Max. Wartezeit (ms) auf TRADING, danach greift die Retry-Policy
+    pub snipe_trading_state_max_wait_ms: u64,
+    /// Mindestabstand (ms) zwischen zwei Order-Platzierungen je Symbol (0 = aus)
+    pub order_symbol_cooldown_ms: u64,
+    /// Orders auf pausierte Symbole (exchangeInfo) ablehnen
+    pub order_symbol_state_check: bool,
+    /// Paper-Trading: API-Orders nicht an MEXC senden, sondern simulieren
+    /// (Market sofort zum Ticker, Limit je nach `paper_simulate_fills`)
+    pub paper_trading: bool,
+    /// Paper-Limit-Orders erst füllen, wenn der Preis das Limit kreuzt
+    /// (false = sofort zum Limitpreis)
+    pub paper_simulate_fills: bool,
+    /// Batch-Endpoints antworten mit succeeded/failed je Item (false = alte flache Liste)
+    pub batch_results_structured: bool,
+    /// Rohe MEXC-Order-Responses für Audits speichern (kostet Speicher)
+    pub raw_response_audit: bool,
+    /// Aufbewahrung der Roh-Responses in Tagen (DynamoDB TTL)
+    pub raw_response_ttl_days: u64,
+    /// Terminale Orders bleiben so lange (s) im In-Memory-Index
+    pub order_index_grace_secs: u64,
+    /// Gewinn (%) ab dem der Stop auf Break-even gezogen wird (0 = aus)
+    pub stop_break_even_activation_pct: f64,
+    /// Gebühren (%) über Entry für den Break-even-Stop
+    pub stop_break_even_fee_pct: f64,
+    /// Trailing-Abstand (%) nach Break-even (0 = kein Trailing)
+    pub stop_trailing_pct: f64,
+    /// Gewinn (%) über dem Entry, ab dem nachgekauft wird (0 = kein Pyramiding)
+    pub pyramid_trigger_pct: f64,
+    /// Nachkauf als Anteil der aktuellen Positionsmenge
+    pub pyramid_add_fraction: f64,
+    /// Max. Nachkäufe pro Position
+    pub pyramid_max_add_ons: u32,
+    /// Obergrenze des Positionswerts in Quote inkl. Nachkäufen (0 = unbegrenzt)
+    pub pyramid_max_notional: f64,
+    /// Orders im Hintergrund schreiben statt synchron im Order-Pfad (weniger Latenz, weniger Haltbarkeit)
+    pub order_write_behind: bool,
+    /// Ab so vielen ungeschriebenen Orders werden neue Orders abgelehnt
+    pub order_write_behind_max_backlog: usize,
+    /// Flush-Intervall des Write-behind-Puffers in ms
+    pub order_write_behind_flush_ms: u64,
+    /// Fehlgeschlagene Schreibversuche je Order, danach Dead Letter statt Blockade des Puffers
+    pub order_write_behind_max_attempts: u32,
+    /// Neue Calendar Events beim Einlesen per BatchWriteItem statt einzeln schreiben
+    pub calendar_batch_writes: bool,
+    /// Launch-Scheduler lädt nur Events der nächsten N Stunden
+    pub scheduler_lookahead_hours: u64,
+    /// Intervall (s), in dem der Scheduler das Fenster neu lädt
+    pub scheduler_reload_secs: u64,
+    /// User, deren geplante Launches automatisch gesnipet werden (leer = Scheduler aus)
+    pub scheduler_user_ids: Vec<String>,
+    /// Webhook für Alerts (None = nur Log)
+    pub alert_webhook_url: Option<String>,
+    /// Identische Alerts innerhalb dieses Fensters (Sekunden) zusammenfassen
+    pub alert_dedup_window_secs: u64,
+    /// Max. zugestellte Alerts pro Minute (0 = unbegrenzt)
+    pub alert_max_per_minute: u32,
+    /// Webhook für Positions-Events (Open/Stop-Update/Close/Liquidation, None = aus)
+    pub position_webhook_url: Option<String>,
+    /// Retries je Positions-Event bei fehlgeschlagener Zustellung
+    pub position_webhook_retries: u32,
+    /// Label für Positionen ohne Strategie im PnL-by-Strategy Report
+    pub pnl_default_strategy: String,
+    /// Max. gleichzeitige MEXC Order-/Account-Requests
+    pub mexc_order_concurrency: usize,
+    /// Max. gleichzeitige MEXC Market-Data-Requests
+    pub mexc_market_concurrency: usize,
+    /// Worker der priorisierten MEXC Request-Queue (0 = keine Queue)
+    pub mexc_queue_workers: usize,
+    /// Cache-Dauer des MEXC-Probes im Status-Endpunkt (Sekunden, 0 = aus)
+    pub status_health_cache_secs: u64,
+    /// Ab diesem Offset zur MEXC-Serverzeit (ms) meldet der Status `time_sync.healthy = false`
+    pub status_time_offset_warn_ms: i64,
+    /// Bei MEXC-Ausfall Ticker/Portfolio mit zuletzt bekanntem Preis bis zu diesem Alter (s) bedienen (0 = aus)
+    pub stale_price_fallback_secs: u64,
+    /// `?refresh=true` auf Positionen holt Preise nur, wenn `updated_at` älter ist (s)
+    pub position_refresh_staleness_secs: u64,
+    /// Status-Endpunkt prüft zusätzlich den signierten MEXC Write-Path
+    pub mexc_write_health_check: bool,
+    /// Detailliertes Request/Response-Logging der MEXC Calls (Debug-Level)
+    pub mexc_debug_log: bool,
+    /// Versuche je MEXC-Request bei Verbindungsfehlern und 429/500/502/503 (1 = kein Retry);
+    /// nur GETs und Orders mit newClientOrderId werden wiederholt
+    pub mexc_retry_max_attempts: u32,
+    /// Backoff vor dem ersten Retry (ms), verdoppelt sich pro Versuch; Retry-After hat Vorrang
+    pub mexc_retry_base_delay_ms: u64,
+    /// Längste Wartezeit vor einem Retry (ms), deckelt auch Retry-After
+    pub mexc_retry_max_delay_ms: u64,
+    /// Anzahl der letzten MEXC-Fehler für `/api/admin/errors/recent` (0 = aus)
+    pub mexc_error_log_size: usize,
+    /// Signatur-Variante für signierte MEXC Requests (v1, v2)
+    pub mexc_signing_version: SigningVersion,
+    /// Doppelte newClientOrderId: bestehende Order nachschlagen statt Fehler
+    pub mexc_idempotent_create: bool,
+    /// MEXC-Wartung erkennen und Hintergrund-Polling pausieren
+    pub mexc_maintenance_detection: bool,
+    /// Request-Weight-Limit pro Minute für die Restanzeige (0 = unbekannt)
+    pub mexc_weight_limit: u32,
+    /// Intervall der Recovery-Probes während einer Wartung (Sekunden)
+    pub mexc_maintenance_probe_secs: u64,
+    /// Egress-Region beim Start gegen die Sperrliste prüfen
+    pub geo_check_enabled: bool,
+    /// Start abbrechen wenn die Region gesperrt ist (sonst nur Log)
+    pub geo_check_fatal: bool,
+    /// ipinfo-kompatibler Endpunkt, liefert `{ "ip", "country" }`
+    pub geo_lookup_url: String,
+    /// Von MEXC gesperrte Länder (ISO 3166-1 alpha-2)
+    pub geo_restricted_countries: Vec<String>,
+    /// Poll-Intervall des Order Monitors in Sekunden
+    pub order_monitor_interval_secs: u64,
+    /// Symbole, deren MEXC-Orders beim Start mit DynamoDB abgeglichen werden (leer = aus)
+    pub order_recovery_symbols: Vec<String>,
+    /// Anzahl Status-Polls nach einer Order bis zum Fill (0 = aus)
+    pub fill_confirm_polls: u32,
+    /// Abstand der Fill-Polls in ms
+    pub fill_confirm_interval_ms: u64,
+    /// Maximale Wartezeit beim Shutdown für laufende Hintergrund-Tasks
+    pub shutdown_grace_secs: u64,
+    /// Max. Alter (bzw. Uhrabweichung) einer Webhook-Delivery in Sekunden
+    pub webhook_max_age_secs: u64,
+}
+
+/// Herkunft eines Werts im effektiven Config-Snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    Env,
+    Ssm,
+    Default,
+    /// Zur Laufzeit per `/api/admin/config/reload` geändert
+    Reloaded,
+}
+
+/// Ein Eintrag im effektiven Config-Snapshot
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveSetting {
+    pub value: serde_json::Value,
+    pub source: ConfigSource,
 }
 
+/// Werte, die im Snapshot nur als "***" erscheinen
+const SECRET_FIELDS: &[&str] = &[
+    "mexc_api_key",
+    "mexc_secret_key",
+    "jwt_secret",
+    "clerk_secret_key",
+    "supabase_service_role_key",
+    "openai_api_key",
+    "admin_api_token",
+    "listing_webhook_secret",
+    "credentials_encryption_key",
+];
+
+/// Werte, die im SSM-Modus aus dem Parameter Store kommen (siehe `from_ssm`)
+const SSM_FIELDS: &[&str] = &[
+    "mexc_api_key",
+    "mexc_secret_key",
+    "jwt_secret",
+    "clerk_secret_key",
+    "supabase_url",
+    "supabase_service_role_key",
+    "openai_api_key",
+    "admin_api_token",
+    "listing_webhook_secret",
+    "credentials_encryption_key",
+    "mexc_accounts",
+];
+
 impl Config {
     /// Lade Config aus Environment Variablen (Fallback wenn SSM deaktiviert)
     pub fn from_env() -> Self {
@@ -28,21 +321,218 @@ impl Config {
                 .expect("MEXC_API_KEY nicht gesetzt"),
             mexc_secret_key: std::env::var("MEXC_SECRET_KEY")
                 .expect("MEXC_SECRET_KEY nicht gesetzt"),
+            jwt_secret: std::env::var("JWT_SECRET").ok(),
+            clerk_secret_key: std::env::var("CLERK_SECRET_KEY").ok(),
+            supabase_url: std::env::var("SUPABASE_URL").ok(),
+            supabase_service_role_key: std::env::var("SUPABASE_SERVICE_ROLE_KEY").ok(),
+            openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
+            admin_api_token: std::env::var("ADMIN_API_TOKEN").ok(),
+            listing_webhook_secret: std::env::var("LISTING_WEBHOOK_SECRET").ok(),
+            credentials_encryption_key: std::env::var("CREDENTIALS_ENCRYPTION_KEY").ok(),
+            mexc_accounts: accounts_from_env(),
+            ..Self::settings_from_env()
+        }
+    }
+
+    /// Nicht-geheime Einstellungen aus Env (gemeinsam für Env- und SSM-Modus)
+    pub(crate) fn settings_from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
             mexc_base_url: std::env::var("MEXC_BASE_URL")
-                .unwrap_or_else(|_| "https://api.mexc.com".to_string()),
+                .unwrap_or(defaults.mexc_base_url),
             aws_region: std::env::var("AWS_REGION")
-                .unwrap_or_else(|_| "ap-southeast-1".to_string()),
+                .unwrap_or(defaults.aws_region),
             dynamodb_table: std::env::var("DYNAMODB_TABLE")
-                .unwrap_or_else(|_| "mexc_trading_data".to_string()),
+                .unwrap_or(defaults.dynamodb_table),
+            dynamodb_failover_region: std::env::var("DYNAMODB_FAILOVER_REGION")
+                .ok()
+                .filter(|r| !r.trim().is_empty()),
+            dynamodb_write_policy: env_or("DYNAMODB_WRITE_POLICY", defaults.dynamodb_write_policy),
             rust_api_port: std::env::var("RUST_API_PORT")
                 .unwrap_or_else(|_| "8080".to_string())
                 .parse()
                 .expect("RUST_API_PORT muss eine Zahl sein"),
-            jwt_secret: std::env::var("JWT_SECRET").ok(),
-            clerk_secret_key: std::env::var("CLERK_SECRET_KEY").ok(),
-            supabase_url: std::env::var("SUPABASE_URL").ok(),
-            supabase_service_role_key: std::env::var("SUPABASE_SERVICE_ROLE_KEY").ok(),
-            openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
+            symbol_blacklist: env_list("SYMBOL_BLACKLIST"),
+            blacklist_reload_secs: env_or("BLACKLIST_RELOAD_SECS", defaults.blacklist_reload_secs),
+            risk_per_trade_pct: env_or("RISK_PER_TRADE_PCT", defaults.risk_per_trade_pct),
+            quote_asset: std::env::var("QUOTE_ASSET").unwrap_or(defaults.quote_asset),
+            qty_step_size: env_or("QTY_STEP_SIZE", defaults.qty_step_size),
+            default_qty_precision: env_or("DEFAULT_QTY_PRECISION", defaults.default_qty_precision),
+            default_price_precision: env_or("DEFAULT_PRICE_PRECISION", defaults.default_price_precision),
+            exchange_info_refresh_secs: env_or("EXCHANGE_INFO_REFRESH_SECS", defaults.exchange_info_refresh_secs),
+            qty_rounding_mode: env_or("QTY_ROUNDING_MODE", defaults.qty_rounding_mode),
+            sizing_base_capital: env_or("SIZING_BASE_CAPITAL", defaults.sizing_base_capital),
+            sizing_compounding: env_or("SIZING_COMPOUNDING", defaults.sizing_compounding),
+            sizing_compounding_max_multiple: env_or(
+                "SIZING_COMPOUNDING_MAX_MULTIPLE",
+                defaults.sizing_compounding_max_multiple,
+            ),
+            mexc_admin_user_id: std::env::var("MEXC_ADMIN_USER_ID").ok().filter(|v| !v.is_empty()),
+            max_open_orders_per_symbol: env_or(
+                "MAX_OPEN_ORDERS_PER_SYMBOL",
+                defaults.max_open_orders_per_symbol,
+            ),
+            api_rate_limit_rps: env_or("API_RATE_LIMIT_RPS", defaults.api_rate_limit_rps),
+            api_rate_limit_burst: env_or("API_RATE_LIMIT_BURST", defaults.api_rate_limit_burst),
+            large_order_confirm_notional: env_or(
+                "LARGE_ORDER_CONFIRM_NOTIONAL",
+                defaults.large_order_confirm_notional,
+            ),
+            large_order_confirm_ttl_secs: env_or(
+                "LARGE_ORDER_CONFIRM_TTL_SECS",
+                defaults.large_order_confirm_ttl_secs,
+            ),
+            pretrade_balance_check: env_or("PRETRADE_BALANCE_CHECK", defaults.pretrade_balance_check),
+            trading_fee_pct: env_or("TRADING_FEE_PCT", defaults.trading_fee_pct),
+            balance_cache_ms: env_or("BALANCE_CACHE_MS", defaults.balance_cache_ms),
+            min_snipe_confidence: env_or("MIN_SNIPE_CONFIDENCE", defaults.min_snipe_confidence),
+            min_snipe_confidence_by_pattern: env_map("MIN_SNIPE_CONFIDENCE_BY_PATTERN"),
+            decision_log_size: env_or("DECISION_LOG_SIZE", defaults.decision_log_size),
+            snipe_max_attempts: env_or("SNIPE_MAX_ATTEMPTS", defaults.snipe_max_attempts),
+            snipe_retry_backoff_ms: env_or("SNIPE_RETRY_BACKOFF_MS", defaults.snipe_retry_backoff_ms),
+            snipe_retry_window_secs: env_or("SNIPE_RETRY_WINDOW_SECS", defaults.snipe_retry_window_secs),
+            max_entry_slippage_pct: env_or("MAX_ENTRY_SLIPPAGE_PCT", defaults.max_entry_slippage_pct),
+            unwind_on_slippage: env_or("UNWIND_ON_SLIPPAGE", defaults.unwind_on_slippage),
+            snipe_imbalance_levels: env_or("SNIPE_IMBALANCE_LEVELS", defaults.snipe_imbalance_levels),
+            snipe_min_book_imbalance: env_or(
+                "SNIPE_MIN_BOOK_IMBALANCE",
+                defaults.snipe_min_book_imbalance,
+            ),
+            snipe_max_spread_pct: env_or("SNIPE_MAX_SPREAD_PCT", defaults.snipe_max_spread_pct),
+            snipe_spread_confirm: env_or("SNIPE_SPREAD_CONFIRM", defaults.snipe_spread_confirm),
+            snipe_min_quote_volume: env_or("SNIPE_MIN_QUOTE_VOLUME", defaults.snipe_min_quote_volume),
+            snipe_allow_new_listings: env_or("SNIPE_ALLOW_NEW_LISTINGS", defaults.snipe_allow_new_listings),
+            snipe_entry_tiers: Some(env_list("SNIPE_ENTRY_TIERS"))
+                .filter(|tiers| !tiers.is_empty())
+                .unwrap_or(defaults.snipe_entry_tiers),
+            snipe_entry_limit_offset_pct: env_or(
+                "SNIPE_ENTRY_LIMIT_OFFSET_PCT",
+                defaults.snipe_entry_limit_offset_pct,
+            ),
+            snipe_entry_deadline_ms: env_or("SNIPE_ENTRY_DEADLINE_MS", defaults.snipe_entry_deadline_ms),
+            snipe_market_fallback_pct: env_or("SNIPE_MARKET_FALLBACK_PCT", defaults.snipe_market_fallback_pct),
+            snipe_book_wait_poll_ms: env_or("SNIPE_BOOK_WAIT_POLL_MS", defaults.snipe_book_wait_poll_ms),
+            snipe_book_min_levels: env_or("SNIPE_BOOK_MIN_LEVELS", defaults.snipe_book_min_levels),
+            snipe_chase_after_secs: env_or("SNIPE_CHASE_AFTER_SECS", defaults.snipe_chase_after_secs),
+            snipe_chase_step_pct: env_or("SNIPE_CHASE_STEP_PCT", defaults.snipe_chase_step_pct),
+            snipe_chase_max_pct: env_or("SNIPE_CHASE_MAX_PCT", defaults.snipe_chase_max_pct),
+            snipe_validate_before_fire: env_or("SNIPE_VALIDATE_BEFORE_FIRE", defaults.snipe_validate_before_fire),
+            snipe_canary_min_notional: env_or("SNIPE_CANARY_MIN_NOTIONAL", defaults.snipe_canary_min_notional),
+            snipe_canary_fraction: env_or("SNIPE_CANARY_FRACTION", defaults.snipe_canary_fraction),
+            snipe_canary_max_slippage_pct: env_or(
+                "SNIPE_CANARY_MAX_SLIPPAGE_PCT",
+                defaults.snipe_canary_max_slippage_pct,
+            ),
+            relisting_min_age_hours: env_or("RELISTING_MIN_AGE_HOURS", defaults.relisting_min_age_hours),
+            relisting_action: env_or("RELISTING_ACTION", defaults.relisting_action),
+            relisting_confidence_penalty: env_or(
+                "RELISTING_CONFIDENCE_PENALTY",
+                defaults.relisting_confidence_penalty,
+            ),
+            flatten_concurrency: env_or("FLATTEN_CONCURRENCY", defaults.flatten_concurrency),
+            snipe_trading_state_poll_ms: env_or(
+                "SNIPE_TRADING_STATE_POLL_MS",
+                defaults.snipe_trading_state_poll_ms,
+            ),
+            snipe_trading_state_max_wait_ms: env_or(
+                "SNIPE_TRADING_STATE_MAX_WAIT_MS",
+                defaults.snipe_trading_state_max_wait_ms,
+            ),
+            order_symbol_cooldown_ms: env_or("ORDER_SYMBOL_COOLDOWN_MS", defaults.order_symbol_cooldown_ms),
+            order_symbol_state_check: env_or("ORDER_SYMBOL_STATE_CHECK", defaults.order_symbol_state_check),
+            paper_trading: env_or("PAPER_TRADING", defaults.paper_trading),
+            paper_simulate_fills: env_or("PAPER_SIMULATE_FILLS", defaults.paper_simulate_fills),
+            batch_results_structured: env_or("BATCH_RESULTS_STRUCTURED", defaults.batch_results_structured),
+            raw_response_audit: env_or("RAW_RESPONSE_AUDIT", defaults.raw_response_audit),
+            raw_response_ttl_days: env_or("RAW_RESPONSE_TTL_DAYS", defaults.raw_response_ttl_days),
+            order_index_grace_secs: env_or("ORDER_INDEX_GRACE_SECS", defaults.order_index_grace_secs),
+            stop_break_even_activation_pct: env_or(
+                "STOP_BREAK_EVEN_ACTIVATION_PCT",
+                defaults.stop_break_even_activation_pct,
+            ),
+            stop_break_even_fee_pct: env_or("STOP_BREAK_EVEN_FEE_PCT", defaults.stop_break_even_fee_pct),
+            stop_trailing_pct: env_or("STOP_TRAILING_PCT", defaults.stop_trailing_pct),
+            pyramid_trigger_pct: env_or("PYRAMID_TRIGGER_PCT", defaults.pyramid_trigger_pct),
+            pyramid_add_fraction: env_or("PYRAMID_ADD_FRACTION", defaults.pyramid_add_fraction),
+            pyramid_max_add_ons: env_or("PYRAMID_MAX_ADD_ONS", defaults.pyramid_max_add_ons),
+            pyramid_max_notional: env_or("PYRAMID_MAX_NOTIONAL", defaults.pyramid_max_notional),
+            order_write_behind: env_or("ORDER_WRITE_BEHIND", defaults.order_write_behind),
+            calendar_batch_writes: env_or("CALENDAR_BATCH_WRITES", defaults.calendar_batch_writes),
+            order_write_behind_max_backlog: env_or(
+                "ORDER_WRITE_BEHIND_MAX_BACKLOG",
+                defaults.order_write_behind_max_backlog,
+            ),
+            order_write_behind_flush_ms: env_or("ORDER_WRITE_BEHIND_FLUSH_MS", defaults.order_write_behind_flush_ms),
+            order_write_behind_max_attempts: env_or(
+                "ORDER_WRITE_BEHIND_MAX_ATTEMPTS",
+                defaults.order_write_behind_max_attempts,
+            ),
+            scheduler_lookahead_hours: env_or("SCHEDULER_LOOKAHEAD_HOURS", defaults.scheduler_lookahead_hours),
+            scheduler_reload_secs: env_or("SCHEDULER_RELOAD_SECS", defaults.scheduler_reload_secs),
+            scheduler_user_ids: env_list("SCHEDULER_USER_IDS"),
+            alert_webhook_url: std::env::var("ALERT_WEBHOOK_URL").ok().filter(|v| !v.is_empty()),
+            alert_dedup_window_secs: env_or("ALERT_DEDUP_WINDOW_SECS", defaults.alert_dedup_window_secs),
+            alert_max_per_minute: env_or("ALERT_MAX_PER_MINUTE", defaults.alert_max_per_minute),
+            position_webhook_url: std::env::var("POSITION_WEBHOOK_URL").ok().filter(|v| !v.is_empty()),
+            position_webhook_retries: env_or("POSITION_WEBHOOK_RETRIES", defaults.position_webhook_retries),
+            pnl_default_strategy: std::env::var("PNL_DEFAULT_STRATEGY")
+                .unwrap_or(defaults.pnl_default_strategy),
+            mexc_order_concurrency: env_or("MEXC_ORDER_CONCURRENCY", defaults.mexc_order_concurrency),
+            mexc_market_concurrency: env_or(
+                "MEXC_MARKET_CONCURRENCY",
+                defaults.mexc_market_concurrency,
+            ),
+            mexc_queue_workers: env_or("MEXC_QUEUE_WORKERS", defaults.mexc_queue_workers),
+            status_health_cache_secs: env_or(
+                "STATUS_HEALTH_CACHE_SECS",
+                defaults.status_health_cache_secs,
+            ),
+            status_time_offset_warn_ms: env_or(
+                "STATUS_TIME_OFFSET_WARN_MS",
+                defaults.status_time_offset_warn_ms,
+            ),
+            stale_price_fallback_secs: env_or("STALE_PRICE_FALLBACK_SECS", defaults.stale_price_fallback_secs),
+            position_refresh_staleness_secs: env_or(
+                "POSITION_REFRESH_STALENESS_SECS",
+                defaults.position_refresh_staleness_secs,
+            ),
+            mexc_write_health_check: env_or(
+                "MEXC_WRITE_HEALTH_CHECK",
+                defaults.mexc_write_health_check,
+            ),
+            mexc_debug_log: env_or("MEXC_DEBUG_LOG", defaults.mexc_debug_log),
+            mexc_retry_max_attempts: env_or("MEXC_RETRY_MAX_ATTEMPTS", defaults.mexc_retry_max_attempts),
+            mexc_retry_base_delay_ms: env_or("MEXC_RETRY_BASE_DELAY_MS", defaults.mexc_retry_base_delay_ms),
+            mexc_retry_max_delay_ms: env_or("MEXC_RETRY_MAX_DELAY_MS", defaults.mexc_retry_max_delay_ms),
+            mexc_error_log_size: env_or("MEXC_ERROR_LOG_SIZE", defaults.mexc_error_log_size),
+            mexc_signing_version: env_or("MEXC_SIGNING_VERSION", defaults.mexc_signing_version),
+            mexc_idempotent_create: env_or("MEXC_IDEMPOTENT_CREATE", defaults.mexc_idempotent_create),
+            mexc_maintenance_detection: env_or(
+                "MEXC_MAINTENANCE_DETECTION",
+                defaults.mexc_maintenance_detection,
+            ),
+            mexc_weight_limit: env_or("MEXC_WEIGHT_LIMIT", defaults.mexc_weight_limit),
+            mexc_maintenance_probe_secs: env_or(
+                "MEXC_MAINTENANCE_PROBE_SECS",
+                defaults.mexc_maintenance_probe_secs,
+            ),
+            geo_check_enabled: env_or("GEO_CHECK_ENABLED", defaults.geo_check_enabled),
+            geo_check_fatal: env_or("GEO_CHECK_FATAL", defaults.geo_check_fatal),
+            geo_lookup_url: std::env::var("GEO_LOOKUP_URL").unwrap_or(defaults.geo_lookup_url),
+            geo_restricted_countries: std::env::var("GEO_RESTRICTED_COUNTRIES")
+                .map(|_| env_list("GEO_RESTRICTED_COUNTRIES"))
+                .unwrap_or(defaults.geo_restricted_countries),
+            order_monitor_interval_secs: env_or(
+                "ORDER_MONITOR_INTERVAL_SECS",
+                defaults.order_monitor_interval_secs,
+            ),
+            order_recovery_symbols: env_list("ORDER_RECOVERY_SYMBOLS"),
+            fill_confirm_polls: env_or("FILL_CONFIRM_POLLS", defaults.fill_confirm_polls),
+            fill_confirm_interval_ms: env_or("FILL_CONFIRM_INTERVAL_MS", defaults.fill_confirm_interval_ms),
+            shutdown_grace_secs: env_or("SHUTDOWN_GRACE_SECS", defaults.shutdown_grace_secs),
+            webhook_max_age_secs: env_or("WEBHOOK_MAX_AGE_SECS", defaults.webhook_max_age_secs),
+            ..defaults
         }
     }
 
@@ -57,6 +547,10 @@ impl Config {
     ///   {prefix}/supabase/service-role-key
     ///   {prefix}/openai/api-key
     ///   {prefix}/jwt-secret (optional)
+    ///   {prefix}/admin-token (optional)
+    ///   {prefix}/listing-webhook-secret (optional)
+    ///   {prefix}/credentials-encryption-key (optional)
+    ///   {prefix}/mexc/accounts/{name}/api-key, .../secret-key (je Name aus MEXC_ACCOUNTS)
     pub async fn from_ssm() -> Self {
         dotenvy::dotenv().ok();
 
@@ -73,35 +567,40 @@ impl Config {
         let supabase_service_role_key = fetch_ssm_param_opt(&ssm, &format!("{}/supabase/service-role-key", prefix)).await;
         let openai_api_key = fetch_ssm_param_opt(&ssm, &format!("{}/openai/api-key", prefix)).await;
         let jwt_secret = fetch_ssm_param_opt(&ssm, &format!("{}/jwt-secret", prefix)).await;
+        let admin_api_token = fetch_ssm_param_opt(&ssm, &format!("{}/admin-token", prefix)).await;
+        let listing_webhook_secret =
+            fetch_ssm_param_opt(&ssm, &format!("{}/listing-webhook-secret", prefix)).await;
+        let credentials_encryption_key =
+            fetch_ssm_param_opt(&ssm, &format!("{}/credentials-encryption-key", prefix)).await;
+        let mut mexc_accounts = BTreeMap::new();
+        for name in env_list("MEXC_ACCOUNTS") {
+            let path = format!("{}/mexc/accounts/{}", prefix, name);
+            let credentials = Credentials {
+                api_key: fetch_ssm_param(&ssm, &format!("{}/api-key", path)).await,
+                secret_key: fetch_ssm_param(&ssm, &format!("{}/secret-key", path)).await,
+            };
+            mexc_accounts.insert(name, credentials);
+        }
 
         Self {
             mexc_api_key,
             mexc_secret_key,
-            mexc_base_url: std::env::var("MEXC_BASE_URL")
-                .unwrap_or_else(|_| "https://api.mexc.com".to_string()),
-            aws_region: std::env::var("AWS_REGION")
-                .unwrap_or_else(|_| "ap-southeast-1".to_string()),
-            dynamodb_table: std::env::var("DYNAMODB_TABLE")
-                .unwrap_or_else(|_| "mexc_trading_data".to_string()),
-            rust_api_port: std::env::var("RUST_API_PORT")
-                .unwrap_or_else(|_| "8080".to_string())
-                .parse()
-                .expect("RUST_API_PORT muss eine Zahl sein"),
             jwt_secret,
             clerk_secret_key,
             supabase_url,
             supabase_service_role_key,
             openai_api_key,
+            admin_api_token,
+            listing_webhook_secret,
+            credentials_encryption_key,
+            mexc_accounts,
+            ..Self::settings_from_env()
         }
     }
 
     /// Wähle automatisch: SSM wenn USE_SSM=true, sonst Env.
     pub async fn load() -> Self {
-        let use_ssm = std::env::var("USE_SSM")
-            .map(|v| v == "true" || v == "1")
-            .unwrap_or(false);
-
-        if use_ssm {
+        if use_ssm() {
             tracing::info!("Config: Lade Secrets aus AWS SSM Parameter Store");
             Self::from_ssm().await
         } else {
@@ -109,17 +608,288 @@ impl Config {
             Self::from_env()
         }
     }
+
+    /// Effektive Einstellungen inkl. zur Laufzeit neu geladener Tunables, Secrets
+    /// geschwärzt. Env-Variablen heißen wie das Feld in Großbuchstaben.
+    pub fn effective_snapshot(&self, runtime: &RuntimeConfig) -> BTreeMap<String, EffectiveSetting> {
+        self.snapshot_with(runtime, use_ssm(), |name| std::env::var_os(name).is_some())
+    }
+
+    fn snapshot_with(
+        &self,
+        runtime: &RuntimeConfig,
+        secrets_from_ssm: bool,
+        env_is_set: impl Fn(&str) -> bool,
+    ) -> BTreeMap<String, EffectiveSetting> {
+        let serde_json::Value::Object(mut fields) = serde_json::to_value(self).unwrap_or_default() else {
+            return BTreeMap::new();
+        };
+        // Nur die Kontonamen, nie die Credentials
+        fields.insert("mexc_accounts".to_string(), self.mexc_accounts.keys().cloned().collect());
+
+        let mut snapshot: BTreeMap<String, EffectiveSetting> = fields
+            .into_iter()
+            .map(|(name, value)| {
+                let source = if secrets_from_ssm && SSM_FIELDS.contains(&name.as_str()) {
+                    ConfigSource::Ssm
+                } else if env_is_set(&name.to_ascii_uppercase()) {
+                    ConfigSource::Env
+                } else {
+                    ConfigSource::Default
+                };
+                let value = match value {
+                    serde_json::Value::Null => serde_json::Value::Null,
+                    _ if SECRET_FIELDS.contains(&name.as_str()) => "***".into(),
+                    value => value,
+                };
+                (name, EffectiveSetting { value, source })
+            })
+            .collect();
+
+        if let Ok(serde_json::Value::Object(tunables)) = serde_json::to_value(runtime) {
+            for (name, value) in tunables {
+                let name = match name.as_str() {
+                    "min_confidence_by_pattern" => "min_snipe_confidence_by_pattern".to_string(),
+                    _ => name,
+                };
+                if let Some(setting) = snapshot.get_mut(&name) {
+                    if setting.value != value {
+                        *setting = EffectiveSetting {
+                            value,
+                            source: ConfigSource::Reloaded,
+                        };
+                    }
+                }
+            }
+        }
+        snapshot
+    }
+}
+
+/// USE_SSM=true|1: Secrets aus dem SSM Parameter Store laden
+fn use_ssm() -> bool {
+    std::env::var("USE_SSM")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+impl Default for Config {
+    /// Defaults entsprechen den Env-Fallbacks, Secrets bleiben leer
+    fn default() -> Self {
+        Self {
+            mexc_api_key: String::new(),
+            mexc_secret_key: String::new(),
+            mexc_base_url: "https://api.mexc.com".to_string(),
+            aws_region: "ap-southeast-1".to_string(),
+            dynamodb_table: "mexc_trading_data".to_string(),
+            dynamodb_failover_region: None,
+            dynamodb_write_policy: WritePolicy::Primary,
+            rust_api_port: 8080,
+            jwt_secret: None,
+            clerk_secret_key: None,
+            supabase_url: None,
+            supabase_service_role_key: None,
+            openai_api_key: None,
+            admin_api_token: None,
+            listing_webhook_secret: None,
+            credentials_encryption_key: None,
+            mexc_accounts: BTreeMap::new(),
+            mexc_admin_user_id: None,
+            symbol_blacklist: Vec::new(),
+            blacklist_reload_secs: 300,
+            risk_per_trade_pct: 2.0,
+            quote_asset: "USDT".to_string(),
+            qty_step_size: 0.01,
+            default_qty_precision: 2,
+            default_price_precision: 6,
+            exchange_info_refresh_secs: 0,
+            qty_rounding_mode: RoundingMode::Down,
+            sizing_base_capital: 0.0,
+            sizing_compounding: false,
+            sizing_compounding_max_multiple: 2.0,
+            max_open_orders_per_symbol: 10,
+            api_rate_limit_rps: 5.0,
+            api_rate_limit_burst: 10,
+            large_order_confirm_notional: 0.0,
+            large_order_confirm_ttl_secs: 60,
+            pretrade_balance_check: true,
+            trading_fee_pct: 0.1,
+            balance_cache_ms: 2000,
+            min_snipe_confidence: 0.7,
+            min_snipe_confidence_by_pattern: BTreeMap::new(),
+            decision_log_size: 1000,
+            snipe_max_attempts: 3,
+            snipe_retry_backoff_ms: 500,
+            snipe_retry_window_secs: 60,
+            max_entry_slippage_pct: 5.0,
+            unwind_on_slippage: true,
+            snipe_imbalance_levels: 0,
+            snipe_min_book_imbalance: 0.0,
+            snipe_max_spread_pct: 0.0,
+            snipe_spread_confirm: false,
+            snipe_min_quote_volume: 0.0,
+            snipe_allow_new_listings: true,
+            snipe_entry_tiers: vec!["market".to_string()],
+            snipe_entry_limit_offset_pct: 1.0,
+            snipe_entry_deadline_ms: 3_000,
+            snipe_market_fallback_pct: 0.0,
+            snipe_book_wait_poll_ms: 0,
+            snipe_book_min_levels: 1,
+            snipe_chase_after_secs: 0,
+            snipe_chase_step_pct: 0.5,
+            snipe_chase_max_pct: 2.0,
+            snipe_validate_before_fire: false,
+            snipe_canary_min_notional: 0.0,
+            snipe_canary_fraction: 0.05,
+            snipe_canary_max_slippage_pct: 2.0,
+            relisting_min_age_hours: 0,
+            relisting_action: RelistingAction::Downgrade,
+            relisting_confidence_penalty: 0.2,
+            flatten_concurrency: 4,
+            snipe_trading_state_poll_ms: 0,
+            snipe_trading_state_max_wait_ms: 30_000,
+            order_symbol_cooldown_ms: 0,
+            order_symbol_state_check: true,
+            paper_trading: false,
+            paper_simulate_fills: true,
+            batch_results_structured: true,
+            raw_response_audit: false,
+            raw_response_ttl_days: 30,
+            order_index_grace_secs: 300,
+            stop_break_even_activation_pct: 0.0,
+            stop_break_even_fee_pct: 0.2,
+            stop_trailing_pct: 0.0,
+            pyramid_trigger_pct: 0.0,
+            pyramid_add_fraction: 0.5,
+            pyramid_max_add_ons: 2,
+            pyramid_max_notional: 0.0,
+            order_write_behind: false,
+            calendar_batch_writes: true,
+            order_write_behind_max_backlog: 500,
+            order_write_behind_flush_ms: 200,
+            order_write_behind_max_attempts: 10,
+            scheduler_lookahead_hours: 24,
+            scheduler_reload_secs: 300,
+            scheduler_user_ids: Vec::new(),
+            alert_webhook_url: None,
+            alert_dedup_window_secs: 300,
+            alert_max_per_minute: 10,
+            position_webhook_url: None,
+            position_webhook_retries: 3,
+            pnl_default_strategy: "untagged".to_string(),
+            mexc_order_concurrency: 10,
+            mexc_market_concurrency: 20,
+            mexc_queue_workers: 10,
+            status_health_cache_secs: 5,
+            status_time_offset_warn_ms: 1_000,
+            stale_price_fallback_secs: 0,
+            position_refresh_staleness_secs: 30,
+            mexc_write_health_check: false,
+            mexc_debug_log: false,
+            mexc_retry_max_attempts: 3,
+            mexc_retry_base_delay_ms: 200,
+            mexc_retry_max_delay_ms: 5_000,
+            mexc_error_log_size: 100,
+            mexc_signing_version: SigningVersion::V1,
+            mexc_idempotent_create: true,
+            mexc_maintenance_detection: true,
+            mexc_weight_limit: 0,
+            mexc_maintenance_probe_secs: 30,
+            geo_check_enabled: false,
+            geo_check_fatal: false,
+            geo_lookup_url: "https://ipinfo.io/json".to_string(),
+            geo_restricted_countries: ["US", "CA", "CN", "SG"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            order_monitor_interval_secs: 5,
+            order_recovery_symbols: Vec::new(),
+            fill_confirm_polls: 0,
+            fill_confirm_interval_ms: 200,
+            shutdown_grace_secs: 10,
+            webhook_max_age_secs: 300,
+        }
+    }
+}
+
+/// Wert aus Env-Variable parsen, Default bei fehlender oder ungültiger Angabe
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match std::env::var(name) {
+        Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("Config: {} ungültig ({}), nutze Default", name, raw);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Benannte Konten aus MEXC_ACCOUNTS (kommagetrennt) und je Name
+/// MEXC_ACCOUNT_<NAME>_API_KEY / MEXC_ACCOUNT_<NAME>_SECRET_KEY
+fn accounts_from_env() -> BTreeMap<String, Credentials> {
+    env_list("MEXC_ACCOUNTS")
+        .into_iter()
+        .map(|name| {
+            let prefix = format!("MEXC_ACCOUNT_{}", name.to_ascii_uppercase().replace('-', "_"));
+            let credentials = Credentials {
+                api_key: std::env::var(format!("{}_API_KEY", prefix))
+                    .unwrap_or_else(|_| panic!("{}_API_KEY nicht gesetzt", prefix)),
+                secret_key: std::env::var(format!("{}_SECRET_KEY", prefix))
+                    .unwrap_or_else(|_| panic!("{}_SECRET_KEY nicht gesetzt", prefix)),
+            };
+            (name, credentials)
+        })
+        .collect()
+}
+
+/// Komma-separierte Liste aus Env-Variable lesen
+fn env_list(name: &str) -> Vec<String> {
+    std::env::var(name)
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Komma-separierte `key=wert`-Paare aus Env-Variable lesen (Keys klein geschrieben);
+/// ungültige Einträge werden mit Warnung übersprungen
+fn env_map(name: &str) -> BTreeMap<String, f64> {
+    env_list(name)
+        .into_iter()
+        .filter_map(|entry| {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(key, value)| {
+                    Some((key.trim().to_ascii_lowercase(), value.trim().parse().ok()?))
+                });
+            if parsed.is_none() {
+                tracing::warn!("Config: {} Eintrag '{}' ungültig, ignoriert", name, entry);
+            }
+            parsed
+        })
+        .collect()
 }
 
-/// SSM Parameter laden (required – panicked wenn er fehlt)
+/// SSM Parameter mit Retry bei Throttling laden
+async fn get_ssm_param(
+    client: &SsmClient,
+    name: &str,
+) -> Result<GetParameterOutput, SdkError<GetParameterError>> {
+    StartupRetry::default()
+        .run(&format!("SSM Parameter '{}'", name), || {
+            client.get_parameter().name(name).with_decryption(true).send()
+        })
+        .await
+}
+
+/// SSM Parameter laden (required – panicked wenn er fehlt oder nach allen
+/// Retries noch gedrosselt wird)
 async fn fetch_ssm_param(client: &SsmClient, name: &str) -> String {
-    let resp = client
-        .get_parameter()
-        .name(name)
-        .with_decryption(true)
-        .send()
+    let resp = get_ssm_param(client, name)
         .await
-        .unwrap_or_else(|e| panic!("SSM Parameter '{}' nicht lesbar: {}", name, e));
+        .unwrap_or_else(|e| panic!("SSM Parameter '{}' nicht lesbar: {}", name, DisplayErrorContext(&e)));
 
     resp.parameter()
         .and_then(|p| p.value())
@@ -129,12 +899,80 @@ async fn fetch_ssm_param(client: &SsmClient, name: &str) -> String {
 
 /// SSM Parameter laden (optional – gibt None zurück wenn er fehlt)
 async fn fetch_ssm_param_opt(client: &SsmClient, name: &str) -> Option<String> {
-    client
-        .get_parameter()
-        .name(name)
-        .with_decryption(true)
-        .send()
-        .await
-        .ok()
-        .and_then(|r| r.parameter().and_then(|p| p.value().map(|v| v.to_string())))
+    match get_ssm_param(client, name).await {
+        Ok(resp) => resp.parameter().and_then(|p| p.value().map(|v| v.to_string())),
+        Err(e) => {
+            if !matches!(e.as_service_error(), Some(GetParameterError::ParameterNotFound(_))) {
+                tracing::error!("SSM Parameter '{}' nicht lesbar: {}", name, DisplayErrorContext(&e));
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockDynamo;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_throttled_ssm_param_is_retried() {
+        let mock = MockDynamo::start().await;
+        mock.respond_error("GetParameter", "ThrottlingException");
+        mock.respond_error("GetParameter", "ThrottlingException");
+        mock.respond(
+            "GetParameter",
+            json!({ "Parameter": { "Name": "/app/mexc/api-key", "Type": "SecureString", "Value": "key-1" } }),
+        );
+
+        let value = fetch_ssm_param(&mock.ssm_client(), "/app/mexc/api-key").await;
+
+        assert_eq!(value, "key-1");
+        assert_eq!(mock.requests("GetParameter").len(), 3);
+    }
+
+    #[test]
+    fn test_effective_snapshot_redacts_secrets_and_reports_sources() {
+        let mut config = Config {
+            mexc_api_key: "live-key".to_string(),
+            mexc_secret_key: "live-secret".to_string(),
+            admin_api_token: Some("admin-token".to_string()),
+            dynamodb_table: "prod-table".to_string(),
+            ..Default::default()
+        };
+        config.mexc_accounts.insert(
+            "alt".to_string(),
+            Credentials {
+                api_key: "alt-key".to_string(),
+                secret_key: "alt-secret".to_string(),
+            },
+        );
+        let mut runtime = RuntimeConfig::from_config(&config);
+        runtime.min_snipe_confidence = 0.9;
+        let env_is_set = |name: &str| name == "DYNAMODB_TABLE";
+
+        let snapshot = config.snapshot_with(&runtime, false, env_is_set);
+
+        let rendered = serde_json::to_string(&snapshot).unwrap();
+        for secret in ["live-key", "live-secret", "admin-token", "alt-key", "alt-secret"] {
+            assert!(!rendered.contains(secret), "{} leaked", secret);
+        }
+        assert_eq!(snapshot["mexc_api_key"].value, "***");
+        assert_eq!(snapshot["mexc_api_key"].source, ConfigSource::Default);
+        assert_eq!(snapshot["jwt_secret"].value, serde_json::Value::Null);
+        assert_eq!(snapshot["mexc_accounts"].value, json!(["alt"]));
+        assert_eq!(snapshot["dynamodb_table"].value, "prod-table");
+        assert_eq!(snapshot["dynamodb_table"].source, ConfigSource::Env);
+        assert_eq!(snapshot["aws_region"].source, ConfigSource::Default);
+        assert_eq!(snapshot["min_snipe_confidence"].value, 0.9);
+        assert_eq!(snapshot["min_snipe_confidence"].source, ConfigSource::Reloaded);
+        assert_eq!(snapshot["risk_per_trade_pct"].source, ConfigSource::Default);
+
+        let snapshot = config.snapshot_with(&runtime, true, env_is_set);
+        assert_eq!(snapshot["admin_api_token"].value, "***");
+        assert_eq!(snapshot["admin_api_token"].source, ConfigSource::Ssm);
+        assert_eq!(snapshot["supabase_url"].source, ConfigSource::Ssm);
+        assert_eq!(snapshot["dynamodb_table"].source, ConfigSource::Env);
+    }
 }
@@ -0,0 +1,145 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Result};
+
+use crate::utils::Config;
+
+const NONCE_LEN: usize = 12;
+
+/// Markiert verschlüsselte Attributwerte in DynamoDB (Klartext-Altbestand bleibt lesbar)
+pub const SEALED_PREFIX: &str = "enc:v1:";
+
+/// Ist der gespeicherte Wert verschlüsselt?
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// AES-256-GCM für einzelne gespeicherte Felder.
+/// Ausgabe: Hex(Nonce || Ciphertext+Tag), pro Aufruf neue Nonce.
+pub struct FieldCipher {
+    cipher: Aes256Gcm,
+}
+
+impl FieldCipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// Key als 64 Hex-Zeichen
+    pub fn from_hex(key: &str) -> Result<Self> {
+        let bytes = hex::decode(key.trim()).map_err(|_| anyhow!("Encryption key is not valid hex"))?;
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow!("Encryption key must be 32 bytes (64 hex chars)"))?;
+        Ok(Self::new(&key))
+    }
+
+    /// Aus CREDENTIALS_ENCRYPTION_KEY; None wenn nicht konfiguriert
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        config
+            .credentials_encryption_key
+            .as_deref()
+            .filter(|key| !key.is_empty())
+            .map(Self::from_hex)
+            .transpose()
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("Encryption failed"))?;
+
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&ciphertext);
+        Ok(hex::encode(out))
+    }
+
+    pub fn decrypt(&self, encoded: &str) -> Result<String> {
+        let bytes = hex::decode(encoded).map_err(|_| anyhow!("Ciphertext is not valid hex"))?;
+        if bytes.len() <= NONCE_LEN {
+            return Err(anyhow!("Ciphertext too short"));
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Decryption failed (wrong key or tampered data)"))?;
+        String::from_utf8(plaintext).map_err(|_| anyhow!("Decrypted value is not UTF-8"))
+    }
+
+    /// Verschlüsseln für die Speicherung: `enc:v1:` + Hex(Nonce || Ciphertext+Tag)
+    pub fn seal(&self, plaintext: &str) -> Result<String> {
+        Ok(format!("{}{}", SEALED_PREFIX, self.encrypt(plaintext)?))
+    }
+
+    /// Gegenstück zu `seal`; schlägt bei fehlendem Präfix, falschem Key oder
+    /// manipuliertem Wert fehl
+    pub fn open(&self, sealed: &str) -> Result<String> {
+        let encoded = sealed
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or_else(|| anyhow!("Value is not encrypted"))?;
+        self.decrypt(encoded)
+    }
+}
+
+impl std::fmt::Debug for FieldCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FieldCipher(***)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let cipher = FieldCipher::from_hex(KEY).unwrap();
+
+        let first = cipher.encrypt("mx0-secret").unwrap();
+        let second = cipher.encrypt("mx0-secret").unwrap();
+        assert_ne!(first, second, "nonce must differ per encryption");
+        assert!(!first.contains("mx0-secret"));
+
+        assert_eq!(cipher.decrypt(&first).unwrap(), "mx0-secret");
+        assert_eq!(cipher.decrypt(&second).unwrap(), "mx0-secret");
+
+        let other = FieldCipher::new(&[7u8; 32]);
+        assert!(other.decrypt(&first).is_err());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let cipher = FieldCipher::from_hex(KEY).unwrap();
+        let sealed = cipher.seal("user@example.com").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_eq!(cipher.open(&sealed).unwrap(), "user@example.com");
+
+        // Ein geflipptes Bit im Ciphertext oder im Tag -> GCM-Authentifizierung schlägt fehl
+        let mut bytes = hex::decode(sealed.strip_prefix(SEALED_PREFIX).unwrap()).unwrap();
+        for index in [NONCE_LEN, bytes.len() - 1] {
+            bytes[index] ^= 0x01;
+            let tampered = format!("{}{}", SEALED_PREFIX, hex::encode(&bytes));
+            let err = cipher.open(&tampered).unwrap_err();
+            assert!(err.to_string().contains("tampered"));
+            bytes[index] ^= 0x01;
+        }
+
+        assert!(cipher.open("plaintext").is_err());
+        assert!(cipher.open(&format!("{}abcd", SEALED_PREFIX)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_keys_rejected() {
+        assert!(FieldCipher::from_hex("abcd").is_err());
+        assert!(FieldCipher::from_hex("not hex").is_err());
+        assert!(FieldCipher::from_config(&Config::default()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::utils::Config;
+
+/// Einordnung der Egress-Region gegenüber der Sperrliste
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegionStatus {
+    Allowed(String),
+    Restricted(String),
+    /// Lookup lieferte kein Land
+    Unknown,
+}
+
+/// Öffentliche IP und Land (ISO 3166-1 alpha-2) unseres Egress
+#[derive(Debug, Clone, Deserialize)]
+pub struct EgressLocation {
+    pub ip: String,
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+/// Land gegen die konfigurierten Sperr-Regionen prüfen (case-insensitive)
+pub fn classify_region(country: Option<&str>, restricted: &[String]) -> RegionStatus {
+    match country.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(country) => {
+            let country = country.to_ascii_uppercase();
+            if restricted.iter().any(|r| r.eq_ignore_ascii_case(&country)) {
+                RegionStatus::Restricted(country)
+            } else {
+                RegionStatus::Allowed(country)
+            }
+        }
+        None => RegionStatus::Unknown,
+    }
+}
+
+/// Egress-IP und Land über einen ipinfo-kompatiblen Dienst ermitteln
+pub async fn lookup_egress(url: &str) -> anyhow::Result<EgressLocation> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+
+    let location = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<EgressLocation>()
+        .await?;
+
+    Ok(location)
+}
+
+/// Startup-Check (opt-in über GEO_CHECK_ENABLED).
+/// Gesperrte Region wird geloggt und bricht nur mit GEO_CHECK_FATAL ab;
+/// Lookup-Fehler sind nie fatal.
+pub async fn check_egress_region(config: &Config) -> anyhow::Result<()> {
+    if !config.geo_check_enabled {
+        return Ok(());
+    }
+
+    let location = match lookup_egress(&config.geo_lookup_url).await {
+        Ok(location) => location,
+        Err(e) => {
+            tracing::warn!("Geo check: Egress-Lookup fehlgeschlagen: {}", e);
+            return Ok(());
+        }
+    };
+
+    match classify_region(location.country.as_deref(), &config.geo_restricted_countries) {
+        RegionStatus::Allowed(country) => {
+            tracing::info!("Geo check: Egress {} in {}", location.ip, country);
+        }
+        RegionStatus::Unknown => {
+            tracing::warn!("Geo check: Land für Egress {} unbekannt", location.ip);
+        }
+        RegionStatus::Restricted(country) => {
+            tracing::error!(
+                "Geo check: Egress {} liegt in {}, MEXC sperrt diese Region",
+                location.ip,
+                country
+            );
+            if config.geo_check_fatal {
+                anyhow::bail!("Egress region {} is restricted by MEXC", country);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::spawn_server;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_region_from_ip_mapping() {
+        let restricted = vec!["US".to_string(), "sg".to_string()];
+        let samples = [
+            ("8.8.8.8", Some("US"), RegionStatus::Restricted("US".to_string())),
+            ("203.0.113.7", Some("SG"), RegionStatus::Restricted("SG".to_string())),
+            ("85.214.132.117", Some("de"), RegionStatus::Allowed("DE".to_string())),
+            ("198.51.100.1", Some(" "), RegionStatus::Unknown),
+            ("192.0.2.1", None, RegionStatus::Unknown),
+        ];
+
+        for (ip, country, expected) in samples {
+            assert_eq!(classify_region(country, &restricted), expected, "ip {}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restricted_region_is_fatal_only_when_configured() {
+        let router = Router::new().route(
+            "/json",
+            get(|| async { Json(json!({ "ip": "8.8.8.8", "country": "US" })) }),
+        );
+        let base_url = spawn_server(router).await;
+
+        let mut config = Config {
+            geo_check_enabled: true,
+            geo_lookup_url: format!("{}/json", base_url),
+            geo_restricted_countries: vec!["US".to_string()],
+            ..Default::default()
+        };
+        assert!(check_egress_region(&config).await.is_ok());
+
+        config.geo_check_fatal = true;
+        assert!(check_egress_region(&config).await.is_err());
+    }
+}
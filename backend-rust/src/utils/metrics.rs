@@ -1,5 +1,4 @@
-use prometheus::{Counter, CounterVec, Histogram, HistogramVec, IntGauge, Registry};
-use std::sync::Arc;
+use prometheus::{Counter, CounterVec, HistogramVec, IntGauge, Registry};
 
 /// Prometheus Metrics für Order Latency, Error Rates, etc.
 pub struct Metrics {
@@ -10,6 +9,8 @@ pub struct Metrics {
     pub mexc_api_errors: Counter,
     pub active_orders: IntGauge,
     pub active_positions: IntGauge,
+    pub price_cache_stale: CounterVec,
+    pub order_write_backlog: IntGauge,
 }
 
 impl Metrics {
@@ -21,7 +22,7 @@ impl Metrics {
                 "order_latency_seconds",
                 "Order execution latency in seconds",
             ),
-            &["endpoint"],
+            &["endpoint", "stage"],
         )
         .expect("Failed to create order_latency metric");
 
@@ -46,12 +47,29 @@ impl Metrics {
         let active_positions = IntGauge::new("active_positions", "Currently active positions")
             .expect("Failed to create active_positions metric");
 
+        let price_cache_stale = CounterVec::new(
+            prometheus::Opts::new(
+                "price_cache_stale_total",
+                "Cached stream prices that went stale",
+            ),
+            &["symbol"],
+        )
+        .expect("Failed to create price_cache_stale metric");
+
+        let order_write_backlog = IntGauge::new(
+            "order_write_backlog",
+            "Orders waiting in the write-behind buffer",
+        )
+        .expect("Failed to create order_write_backlog metric");
+
         registry.register(Box::new(order_latency.clone())).ok();
         registry.register(Box::new(api_request_count.clone())).ok();
         registry.register(Box::new(api_error_count.clone())).ok();
         registry.register(Box::new(mexc_api_errors.clone())).ok();
         registry.register(Box::new(active_orders.clone())).ok();
         registry.register(Box::new(active_positions.clone())).ok();
+        registry.register(Box::new(price_cache_stale.clone())).ok();
+        registry.register(Box::new(order_write_backlog.clone())).ok();
 
         Self {
             registry,
@@ -61,12 +79,21 @@ impl Metrics {
             mexc_api_errors,
             active_orders,
             active_positions,
+            price_cache_stale,
+            order_write_backlog,
         }
     }
 
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
+
+    /// Erfasse die Dauer einer Order-Phase (sign, request, response_read, parse)
+    pub fn observe_order_stage(&self, endpoint: &str, stage: &str, elapsed: std::time::Duration) {
+        self.order_latency
+            .with_label_values(&[endpoint, stage])
+            .observe(elapsed.as_secs_f64());
+    }
 }
 
 impl Default for Metrics {
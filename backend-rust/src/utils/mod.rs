@@ -1,7 +1,20 @@
+pub mod alerts;
+pub mod clock;
 pub mod config;
+pub mod crypto;
+pub mod geo;
 pub mod logging;
 pub mod metrics;
+pub mod retry;
+pub mod runtime;
 
-pub use config::Config;
+pub use alerts::{AlertSink, WebhookDelivery};
+pub use clock::{Clock, SystemClock};
+#[cfg(test)]
+pub use clock::MockClock;
+pub use config::{Config, ConfigSource, EffectiveSetting};
+pub use crypto::FieldCipher;
 pub use logging::init_logging;
 pub use metrics::Metrics;
+pub use retry::StartupRetry;
+pub use runtime::{RuntimeConfig, RuntimeSettings};
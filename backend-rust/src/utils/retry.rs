@@ -0,0 +1,102 @@
+//! Retry mit Backoff für AWS-Calls beim Start: bei Cold Starts vieler
+//! Instanzen drosseln SSM/DynamoDB, das darf den Start nicht abbrechen
+use aws_sdk_dynamodb::config::http::HttpResponse;
+use aws_sdk_dynamodb::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use std::future::Future;
+use std::time::Duration;
+
+/// Fehlercodes, mit denen AWS-Services Throttling melden
+const THROTTLING_CODES: [&str; 6] = [
+    "ThrottlingException",
+    "Throttling",
+    "ThrottledException",
+    "TooManyRequestsException",
+    "ProvisionedThroughputExceededException",
+    "RequestLimitExceeded",
+];
+
+/// Begrenzter Retry (nur bei Throttling) mit exponentiellem Backoff und Jitter
+#[derive(Debug, Clone, Copy)]
+pub struct StartupRetry {
+    pub max_attempts: u32,
+    /// Wartezeit vor dem ersten Retry, verdoppelt sich pro Versuch
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for StartupRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl StartupRetry {
+    /// `op` wiederholen solange AWS drosselt; andere Fehler und der letzte
+    /// Throttling-Fehler nach `max_attempts` gehen an den Aufrufer
+    pub async fn run<T, E, F, Fut>(&self, what: &str, op: F) -> Result<T, SdkError<E>>
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E>>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match op().await {
+                Ok(value) => return Ok(value),
+                Err(e) if is_throttling(&e) && attempt < self.max_attempts => e,
+                Err(e) => return Err(e),
+            };
+
+            let delay = retry_after(&error).unwrap_or_else(|| self.backoff(attempt));
+            tracing::warn!(
+                "{} throttled (attempt {}/{}), retrying in {:?}: {}",
+                what,
+                attempt,
+                self.max_attempts,
+                delay,
+                DisplayErrorContext(&error)
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Exponentieller Backoff mit Jitter (halbe Wartezeit fest, Rest zufällig),
+    /// damit parallel startende Instanzen nicht im Gleichschritt wiederholen
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << (attempt - 1).min(16))
+            .min(self.max_delay);
+        let mut random = [0u8; 4];
+        let fraction = match getrandom::getrandom(&mut random) {
+            Ok(()) => f64::from(u32::from_le_bytes(random)) / f64::from(u32::MAX),
+            Err(_) => 1.0,
+        };
+        delay / 2 + delay.mul_f64(fraction / 2.0)
+    }
+}
+
+/// Throttling-Fehler (Fehlercode oder HTTP 429)
+pub fn is_throttling<E: ProvideErrorMetadata>(error: &SdkError<E>) -> bool {
+    if error.code().is_some_and(|code| THROTTLING_CODES.contains(&code)) {
+        return true;
+    }
+    error
+        .raw_response()
+        .is_some_and(|raw| raw.status().as_u16() == 429)
+}
+
+/// Vom Service vorgegebene Wartezeit (Retry-After in Sekunden)
+fn retry_after<E>(error: &SdkError<E>) -> Option<Duration> {
+    let raw: &HttpResponse = error.raw_response()?;
+    raw.headers()
+        .get("retry-after")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| Duration::from_secs_f64(secs.min(60.0)))
+}
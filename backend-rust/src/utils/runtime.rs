@@ -0,0 +1,64 @@
+use arc_swap::ArcSwap;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::utils::Config;
+
+/// Zur Laufzeit änderbare, nicht-geheime Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuntimeConfig {
+    pub risk_per_trade_pct: f64,
+    pub min_snipe_confidence: f64,
+    /// Pattern-spezifische Schwellen (Keys klein geschrieben)
+    pub min_confidence_by_pattern: BTreeMap<String, f64>,
+    pub max_entry_slippage_pct: f64,
+}
+
+impl RuntimeConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            risk_per_trade_pct: config.risk_per_trade_pct,
+            min_snipe_confidence: config.min_snipe_confidence,
+            min_confidence_by_pattern: config.min_snipe_confidence_by_pattern.clone(),
+            max_entry_slippage_pct: config.max_entry_slippage_pct,
+        }
+    }
+
+    /// Mindest-Confidence für ein Pattern, Fallback auf den globalen Wert
+    pub fn min_confidence_for(&self, pattern: &str) -> f64 {
+        self.min_confidence_by_pattern
+            .get(&pattern.trim().to_ascii_lowercase())
+            .copied()
+            .unwrap_or(self.min_snipe_confidence)
+    }
+}
+
+/// Hot-reloadbare Einstellungen; Leser sehen nach `reload` beim nächsten `load` die neuen Werte
+pub struct RuntimeSettings {
+    current: ArcSwap<RuntimeConfig>,
+}
+
+impl RuntimeSettings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(RuntimeConfig::from_config(config)),
+        }
+    }
+
+    pub fn load(&self) -> Arc<RuntimeConfig> {
+        self.current.load_full()
+    }
+
+    /// Tunables aus `config` übernehmen und atomar austauschen
+    pub fn reload(&self, config: &Config) -> Arc<RuntimeConfig> {
+        let next = Arc::new(RuntimeConfig::from_config(config));
+        self.current.store(next.clone());
+        next
+    }
+
+    /// Tunables erneut aus dem Environment lesen (Secrets bleiben unverändert)
+    pub fn reload_from_env(&self) -> Arc<RuntimeConfig> {
+        self.reload(&Config::settings_from_env())
+    }
+}
@@ -21,6 +21,7 @@ aws dynamodb create-table \
     AttributeName=sk,AttributeType=S \
     AttributeName=symbol,AttributeType=S \
     AttributeName=data_type,AttributeType=S \
+    AttributeName=mexc_order_id,AttributeType=S \
   --key-schema \
     AttributeName=user_id,KeyType=HASH \
     AttributeName=sk,KeyType=RANGE \
@@ -44,6 +45,14 @@ aws dynamodb create-table \
         ],
         "Projection": {"ProjectionType": "ALL"},
         "BillingMode": "'$BILLING_MODE'"
+      },
+      {
+        "IndexName": "mexc_order_id-index",
+        "KeySchema": [
+          {"AttributeName": "mexc_order_id", "KeyType": "HASH"}
+        ],
+        "Projection": {"ProjectionType": "ALL"},
+        "BillingMode": "'$BILLING_MODE'"
       }
     ]' \
   --tags Key=Environment,Value=production Key=Application,Value=mexc-sniper-bot \
@@ -85,7 +94,7 @@ echo "  Table Name: $TABLE_NAME"
 echo "  Region: $AWS_REGION"
 echo "  Billing Mode: $BILLING_MODE"
 echo "  Primary Key: user_id (HASH) + sk (RANGE)"
-echo "  GSI: symbol-index, data_type-index"
+echo "  GSI: symbol-index, data_type-index, mexc_order_id-index"
 echo "  TTL: Enabled (expiry_time)"
 echo "  Point-in-Time Recovery: Enabled"
 echo "  Streams: Enabled (NEW_AND_OLD_IMAGES)"